@@ -5,7 +5,8 @@ use bevy_ecs::{entity::Entity, prelude::Event};
 use naia_client::{Events, NaiaClientError};
 
 use naia_bevy_shared::{
-    Channel, ChannelKind, ComponentKind, Message, MessageContainer, MessageKind, Replicate, Tick,
+    Channel, ChannelKind, ComponentKind, Message, MessageContainer, MessageKind, RejectReason,
+    Replicate, Tick,
 };
 
 // ConnectEvent
@@ -18,7 +19,11 @@ pub struct DisconnectEvent;
 
 // RejectEvent
 #[derive(Event)]
-pub struct RejectEvent;
+pub struct RejectEvent(pub RejectReason);
+
+// QueueEvent
+#[derive(Event)]
+pub struct QueueEvent(pub usize);
 
 // ErrorEvent
 #[derive(Event)]
@@ -9,8 +9,8 @@ use naia_client::{Client, ClientConfig};
 use super::{
     events::{
         ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
-        InsertComponentEvents, MessageEvents, RejectEvent, RemoveComponentEvents, ServerTickEvent,
-        SpawnEntityEvent, UpdateComponentEvents,
+        InsertComponentEvents, MessageEvents, QueueEvent, RejectEvent, RemoveComponentEvents,
+        ServerTickEvent, SpawnEntityEvent, UpdateComponentEvents,
     },
     systems::before_receive_events,
 };
@@ -61,6 +61,7 @@ impl PluginType for Plugin {
             .add_event::<ConnectEvent>()
             .add_event::<DisconnectEvent>()
             .add_event::<RejectEvent>()
+            .add_event::<QueueEvent>()
             .add_event::<ErrorEvent>()
             .add_event::<ClientTickEvent>()
             .add_event::<ServerTickEvent>()
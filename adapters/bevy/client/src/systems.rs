@@ -13,7 +13,7 @@ use crate::ServerOwned;
 
 mod naia_events {
     pub use naia_client::{
-        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
+        ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent, QueueEvent,
         RejectEvent, ServerTickEvent, SpawnEntityEvent,
     };
 }
@@ -21,8 +21,8 @@ mod naia_events {
 mod bevy_events {
     pub use crate::events::{
         ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent,
-        InsertComponentEvents, MessageEvents, RejectEvent, RemoveComponentEvents, ServerTickEvent,
-        SpawnEntityEvent, UpdateComponentEvents,
+        InsertComponentEvents, MessageEvents, QueueEvent, RejectEvent, RemoveComponentEvents,
+        ServerTickEvent, SpawnEntityEvent, UpdateComponentEvents,
     };
 }
 
@@ -81,8 +81,18 @@ pub fn before_receive_events(world: &mut World) {
                 let mut reject_event_writer = world
                     .get_resource_mut::<Events<bevy_events::RejectEvent>>()
                     .unwrap();
-                for _ in events.read::<naia_events::RejectEvent>() {
-                    reject_event_writer.send(bevy_events::RejectEvent);
+                for (_, reason) in events.read::<naia_events::RejectEvent>() {
+                    reject_event_writer.send(bevy_events::RejectEvent(reason));
+                }
+            }
+
+            // Queue Event
+            if events.has::<naia_events::QueueEvent>() {
+                let mut queue_event_writer = world
+                    .get_resource_mut::<Events<bevy_events::QueueEvent>>()
+                    .unwrap();
+                for position in events.read::<naia_events::QueueEvent>() {
+                    queue_event_writer.send(bevy_events::QueueEvent(position));
                 }
             }
 
@@ -1,15 +1,18 @@
 pub use naia_shared::{
-    sequence_greater_than, BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelKind,
-    ChannelMode, ComponentFieldUpdate, ComponentKind, ComponentKinds, ComponentUpdate,
-    ConstBitLength, DiffMask, EntityAndGlobalEntityConverter, EntityDoesNotExistError,
-    EntityProperty, GlobalEntity, LinkConditionerConfig, LocalEntity,
+    codecs, schema, sequence_greater_than, AsciiString, BitReader, BitSet, BitVec, BitWrite,
+    BitWriter, BoundedString, Channel, ChannelDirection, ChannelKind, ChannelMode,
+    ComponentFieldUpdate, ComponentKind, ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask,
+    EntityAndGlobalEntityConverter, EntityDoesNotExistError, EntityProperty, EnumComponent,
+    GlobalEntity, InternedString, LinkConditionerConfig, LocalEntity,
     LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut,
     MessageBevy as Message, MessageBuilder, MessageContainer, MessageKind, MessageKinds, Named,
-    OwnedBitReader, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
-    ReplicaDynMut, ReplicaDynRef, ReplicateBevy as Replicate, ReplicateBuilder, SerdeBevy as Serde,
-    SerdeErr, Tick, TickBufferSettings, UnsignedInteger, WorldMutType, WorldRefType,
-    MTU_SIZE_BYTES,
+    OwnedBitReader, Property, PropertyMutate, PropertyMutator, ProtocolError, ProtocolName, Random,
+    RejectReason, ReliableSettings, ReplicaDynMut, ReplicaDynRef, ReplicateBevy as Replicate,
+    ReplicateBuilder, ReplicateEnum, SerdeBevy as Serde, SerdeErr, SerdeErrReason, Tick,
+    TickBufferSettings, UnsignedInteger, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
 };
+#[cfg(feature = "serde_bridge")]
+pub use naia_shared::{serde_bridge, SerdeRs};
 
 mod change_detection;
 mod component_access;
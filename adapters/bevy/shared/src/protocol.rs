@@ -1,8 +1,9 @@
 use std::time::Duration;
 
 use naia_shared::{
-    Channel, ChannelDirection, ChannelMode, ComponentKind, CompressionConfig,
-    LinkConditionerConfig, Message, Protocol as InnerProtocol, Replicate,
+    schema::ProtocolSchema, Channel, ChannelDirection, ChannelMode, ComponentKind,
+    CompressionConfig, LinkConditionerConfig, Message, Protocol as InnerProtocol, ProtocolError,
+    Replicate,
 };
 
 use crate::{ProtocolPlugin, WorldData};
@@ -89,10 +90,18 @@ impl Protocol {
         self
     }
 
+    /// # Panics
+    /// If the registered kinds can't be given ids, see [`ProtocolError`]
     pub fn lock(&mut self) {
         self.inner.lock();
     }
 
+    /// Describes every registered Channel, Message & Component, see
+    /// [`ProtocolSchema`]
+    pub fn schema(&self) -> ProtocolSchema {
+        self.inner.schema()
+    }
+
     pub fn into(self) -> InnerProtocol {
         self.inner
     }
@@ -104,4 +113,12 @@ impl Protocol {
     pub fn build(&mut self) -> Self {
         std::mem::take(self)
     }
+
+    /// Builds the Protocol, or returns why the registered kinds can't be
+    /// given ids, see [`InnerProtocol::try_build`]
+    pub fn try_build(&mut self) -> Result<Self, ProtocolError> {
+        let mut protocol = std::mem::take(self);
+        protocol.inner = protocol.inner.try_build()?;
+        Ok(protocol)
+    }
 }
@@ -1,13 +1,16 @@
 pub use naia_shared::{
-    BitReader, BitWrite, BitWriter, Channel, ChannelDirection, ChannelMode, ComponentFieldUpdate,
-    ComponentKind, ComponentKinds, ComponentUpdate, ConstBitLength, DiffMask, EntityProperty,
-    GlobalEntity, LinkConditionerConfig, LocalEntity, LocalEntityAndGlobalEntityConverter,
+    codecs, schema, AsciiString, BitReader, BitSet, BitVec, BitWrite, BitWriter, BoundedString,
+    Channel, ChannelDirection, ChannelMode, ComponentFieldUpdate, ComponentKind, ComponentKinds,
+    ComponentUpdate, ConstBitLength, DiffMask, EntityProperty, EnumComponent, GlobalEntity,
+    InternedString, LinkConditionerConfig, LocalEntity, LocalEntityAndGlobalEntityConverter,
     LocalEntityAndGlobalEntityConverterMut, MessageBuilder, MessageContainer,
     MessageHecs as Message, MessageKind, MessageKinds, Named, OwnedBitReader, Property,
-    PropertyMutate, PropertyMutator, Random, ReliableSettings, ReplicaDynMut, ReplicaDynRef,
-    ReplicateBuilder, ReplicateHecs as Replicate, SerdeErr, SerdeHecs as Serde, TickBufferSettings,
-    UnsignedInteger,
+    PropertyMutate, PropertyMutator, ProtocolError, ProtocolName, Random, ReliableSettings,
+    ReplicaDynMut, ReplicaDynRef, ReplicateBuilder, ReplicateEnum, ReplicateHecs as Replicate,
+    SerdeErr, SerdeErrReason, SerdeHecs as Serde, TickBufferSettings, UnsignedInteger,
 };
+#[cfg(feature = "serde_bridge")]
+pub use naia_shared::{serde_bridge, SerdeRs};
 
 mod component_access;
 mod component_ref;
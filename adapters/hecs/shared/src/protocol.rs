@@ -3,9 +3,9 @@ use std::time::Duration;
 use hecs::World;
 
 use naia_shared::{
-    Channel, ChannelDirection, ChannelMode, ComponentKind, CompressionConfig,
-    LinkConditionerConfig, Message, Protocol as InnerProtocol, ProtocolPlugin, Replicate,
-    SocketConfig,
+    schema::ProtocolSchema, Channel, ChannelDirection, ChannelMode, ComponentKind,
+    CompressionConfig, LinkConditionerConfig, Message, Protocol as InnerProtocol, ProtocolError,
+    ProtocolPlugin, Replicate, SocketConfig,
 };
 
 use crate::{WorldData, WorldWrapper};
@@ -96,14 +96,30 @@ impl Protocol {
         self
     }
 
+    /// # Panics
+    /// If the registered kinds can't be given ids, see [`ProtocolError`]
     pub fn lock(&mut self) {
         self.inner.lock();
     }
 
+    /// Describes every registered Channel, Message & Component, see
+    /// [`ProtocolSchema`]
+    pub fn schema(&self) -> ProtocolSchema {
+        self.inner.schema()
+    }
+
     pub fn build(&mut self) -> Self {
         std::mem::take(self)
     }
 
+    /// Builds the Protocol, or returns why the registered kinds can't be
+    /// given ids, see [`InnerProtocol::try_build`]
+    pub fn try_build(&mut self) -> Result<Self, ProtocolError> {
+        let mut protocol = std::mem::take(self);
+        protocol.inner = protocol.inner.try_build()?;
+        Ok(protocol)
+    }
+
     pub fn socket_config(&self) -> &SocketConfig {
         &self.inner.socket
     }
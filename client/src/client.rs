@@ -1,6 +1,4 @@
-use std::{hash::Hash, net::SocketAddr};
-
-use log::warn;
+use std::{hash::Hash, net::SocketAddr, time::Duration};
 
 #[cfg(feature = "bevy_support")]
 use bevy_ecs::prelude::Resource;
@@ -8,9 +6,10 @@ use bevy_ecs::prelude::Resource;
 pub use naia_shared::{
     BitReader, BitWriter, Channel, ChannelKind, ChannelKinds, ComponentKind, ConnectionConfig,
     EntityAndGlobalEntityConverter, EntityConverter, EntityConverterMut, EntityDoesNotExistError,
-    EntityRef, FakeEntityConverter, GameInstant, GlobalEntity, Instant, Message, MessageContainer,
-    PacketType, PingIndex, Protocol, Replicate, Serde, SocketConfig, StandardHeader, Tick, Timer,
-    Timestamp, WorldMutType, WorldRefType,
+    EntityRef, FakeEntityConverter, GameInstant, GlobalEntity, Instant, KeyedMessage, Message,
+    MessageContainer, MessageHandle, MessageKey, MessageTtl, PacketType, PingIndex, Protocol,
+    Replicate, Request, Response, ResponseKey, Serde, SocketConfig, StandardHeader, StreamId,
+    StreamOptions, StreamProgress, Tick, Timer, Timestamp, WorldMutType, WorldRefType,
 };
 
 use crate::{
@@ -26,7 +25,9 @@ use crate::{
     },
 };
 
-use super::{client_config::ClientConfig, error::NaiaClientError, events::Events};
+use super::{
+    client_config::ClientConfig, error::NaiaClientError, events::Events, request::Responder,
+};
 
 /// Client can send/receive messages to/from a server, and has a pool of
 /// in-scope entities/components that are synced with the server
@@ -40,6 +41,7 @@ pub struct Client<E: Copy + Eq + Hash + Send + Sync> {
     server_connection: Option<Connection<E>>,
     handshake_manager: HandshakeManager,
     manual_disconnect: bool,
+    connection_error: Option<NaiaClientError>,
     // World
     global_world_manager: GlobalWorldManager<E>,
     // Events
@@ -56,6 +58,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             client_config.send_handshake_interval,
             client_config.ping_interval,
             client_config.handshake_pings,
+            protocol.fingerprint(),
         );
 
         let compression_config = protocol.compression.clone();
@@ -68,10 +71,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             io: Io::new(
                 &client_config.connection.bandwidth_measure_duration,
                 &compression_config,
+                client_config.read_limits,
             ),
             server_connection: None,
             handshake_manager,
             manual_disconnect: false,
+            connection_error: None,
             // World
             global_world_manager: GlobalWorldManager::new(),
             // Events
@@ -122,8 +127,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         for _ in 0..10 {
             let writer = self.handshake_manager.write_disconnect();
             if self.io.send_packet(writer.to_packet()).is_err() {
-                // TODO: pass this on and handle above
-                warn!("Client Error: Cannot send disconnect packet to Server");
+                self.incoming_events.push_error(NaiaClientError::SendError);
             }
         }
 
@@ -147,7 +151,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
         // all other operations
         if let Some(connection) = self.server_connection.as_mut() {
-            if connection.base.should_drop() || self.manual_disconnect {
+            if connection.base.should_drop()
+                || self.manual_disconnect
+                || self.connection_error.is_some()
+            {
                 self.disconnect_with_events(&mut world);
                 return std::mem::take(&mut self.incoming_events);
             }
@@ -157,24 +164,26 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
             if let Some((prev_receiving_tick, current_receiving_tick)) = receiving_tick_happened {
                 // read packets on tick boundary, de-jittering
-                if connection
-                    .read_buffered_packets(&self.protocol, &mut self.global_world_manager)
-                    .is_err()
+                if let Err(error) =
+                    connection.read_buffered_packets(&self.protocol, &mut self.global_world_manager)
                 {
-                    // TODO: Except for cosmic radiation .. Server should never send a malformed packet .. handle this
-                    warn!("Error reading from buffered packet!");
+                    self.connection_error = Some(NaiaClientError::DeserializeError(error));
+                    self.disconnect_with_events(&mut world);
+                    return std::mem::take(&mut self.incoming_events);
                 }
 
                 // receive packets, process into events
                 connection.process_packets(
+                    &self.protocol,
                     &mut self.global_world_manager,
-                    &self.protocol.component_kinds,
                     &mut world,
                     &mut self.incoming_events,
                 );
 
                 let mut index_tick = prev_receiving_tick.wrapping_add(1);
                 loop {
+                    connection
+                        .receive_tick_buffered_messages(&index_tick, &mut self.incoming_events);
                     self.incoming_events.push_server_tick(index_tick);
 
                     if index_tick == current_receiving_tick {
@@ -208,8 +217,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 }
             }
         } else {
-            self.handshake_manager
-                .send(&self.protocol.message_kinds, &mut self.io);
+            if let Err(error) = self
+                .handshake_manager
+                .send(&self.protocol.message_kinds, &mut self.io)
+            {
+                self.incoming_events.push_error(error);
+            }
         }
 
         std::mem::take(&mut self.incoming_events)
@@ -223,6 +236,60 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.send_message_inner(&ChannelKind::of::<C>(), cloned_message);
     }
 
+    /// Queues up a Message to be sent to the Server over a reliable Channel.
+    /// Once the Server acknowledges it, the returned handle is yielded by a
+    /// `MessageDeliveredEvent`. Returns None if not connected.
+    pub fn send_tracked_message<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+    ) -> Option<MessageHandle> {
+        self.send_tracked_message_inner(&ChannelKind::of::<C>(), message, None)
+    }
+
+    /// Queues up a Message to be sent to the Server over a reliable Channel.
+    /// If the Server has not acknowledged it once `ttl` has elapsed, it is
+    /// dropped and the returned handle is yielded by a `MessageExpiredEvent`.
+    /// Returns None if not connected.
+    pub fn send_message_with_ttl<C: Channel, M: Message>(
+        &mut self,
+        message: &M,
+        ttl: MessageTtl,
+    ) -> Option<MessageHandle> {
+        let ttl = ttl.to_duration(&self.protocol.tick_interval);
+        self.send_tracked_message_inner(&ChannelKind::of::<C>(), message, Some(ttl))
+    }
+
+    fn send_tracked_message_inner<M: Message>(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message: &M,
+        ttl: Option<Duration>,
+    ) -> Option<MessageHandle> {
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(channel_kind)
+            .can_send_to_server()
+        {
+            panic!("Cannot send message to Server on this Channel");
+        }
+
+        let connection = self.server_connection.as_mut()?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        let handle = connection.base.message_manager.send_tracked_message(
+            &self.protocol.message_kinds,
+            &mut converter,
+            channel_kind,
+            message,
+            ttl,
+        );
+        Some(handle)
+    }
+
     fn send_message_inner(&mut self, channel_kind: &ChannelKind, message_box: Box<dyn Message>) {
         let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
         if !channel_settings.can_send_to_server() {
@@ -248,6 +315,68 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
+    /// Queues up a KeyedMessage to be sent to the Server over a Keyed Channel.
+    /// Replaces any older value under the same key which the Server has not
+    /// yet acknowledged.
+    pub fn send_keyed_message<C: Channel, M: KeyedMessage>(&mut self, message: &M) {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_server()
+        {
+            panic!("Cannot send message to Server on this Channel");
+        }
+
+        let Some(connection) = &mut self.server_connection else {
+            return;
+        };
+        let key = MessageKey::of(message);
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        connection
+            .base
+            .message_manager
+            .send_keyed_message(&channel_kind, key, message);
+    }
+
+    /// Queues up a Message to be sent to the Server, in a numbered lane of an
+    /// Ordered Reliable Channel. Messages are only ordered against others in
+    /// the same lane, so a lost Message does not hold back unrelated lanes.
+    /// Messages sent with `Client.send_message()` still wait on every Message
+    /// sent before them. The Channel must not have a TTL.
+    pub fn send_message_in_lane<C: Channel, M: Message>(&mut self, lane: u16, message: &M) {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_server()
+        {
+            panic!("Cannot send message to Server on this Channel");
+        }
+
+        let Some(connection) = &mut self.server_connection else {
+            return;
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        connection.base.message_manager.send_message_in_lane(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            lane,
+            message,
+        );
+    }
+
     pub fn send_tick_buffer_message<C: Channel, M: Message>(&mut self, tick: &Tick, message: &M) {
         let cloned_message = M::clone_box(message);
         self.send_tick_buffer_message_inner(tick, &ChannelKind::of::<C>(), cloned_message);
@@ -281,6 +410,155 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
+    // Requests
+
+    /// Sends a Request to the Server, over a bidirectional, reliable Channel.
+    /// The Response arrives as a `ResponseEvent`, or a `RequestTimeoutEvent`
+    /// if none comes in time. Returns None if not connected.
+    pub fn send_request<C: Channel, Q: Request>(
+        &mut self,
+        request: &Q,
+    ) -> Option<ResponseKey<Q::Response>> {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_server()
+        {
+            panic!("Cannot send Request to Server on this Channel");
+        }
+
+        let connection = self.server_connection.as_mut()?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let request = MessageContainer::from_write(Q::clone_box(request), &mut converter);
+        let id = connection.base.message_manager.send_request(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            request,
+            &self.client_config.connection.request_timeout_duration,
+        );
+        Some(ResponseKey::new(id))
+    }
+
+    /// Answers a Request received from the Server, through the Responder
+    /// which came with its `RequestEvent`
+    pub fn send_response<S: Response>(&mut self, responder: Responder<S>, response: &S) {
+        let Some(connection) = self.server_connection.as_mut() else {
+            return;
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let response = MessageContainer::from_write(S::clone_box(response), &mut converter);
+        connection.base.message_manager.send_response(
+            &self.protocol.message_kinds,
+            &mut converter,
+            responder.channel_kind(),
+            responder.id(),
+            response,
+        );
+    }
+
+    // Streams
+
+    /// Opens a Stream of bytes to the Server, over a Channel configured with
+    /// `ChannelMode::Stream`. The metadata Message arrives with
+    /// `StreamUpdate::Opened`. Returns None if not connected.
+    pub fn open_stream<C: Channel, M: Message>(&mut self, metadata: &M) -> Option<StreamId> {
+        self.open_stream_with_options::<C, M>(metadata, StreamOptions::default())
+    }
+
+    /// Opens a Stream with a known length, content hash, or starting offset.
+    /// See [`StreamOptions`].
+    pub fn open_stream_with_options<C: Channel, M: Message>(
+        &mut self,
+        metadata: &M,
+        options: StreamOptions,
+    ) -> Option<StreamId> {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_server()
+        {
+            panic!("Cannot open a Stream to Server on this Channel");
+        }
+
+        let connection = self.server_connection.as_mut()?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let metadata = MessageContainer::from_write(M::clone_box(metadata), &mut converter);
+        let id = connection.base.message_manager.open_stream(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            metadata,
+            options,
+        );
+        Some(id)
+    }
+
+    /// Queues bytes to be sent over an open Stream
+    pub fn write_stream<C: Channel>(&mut self, id: &StreamId, bytes: &[u8]) {
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .base
+                .message_manager
+                .write_stream(&ChannelKind::of::<C>(), id, bytes);
+        }
+    }
+
+    /// Marks a Stream as complete, once every byte written to it has been sent
+    pub fn finish_stream<C: Channel>(&mut self, id: &StreamId) {
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .base
+                .message_manager
+                .finish_stream(&ChannelKind::of::<C>(), id);
+        }
+    }
+
+    /// Abandons a Stream, discarding any bytes not yet sent
+    pub fn cancel_stream<C: Channel>(&mut self, id: &StreamId) {
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .base
+                .message_manager
+                .cancel_stream(&ChannelKind::of::<C>(), id);
+        }
+    }
+
+    /// Stops reading a Stream opened by the Server, and asks the Server to
+    /// stop sending it. Useful when `StreamUpdate::Opened` carries a content
+    /// hash which is already cached.
+    pub fn reject_stream<C: Channel>(&mut self, id: &StreamId) {
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .base
+                .message_manager
+                .reject_stream(&ChannelKind::of::<C>(), id);
+        }
+    }
+
+    /// Returns how much of a Stream has been sent & acknowledged, or None if
+    /// the Stream is no longer open
+    pub fn stream_progress<C: Channel>(&self, id: &StreamId) -> Option<StreamProgress> {
+        let connection = self.server_connection.as_ref()?;
+        connection
+            .base
+            .message_manager
+            .stream_progress(&ChannelKind::of::<C>(), id)
+    }
+
     // Entities
 
     pub fn enable_replication(&mut self, entity: &E) {
@@ -558,7 +836,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             match self.io.recv_reader() {
                 Ok(Some(mut reader)) => {
                     match self.handshake_manager.recv(&mut reader) {
-                        Some(HandshakeResult::Connected(time_manager)) => {
+                        Ok(Some(HandshakeResult::Connected(time_manager))) => {
                             // new connect!
                             self.server_connection = Some(Connection::new(
                                 &self.client_config.connection,
@@ -570,14 +848,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                             let server_addr = self.server_address_unwrapped();
                             self.incoming_events.push_connection(&server_addr);
                         }
-                        Some(HandshakeResult::Rejected) => {
+                        Ok(Some(HandshakeResult::Queued(position))) => {
+                            self.incoming_events.push_queue_position(position);
+                        }
+                        Ok(Some(HandshakeResult::Rejected(reason))) => {
                             let server_addr = self.server_address_unwrapped();
                             self.incoming_events.clear();
-                            self.incoming_events.push_rejection(&server_addr);
+                            self.incoming_events.push_rejection(&server_addr, reason);
+                            self.disconnect_reset_connection();
+                            return;
+                        }
+                        Ok(None) => {}
+                        Err(error) => {
+                            // abandon the handshake, the Server cannot be trusted
+                            self.incoming_events
+                                .push_error(NaiaClientError::DeserializeError(error));
                             self.disconnect_reset_connection();
                             return;
                         }
-                        None => {}
                     }
                 }
                 Ok(None) => {
@@ -598,7 +886,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             panic!("Should have checked for this above");
         };
 
-        Self::handle_heartbeats(connection, &mut self.io);
+        Self::handle_heartbeats(connection, &mut self.io, &mut self.incoming_events);
         Self::handle_pings(connection, &mut self.io);
 
         // receive from socket
@@ -607,8 +895,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 Ok(Some(mut reader)) => {
                     connection.base.mark_heard();
 
-                    let header = StandardHeader::de(&mut reader)
-                        .expect("unable to parse header from incoming packet");
+                    let header = match StandardHeader::de(&mut reader) {
+                        Ok(header) => header,
+                        Err(error) => {
+                            self.connection_error = Some(NaiaClientError::DeserializeError(error));
+                            return;
+                        }
+                    };
 
                     match header.packet_type {
                         PacketType::Data
@@ -629,15 +922,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     connection.process_incoming_header(&header);
 
                     // read server tick
-                    let Ok(server_tick) = Tick::de(&mut reader) else {
-                        warn!("unable to parse server_tick from packet");
-                        continue;
+                    let server_tick = match Tick::de(&mut reader) {
+                        Ok(server_tick) => server_tick,
+                        Err(error) => {
+                            self.connection_error = Some(NaiaClientError::DeserializeError(error));
+                            return;
+                        }
                     };
 
                     // read time since last tick
-                    let Ok(server_tick_instant) = GameInstant::de(&mut reader) else {
-                        warn!("unable to parse server_tick_instant from packet");
-                        continue;
+                    let server_tick_instant = match GameInstant::de(&mut reader) {
+                        Ok(server_tick_instant) => server_tick_instant,
+                        Err(error) => {
+                            self.connection_error = Some(NaiaClientError::DeserializeError(error));
+                            return;
+                        }
                     };
 
                     connection
@@ -647,27 +946,33 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                     // Handle based on PacketType
                     match header.packet_type {
                         PacketType::Data => {
-                            if connection
-                                .buffer_data_packet(&server_tick, &mut reader)
-                                .is_err()
+                            if let Err(error) =
+                                connection.buffer_data_packet(&server_tick, &mut reader)
                             {
-                                warn!("unable to parse data packet");
-                                continue;
+                                self.connection_error =
+                                    Some(NaiaClientError::DeserializeError(error));
+                                return;
                             }
                         }
                         PacketType::Heartbeat => {
                             // already marked as heard, job done
                         }
                         PacketType::Ping => {
-                            let Ok(ping_index) = BaseTimeManager::read_ping(&mut reader) else {
-                                panic!("unable to read ping index");
+                            let ping_index = match BaseTimeManager::read_ping(&mut reader) {
+                                Ok(ping_index) => ping_index,
+                                Err(error) => {
+                                    self.connection_error =
+                                        Some(NaiaClientError::DeserializeError(error));
+                                    return;
+                                }
                             };
                             BaseTimeManager::send_pong(connection, &mut self.io, ping_index);
                         }
                         PacketType::Pong => {
-                            if connection.time_manager.read_pong(&mut reader).is_err() {
-                                // TODO: pass this on and handle above
-                                warn!("Client Error: Cannot process pong packet from Server");
+                            if let Err(error) = connection.time_manager.read_pong(&mut reader) {
+                                self.connection_error =
+                                    Some(NaiaClientError::DeserializeError(error));
+                                return;
                             }
                         }
                         _ => {
@@ -687,7 +992,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
-    fn handle_heartbeats(connection: &mut Connection<E>, io: &mut Io) {
+    fn handle_heartbeats(
+        connection: &mut Connection<E>,
+        io: &mut Io,
+        incoming_events: &mut Events<E>,
+    ) {
         // send heartbeats
         if connection.base.should_send_heartbeat() {
             let mut writer = BitWriter::new();
@@ -699,8 +1008,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
 
             // send packet
             if io.send_packet(writer.to_packet()).is_err() {
-                // TODO: pass this on and handle above
-                warn!("Client Error: Cannot send heartbeat packet to Server");
+                incoming_events.push_error(NaiaClientError::SendError);
             }
             connection.base.mark_sent();
         }
@@ -719,9 +1027,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.incoming_events.clear();
 
         self.despawn_all_remote_entities(world);
+        self.expire_undelivered_messages();
         self.disconnect_reset_connection();
 
         self.incoming_events.push_disconnection(&server_addr);
+        if let Some(error) = self.connection_error.take() {
+            self.incoming_events.push_error(error);
+        }
     }
 
     fn despawn_all_remote_entities<W: WorldMutType<E>>(&mut self, world: &mut W) {
@@ -739,18 +1051,29 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         self.incoming_events.receive_world_events(events);
     }
 
+    fn expire_undelivered_messages(&mut self) {
+        let Some(connection) = self.server_connection.as_mut() else {
+            return;
+        };
+        for handle in connection.base.message_manager.take_undelivered_messages() {
+            self.incoming_events.push_expired_message(handle);
+        }
+    }
+
     fn disconnect_reset_connection(&mut self) {
         self.server_connection = None;
 
         self.io = Io::new(
             &self.client_config.connection.bandwidth_measure_duration,
             &self.protocol.compression,
+            self.client_config.read_limits,
         );
 
         self.handshake_manager = HandshakeManager::new(
             self.client_config.send_handshake_interval,
             self.client_config.ping_interval,
             self.client_config.handshake_pings,
+            self.protocol.fingerprint(),
         );
     }
 
@@ -1,6 +1,6 @@
 use std::{default::Default, time::Duration};
 
-use naia_shared::ConnectionConfig;
+use naia_shared::{ConnectionConfig, ReadLimits};
 
 /// Contains Config properties which will be used by a Server or Client
 #[derive(Clone)]
@@ -17,6 +17,9 @@ pub struct ClientConfig {
     /// taking longer. Keep in mind that the network measurements affect how likely commands
     /// are able to arrive at the server before processing.
     pub handshake_pings: u8,
+    /// Limits on the allocations made while reading packets received from the
+    /// Server
+    pub read_limits: ReadLimits,
 }
 
 impl Default for ClientConfig {
@@ -26,6 +29,7 @@ impl Default for ClientConfig {
             send_handshake_interval: Duration::from_millis(250),
             ping_interval: Duration::from_secs(1),
             handshake_pings: 10,
+            read_limits: ReadLimits::default(),
         }
     }
 }
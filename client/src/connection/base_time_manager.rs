@@ -91,10 +91,9 @@ impl BaseTimeManager {
 
         // get client sent time from ping index
         let Some(client_sent_time) = self.sent_pings.remove(ping_index) else {
+            // the Ping may have been evicted from the store already, treat as stale
             warn!("Unknown pong received");
-
-            // TODO: should bubble up another error
-            return Err(SerdeErr);
+            return Ok(None);
         };
 
         // read server received time
@@ -3,9 +3,10 @@ use std::hash::Hash;
 use log::warn;
 
 use naia_shared::{
-    BaseConnection, BitReader, BitWriter, ChannelKinds, ComponentKinds, ConnectionConfig,
-    EntityConverter, EntityConverterMut, HostType, HostWorldEvents, Instant, OwnedBitReader,
-    PacketType, Protocol, Serde, SerdeErr, StandardHeader, Tick, WorldMutType, WorldRefType,
+    BaseConnection, BitReader, BitWriter, ChannelKind, ChannelKinds, ConnectionConfig,
+    EntityConverter, EntityConverterMut, HostType, HostWorldEvents, Instant, MessageContainer,
+    OwnedBitReader, PacketType, Protocol, Serde, SerdeErr, StandardHeader, Tick, WorldMutType,
+    WorldRefType,
 };
 
 use crate::{
@@ -24,6 +25,8 @@ pub struct Connection<E: Copy + Eq + Hash + Send + Sync> {
     /// Small buffer when receiving updates (entity actions, entity updates) from the server
     /// to make sure we receive them in order
     jitter_buffer: TickQueue<OwnedBitReader>,
+    /// Messages sent over Tick Buffered Channels, held until their server Tick
+    tick_buffered_messages: TickQueue<(ChannelKind, MessageContainer)>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
@@ -47,6 +50,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             time_manager,
             tick_buffer,
             jitter_buffer: TickQueue::new(),
+            tick_buffered_messages: TickQueue::new(),
         };
 
         let existing_entities = global_world_manager.entities();
@@ -90,6 +94,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
 
         while let Some((server_tick, owned_reader)) = self.jitter_buffer.pop_item(receiving_tick) {
             let mut reader = owned_reader.borrow();
+            reader.begin_decoding()?;
 
             // read messages
             {
@@ -119,8 +124,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
     /// Receive & process messages / entity actions / entity updates and emit events for them
     pub fn process_packets<W: WorldMutType<E>>(
         &mut self,
+        protocol: &Protocol,
         global_world_manager: &mut GlobalWorldManager<E>,
-        component_kinds: &ComponentKinds,
         world: &mut W,
         incoming_events: &mut Events<E>,
     ) {
@@ -136,18 +141,76 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             }
         }
 
+        // Hold Tick Buffered Messages until their Tick
+        let tick_buffered_messages = self.base.message_manager.receive_tick_buffered_messages();
+        for (channel_kind, tick, message) in tick_buffered_messages {
+            self.tick_buffered_messages
+                .add_item(tick, (channel_kind, message));
+        }
+
+        // Receive Delivery Events
+        for handle in self.base.message_manager.receive_delivered_messages() {
+            incoming_events.push_delivered_message(handle);
+        }
+        for handle in self.base.message_manager.receive_expired_messages() {
+            incoming_events.push_expired_message(handle);
+        }
+        for (channel_kind, message_kind) in self.base.message_manager.receive_dropped_messages() {
+            incoming_events.push_dropped_message(&channel_kind, &message_kind);
+        }
+        for entity in self.base.host_world_manager.take_delivered_spawns() {
+            incoming_events.push_delivered_spawn(&entity);
+        }
+        for (entity, component_kind) in self.base.host_world_manager.take_delivered_inserts() {
+            incoming_events.push_delivered_insert(&entity, &component_kind);
+        }
+
+        // Receive Request & Response Events
+        let requests = self.base.message_manager.receive_requests();
+        for (channel_kind, id, request) in requests {
+            incoming_events.push_request(&channel_kind, id, request);
+        }
+        let responses = self.base.message_manager.receive_responses();
+        for (channel_kind, id, response) in responses {
+            incoming_events.push_response(&channel_kind, id, response);
+        }
+        for id in self.base.message_manager.receive_request_timeouts() {
+            incoming_events.push_request_timeout(id);
+        }
+
+        // Receive Stream Events
+        let stream_updates = self.base.message_manager.receive_stream_updates();
+        for (channel_kind, update) in stream_updates {
+            incoming_events.push_stream_update(&channel_kind, update);
+        }
+
         // Receive World Events
         let remote_events = self.base.remote_world_reader.take_incoming_events();
         let world_events = self.base.remote_world_manager.process_world_events(
             global_world_manager,
             &mut self.base.local_world_manager,
-            component_kinds,
+            &protocol.component_kinds,
             world,
             remote_events,
         );
         incoming_events.receive_world_events(world_events);
     }
 
+    /// Emit events for Messages sent over Tick Buffered Channels which are
+    /// due at the given server Tick. Messages which arrived after their Tick
+    /// are emitted immediately.
+    pub fn receive_tick_buffered_messages(
+        &mut self,
+        server_tick: &Tick,
+        incoming_events: &mut Events<E>,
+    ) {
+        while let Some((_, (channel_kind, message))) =
+            self.tick_buffered_messages.pop_item(*server_tick)
+        {
+            incoming_events.push_message(&channel_kind, message);
+        }
+    }
+
     // Outgoing data
 
     /// Collect and send any outgoing packets from client to server
@@ -224,6 +287,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             let client_tick: Tick = self.time_manager.client_sending_tick;
             client_tick.ser(&mut writer);
 
+            // the rest of the packet is coded as configured
+            writer.begin_coding(self.base.packet_coding());
+
             let mut has_written = false;
 
             // write tick buffered messages
@@ -1,14 +1,16 @@
 use std::time::Duration;
 
-use log::warn;
-
 use naia_shared::{
-    BitReader, BitWriter, FakeEntityConverter, MessageContainer, MessageKinds, PacketType, Serde,
-    StandardHeader, Timer, Timestamp as stamp_time,
+    BitReader, BitWriter, FakeEntityConverter, MessageContainer, MessageKinds, PacketType,
+    RejectReason, Serde, SerdeErr, StandardHeader, Timer, Timestamp as stamp_time,
+    UnsignedVariableInteger,
 };
 
 use super::io::Io;
-use crate::connection::{handshake_time_manager::HandshakeTimeManager, time_manager::TimeManager};
+use crate::{
+    connection::{handshake_time_manager::HandshakeTimeManager, time_manager::TimeManager},
+    NaiaClientError,
+};
 
 pub type Timestamp = u64;
 
@@ -42,7 +44,8 @@ impl PartialEq for HandshakeState {
 
 pub enum HandshakeResult {
     Connected(TimeManager),
-    Rejected,
+    Queued(usize),
+    Rejected(RejectReason),
 }
 
 pub struct HandshakeManager {
@@ -53,10 +56,17 @@ pub struct HandshakeManager {
     pre_connection_timestamp: Timestamp,
     pre_connection_digest: Option<Vec<u8>>,
     auth_message: Option<MessageContainer>,
+    queue_position: Option<usize>,
+    protocol_fingerprint: u64,
 }
 
 impl HandshakeManager {
-    pub fn new(send_interval: Duration, ping_interval: Duration, handshake_pings: u8) -> Self {
+    pub fn new(
+        send_interval: Duration,
+        ping_interval: Duration,
+        handshake_pings: u8,
+        protocol_fingerprint: u64,
+    ) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
 
@@ -68,6 +78,8 @@ impl HandshakeManager {
             pre_connection_digest: None,
             connection_state: HandshakeState::AwaitingChallengeResponse,
             auth_message: None,
+            queue_position: None,
+            protocol_fingerprint,
             ping_interval,
             handshake_pings,
         }
@@ -82,10 +94,14 @@ impl HandshakeManager {
     }
 
     // Give handshake manager the opportunity to send out messages to the server
-    pub fn send(&mut self, message_kinds: &MessageKinds, io: &mut Io) {
+    pub fn send(
+        &mut self,
+        message_kinds: &MessageKinds,
+        io: &mut Io,
+    ) -> Result<(), NaiaClientError> {
         if io.is_loaded() {
             if !self.handshake_timer.ringing() {
-                return;
+                return Ok(());
             }
 
             self.handshake_timer.reset();
@@ -94,15 +110,13 @@ impl HandshakeManager {
                 HandshakeState::AwaitingChallengeResponse => {
                     let writer = self.write_challenge_request();
                     if io.send_packet(writer.to_packet()).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!("Client Error: Cannot send challenge request packet to Server");
+                        return Err(NaiaClientError::SendError);
                     }
                 }
                 HandshakeState::AwaitingValidateResponse => {
                     let writer = self.write_validate_request(message_kinds);
                     if io.send_packet(writer.to_packet()).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!("Client Error: Cannot send validate request packet to Server");
+                        return Err(NaiaClientError::SendError);
                     }
                 }
                 HandshakeState::TimeSync(time_manager) => {
@@ -113,8 +127,7 @@ impl HandshakeManager {
                 HandshakeState::AwaitingConnectResponse(_) => {
                     let writer = self.write_connect_request();
                     if io.send_packet(writer.to_packet()).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!("Client Error: Cannot send connect request packet to Server");
+                        return Err(NaiaClientError::SendError);
                     }
                 }
                 HandshakeState::Connected => {
@@ -122,42 +135,42 @@ impl HandshakeManager {
                 }
             }
         }
+
+        Ok(())
     }
 
     // Call this regularly so handshake manager can process incoming requests
-    pub fn recv(&mut self, reader: &mut BitReader) -> Option<HandshakeResult> {
-        let header_result = StandardHeader::de(reader);
-        if header_result.is_err() {
-            return None;
-        }
-        let header = header_result.unwrap();
+    pub fn recv(&mut self, reader: &mut BitReader) -> Result<Option<HandshakeResult>, SerdeErr> {
+        let header = StandardHeader::de(reader)?;
         match header.packet_type {
             PacketType::ServerChallengeResponse => {
-                self.recv_challenge_response(reader);
-                return None;
+                self.recv_challenge_response(reader)?;
+                return Ok(None);
             }
             PacketType::ServerValidateResponse => {
                 if self.connection_state == HandshakeState::AwaitingValidateResponse {
                     self.recv_validate_response();
                 }
-                return None;
+                return Ok(None);
             }
             PacketType::ServerConnectResponse => {
-                return self.recv_connect_response();
+                return Ok(self.recv_connect_response());
             }
             PacketType::ServerRejectResponse => {
-                return Some(HandshakeResult::Rejected);
+                let reason = RejectReason::de(reader)?;
+                return Ok(Some(HandshakeResult::Rejected(reason)));
+            }
+            PacketType::ServerQueueResponse => {
+                if self.connection_state == HandshakeState::AwaitingValidateResponse {
+                    return self.recv_queue_response(reader);
+                }
+                return Ok(None);
             }
             PacketType::Pong => {
                 // Time Manager should record incoming Pongs in order to sync time
                 let mut success = false;
                 if let HandshakeState::TimeSync(time_manager) = &mut self.connection_state {
-                    let Ok(success_inner) = time_manager.read_pong(reader) else {
-                        // TODO: bubble this up
-                        warn!("Time Manager cannot process pong");
-                        return None;
-                    };
-                    success = success_inner;
+                    success = time_manager.read_pong(reader)?;
                 }
                 if success {
                     let HandshakeState::TimeSync(time_manager) = std::mem::replace(&mut self.connection_state, HandshakeState::Connected) else {
@@ -166,7 +179,7 @@ impl HandshakeManager {
                     self.connection_state =
                         HandshakeState::AwaitingConnectResponse(time_manager.finalize());
                 }
-                return None;
+                return Ok(None);
             }
             PacketType::Data
             | PacketType::Heartbeat
@@ -175,7 +188,7 @@ impl HandshakeManager {
             | PacketType::ClientConnectRequest
             | PacketType::Ping
             | PacketType::Disconnect => {
-                return None;
+                return Ok(None);
             }
         }
     }
@@ -186,30 +199,25 @@ impl HandshakeManager {
         StandardHeader::new(PacketType::ClientChallengeRequest, 0, 0, 0).ser(&mut writer);
 
         self.pre_connection_timestamp.ser(&mut writer);
+        self.protocol_fingerprint.ser(&mut writer);
 
         writer
     }
 
     // Step 2 of Handshake
-    pub fn recv_challenge_response(&mut self, reader: &mut BitReader) {
+    pub fn recv_challenge_response(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
         if self.connection_state == HandshakeState::AwaitingChallengeResponse {
-            let timestamp_result = Timestamp::de(reader);
-            if timestamp_result.is_err() {
-                return;
-            }
-            let timestamp = timestamp_result.unwrap();
+            let timestamp = Timestamp::de(reader)?;
 
             if self.pre_connection_timestamp == timestamp {
-                let digest_bytes_result = Vec::<u8>::de(reader);
-                if digest_bytes_result.is_err() {
-                    return;
-                }
-                let digest_bytes = digest_bytes_result.unwrap();
+                let digest_bytes = Vec::<u8>::de(reader)?;
                 self.pre_connection_digest = Some(digest_bytes);
 
                 self.connection_state = HandshakeState::AwaitingValidateResponse;
             }
         }
+
+        Ok(())
     }
 
     // Step 3 of Handshake
@@ -243,6 +251,22 @@ impl HandshakeManager {
         ));
     }
 
+    // Step 4 of Handshake, when the Server is at capacity
+    fn recv_queue_response(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<Option<HandshakeResult>, SerdeErr> {
+        let position = UnsignedVariableInteger::<7>::de(reader)?.get() as usize;
+
+        // only report changes in queue position
+        if self.queue_position == Some(position) {
+            return Ok(None);
+        }
+        self.queue_position = Some(position);
+
+        return Ok(Some(HandshakeResult::Queued(position)));
+    }
+
     // Step 5 of Handshake
     pub fn write_connect_request(&self) -> BitWriter {
         let mut writer = BitWriter::new();
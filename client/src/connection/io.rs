@@ -1,7 +1,7 @@
 use std::{net::SocketAddr, time::Duration};
 
 use naia_shared::{
-    BandwidthMonitor, BitReader, CompressionConfig, Decoder, Encoder, OutgoingPacket,
+    BandwidthMonitor, BitReader, CompressionConfig, Decoder, Encoder, OutgoingPacket, ReadLimits,
 };
 
 use crate::{
@@ -16,12 +16,14 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    read_limits: ReadLimits,
 }
 
 impl Io {
     pub fn new(
         bandwidth_measure_duration: &Option<Duration>,
         compression_config: &Option<CompressionConfig>,
+        read_limits: ReadLimits,
     ) -> Self {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
@@ -46,6 +48,7 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            read_limits,
         }
     }
 
@@ -105,10 +108,13 @@ impl Io {
                 payload = decoder.decode(payload);
             }
 
-            Ok(Some(BitReader::new(payload)))
+            Ok(Some(BitReader::with_limits(payload, self.read_limits)))
         } else {
+            let read_limits = self.read_limits;
             receive_result
-                .map(|payload_opt| payload_opt.map(BitReader::new))
+                .map(|payload_opt| {
+                    payload_opt.map(|payload| BitReader::with_limits(payload, read_limits))
+                })
                 .map_err(|_| NaiaClientError::RecvError)
         }
     }
@@ -20,6 +20,11 @@ impl TickBufferSender {
         let mut channel_senders = HashMap::new();
         for (channel_kind, channel) in channel_kinds.channels() {
             if let ChannelMode::TickBuffered(settings) = &channel.mode {
+                if !channel.can_send_to_server() {
+                    // Server to Client Tick Buffered Channels are handled by
+                    // the MessageManager
+                    continue;
+                }
                 channel_senders
                     .insert(channel_kind, ChannelTickBufferSender::new(settings.clone()));
             }
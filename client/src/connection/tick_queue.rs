@@ -3,9 +3,10 @@ use std::{cmp::Ordering, collections::BinaryHeap};
 use naia_shared::{sequence_greater_than, Tick};
 
 /// A queue for items marked by tick, will only ever pop items from the queue if
-/// the tick has elapsed
+/// the tick has elapsed. Items of the same tick are popped in the order added.
 pub struct TickQueue<T> {
     queue: BinaryHeap<ItemContainer<T>>,
+    next_order: u64,
 }
 
 impl<T> TickQueue<T> {
@@ -13,12 +14,15 @@ impl<T> TickQueue<T> {
     pub fn new() -> Self {
         TickQueue {
             queue: BinaryHeap::new(),
+            next_order: 0,
         }
     }
 
     /// Adds an item to the queue marked by tick
     pub fn add_item(&mut self, tick: Tick, item: T) {
-        self.queue.push(ItemContainer { tick, item });
+        let order = self.next_order;
+        self.next_order += 1;
+        self.queue.push(ItemContainer { tick, order, item });
     }
 
     /// Returns whether or not there is an item that is ready to be returned
@@ -45,12 +49,14 @@ impl<T> TickQueue<T> {
 
 pub struct ItemContainer<T> {
     pub tick: Tick,
+    // the order the item was added in, among those of the same tick
+    order: u64,
     pub item: T,
 }
 
 impl<T> PartialEq for ItemContainer<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.tick == other.tick
+        self.tick == other.tick && self.order == other.order
     }
 }
 
@@ -59,7 +65,7 @@ impl<T> Eq for ItemContainer<T> {}
 impl<T> Ord for ItemContainer<T> {
     fn cmp(&self, other: &ItemContainer<T>) -> Ordering {
         if self.tick == other.tick {
-            return Ordering::Equal;
+            return other.order.cmp(&self.order);
         }
         if sequence_greater_than(other.tick, self.tick) {
             Ordering::Greater
@@ -74,3 +80,50 @@ impl<T> PartialOrd for ItemContainer<T> {
         Some(self.cmp(other))
     }
 }
+
+// Tests
+#[cfg(test)]
+mod tick_queue_tests {
+    use crate::connection::tick_queue::TickQueue;
+
+    #[test]
+    fn pops_in_tick_order() {
+        let mut queue = TickQueue::new();
+        queue.add_item(12, "c");
+        queue.add_item(10, "a");
+        queue.add_item(11, "b");
+
+        assert_eq!(queue.pop_item(11), Some((10, "a")));
+        assert_eq!(queue.pop_item(11), Some((11, "b")));
+        assert_eq!(queue.pop_item(11), None);
+        assert_eq!(queue.pop_item(12), Some((12, "c")));
+    }
+
+    #[test]
+    fn pops_in_order_across_wraparound() {
+        let mut queue = TickQueue::new();
+        queue.add_item(1, "d");
+        queue.add_item(u16::MAX, "b");
+        queue.add_item(0, "c");
+        queue.add_item(u16::MAX - 1, "a");
+
+        // Tick 0 comes after u16::MAX, so everything up to it has elapsed
+        assert_eq!(queue.pop_item(0), Some((u16::MAX - 1, "a")));
+        assert_eq!(queue.pop_item(0), Some((u16::MAX, "b")));
+        assert_eq!(queue.pop_item(0), Some((0, "c")));
+        assert_eq!(queue.pop_item(0), None);
+        assert_eq!(queue.pop_item(1), Some((1, "d")));
+    }
+
+    #[test]
+    fn pops_same_tick_in_order_added() {
+        let mut queue = TickQueue::new();
+        for item in ["a", "b", "c", "d", "e"] {
+            queue.add_item(10, item);
+        }
+
+        for item in ["a", "b", "c", "d", "e"] {
+            assert_eq!(queue.pop_item(10), Some((10, item)));
+        }
+    }
+}
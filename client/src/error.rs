@@ -1,11 +1,16 @@
 use std::{error::Error, fmt};
 
+use naia_shared::SerdeErr;
+
 #[derive(Debug)]
 pub enum NaiaClientError {
     Message(String),
     Wrapped(Box<dyn Error + Send>),
     SendError,
     RecvError,
+    /// A packet from the Server could not be read. The error names the
+    /// Message or Component kind being read, if any.
+    DeserializeError(SerdeErr),
 }
 
 impl NaiaClientError {
@@ -21,6 +26,9 @@ impl fmt::Display for NaiaClientError {
             NaiaClientError::Wrapped(boxed_err) => fmt::Display::fmt(boxed_err.as_ref(), f),
             NaiaClientError::SendError => write!(f, "Naia Client Error: Send Error"),
             NaiaClientError::RecvError => write!(f, "Naia Client Error: Recv Error"),
+            NaiaClientError::DeserializeError(error) => {
+                write!(f, "Naia Client Error: Deserialize Error: {}", error)
+            }
         }
     }
 }
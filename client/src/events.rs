@@ -1,20 +1,34 @@
 use std::{collections::HashMap, marker::PhantomData, mem, net::SocketAddr, vec::IntoIter};
 
 use naia_shared::{
-    Channel, ChannelKind, ComponentKind, EntityEvent, Message, MessageContainer, MessageKind,
-    Replicate, Tick,
+    Channel, ChannelKind, ComponentKind, EntityEvent, Message, MessageContainer, MessageHandle,
+    MessageKind, RejectReason, Replicate, Request, RequestId, Response, ResponseKey, StreamUpdate,
+    Tick,
 };
 
-use crate::NaiaClientError;
+use crate::{NaiaClientError, Responder};
+
+// Requests or Responses, grouped by Channel then Message kind
+type RequestMap = HashMap<ChannelKind, HashMap<MessageKind, Vec<(RequestId, MessageContainer)>>>;
 
 pub struct Events<E: Copy> {
     connections: Vec<SocketAddr>,
-    rejections: Vec<SocketAddr>,
+    rejections: Vec<(SocketAddr, RejectReason)>,
+    queue_positions: Vec<usize>,
     disconnections: Vec<SocketAddr>,
     client_ticks: Vec<Tick>,
     server_ticks: Vec<Tick>,
     errors: Vec<NaiaClientError>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<MessageContainer>>>,
+    requests: RequestMap,
+    responses: RequestMap,
+    request_timeouts: Vec<RequestId>,
+    delivered_messages: Vec<MessageHandle>,
+    expired_messages: Vec<MessageHandle>,
+    dropped_messages: Vec<(ChannelKind, MessageKind)>,
+    delivered_spawns: Vec<E>,
+    delivered_inserts: HashMap<ComponentKind, Vec<E>>,
+    streams: HashMap<ChannelKind, Vec<StreamUpdate>>,
     spawns: Vec<E>,
     despawns: Vec<E>,
     inserts: HashMap<ComponentKind, Vec<E>>,
@@ -34,11 +48,21 @@ impl<E: Copy> Events<E> {
         Self {
             connections: Vec::new(),
             rejections: Vec::new(),
+            queue_positions: Vec::new(),
             disconnections: Vec::new(),
             client_ticks: Vec::new(),
             server_ticks: Vec::new(),
             errors: Vec::new(),
             messages: HashMap::new(),
+            requests: HashMap::new(),
+            responses: HashMap::new(),
+            request_timeouts: Vec::new(),
+            delivered_messages: Vec::new(),
+            expired_messages: Vec::new(),
+            dropped_messages: Vec::new(),
+            delivered_spawns: Vec::new(),
+            delivered_inserts: HashMap::new(),
+            streams: HashMap::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             inserts: HashMap::new(),
@@ -113,8 +137,13 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
-    pub(crate) fn push_rejection(&mut self, socket_addr: &SocketAddr) {
-        self.rejections.push(*socket_addr);
+    pub(crate) fn push_rejection(&mut self, socket_addr: &SocketAddr, reason: RejectReason) {
+        self.rejections.push((*socket_addr, reason));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_queue_position(&mut self, position: usize) {
+        self.queue_positions.push(position);
         self.empty = false;
     }
 
@@ -138,6 +167,72 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_delivered_message(&mut self, handle: MessageHandle) {
+        self.delivered_messages.push(handle);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_expired_message(&mut self, handle: MessageHandle) {
+        self.expired_messages.push(handle);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_dropped_message(
+        &mut self,
+        channel_kind: &ChannelKind,
+        message_kind: &MessageKind,
+    ) {
+        self.dropped_messages.push((*channel_kind, *message_kind));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_delivered_spawn(&mut self, entity: &E) {
+        self.delivered_spawns.push(*entity);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_delivered_insert(&mut self, entity: &E, component_kind: &ComponentKind) {
+        self.delivered_inserts
+            .entry(*component_kind)
+            .or_default()
+            .push(*entity);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_request(
+        &mut self,
+        channel_kind: &ChannelKind,
+        id: RequestId,
+        request: MessageContainer,
+    ) {
+        push_request_or_response(&mut self.requests, channel_kind, id, request);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_response(
+        &mut self,
+        channel_kind: &ChannelKind,
+        id: RequestId,
+        response: MessageContainer,
+    ) {
+        push_request_or_response(&mut self.responses, channel_kind, id, response);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_request_timeout(&mut self, id: RequestId) {
+        self.request_timeouts.push(id);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_stream_update(&mut self, channel_kind: &ChannelKind, update: StreamUpdate) {
+        if !self.streams.contains_key(channel_kind) {
+            self.streams.insert(*channel_kind, Vec::new());
+        }
+        let list = self.streams.get_mut(channel_kind).unwrap();
+        list.push(update);
+        self.empty = false;
+    }
+
     pub(crate) fn push_client_tick(&mut self, tick: Tick) {
         self.client_ticks.push(tick);
         self.empty = false;
@@ -216,11 +311,21 @@ impl<E: Copy> Events<E> {
     pub(crate) fn clear(&mut self) {
         self.connections.clear();
         self.rejections.clear();
+        self.queue_positions.clear();
         self.disconnections.clear();
         self.client_ticks.clear();
         self.server_ticks.clear();
         self.errors.clear();
         self.messages.clear();
+        self.requests.clear();
+        self.responses.clear();
+        self.request_timeouts.clear();
+        self.delivered_messages.clear();
+        self.expired_messages.clear();
+        self.dropped_messages.clear();
+        self.delivered_spawns.clear();
+        self.delivered_inserts.clear();
+        self.streams.clear();
         self.spawns.clear();
         self.despawns.clear();
         self.inserts.clear();
@@ -257,7 +362,7 @@ impl<E: Copy> Event<E> for ConnectEvent {
 // RejectEvent
 pub struct RejectEvent;
 impl<E: Copy> Event<E> for RejectEvent {
-    type Iter = IntoIter<SocketAddr>;
+    type Iter = IntoIter<(SocketAddr, RejectReason)>;
 
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let list = std::mem::take(&mut events.rejections);
@@ -269,6 +374,23 @@ impl<E: Copy> Event<E> for RejectEvent {
     }
 }
 
+// QueueEvent
+/// Yields the Client's (one-based) position in the Server's connection queue,
+/// whenever it changes
+pub struct QueueEvent;
+impl<E: Copy> Event<E> for QueueEvent {
+    type Iter = IntoIter<usize>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.queue_positions);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.queue_positions.is_empty()
+    }
+}
+
 // DisconnectEvent
 pub struct DisconnectEvent;
 impl<E: Copy> Event<E> for DisconnectEvent {
@@ -366,6 +488,223 @@ impl<E: Copy, C: Channel, M: Message> Event<E> for MessageEvent<C, M> {
     }
 }
 
+// Message Delivered Event
+/// Yields Messages sent with `Client::send_tracked_message` which the Server
+/// has acknowledged
+pub struct MessageDeliveredEvent;
+impl<E: Copy> Event<E> for MessageDeliveredEvent {
+    type Iter = IntoIter<MessageHandle>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_messages.is_empty()
+    }
+}
+
+// Message Expired Event
+/// Yields tracked Messages which will never be acknowledged: those whose
+/// time-to-live elapsed, those superseded by a newer Message, and those still
+/// in flight when the Client disconnects
+pub struct MessageExpiredEvent;
+impl<E: Copy> Event<E> for MessageExpiredEvent {
+    type Iter = IntoIter<MessageHandle>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.expired_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.expired_messages.is_empty()
+    }
+}
+
+// Message Dropped Event
+/// Yields the Channel and kind of each Message sent without a handle which
+/// was given up on before the Server acknowledged it, as its time-to-live
+/// elapsed or a newer Message superseded it
+pub struct MessageDroppedEvent;
+impl<E: Copy> Event<E> for MessageDroppedEvent {
+    type Iter = IntoIter<(ChannelKind, MessageKind)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.dropped_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.dropped_messages.is_empty()
+    }
+}
+
+// Spawn Entity Delivered Event
+/// Yields Entities spawned by the Client whose spawn the Server has
+/// acknowledged
+pub struct SpawnEntityDeliveredEvent;
+impl<E: Copy> Event<E> for SpawnEntityDeliveredEvent {
+    type Iter = IntoIter<E>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_spawns);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_spawns.is_empty()
+    }
+}
+
+// Insert Component Delivered Event
+/// Yields Entities whose Component the Server has acknowledged, whether it was
+/// inserted later or spawned along with the Entity
+pub struct InsertComponentDeliveredEvent<C: Replicate> {
+    phantom_c: PhantomData<C>,
+}
+impl<E: Copy, C: Replicate> Event<E> for InsertComponentDeliveredEvent<C> {
+    type Iter = IntoIter<E>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let component_kind: ComponentKind = ComponentKind::of::<C>();
+        if let Some(list) = events.delivered_inserts.remove(&component_kind) {
+            return IntoIterator::into_iter(list);
+        }
+
+        return IntoIterator::into_iter(Vec::new());
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let component_kind: ComponentKind = ComponentKind::of::<C>();
+        events.delivered_inserts.contains_key(&component_kind)
+    }
+}
+
+// Request Event
+/// Yields Requests sent by the Server, each with a Responder to answer it
+/// through `Client::send_response`
+pub struct RequestEvent<C: Channel, Q: Request> {
+    phantom_c: PhantomData<C>,
+    phantom_q: PhantomData<Q>,
+}
+impl<E: Copy, C: Channel, Q: Request> Event<E> for RequestEvent<C, Q> {
+    type Iter = IntoIter<(Responder<Q::Response>, Q)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let output = take_requests_or_responses::<Q>(&mut events.requests, &channel_kind)
+            .into_iter()
+            .map(|(id, request)| (Responder::new(&channel_kind, &id), request))
+            .collect::<Vec<_>>();
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        has_request_or_response::<C, Q>(&events.requests)
+    }
+}
+
+// Response Event
+/// Yields Responses to Requests the Client sent to the Server
+pub struct ResponseEvent<C: Channel, S: Response> {
+    phantom_c: PhantomData<C>,
+    phantom_s: PhantomData<S>,
+}
+impl<E: Copy, C: Channel, S: Response> Event<E> for ResponseEvent<C, S> {
+    type Iter = IntoIter<(ResponseKey<S>, S)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let output = take_requests_or_responses::<S>(&mut events.responses, &channel_kind)
+            .into_iter()
+            .map(|(id, response)| (ResponseKey::new(id), response))
+            .collect::<Vec<_>>();
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        has_request_or_response::<C, S>(&events.responses)
+    }
+}
+
+// Request Timeout Event
+/// Yields Requests the Client sent which received no Response in time. Match
+/// these against `ResponseKey::id`.
+pub struct RequestTimeoutEvent;
+impl<E: Copy> Event<E> for RequestTimeoutEvent {
+    type Iter = IntoIter<RequestId>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.request_timeouts);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.request_timeouts.is_empty()
+    }
+}
+
+fn push_request_or_response(
+    map: &mut RequestMap,
+    channel_kind: &ChannelKind,
+    id: RequestId,
+    message: MessageContainer,
+) {
+    let channel_map = map.entry(*channel_kind).or_default();
+    let list = channel_map.entry(message.kind()).or_default();
+    list.push((id, message));
+}
+
+fn take_requests_or_responses<M: Message>(
+    map: &mut RequestMap,
+    channel_kind: &ChannelKind,
+) -> Vec<(RequestId, M)> {
+    let Some(channel_map) = map.get_mut(channel_kind) else {
+        return Vec::new();
+    };
+    let Some(list) = channel_map.remove(&MessageKind::of::<M>()) else {
+        return Vec::new();
+    };
+    list.into_iter()
+        .map(|(id, message)| {
+            let message = message.to_boxed_any().downcast::<M>().unwrap();
+            (id, *message)
+        })
+        .collect()
+}
+
+fn has_request_or_response<C: Channel, M: Message>(map: &RequestMap) -> bool {
+    let Some(channel_map) = map.get(&ChannelKind::of::<C>()) else {
+        return false;
+    };
+    channel_map.contains_key(&MessageKind::of::<M>())
+}
+
+// Stream Event
+/// Yields updates to Streams opened over the given Channel, whether by the
+/// Client or by the Server
+pub struct StreamEvent<C: Channel> {
+    phantom_c: PhantomData<C>,
+}
+impl<E: Copy, C: Channel> Event<E> for StreamEvent<C> {
+    type Iter = IntoIter<StreamUpdate>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        if let Some(list) = events.streams.remove(&channel_kind) {
+            return IntoIterator::into_iter(list);
+        }
+        return IntoIterator::into_iter(Vec::new());
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        events.streams.contains_key(&channel_kind)
+    }
+}
+
 // Spawn Event
 pub struct SpawnEntityEvent;
 impl<E: Copy> Event<E> for SpawnEntityEvent {
@@ -20,7 +20,9 @@ pub mod shared {
     };
 }
 pub mod internal {
-    pub use crate::connection::handshake_manager::{HandshakeManager, HandshakeState};
+    pub use crate::connection::handshake_manager::{
+        HandshakeManager, HandshakeResult, HandshakeState,
+    };
 }
 
 mod client;
@@ -29,6 +31,7 @@ mod command_history;
 mod connection;
 mod error;
 mod events;
+mod request;
 mod world;
 
 pub use client::Client;
@@ -37,7 +40,10 @@ pub use command_history::CommandHistory;
 pub use error::NaiaClientError;
 pub use events::{
     ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent, Events,
-    InsertComponentEvent, MessageEvent, RejectEvent, RemoveComponentEvent, ServerTickEvent,
-    SpawnEntityEvent, UpdateComponentEvent,
+    InsertComponentDeliveredEvent, InsertComponentEvent, MessageDeliveredEvent,
+    MessageDroppedEvent, MessageEvent, MessageExpiredEvent, QueueEvent, RejectEvent,
+    RemoveComponentEvent, RequestEvent, RequestTimeoutEvent, ResponseEvent, ServerTickEvent,
+    SpawnEntityDeliveredEvent, SpawnEntityEvent, StreamEvent, UpdateComponentEvent,
 };
+pub use request::Responder;
 pub use world::entity_mut::EntityMut;
@@ -0,0 +1,29 @@
+use std::marker::PhantomData;
+
+use naia_shared::{ChannelKind, RequestId, Response};
+
+/// Handed out with each incoming Request, and used to send its Response back
+/// to the Server with `Client::send_response`
+pub struct Responder<S: Response> {
+    channel_kind: ChannelKind,
+    id: RequestId,
+    phantom_s: PhantomData<S>,
+}
+
+impl<S: Response> Responder<S> {
+    pub(crate) fn new(channel_kind: &ChannelKind, id: &RequestId) -> Self {
+        Self {
+            channel_kind: *channel_kind,
+            id: *id,
+            phantom_s: PhantomData,
+        }
+    }
+
+    pub(crate) fn channel_kind(&self) -> &ChannelKind {
+        &self.channel_kind
+    }
+
+    pub(crate) fn id(&self) -> &RequestId {
+        &self.id
+    }
+}
@@ -60,10 +60,10 @@ impl App {
         for server_address in events.read::<ConnectEvent>() {
             info!("Client connected to: {}", server_address);
         }
-        for server_address in events.read::<RejectEvent>() {
+        for (server_address, reason) in events.read::<RejectEvent>() {
             info!(
-                "Client received unauthorized response from: {}",
-                server_address
+                "Client received rejection ({:?}) from: {}",
+                reason, server_address
             );
 
             // Now give the correct username / password
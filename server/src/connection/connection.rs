@@ -129,6 +129,42 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             }
         }
 
+        // Receive Delivery Events
+        for handle in self.base.message_manager.receive_delivered_messages() {
+            incoming_events.push_delivered_message(&self.user_key, handle);
+        }
+        for handle in self.base.message_manager.receive_expired_messages() {
+            incoming_events.push_expired_message(&self.user_key, handle);
+        }
+        for (channel_kind, message_kind) in self.base.message_manager.receive_dropped_messages() {
+            incoming_events.push_dropped_message(&self.user_key, &channel_kind, &message_kind);
+        }
+        for entity in self.base.host_world_manager.take_delivered_spawns() {
+            incoming_events.push_delivered_spawn(&self.user_key, &entity);
+        }
+        for (entity, component_kind) in self.base.host_world_manager.take_delivered_inserts() {
+            incoming_events.push_delivered_insert(&self.user_key, &entity, &component_kind);
+        }
+
+        // Receive Request & Response Events
+        let requests = self.base.message_manager.receive_requests();
+        for (channel_kind, id, request) in requests {
+            incoming_events.push_request(&self.user_key, &channel_kind, id, request);
+        }
+        let responses = self.base.message_manager.receive_responses();
+        for (channel_kind, id, response) in responses {
+            incoming_events.push_response(&self.user_key, &channel_kind, id, response);
+        }
+        for id in self.base.message_manager.receive_request_timeouts() {
+            incoming_events.push_request_timeout(&self.user_key, id);
+        }
+
+        // Receive Stream Events
+        let stream_updates = self.base.message_manager.receive_stream_updates();
+        for (channel_kind, update) in stream_updates {
+            incoming_events.push_stream_update(&self.user_key, &channel_kind, update);
+        }
+
         // read world events
         if protocol.client_authoritative_entities {
             let remote_events = self.base.remote_world_reader.take_incoming_events();
@@ -228,6 +264,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Connection<E> {
             // write server tick instant
             time_manager.current_tick_instant().ser(&mut writer);
 
+            // the rest of the packet is coded as configured
+            writer.begin_coding(self.base.packet_coding());
+
             // write common data packet
             let mut has_written = false;
 
@@ -0,0 +1,63 @@
+use std::{collections::VecDeque, net::SocketAddr, time::Duration};
+
+use naia_shared::Instant;
+
+/// Holds Clients which have completed validation while the Server was at
+/// capacity, in the order they arrived
+pub struct ConnectionQueue {
+    capacity: usize,
+    timeout: Duration,
+    queue: VecDeque<(SocketAddr, Instant)>,
+}
+
+impl ConnectionQueue {
+    pub fn new(capacity: usize, timeout: Duration) -> Self {
+        Self {
+            capacity,
+            timeout,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of Clients waiting ahead of the given address. For an
+    /// address which is not queued, this is the length of the whole queue.
+    pub fn ahead_of(&self, address: &SocketAddr) -> usize {
+        self.position(address).unwrap_or(self.queue.len())
+    }
+
+    /// Records that the queued Client is still waiting, returning its position.
+    /// If the Client is not already in the queue, it is added to the back, or
+    /// `None` is returned if the queue is full.
+    pub fn touch(&mut self, address: &SocketAddr) -> Option<usize> {
+        if let Some(position) = self.position(address) {
+            self.queue[position].1 = Instant::now();
+            return Some(position);
+        }
+
+        if self.queue.len() >= self.capacity {
+            return None;
+        }
+
+        self.queue.push_back((*address, Instant::now()));
+        Some(self.queue.len() - 1)
+    }
+
+    pub fn remove(&mut self, address: &SocketAddr) {
+        if let Some(position) = self.position(address) {
+            self.queue.remove(position);
+        }
+    }
+
+    fn position(&self, address: &SocketAddr) -> Option<usize> {
+        self.queue
+            .iter()
+            .position(|(queued_address, _)| queued_address == address)
+    }
+
+    /// Drops Clients which have stopped re-sending their validation request
+    pub fn remove_timed_out(&mut self) {
+        let timeout = self.timeout;
+        self.queue
+            .retain(|(_, last_heard)| last_heard.elapsed() < timeout);
+    }
+}
@@ -5,7 +5,8 @@ use ring::{hmac, rand};
 pub use naia_shared::{
     wrapping_diff, BaseConnection, BitReader, BitWriter, ConnectionConfig, FakeEntityConverter,
     Instant, KeyGenerator, Message, MessageContainer, MessageKinds, PacketType, PropertyMutate,
-    PropertyMutator, Replicate, Serde, SerdeErr, StandardHeader, Timer, WorldMutType, WorldRefType,
+    PropertyMutator, RejectReason, Replicate, Serde, SerdeErr, StandardHeader, Timer,
+    UnsignedVariableInteger, WorldMutType, WorldRefType,
 };
 
 use crate::{cache_map::CacheMap, connection::connection::Connection};
@@ -20,18 +21,20 @@ pub enum HandshakeResult {
 pub struct HandshakeManager {
     connection_hash_key: hmac::Key,
     require_auth: bool,
+    protocol_fingerprint: u64,
     address_to_timestamp_map: HashMap<SocketAddr, Timestamp>,
     timestamp_digest_map: CacheMap<Timestamp, Vec<u8>>,
 }
 
 impl HandshakeManager {
-    pub fn new(require_auth: bool) -> Self {
+    pub fn new(require_auth: bool, protocol_fingerprint: u64) -> Self {
         let connection_hash_key =
             hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
 
         Self {
             connection_hash_key,
             require_auth,
+            protocol_fingerprint,
             address_to_timestamp_map: HashMap::new(),
             timestamp_digest_map: CacheMap::with_capacity(64),
         }
@@ -43,6 +46,11 @@ impl HandshakeManager {
         reader: &mut BitReader,
     ) -> Result<BitWriter, SerdeErr> {
         let timestamp = Timestamp::de(reader)?;
+        let protocol_fingerprint = u64::de(reader)?;
+
+        if protocol_fingerprint != self.protocol_fingerprint {
+            return Ok(self.write_reject_response(RejectReason::ProtocolMismatch));
+        }
 
         Ok(self.write_challenge_response(&timestamp))
     }
@@ -132,9 +140,17 @@ impl HandshakeManager {
         false
     }
 
-    pub fn write_reject_response(&self) -> BitWriter {
+    pub fn write_reject_response(&self, reason: RejectReason) -> BitWriter {
         let mut writer = BitWriter::new();
         StandardHeader::new(PacketType::ServerRejectResponse, 0, 0, 0).ser(&mut writer);
+        reason.ser(&mut writer);
+        writer
+    }
+
+    pub fn write_queue_response(&self, position: usize) -> BitWriter {
+        let mut writer = BitWriter::new();
+        StandardHeader::new(PacketType::ServerQueueResponse, 0, 0, 0).ser(&mut writer);
+        UnsignedVariableInteger::<7>::new(position as u64).ser(&mut writer);
         writer
     }
 
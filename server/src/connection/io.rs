@@ -1,6 +1,8 @@
 use std::{net::SocketAddr, panic, time::Duration};
 
-use naia_shared::{CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader};
+use naia_shared::{
+    CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader, ReadLimits,
+};
 
 use super::bandwidth_monitor::BandwidthMonitor;
 use crate::{
@@ -15,12 +17,14 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    read_limits: ReadLimits,
 }
 
 impl Io {
     pub fn new(
         bandwidth_measure_duration: &Option<Duration>,
         compression_config: &Option<CompressionConfig>,
+        read_limits: ReadLimits,
     ) -> Self {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
@@ -45,6 +49,7 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            read_limits,
         }
     }
 
@@ -109,7 +114,7 @@ impl Io {
                     payload = decoder.decode(payload);
                 }
 
-                Ok(Some((address, OwnedBitReader::new(payload))))
+                Ok(Some((address, OwnedBitReader::with_limits(payload, self.read_limits))))
             }
             Ok(None) => Ok(None),
             Err(_) => Err(NaiaServerError::RecvError),
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use naia_shared::{
     BitReader, ChannelKind, ChannelKinds, ChannelMode, LocalEntityAndGlobalEntityConverter,
-    MessageContainer, Protocol, Serde, SerdeErr, Tick,
+    MessageContainer, Protocol, Serde, SerdeErr, SerdeErrReason, Tick,
 };
 
 use crate::connection::channel_tick_buffer_receiver::ChannelTickBufferReceiver;
@@ -16,7 +16,12 @@ impl TickBufferReceiver {
         // initialize receivers
         let mut channel_receivers = HashMap::new();
         for (channel_kind, channel_settings) in channel_kinds.channels() {
-            if let ChannelMode::TickBuffered(settings) = channel_settings.mode {
+            if let ChannelMode::TickBuffered(settings) = &channel_settings.mode {
+                if !channel_settings.can_send_to_server() {
+                    // Server to Client Tick Buffered Channels are handled by
+                    // the MessageManager
+                    continue;
+                }
                 channel_receivers.insert(
                     channel_kind,
                     ChannelTickBufferReceiver::new(settings.clone()),
@@ -48,7 +53,12 @@ impl TickBufferReceiver {
             let channel_kind = ChannelKind::de(&protocol.channel_kinds, reader)?;
 
             // continue read inside channel
-            let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
+            let Some(channel) = self.channel_receivers.get_mut(&channel_kind) else {
+                return Err(SerdeErr::new(
+                    SerdeErrReason::Other("channel is not configured for tick buffered messages".into()),
+                    reader.bit_offset(),
+                ));
+            };
             channel.read_messages(
                 converter,
                 &protocol.message_kinds,
@@ -1,11 +1,19 @@
 use std::{error::Error, fmt, net::SocketAddr};
 
+use naia_shared::{PacketType, SerdeErr};
+
 #[derive(Debug)]
 pub enum NaiaServerError {
     Message(String),
     Wrapped(Box<dyn Error>),
     SendError(SocketAddr),
     RecvError,
+    /// A packet from the given address could not be read. The error names the
+    /// Message or Component kind being read, if any.
+    DeserializeError(SocketAddr, SerdeErr),
+    /// A packet arrived from the given address which is not valid at the
+    /// current stage of its connection
+    UnexpectedPacket(SocketAddr, PacketType),
 }
 
 impl NaiaServerError {
@@ -25,6 +33,20 @@ impl fmt::Display for NaiaServerError {
             NaiaServerError::RecvError => {
                 write!(f, "Naia Server Error: RecvError")
             }
+            NaiaServerError::DeserializeError(address, error) => {
+                write!(
+                    f,
+                    "Naia Server Error: DeserializeError: {}: {}",
+                    address, error
+                )
+            }
+            NaiaServerError::UnexpectedPacket(address, packet_type) => {
+                write!(
+                    f,
+                    "Naia Server Error: UnexpectedPacket: {}: {:?}",
+                    address, packet_type
+                )
+            }
         }
     }
 }
@@ -3,13 +3,17 @@ use std::{any::Any, collections::HashMap, marker::PhantomData, mem, vec::IntoIte
 use log::warn;
 
 use naia_shared::{
-    Channel, ChannelKind, ComponentKind, EntityEvent, Message, MessageContainer, MessageKind,
-    Replicate, Tick,
+    Channel, ChannelKind, ComponentKind, EntityEvent, Message, MessageContainer, MessageHandle,
+    MessageKind, Replicate, Request, RequestId, Response, ResponseKey, StreamUpdate, Tick,
 };
 
 use super::user::{User, UserKey};
 
-use crate::NaiaServerError;
+use crate::{NaiaServerError, Responder};
+
+// Requests or Responses, grouped by Channel then Message kind
+type RequestMap =
+    HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, RequestId, MessageContainer)>>>;
 
 pub struct Events<E: Copy> {
     connections: Vec<UserKey>,
@@ -18,6 +22,15 @@ pub struct Events<E: Copy> {
     errors: Vec<NaiaServerError>,
     auths: HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>,
     messages: HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
+    requests: RequestMap,
+    responses: RequestMap,
+    request_timeouts: Vec<(UserKey, RequestId)>,
+    delivered_messages: Vec<(UserKey, MessageHandle)>,
+    expired_messages: Vec<(UserKey, MessageHandle)>,
+    dropped_messages: Vec<(UserKey, ChannelKind, MessageKind)>,
+    delivered_spawns: Vec<(UserKey, E)>,
+    delivered_inserts: HashMap<ComponentKind, Vec<(UserKey, E)>>,
+    streams: HashMap<ChannelKind, Vec<(UserKey, StreamUpdate)>>,
     spawns: Vec<(UserKey, E)>,
     despawns: Vec<(UserKey, E)>,
     inserts: HashMap<ComponentKind, Vec<(UserKey, E)>>,
@@ -35,6 +48,15 @@ impl<E: Copy> Events<E> {
             errors: Vec::new(),
             auths: HashMap::new(),
             messages: HashMap::new(),
+            requests: HashMap::new(),
+            responses: HashMap::new(),
+            request_timeouts: Vec::new(),
+            delivered_messages: Vec::new(),
+            expired_messages: Vec::new(),
+            dropped_messages: Vec::new(),
+            delivered_spawns: Vec::new(),
+            delivered_inserts: HashMap::new(),
+            streams: HashMap::new(),
             spawns: Vec::new(),
             despawns: Vec::new(),
             inserts: HashMap::new(),
@@ -146,6 +168,86 @@ impl<E: Copy> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_delivered_message(&mut self, user_key: &UserKey, handle: MessageHandle) {
+        self.delivered_messages.push((*user_key, handle));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_expired_message(&mut self, user_key: &UserKey, handle: MessageHandle) {
+        self.expired_messages.push((*user_key, handle));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_dropped_message(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        message_kind: &MessageKind,
+    ) {
+        self.dropped_messages
+            .push((*user_key, *channel_kind, *message_kind));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_delivered_spawn(&mut self, user_key: &UserKey, entity: &E) {
+        self.delivered_spawns.push((*user_key, *entity));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_delivered_insert(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        component_kind: &ComponentKind,
+    ) {
+        self.delivered_inserts
+            .entry(*component_kind)
+            .or_default()
+            .push((*user_key, *entity));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_request(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        id: RequestId,
+        request: MessageContainer,
+    ) {
+        push_request_or_response(&mut self.requests, user_key, channel_kind, id, request);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_response(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        id: RequestId,
+        response: MessageContainer,
+    ) {
+        push_request_or_response(&mut self.responses, user_key, channel_kind, id, response);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_request_timeout(&mut self, user_key: &UserKey, id: RequestId) {
+        self.request_timeouts.push((*user_key, id));
+        self.empty = false;
+    }
+
+    pub(crate) fn push_stream_update(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        update: StreamUpdate,
+    ) {
+        if !self.streams.contains_key(channel_kind) {
+            self.streams.insert(*channel_kind, Vec::new());
+        }
+        let list = self.streams.get_mut(channel_kind).unwrap();
+        list.push((*user_key, update));
+        self.empty = false;
+    }
+
     pub(crate) fn push_tick(&mut self, tick: Tick) {
         self.ticks.push(tick);
         self.empty = false;
@@ -365,6 +467,223 @@ impl<E: Copy, C: Channel, M: Message> Event<E> for MessageEvent<C, M> {
     }
 }
 
+// Message Delivered Event
+/// Yields Messages sent with `Server::send_tracked_message` which the Client
+/// has acknowledged
+pub struct MessageDeliveredEvent;
+impl<E: Copy> Event<E> for MessageDeliveredEvent {
+    type Iter = IntoIter<(UserKey, MessageHandle)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_messages.is_empty()
+    }
+}
+
+// Message Expired Event
+/// Yields tracked Messages which will never be acknowledged: those whose
+/// time-to-live elapsed, those superseded by a newer Message, and those still
+/// in flight when the User disconnects
+pub struct MessageExpiredEvent;
+impl<E: Copy> Event<E> for MessageExpiredEvent {
+    type Iter = IntoIter<(UserKey, MessageHandle)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.expired_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.expired_messages.is_empty()
+    }
+}
+
+// Message Dropped Event
+/// Yields the Channel and kind of each Message sent without a handle which
+/// was given up on before the Client acknowledged it, as its time-to-live
+/// elapsed or a newer Message superseded it
+pub struct MessageDroppedEvent;
+impl<E: Copy> Event<E> for MessageDroppedEvent {
+    type Iter = IntoIter<(UserKey, ChannelKind, MessageKind)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.dropped_messages);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.dropped_messages.is_empty()
+    }
+}
+
+// Spawn Entity Delivered Event
+/// Yields Entities whose spawn a Client has acknowledged
+pub struct SpawnEntityDeliveredEvent;
+impl<E: Copy> Event<E> for SpawnEntityDeliveredEvent {
+    type Iter = IntoIter<(UserKey, E)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.delivered_spawns);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.delivered_spawns.is_empty()
+    }
+}
+
+// Insert Component Delivered Event
+/// Yields Entities whose Component a Client has acknowledged, whether it was
+/// inserted later or spawned along with the Entity
+pub struct InsertComponentDeliveredEvent<C: Replicate> {
+    phantom_c: PhantomData<C>,
+}
+impl<E: Copy, C: Replicate> Event<E> for InsertComponentDeliveredEvent<C> {
+    type Iter = IntoIter<(UserKey, E)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let component_kind: ComponentKind = ComponentKind::of::<C>();
+        if let Some(list) = events.delivered_inserts.remove(&component_kind) {
+            return IntoIterator::into_iter(list);
+        }
+
+        return IntoIterator::into_iter(Vec::new());
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let component_kind: ComponentKind = ComponentKind::of::<C>();
+        events.delivered_inserts.contains_key(&component_kind)
+    }
+}
+
+// Request Event
+/// Yields Requests sent by Clients, each with a Responder to answer it through
+/// `Server::send_response`
+pub struct RequestEvent<C: Channel, Q: Request> {
+    phantom_c: PhantomData<C>,
+    phantom_q: PhantomData<Q>,
+}
+impl<E: Copy, C: Channel, Q: Request> Event<E> for RequestEvent<C, Q> {
+    type Iter = IntoIter<(Responder<Q::Response>, Q)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let output = take_requests_or_responses::<Q>(&mut events.requests, &channel_kind)
+            .into_iter()
+            .map(|(user_key, id, request)| (Responder::new(&user_key, &channel_kind, &id), request))
+            .collect::<Vec<_>>();
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        has_request_or_response::<C, Q>(&events.requests)
+    }
+}
+
+// Response Event
+/// Yields Responses to Requests the Server sent to Clients
+pub struct ResponseEvent<C: Channel, S: Response> {
+    phantom_c: PhantomData<C>,
+    phantom_s: PhantomData<S>,
+}
+impl<E: Copy, C: Channel, S: Response> Event<E> for ResponseEvent<C, S> {
+    type Iter = IntoIter<(UserKey, ResponseKey<S>, S)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        let output = take_requests_or_responses::<S>(&mut events.responses, &channel_kind)
+            .into_iter()
+            .map(|(user_key, id, response)| (user_key, ResponseKey::new(id), response))
+            .collect::<Vec<_>>();
+        return IntoIterator::into_iter(output);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        has_request_or_response::<C, S>(&events.responses)
+    }
+}
+
+// Request Timeout Event
+/// Yields Requests the Server sent which received no Response in time. Match
+/// these against `ResponseKey::id`.
+pub struct RequestTimeoutEvent;
+impl<E: Copy> Event<E> for RequestTimeoutEvent {
+    type Iter = IntoIter<(UserKey, RequestId)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.request_timeouts);
+        return IntoIterator::into_iter(list);
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.request_timeouts.is_empty()
+    }
+}
+
+fn push_request_or_response(
+    map: &mut RequestMap,
+    user_key: &UserKey,
+    channel_kind: &ChannelKind,
+    id: RequestId,
+    message: MessageContainer,
+) {
+    let channel_map = map.entry(*channel_kind).or_default();
+    let list = channel_map.entry(message.kind()).or_default();
+    list.push((*user_key, id, message));
+}
+
+fn take_requests_or_responses<M: Message>(
+    map: &mut RequestMap,
+    channel_kind: &ChannelKind,
+) -> Vec<(UserKey, RequestId, M)> {
+    let Some(channel_map) = map.get_mut(channel_kind) else {
+        return Vec::new();
+    };
+    let Some(list) = channel_map.remove(&MessageKind::of::<M>()) else {
+        return Vec::new();
+    };
+    list.into_iter()
+        .map(|(user_key, id, message)| {
+            let message = message.to_boxed_any().downcast::<M>().unwrap();
+            (user_key, id, *message)
+        })
+        .collect()
+}
+
+fn has_request_or_response<C: Channel, M: Message>(map: &RequestMap) -> bool {
+    let Some(channel_map) = map.get(&ChannelKind::of::<C>()) else {
+        return false;
+    };
+    channel_map.contains_key(&MessageKind::of::<M>())
+}
+
+// Stream Event
+/// Yields updates to Streams opened over the given Channel, whether by the
+/// Server or by a Client
+pub struct StreamEvent<C: Channel> {
+    phantom_c: PhantomData<C>,
+}
+impl<E: Copy, C: Channel> Event<E> for StreamEvent<C> {
+    type Iter = IntoIter<(UserKey, StreamUpdate)>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        if let Some(list) = events.streams.remove(&channel_kind) {
+            return IntoIterator::into_iter(list);
+        }
+        return IntoIterator::into_iter(Vec::new());
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        let channel_kind: ChannelKind = ChannelKind::of::<C>();
+        events.streams.contains_key(&channel_kind)
+    }
+}
+
 pub(crate) fn read_channel_messages<C: Channel, M: Message>(
     messages: &mut HashMap<ChannelKind, HashMap<MessageKind, Vec<(UserKey, MessageContainer)>>>,
 ) -> Vec<(UserKey, M)> {
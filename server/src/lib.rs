@@ -25,9 +25,11 @@ mod cache_map;
 mod connection;
 mod error;
 mod events;
+mod request;
 mod room;
 mod server;
 mod server_config;
+mod stream;
 mod time_manager;
 mod user;
 mod user_scope;
@@ -37,12 +39,16 @@ pub use connection::tick_buffer_messages::TickBufferMessages;
 pub use error::NaiaServerError;
 pub use events::{
     AuthEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, ErrorEvent, Events,
-    InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent, TickEvent,
+    InsertComponentDeliveredEvent, InsertComponentEvent, MessageDeliveredEvent, MessageDroppedEvent,
+    MessageEvent, MessageExpiredEvent, RemoveComponentEvent, RequestEvent, RequestTimeoutEvent,
+    ResponseEvent, SpawnEntityDeliveredEvent, SpawnEntityEvent, StreamEvent, TickEvent,
     UpdateComponentEvent,
 };
+pub use request::Responder;
 pub use room::{RoomKey, RoomMut, RoomRef};
 pub use server::Server;
 pub use server_config::ServerConfig;
+pub use stream::StreamHandle;
 pub use user::{User, UserKey, UserMut, UserRef};
 pub use user_scope::UserScopeMut;
 pub use world::entity_mut::EntityMut;
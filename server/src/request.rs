@@ -0,0 +1,38 @@
+use std::marker::PhantomData;
+
+use naia_shared::{ChannelKind, RequestId, Response};
+
+use crate::UserKey;
+
+/// Handed out with each incoming Request, and used to send its Response back
+/// to the Client with `Server::send_response`
+pub struct Responder<S: Response> {
+    user_key: UserKey,
+    channel_kind: ChannelKind,
+    id: RequestId,
+    phantom_s: PhantomData<S>,
+}
+
+impl<S: Response> Responder<S> {
+    pub(crate) fn new(user_key: &UserKey, channel_kind: &ChannelKind, id: &RequestId) -> Self {
+        Self {
+            user_key: *user_key,
+            channel_kind: *channel_kind,
+            id: *id,
+            phantom_s: PhantomData,
+        }
+    }
+
+    /// The User who sent the Request
+    pub fn user_key(&self) -> UserKey {
+        self.user_key
+    }
+
+    pub(crate) fn channel_kind(&self) -> &ChannelKind {
+        &self.channel_kind
+    }
+
+    pub(crate) fn id(&self) -> &RequestId {
+        &self.id
+    }
+}
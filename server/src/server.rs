@@ -14,13 +14,16 @@ use bevy_ecs::prelude::Resource;
 use naia_shared::{
     BigMap, BitReader, BitWriter, Channel, ChannelKind, ComponentKind,
     EntityAndGlobalEntityConverter, EntityConverterMut, EntityDoesNotExistError, EntityRef,
-    GlobalEntity, Instant, Message, MessageContainer, PacketType, Protocol, Replicate, Serde,
-    SerdeErr, SocketConfig, StandardHeader, Tick, Timer, WorldMutType, WorldRefType,
+    GlobalEntity, Instant, KeyedMessage, Message, MessageContainer, MessageHandle, MessageKey,
+    MessageTtl, PacketCoding, PacketType, Protocol, RejectReason, Replicate, Request, Response,
+    ResponseKey, Serde, SerdeErr, SocketConfig, StandardHeader, StreamId, StreamOptions,
+    StreamProgress, Tick, Timer, WorldMutType, WorldRefType,
 };
 
 use crate::{
     connection::{
         connection::Connection,
+        connection_queue::ConnectionQueue,
         handshake_manager::{HandshakeManager, HandshakeResult},
         io::Io,
         tick_buffer_messages::TickBufferMessages,
@@ -36,8 +39,10 @@ use crate::{
 use super::{
     error::NaiaServerError,
     events::Events,
+    request::Responder,
     room::{Room, RoomKey, RoomMut, RoomRef},
     server_config::ServerConfig,
+    stream::StreamHandle,
     user::{User, UserKey, UserMut, UserRef},
     user_scope::UserScopeMut,
 };
@@ -55,6 +60,7 @@ pub struct Server<E: Copy + Eq + Hash + Send + Sync> {
     timeout_timer: Timer,
     ping_timer: Timer,
     handshake_manager: HandshakeManager,
+    connection_queue: Option<ConnectionQueue>,
     // Users
     users: BigMap<UserKey, User>,
     user_connections: HashMap<SocketAddr, Connection<E>>,
@@ -79,9 +85,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
 
         let time_manager = TimeManager::new(protocol.tick_interval);
 
+        let handshake_manager =
+            HandshakeManager::new(server_config.require_auth, protocol.fingerprint());
+
         let io = Io::new(
             &server_config.connection.bandwidth_measure_duration,
             &protocol.compression,
+            server_config.read_limits,
         );
 
         Server {
@@ -93,7 +103,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             heartbeat_timer: Timer::new(server_config.connection.heartbeat_interval),
             timeout_timer: Timer::new(server_config.connection.disconnection_timeout_duration),
             ping_timer: Timer::new(server_config.ping.ping_interval),
-            handshake_manager: HandshakeManager::new(server_config.require_auth),
+            handshake_manager,
+            connection_queue: server_config.connection_queue_size.map(|size| {
+                ConnectionQueue::new(
+                    size,
+                    server_config.connection.disconnection_timeout_duration,
+                )
+            }),
             // Users
             users: BigMap::new(),
             user_connections: HashMap::new(),
@@ -163,11 +179,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .send_packet(&user.address, writer.to_packet())
             .is_err()
         {
-            // TODO: pass this on and handle above
-            warn!(
-                "Server Error: Cannot send validate response packet to {}",
-                &user.address
-            );
+            self.incoming_events
+                .push_error(NaiaServerError::SendError(user.address));
         }
 
         self.validated_users.insert(user.address, *user_key);
@@ -194,11 +207,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .send_packet(&user.address, writer.to_packet())
             .is_err()
         {
-            // TODO: pass this on and handle above
-            warn!(
-                "Server Error: Cannot send connect response packet to {}",
-                &user.address
-            );
+            self.incoming_events
+                .push_error(NaiaServerError::SendError(user.address));
         }
 
         self.user_connections.insert(user.address, new_connection);
@@ -213,17 +223,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     pub fn reject_connection(&mut self, user_key: &UserKey) {
         if let Some(user) = self.users.get(user_key) {
             // send connect reject response
-            let writer = self.handshake_manager.write_reject_response();
+            let writer = self
+                .handshake_manager
+                .write_reject_response(RejectReason::Auth);
             if self
                 .io
                 .send_packet(&user.address, writer.to_packet())
                 .is_err()
             {
-                // TODO: pass this on and handle above
-                warn!(
-                    "Server Error: Cannot send auth rejection packet to {}",
-                    &user.address
-                );
+                self.incoming_events
+                    .push_error(NaiaServerError::SendError(user.address));
             }
             //
         }
@@ -239,6 +248,65 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.send_message_inner(user_key, &ChannelKind::of::<C>(), cloned_message);
     }
 
+    /// Queues up a Message to be sent to the Client associated with a given
+    /// UserKey, over a reliable Channel. Once the Client acknowledges it, the
+    /// returned handle is yielded by a `MessageDeliveredEvent`. Returns None
+    /// if the User is not connected.
+    pub fn send_tracked_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) -> Option<MessageHandle> {
+        self.send_tracked_message_inner(user_key, &ChannelKind::of::<C>(), message, None)
+    }
+
+    /// Queues up a Message to be sent to the Client associated with a given
+    /// UserKey, over a reliable Channel. If the Client has not acknowledged it
+    /// once `ttl` has elapsed, it is dropped and the returned handle is yielded
+    /// by a `MessageExpiredEvent`. Returns None if the User is not connected.
+    pub fn send_message_with_ttl<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+        ttl: MessageTtl,
+    ) -> Option<MessageHandle> {
+        let ttl = ttl.to_duration(&self.protocol.tick_interval);
+        self.send_tracked_message_inner(user_key, &ChannelKind::of::<C>(), message, Some(ttl))
+    }
+
+    fn send_tracked_message_inner<M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        channel_kind: &ChannelKind,
+        message: &M,
+        ttl: Option<Duration>,
+    ) -> Option<MessageHandle> {
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(channel_kind)
+            .can_send_to_client()
+        {
+            panic!("Cannot send message to Client on this Channel");
+        }
+
+        let user = self.users.get(user_key)?;
+        let connection = self.user_connections.get_mut(&user.address)?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        let handle = connection.base.message_manager.send_tracked_message(
+            &self.protocol.message_kinds,
+            &mut converter,
+            channel_kind,
+            message,
+            ttl,
+        );
+        Some(handle)
+    }
+
     /// Queues up an Message to be sent to the Client associated with a given
     /// UserKey
     fn send_message_inner(
@@ -247,11 +315,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         channel_kind: &ChannelKind,
         message_box: Box<dyn Message>,
     ) {
-        let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
-
-        if !channel_settings.can_send_to_client() {
-            panic!("Cannot send message to Client on this Channel");
-        }
+        self.check_message_channel(channel_kind);
 
         if let Some(user) = self.users.get(user_key) {
             if let Some(connection) = self.user_connections.get_mut(&user.address) {
@@ -270,6 +334,60 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
+    /// Queues up a Message to be sent to each of the given users. A Message
+    /// without EntityProperty fields is serialized only once, and shared by
+    /// every connection. Otherwise each connection is given its own copy, as
+    /// its EntityProperty fields are converted per connection.
+    fn send_message_to_users(
+        &mut self,
+        user_keys: &[UserKey],
+        channel_kind: &ChannelKind,
+        message_box: Box<dyn Message>,
+    ) {
+        if message_box.has_entity_relations() {
+            // entities are converted differently for each connection
+            for user_key in user_keys {
+                self.send_message_inner(user_key, channel_kind, message_box.clone());
+            }
+            return;
+        }
+
+        self.check_message_channel(channel_kind);
+
+        let message =
+            MessageContainer::from_write_shared(message_box, &self.protocol.message_kinds);
+        for user_key in user_keys {
+            let Some(user) = self.users.get(user_key) else {
+                continue;
+            };
+            let Some(connection) = self.user_connections.get_mut(&user.address) else {
+                continue;
+            };
+            let mut converter = EntityConverterMut::new(
+                &self.global_world_manager,
+                &mut connection.base.local_world_manager,
+            );
+            connection.base.message_manager.send_message(
+                &self.protocol.message_kinds,
+                &mut converter,
+                channel_kind,
+                message.clone(),
+            );
+        }
+    }
+
+    fn check_message_channel(&self, channel_kind: &ChannelKind) {
+        let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
+
+        if !channel_settings.can_send_to_client() {
+            panic!("Cannot send message to Client on this Channel");
+        }
+
+        if channel_settings.tick_buffered() {
+            panic!("Cannot send or broadcast a Message over a Tick Buffered Channel, use `Server.send_tick_buffer_message()` instead");
+        }
+    }
+
     /// Sends a message to all connected users using a given channel
     pub fn broadcast_message<C: Channel, M: Message>(&mut self, message: &M) {
         let cloned_message = M::clone_box(message);
@@ -281,11 +399,330 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         channel_kind: &ChannelKind,
         message_box: Box<dyn Message>,
     ) {
+        let user_keys = self.user_keys();
+        self.send_message_to_users(&user_keys, channel_kind, message_box);
+    }
+
+    /// Queues up a Message to be sent to the Client associated with a given
+    /// UserKey, over a Tick Buffered Channel. The Client receives it once its
+    /// own view of the server Tick reaches `tick`.
+    pub fn send_tick_buffer_message<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        tick: &Tick,
+        message: &M,
+    ) {
+        let cloned_message = M::clone_box(message);
+        self.send_tick_buffer_message_inner(
+            user_key,
+            tick,
+            &ChannelKind::of::<C>(),
+            cloned_message,
+        );
+    }
+
+    /// Sends a Message to all connected users over a Tick Buffered Channel,
+    /// so that every Client receives it at the same server Tick
+    pub fn broadcast_tick_buffer_message<C: Channel, M: Message>(
+        &mut self,
+        tick: &Tick,
+        message: &M,
+    ) {
+        let channel_kind = ChannelKind::of::<C>();
+        let cloned_message = M::clone_box(message);
         self.user_keys().iter().for_each(|user_key| {
-            self.send_message_inner(user_key, channel_kind, message_box.clone())
+            self.send_tick_buffer_message_inner(
+                user_key,
+                tick,
+                &channel_kind,
+                cloned_message.clone(),
+            )
         })
     }
 
+    fn send_tick_buffer_message_inner(
+        &mut self,
+        user_key: &UserKey,
+        tick: &Tick,
+        channel_kind: &ChannelKind,
+        message_box: Box<dyn Message>,
+    ) {
+        let channel_settings = self.protocol.channel_kinds.channel(channel_kind);
+
+        if !channel_settings.can_send_to_client() {
+            panic!("Cannot send message to Client on this Channel");
+        }
+
+        if !channel_settings.tick_buffered() {
+            panic!("Can only use `Server.send_tick_buffer_message()` on a Channel that is configured for it.");
+        }
+
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address) else {
+            return;
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(message_box, &mut converter);
+        connection.base.message_manager.send_tick_buffered_message(
+            &self.protocol.message_kinds,
+            &mut converter,
+            channel_kind,
+            tick,
+            message,
+        );
+    }
+
+    /// Queues up a KeyedMessage to be sent to the Client associated with a
+    /// given UserKey, over a Keyed Channel. Replaces any older value under the
+    /// same key which the Client has not yet acknowledged.
+    pub fn send_keyed_message<C: Channel, M: KeyedMessage>(
+        &mut self,
+        user_key: &UserKey,
+        message: &M,
+    ) {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_client()
+        {
+            panic!("Cannot send message to Client on this Channel");
+        }
+
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address) else {
+            return;
+        };
+        let key = MessageKey::of(message);
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        connection
+            .base
+            .message_manager
+            .send_keyed_message(&channel_kind, key, message);
+    }
+
+    /// Queues up a Message to be sent to the Client associated with a given
+    /// UserKey, in a numbered lane of an Ordered Reliable Channel. Messages
+    /// are only ordered against others in the same lane, so a lost Message
+    /// does not hold back unrelated lanes. Messages sent with
+    /// `Server.send_message()` still wait on every Message sent before them.
+    /// The Channel must not have a TTL.
+    pub fn send_message_in_lane<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        lane: u16,
+        message: &M,
+    ) {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_client()
+        {
+            panic!("Cannot send message to Client on this Channel");
+        }
+
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address) else {
+            return;
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let message = MessageContainer::from_write(M::clone_box(message), &mut converter);
+        connection.base.message_manager.send_message_in_lane(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            lane,
+            message,
+        );
+    }
+
+    // Requests
+
+    /// Sends a Request to the Client associated with a given UserKey, over a
+    /// bidirectional, reliable Channel. The Response arrives as a
+    /// `ResponseEvent`, or a `RequestTimeoutEvent` if none comes in time.
+    /// Returns None if the User is not connected.
+    pub fn send_request<C: Channel, Q: Request>(
+        &mut self,
+        user_key: &UserKey,
+        request: &Q,
+    ) -> Option<ResponseKey<Q::Response>> {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_client()
+        {
+            panic!("Cannot send Request to Client on this Channel");
+        }
+
+        let user = self.users.get(user_key)?;
+        let connection = self.user_connections.get_mut(&user.address)?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let request = MessageContainer::from_write(Q::clone_box(request), &mut converter);
+        let id = connection.base.message_manager.send_request(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            request,
+            &self.server_config.connection.request_timeout_duration,
+        );
+        Some(ResponseKey::new(id))
+    }
+
+    /// Answers a Request received from a Client, through the Responder which
+    /// came with its `RequestEvent`
+    pub fn send_response<S: Response>(&mut self, responder: Responder<S>, response: &S) {
+        let Some(user) = self.users.get(&responder.user_key()) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address) else {
+            return;
+        };
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let response = MessageContainer::from_write(S::clone_box(response), &mut converter);
+        connection.base.message_manager.send_response(
+            &self.protocol.message_kinds,
+            &mut converter,
+            responder.channel_kind(),
+            responder.id(),
+            response,
+        );
+    }
+
+    // Streams
+
+    /// Opens a Stream of bytes to the Client associated with a given UserKey,
+    /// over a Channel configured with `ChannelMode::Stream`. The metadata
+    /// Message arrives with `StreamUpdate::Opened`. Returns None if the User
+    /// is not connected.
+    pub fn open_stream<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        metadata: &M,
+    ) -> Option<StreamHandle> {
+        self.open_stream_with_options::<C, M>(user_key, metadata, StreamOptions::default())
+    }
+
+    /// Opens a Stream with a known length, content hash, or starting offset.
+    /// See [`StreamOptions`].
+    pub fn open_stream_with_options<C: Channel, M: Message>(
+        &mut self,
+        user_key: &UserKey,
+        metadata: &M,
+        options: StreamOptions,
+    ) -> Option<StreamHandle> {
+        let channel_kind = ChannelKind::of::<C>();
+        if !self
+            .protocol
+            .channel_kinds
+            .channel(&channel_kind)
+            .can_send_to_client()
+        {
+            panic!("Cannot open a Stream to Client on this Channel");
+        }
+
+        let user = self.users.get(user_key)?;
+        let connection = self.user_connections.get_mut(&user.address)?;
+        let mut converter = EntityConverterMut::new(
+            &self.global_world_manager,
+            &mut connection.base.local_world_manager,
+        );
+        let metadata = MessageContainer::from_write(M::clone_box(metadata), &mut converter);
+        let id = connection.base.message_manager.open_stream(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            metadata,
+            options,
+        );
+        Some(StreamHandle::new(user_key, &channel_kind, id))
+    }
+
+    /// Queues bytes to be sent over an open Stream
+    pub fn write_stream(&mut self, handle: &StreamHandle, bytes: &[u8]) {
+        if let Some(connection) = self.user_stream_connection(&handle.user_key()) {
+            connection.base.message_manager.write_stream(
+                &handle.channel_kind(),
+                &handle.id(),
+                bytes,
+            );
+        }
+    }
+
+    /// Marks a Stream as complete, once every byte written to it has been sent
+    pub fn finish_stream(&mut self, handle: &StreamHandle) {
+        if let Some(connection) = self.user_stream_connection(&handle.user_key()) {
+            connection
+                .base
+                .message_manager
+                .finish_stream(&handle.channel_kind(), &handle.id());
+        }
+    }
+
+    /// Abandons a Stream, discarding any bytes not yet sent
+    pub fn cancel_stream(&mut self, handle: &StreamHandle) {
+        if let Some(connection) = self.user_stream_connection(&handle.user_key()) {
+            connection
+                .base
+                .message_manager
+                .cancel_stream(&handle.channel_kind(), &handle.id());
+        }
+    }
+
+    /// Stops reading a Stream opened by a Client, and asks the Client to stop
+    /// sending it
+    pub fn reject_stream<C: Channel>(&mut self, user_key: &UserKey, id: &StreamId) {
+        if let Some(connection) = self.user_stream_connection(user_key) {
+            connection
+                .base
+                .message_manager
+                .reject_stream(&ChannelKind::of::<C>(), id);
+        }
+    }
+
+    /// Returns how much of a Stream has been sent & acknowledged, or None if
+    /// the Stream is no longer open
+    pub fn stream_progress(&self, handle: &StreamHandle) -> Option<StreamProgress> {
+        let user = self.users.get(&handle.user_key())?;
+        let connection = self.user_connections.get(&user.address)?;
+        connection
+            .base
+            .message_manager
+            .stream_progress(&handle.channel_kind(), &handle.id())
+    }
+
+    fn user_stream_connection(&mut self, user_key: &UserKey) -> Option<&mut Connection<E>> {
+        let user = self.users.get(user_key)?;
+        self.user_connections.get_mut(&user.address)
+    }
+
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
         let mut tick_buffer_messages = TickBufferMessages::new();
         for (_user_address, connection) in self.user_connections.iter_mut() {
@@ -729,6 +1166,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return None;
     }
 
+    /// Set how the body of each data packet sent to the given User is written
+    pub(crate) fn user_set_packet_coding(&mut self, user_key: &UserKey, coding: PacketCoding) {
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        if let Some(connection) = self.user_connections.get_mut(&user.address) {
+            connection.base.set_packet_coding(coding);
+        }
+    }
+
     pub(crate) fn user_disconnect<W: WorldMutType<E>>(
         &mut self,
         user_key: &UserKey,
@@ -737,6 +1184,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         if self.protocol.client_authoritative_entities {
             self.despawn_all_remote_entities(user_key, world);
         }
+        self.expire_undelivered_messages(user_key);
         let user = self.user_delete(user_key);
         self.incoming_events.push_disconnection(user_key, user);
     }
@@ -761,6 +1209,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             .receive_entity_events(user_key, entity_events);
     }
 
+    fn expire_undelivered_messages(&mut self, user_key: &UserKey) {
+        let Some(user) = self.users.get(user_key) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address) else {
+            return;
+        };
+        for handle in connection.base.message_manager.take_undelivered_messages() {
+            self.incoming_events.push_expired_message(user_key, handle);
+        }
+    }
+
     pub(crate) fn user_delete(&mut self, user_key: &UserKey) -> User {
         let Some(user) = self.users.remove(user_key) else {
             panic!("Attempting to delete non-existant user!");
@@ -869,9 +1329,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
     ) {
         if let Some(room) = self.rooms.get(room_key) {
             let user_keys: Vec<UserKey> = room.user_keys().cloned().collect();
-            for user_key in &user_keys {
-                self.send_message_inner(user_key, channel_kind, message_box.clone())
-            }
+            self.send_message_to_users(&user_keys, channel_kind, message_box);
         }
     }
 
@@ -947,27 +1405,31 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     let mut reader = owned_reader.borrow();
 
                     // Read header
-                    let Ok(header) = StandardHeader::de(&mut reader) else {
-                        // Received a malformed packet
-                        // TODO: increase suspicion against packet sender
-                        continue;
+                    let header = match StandardHeader::de(&mut reader) {
+                        Ok(header) => header,
+                        Err(error) => {
+                            self.drop_malformed_connection(&address, error, &mut world);
+                            continue;
+                        }
                     };
 
-                    let Ok(should_continue) = self.maintain_handshake(&address, &header, &mut reader) else {
-                        warn!("Server Error: cannot read malformed packet");
-                        continue;
-                    };
+                    let should_continue =
+                        match self.maintain_handshake(&address, &header, &mut reader) {
+                            Ok(should_continue) => should_continue,
+                            Err(error) => {
+                                self.drop_malformed_connection(&address, error, &mut world);
+                                continue;
+                            }
+                        };
                     if should_continue {
                         continue;
                     }
 
                     addresses.insert(address);
 
-                    if self
-                        .read_packet(&address, &header, &mut reader, &mut world)
-                        .is_err()
+                    if let Err(error) = self.read_packet(&address, &header, &mut reader, &mut world)
                     {
-                        warn!("Server Error: cannot read malformed packet");
+                        self.drop_malformed_connection(&address, error, &mut world);
                         continue;
                     }
                 }
@@ -996,14 +1458,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         // Handshake stuff
         match header.packet_type {
             PacketType::ClientChallengeRequest => {
-                if let Ok(writer) = self.handshake_manager.recv_challenge_request(reader) {
-                    if self.io.send_packet(&address, writer.to_packet()).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!(
-                            "Server Error: Cannot send challenge response packet to {}",
-                            &address
-                        );
-                    }
+                let writer = self.handshake_manager.recv_challenge_request(reader)?;
+                if self.io.send_packet(&address, writer.to_packet()).is_err() {
+                    self.incoming_events
+                        .push_error(NaiaServerError::SendError(*address));
                 }
                 return Ok(true);
             }
@@ -1018,10 +1476,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                             // send validate response
                             let writer = self.handshake_manager.write_validate_response();
                             if self.io.send_packet(address, writer.to_packet()).is_err() {
-                                // TODO: pass this on and handle above
-                                warn!("Server Error: Cannot send validate success response packet to {}", &address);
+                                self.incoming_events
+                                    .push_error(NaiaServerError::SendError(*address));
                             };
-                        } else {
+                        } else if self.has_room_for(address) {
+                            if let Some(connection_queue) = &mut self.connection_queue {
+                                connection_queue.remove(address);
+                            }
+
                             let user = User::new(*address);
                             let user_key = self.users.insert(user);
 
@@ -1030,6 +1492,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                             } else {
                                 self.accept_connection(&user_key);
                             }
+                        } else {
+                            self.queue_or_reject(address);
                         }
                     }
                     HandshakeResult::Invalid => {
@@ -1043,28 +1507,29 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                     // send connect response
                     let writer = self.handshake_manager.write_connect_response();
                     if self.io.send_packet(address, writer.to_packet()).is_err() {
-                        // TODO: pass this on and handle above
-                        warn!(
-                            "Server Error: Cannot send connect success response packet to {}",
-                            address
-                        );
+                        self.incoming_events
+                            .push_error(NaiaServerError::SendError(*address));
                     };
                     //
                 } else {
-                    let user_key = *self
-                        .validated_users
-                        .get(address)
-                        .expect("should be a user by now, from validation step");
+                    let Some(user_key) = self.validated_users.get(address).copied() else {
+                        self.incoming_events
+                            .push_error(NaiaServerError::UnexpectedPacket(
+                                *address,
+                                header.packet_type,
+                            ));
+                        return Ok(true);
+                    };
                     self.finalize_connection(&user_key);
                 }
                 return Ok(true);
             }
             PacketType::Ping => {
-                let response = self.time_manager.process_ping(reader).unwrap();
+                let response = self.time_manager.process_ping(reader)?;
                 // send packet
                 if self.io.send_packet(address, response.to_packet()).is_err() {
-                    // TODO: pass this on and handle above
-                    warn!("Server Error: Cannot send pong packet to {}", address);
+                    self.incoming_events
+                        .push_error(NaiaServerError::SendError(*address));
                 };
                 if let Some(connection) = self.user_connections.get_mut(address) {
                     connection.base.mark_sent();
@@ -1077,6 +1542,41 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return Ok(false);
     }
 
+    /// Returns whether a Client at the given address can become a User now,
+    /// taking into account `max_users` and any Clients queued ahead of it
+    fn has_room_for(&self, address: &SocketAddr) -> bool {
+        let Some(max_users) = self.server_config.max_users else {
+            return true;
+        };
+        let free_slots = max_users.saturating_sub(self.users.len());
+        let queued_ahead = match &self.connection_queue {
+            Some(connection_queue) => connection_queue.ahead_of(address),
+            None => 0,
+        };
+        queued_ahead < free_slots
+    }
+
+    /// Places a Client in the connection queue, or rejects it if the queue is
+    /// full or disabled
+    fn queue_or_reject(&mut self, address: &SocketAddr) {
+        let position = match &mut self.connection_queue {
+            Some(connection_queue) => connection_queue.touch(address),
+            None => None,
+        };
+        let writer = match position {
+            Some(position) => self.handshake_manager.write_queue_response(position + 1),
+            None => {
+                self.handshake_manager.delete_user(address);
+                self.handshake_manager
+                    .write_reject_response(RejectReason::ServerFull)
+            }
+        };
+        if self.io.send_packet(address, writer.to_packet()).is_err() {
+            self.incoming_events
+                .push_error(NaiaServerError::SendError(*address));
+        }
+    }
+
     fn read_packet<W: WorldMutType<E>>(
         &mut self,
         address: &SocketAddr,
@@ -1100,6 +1600,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 // read client tick
                 let client_tick = Tick::de(reader)?;
 
+                // the rest of the packet may be coded
+                reader.begin_decoding()?;
+
                 let server_tick = self.time_manager.current_tick();
 
                 // process data
@@ -1135,6 +1638,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         return Ok(());
     }
 
+    /// Reports a packet which could not be read, and disconnects the User which
+    /// sent it, if any
+    fn drop_malformed_connection<W: WorldMutType<E>>(
+        &mut self,
+        address: &SocketAddr,
+        error: SerdeErr,
+        world: &mut W,
+    ) {
+        self.incoming_events
+            .push_error(NaiaServerError::DeserializeError(*address, error));
+
+        let Some(connection) = self.user_connections.get(address) else {
+            return;
+        };
+        let user_key = connection.user_key;
+        self.user_disconnect(&user_key, world);
+    }
+
     fn process_packets<W: WorldMutType<E>>(&mut self, address: &SocketAddr, world: &mut W) {
         // Packets requiring established connection
         let Some(connection) = self.user_connections.get_mut(address) else {
@@ -1167,6 +1688,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
             for user_key in user_disconnects {
                 self.user_disconnect(&user_key, world);
             }
+
+            // drop queued Clients which have stopped waiting
+            if let Some(connection_queue) = &mut self.connection_queue {
+                connection_queue.remove_timed_out();
+            }
         }
     }
 
@@ -1199,11 +1725,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         .send_packet(user_address, writer.to_packet())
                         .is_err()
                     {
-                        // TODO: pass this on and handle above
-                        warn!(
-                            "Server Error: Cannot send heartbeat packet to {}",
-                            user_address
-                        );
+                        self.incoming_events
+                            .push_error(NaiaServerError::SendError(*user_address));
                     }
                     connection.base.mark_sent();
                 }
@@ -1243,8 +1766,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                         .send_packet(user_address, writer.to_packet())
                         .is_err()
                     {
-                        // TODO: pass this on and handle above
-                        warn!("Server Error: Cannot send ping packet to {}", user_address);
+                        self.incoming_events
+                            .push_error(NaiaServerError::SendError(*user_address));
                     }
                     connection.base.mark_sent();
                 }
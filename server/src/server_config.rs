@@ -1,6 +1,6 @@
 use std::default::Default;
 
-use naia_shared::ConnectionConfig;
+use naia_shared::{ConnectionConfig, ReadLimits};
 
 use crate::connection::ping_config::PingConfig;
 
@@ -14,6 +14,16 @@ pub struct ServerConfig {
     pub require_auth: bool,
     /// Configuration used to monitor the ping & jitter on the network
     pub ping: PingConfig,
+    /// Maximum number of Users which can be connected (or awaiting auth) at
+    /// once. `None` means there is no limit.
+    pub max_users: Option<usize>,
+    /// Maximum number of Clients which can wait in the connection queue for a
+    /// slot to free up once `max_users` has been reached. `None` means there is
+    /// no queue, and Clients beyond capacity are rejected immediately.
+    pub connection_queue_size: Option<usize>,
+    /// Limits on the allocations made while reading packets received from
+    /// Clients
+    pub read_limits: ReadLimits,
 }
 
 impl Default for ServerConfig {
@@ -22,6 +32,9 @@ impl Default for ServerConfig {
             connection: ConnectionConfig::default(),
             require_auth: true,
             ping: PingConfig::default(),
+            max_users: None,
+            connection_queue_size: None,
+            read_limits: ReadLimits::default(),
         }
     }
 }
@@ -0,0 +1,33 @@
+use naia_shared::{ChannelKind, StreamId};
+
+use crate::UserKey;
+
+/// Refers to a Stream the Server has opened to a Client
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StreamHandle {
+    user_key: UserKey,
+    channel_kind: ChannelKind,
+    id: StreamId,
+}
+
+impl StreamHandle {
+    pub(crate) fn new(user_key: &UserKey, channel_kind: &ChannelKind, id: StreamId) -> Self {
+        Self {
+            user_key: *user_key,
+            channel_kind: *channel_kind,
+            id,
+        }
+    }
+
+    pub fn user_key(&self) -> UserKey {
+        self.user_key
+    }
+
+    pub fn channel_kind(&self) -> ChannelKind {
+        self.channel_kind
+    }
+
+    pub fn id(&self) -> StreamId {
+        self.id
+    }
+}
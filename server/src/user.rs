@@ -4,7 +4,7 @@ use std::{
     net::SocketAddr,
 };
 
-use naia_shared::{BigMapKey, WorldMutType};
+use naia_shared::{BigMapKey, PacketCoding, WorldMutType};
 
 use crate::{RoomKey, Server};
 
@@ -108,6 +108,14 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserMut<'s, E> {
         self.server.user_disconnect(&self.key, &mut world);
     }
 
+    /// Set how the body of each data packet sent to the User is written,
+    /// overriding `ConnectionConfig::packet_coding` for this connection
+    pub fn set_packet_coding(&mut self, coding: PacketCoding) -> &mut Self {
+        self.server.user_set_packet_coding(&self.key, coding);
+
+        self
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
@@ -1,5 +1,5 @@
 use quote::quote;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, LitStr};
 
 use super::shared::{get_struct_type, StructType};
 
@@ -18,10 +18,14 @@ pub fn channel_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Names
     let struct_name = input.ident;
 
+    let struct_name_str = LitStr::new(&struct_name.to_string(), struct_name.span());
+
     let gen = quote! {
 
         impl Channel for #struct_name {
-
+            fn protocol_name() -> String where Self: Sized {
+                return format!("{}::{}", module_path!(), #struct_name_str);
+            }
         }
     };
 
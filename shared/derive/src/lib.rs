@@ -8,6 +8,7 @@ use quote::quote;
 mod channel;
 mod message;
 mod replicate;
+mod replicate_enum;
 mod shared;
 
 use channel::channel_impl;
@@ -16,22 +17,25 @@ use replicate::replicate_impl;
 
 // Replicate
 
-/// Derives the Replicate trait for a given struct
-#[proc_macro_derive(Replicate)]
+/// Derives the Replicate trait for a given struct, or for the EnumComponent of a
+/// given enum
+#[proc_macro_derive(Replicate, attributes(naia))]
 pub fn replicate_derive_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_shared };
     replicate_impl(input, shared_crate_name)
 }
 
-/// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateBevy)]
+/// Derives the Replicate trait for a given struct, or for the EnumComponent of a
+/// given enum, for the Bevy adapter
+#[proc_macro_derive(ReplicateBevy, attributes(naia))]
 pub fn replicate_derive_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_bevy_shared };
     replicate_impl(input, shared_crate_name)
 }
 
-/// Derives the Replicate trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(ReplicateHecs)]
+/// Derives the Replicate trait for a given struct, or for the EnumComponent of a
+/// given enum, for the Bevy adapter
+#[proc_macro_derive(ReplicateHecs, attributes(naia))]
 pub fn replicate_derive_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_hecs_shared };
     replicate_impl(input, shared_crate_name)
@@ -48,35 +52,35 @@ pub fn channel_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 // Message
 
 /// Derives the Message trait for a given struct, for internal
-#[proc_macro_derive(MessageInternal)]
+#[proc_macro_derive(MessageInternal, attributes(naia))]
 pub fn message_derive_internal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { crate };
     message_impl(input, shared_crate_name, false)
 }
 
 /// Derives the Message trait for a given struct, for FragmentedMessage
-#[proc_macro_derive(MessageFragment)]
+#[proc_macro_derive(MessageFragment, attributes(naia))]
 pub fn message_derive_fragment(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { crate };
     message_impl(input, shared_crate_name, true)
 }
 
 /// Derives the Message trait for a given struct
-#[proc_macro_derive(Message)]
+#[proc_macro_derive(Message, attributes(naia))]
 pub fn message_derive_shared(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_shared };
     message_impl(input, shared_crate_name, false)
 }
 
 /// Derives the Message trait for a given struct, for the Bevy adapter
-#[proc_macro_derive(MessageBevy)]
+#[proc_macro_derive(MessageBevy, attributes(naia))]
 pub fn message_derive_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_bevy_shared };
     message_impl(input, shared_crate_name, false)
 }
 
 /// Derives the Message trait for a given struct, for the Hecs adapter
-#[proc_macro_derive(MessageHecs)]
+#[proc_macro_derive(MessageHecs, attributes(naia))]
 pub fn message_derive_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let shared_crate_name = quote! { naia_hecs_shared };
     message_impl(input, shared_crate_name, false)
@@ -2,7 +2,11 @@ use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Index, LitStr, Member, Type};
 
-use super::shared::{get_struct_type, StructType};
+use super::shared::{
+    get_builder_marker, get_entity_property_schema, get_field_context, get_field_path,
+    get_field_schema, get_generics, get_name_expression, get_protocol_name_method, get_struct_type,
+    FieldAttributes, StructType,
+};
 
 pub fn message_impl(
     input: proc_macro::TokenStream,
@@ -14,6 +18,10 @@ pub fn message_impl(
     // Helper Properties
     let struct_type = get_struct_type(&input);
     let fields = get_fields(&input);
+    let generics = get_generics(&input, &shared_crate_name);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let turbofish = type_generics.as_turbofish();
+    let builder_marker = get_builder_marker(&generics);
 
     // Names
     let struct_name = input.ident;
@@ -24,18 +32,31 @@ pub fn message_impl(
     );
     let module_name = format_ident!("define_{}", lowercase_struct_name);
     let builder_name = format_ident!("{}Builder", struct_name);
+    let struct_path = quote! { #struct_name #turbofish };
+    let name_expression =
+        get_name_expression(&struct_name_str, &generics, &shared_crate_name);
+    let protocol_name_method =
+        get_protocol_name_method(&struct_name_str, &generics, &shared_crate_name);
 
     // Methods
     let clone_method = get_clone_method(&fields, &struct_type);
-    // let has_entity_propertys_method = get_has_entity_propertys_method(&fields);
+    let has_entity_relations_method = get_has_entity_relations_method(&fields);
     // let entities_method = get_entities_method(&fields, &struct_type);
     let relations_waiting_method = get_relations_waiting_method(&fields, &struct_type);
     let relations_complete_method = get_relations_complete_method(&fields, &struct_type);
-    let bit_length_method = get_bit_length_method(&fields, &struct_type);
-    let write_method = get_write_method(&fields, &struct_type);
-    let create_builder_method = get_create_builder_method(&builder_name);
-    let read_method = get_read_method(&struct_name, &fields, &struct_type);
+    let bit_length_method = get_bit_length_method(&fields, &struct_type, &shared_crate_name);
+    let write_method = get_write_method(&struct_name, &fields, &struct_type, &shared_crate_name);
+    let create_builder_method = get_create_builder_method(&quote! { #builder_name #turbofish });
+    let read_method = get_read_method(
+        &struct_name,
+        &struct_path,
+        &fields,
+        &struct_type,
+        &shared_crate_name,
+    );
     let is_fragment_method = get_is_fragment_method(is_fragment);
+    let layout_method = get_layout_method(&fields);
+    let fields_method = get_fields_method(&fields, &struct_type, &shared_crate_name);
 
     let gen = quote! {
         mod #module_name {
@@ -48,14 +69,21 @@ pub fn message_impl(
             };
             use super::*;
 
-            struct #builder_name;
-            impl MessageBuilder for #builder_name {
+            struct #builder_name #impl_generics (#builder_marker) #where_clause;
+            impl #impl_generics MessageBuilder for #builder_name #type_generics #where_clause {
                 #read_method
+                #layout_method
+                #fields_method
+            }
+            impl #impl_generics Named for #builder_name #type_generics #where_clause {
+                fn name(&self) -> String {
+                    return #name_expression;
+                }
             }
 
-            impl Message for #struct_name {
+            impl #impl_generics Message for #struct_name #type_generics #where_clause {
                 fn kind(&self) -> MessageKind {
-                    MessageKind::of::<#struct_name>()
+                    MessageKind::of::<Self>()
                 }
                 fn to_boxed_any(self: Box<Self>) -> Box<dyn Any> {
                     self
@@ -63,16 +91,18 @@ pub fn message_impl(
                 #is_fragment_method
                 #bit_length_method
                 #create_builder_method
+                #protocol_name_method
                 #relations_waiting_method
                 #relations_complete_method
+                #has_entity_relations_method
                 #write_method
             }
-            impl Named for #struct_name {
+            impl #impl_generics Named for #struct_name #type_generics #where_clause {
                 fn name(&self) -> String {
-                    return #struct_name_str.to_string();
+                    return #name_expression;
                 }
             }
-            impl Clone for #struct_name {
+            impl #impl_generics Clone for #struct_name #type_generics #where_clause {
                 #clone_method
             }
         }
@@ -96,6 +126,63 @@ fn get_is_fragment_method(is_fragment: bool) -> TokenStream {
     }
 }
 
+fn get_has_entity_relations_method(fields: &[Field]) -> TokenStream {
+    let value = fields
+        .iter()
+        .any(|field| matches!(field, Field::EntityProperty(_)));
+    quote! {
+        fn has_entity_relations(&self) -> bool {
+            #value
+        }
+    }
+}
+
+fn get_layout_method(fields: &[Field]) -> TokenStream {
+    let layout = fields
+        .iter()
+        .map(|field| match field {
+            Field::Normal(field) => {
+                let field_type = &field.field_type;
+                format!("{}: {}", field.variable_name, quote! { #field_type })
+            }
+            Field::EntityProperty(field) => format!("{}: EntityProperty", field.variable_name),
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    quote! {
+        fn layout(&self) -> &'static str {
+            #layout
+        }
+    }
+}
+
+fn get_fields_method(
+    fields: &[Field],
+    struct_type: &StructType,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let field_schemas = fields.iter().enumerate().map(|(index, field)| {
+        let field_path = get_field_path(&get_field_name(field, index, struct_type));
+        match field {
+            Field::Normal(field) => get_field_schema(
+                &field_path,
+                "Field",
+                &field.field_type,
+                &field.attributes,
+                shared_crate_name,
+            ),
+            Field::EntityProperty(_) => get_entity_property_schema(&field_path, shared_crate_name),
+        }
+    });
+
+    quote! {
+        fn fields(&self) -> Vec<#shared_crate_name::schema::FieldSchema> {
+            vec![#(#field_schemas),*]
+        }
+    }
+}
+
 fn get_clone_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
     let mut output = quote! {};
 
@@ -227,9 +314,11 @@ fn get_relations_complete_method(fields: &[Field], struct_type: &StructType) ->
 }
 
 pub fn get_read_method(
-    struct_name: &Ident,
+    type_name: &Ident,
+    struct_name: &TokenStream,
     fields: &[Field],
     struct_type: &StructType,
+    shared_crate_name: &TokenStream,
 ) -> TokenStream {
     let mut field_names = quote! {};
     for field in fields.iter() {
@@ -245,19 +334,28 @@ pub fn get_read_method(
     }
 
     let mut field_reads = quote! {};
-    for field in fields.iter() {
+    for (index, field) in fields.iter().enumerate() {
         let field_name = field.variable_name();
+        let field_path = get_field_path(&get_field_name(field, index, struct_type));
+        let context = get_field_context(&type_name.to_string(), &field_path);
         let new_output_right = match field {
             Field::EntityProperty(_property) => {
                 quote! {
-                    let #field_name = EntityProperty::new_read(reader, converter)?;
+                    reader.set_context(#context);
+                    let #field_name = EntityProperty::new_read(reader, converter)
+                        .map_err(|err| err.in_field::<EntityProperty>(#field_path))?;
                 }
             }
             Field::Normal(normal_field) => {
                 let field_name = &normal_field.variable_name;
                 let field_type = &normal_field.field_type;
+                let read =
+                    normal_field
+                        .attributes
+                        .de_expr(&field_path, field_type, shared_crate_name);
                 quote! {
-                    let #field_name = <#field_type>::de(reader)?;
+                    reader.set_context(#context);
+                    let #field_name = #read;
                 }
             }
         };
@@ -300,19 +398,32 @@ pub fn get_read_method(
     }
 }
 
-fn get_write_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
+fn get_write_method(
+    type_name: &Ident,
+    fields: &[Field],
+    struct_type: &StructType,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
     let mut field_writes = quote! {};
 
     for (index, field) in fields.iter().enumerate() {
         let field_name = get_field_name(field, index, struct_type);
+        let context = get_field_context(&type_name.to_string(), &get_field_path(&field_name));
         let new_output_right = match field {
-            Field::Normal(_) => {
+            Field::Normal(normal_field) => {
+                let write = normal_field.attributes.ser_stmt(
+                    quote! { &self.#field_name },
+                    &normal_field.field_type,
+                    shared_crate_name,
+                );
                 quote! {
-                    self.#field_name.ser(writer);
+                    writer.set_context(#context);
+                    #write
                 }
             }
             Field::EntityProperty(_) => {
                 quote! {
+                    writer.set_context(#context);
                     EntityProperty::write(&self.#field_name, writer, converter);
                 }
             }
@@ -333,15 +444,24 @@ fn get_write_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
     }
 }
 
-fn get_bit_length_method(fields: &[Field], struct_type: &StructType) -> TokenStream {
+fn get_bit_length_method(
+    fields: &[Field],
+    struct_type: &StructType,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
     let mut field_bit_lengths = quote! {};
 
     for (index, field) in fields.iter().enumerate() {
         let field_name = get_field_name(field, index, struct_type);
         let new_output_right = match field {
-            Field::Normal(_) => {
+            Field::Normal(normal_field) => {
+                let bit_length = normal_field.attributes.bit_length_expr(
+                    quote! { &self.#field_name },
+                    &normal_field.field_type,
+                    shared_crate_name,
+                );
                 quote! {
-                    output += self.#field_name.bit_length();
+                    output += #bit_length;
                 }
             }
             Field::EntityProperty(_) => {
@@ -368,10 +488,10 @@ fn get_bit_length_method(fields: &[Field], struct_type: &StructType) -> TokenStr
     }
 }
 
-pub fn get_create_builder_method(builder_name: &Ident) -> TokenStream {
+pub fn get_create_builder_method(builder_name: &TokenStream) -> TokenStream {
     quote! {
         fn create_builder() -> Box<dyn MessageBuilder> where Self:Sized {
-            Box::new(#builder_name)
+            Box::new(#builder_name(std::marker::PhantomData))
         }
     }
 }
@@ -397,12 +517,17 @@ fn get_fields(input: &DeriveInput) -> Vec<Field> {
                                         fields.push(Field::normal(
                                             variable_name.clone(),
                                             field.ty.clone(),
+                                            FieldAttributes::parse(&field.attrs),
                                         ));
                                     }
                                 }
                             }
                             _ => {
-                                fields.push(Field::normal(variable_name.clone(), field.ty.clone()));
+                                fields.push(Field::normal(
+                                    variable_name.clone(),
+                                    field.ty.clone(),
+                                    FieldAttributes::parse(&field.attrs),
+                                ));
                             }
                         }
                     }
@@ -419,9 +544,19 @@ fn get_fields(input: &DeriveInput) -> Vec<Field> {
                                 fields.push(Field::entity_property(variable_name));
                                 continue;
                             } else {
-                                fields.push(Field::normal(variable_name, field.ty.clone()))
+                                fields.push(Field::normal(
+                                    variable_name,
+                                    field.ty.clone(),
+                                    FieldAttributes::parse(&field.attrs),
+                                ))
                             }
                         }
+                    } else {
+                        fields.push(Field::normal(
+                            get_variable_name_for_unnamed_field(index, Span::call_site()),
+                            field.ty.clone(),
+                            FieldAttributes::parse(&field.attrs),
+                        ));
                     }
                 }
             }
@@ -464,6 +599,7 @@ pub struct EntityProperty {
 pub struct Normal {
     pub variable_name: Ident,
     pub field_type: Type,
+    pub attributes: FieldAttributes,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -483,10 +619,11 @@ impl Field {
         })
     }
 
-    pub fn normal(variable_name: Ident, field_type: Type) -> Self {
+    pub fn normal(variable_name: Ident, field_type: Type, attributes: FieldAttributes) -> Self {
         Self::Normal(Normal {
             variable_name: variable_name.clone(),
             field_type,
+            attributes,
         })
     }
 
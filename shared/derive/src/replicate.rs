@@ -1,11 +1,18 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, Index, LitStr, Member,
-    PathArguments, Type,
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, Index, LitStr,
+    Member, PathArguments, Type,
 };
 
-use crate::shared::{get_struct_type, StructType};
+use crate::{
+    replicate_enum::replicate_enum_impl,
+    shared::{
+        get_builder_marker, get_entity_property_schema, get_field_context, get_field_path,
+        get_field_schema, get_generics, get_name_expression, get_protocol_name_method,
+        get_struct_type, FieldAttributes, FieldCodec, StructType,
+    },
+};
 
 const UNNAMED_FIELD_PREFIX: &'static str = "unnamed_field_";
 
@@ -14,6 +21,69 @@ pub struct NormalProperty {
     pub inner_type: Type,
     pub uppercase_variable_name: Ident,
     pub index: usize,
+    pub attributes: FieldAttributes,
+    pub codec: Option<PropertyCodec>,
+}
+
+/// The codec functions given to a Property through a `#[naia(..)]` attribute
+pub struct PropertyCodec {
+    pub ser: TokenStream,
+    pub de: TokenStream,
+}
+
+impl PropertyCodec {
+    fn new(
+        attributes: &FieldAttributes,
+        inner_type: &Type,
+        shared_crate_name: &TokenStream,
+    ) -> Option<Self> {
+        Some(Self {
+            ser: attributes.codec_function("ser", inner_type, shared_crate_name)?,
+            de: attributes.codec_function("de", inner_type, shared_crate_name)?,
+        })
+    }
+}
+
+impl NormalProperty {
+    pub(crate) fn new_read_call(&self) -> TokenStream {
+        let field_type = &self.inner_type;
+        match &self.codec {
+            Some(PropertyCodec { de, .. }) => {
+                quote! { Property::<#field_type>::new_read_with(reader, #de) }
+            }
+            None => quote! { Property::<#field_type>::new_read(reader) },
+        }
+    }
+
+    pub(crate) fn read_write_call(&self, writer: TokenStream) -> TokenStream {
+        let field_type = &self.inner_type;
+        match &self.codec {
+            Some(PropertyCodec { ser, de }) => quote! {
+                Property::<#field_type>::read_write_with(reader, #writer, #de, #ser)
+            },
+            None => quote! { Property::<#field_type>::read_write(reader, #writer) },
+        }
+    }
+
+    /// Reads into the Property given as a `&mut Property<T>` expression
+    pub(crate) fn read_into_call(&self, property: TokenStream) -> TokenStream {
+        match &self.codec {
+            Some(PropertyCodec { de, .. }) => {
+                quote! { Property::read_with(#property, reader, #de) }
+            }
+            None => quote! { Property::read(#property, reader) },
+        }
+    }
+
+    /// Writes the Property given as a `&Property<T>` expression
+    pub(crate) fn write_from_call(&self, property: TokenStream) -> TokenStream {
+        match &self.codec {
+            Some(PropertyCodec { ser, .. }) => {
+                quote! { Property::write_with(#property, writer, #ser) }
+            }
+            None => quote! { Property::write(#property, writer) },
+        }
+    }
 }
 
 pub struct EntityProperty {
@@ -40,9 +110,17 @@ pub fn replicate_impl(
 ) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    if let Data::Enum(data_enum) = &input.data {
+        return replicate_enum_impl(&input, data_enum, shared_crate_name);
+    }
+
     // Helper Properties
-    let properties = get_properties(&input);
+    let properties = get_properties(&input, &shared_crate_name);
     let struct_type = get_struct_type(&input);
+    let generics = get_generics(&input, &shared_crate_name);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let turbofish = type_generics.as_turbofish();
+    let builder_marker = get_builder_marker(&generics);
 
     // Names
     let replica_name = input.ident.clone();
@@ -54,6 +132,11 @@ pub fn replicate_impl(
     let module_name = format_ident!("define_{}", lowercase_replica_name);
     let enum_name = format_ident!("{}Property", replica_name);
     let builder_name = format_ident!("{}Builder", replica_name);
+    let replica_path = quote! { #replica_name #turbofish };
+    let name_expression =
+        get_name_expression(&replica_name_str, &generics, &shared_crate_name);
+    let protocol_name_method =
+        get_protocol_name_method(&replica_name_str, &generics, &shared_crate_name);
 
     // Definitions
     let property_enum_definition = get_property_enum_definition(&enum_name, &properties);
@@ -68,26 +151,30 @@ pub fn replicate_impl(
 
     // Methods
     let new_complete_method =
-        get_new_complete_method(&replica_name, &enum_name, &properties, &struct_type);
-    let create_builder_method = get_create_builder_method(&builder_name);
-    let read_method = get_read_method(&replica_name, &properties, &struct_type);
-    let read_create_update_method = get_read_create_update_method(&replica_name, &properties);
+        get_new_complete_method(&replica_path, &enum_name, &properties, &struct_type);
+    let create_builder_method = get_create_builder_method(&quote! { #builder_name #turbofish });
+    let read_method = get_read_method(&replica_name, &replica_path, &properties, &struct_type);
+    let read_create_update_method =
+        get_read_create_update_method(&replica_name, &replica_path, &properties, &struct_type);
 
     let dyn_ref_method = get_dyn_ref_method();
     let dyn_mut_method = get_dyn_mut_method();
-    let clone_method = get_clone_method(&replica_name, &properties, &struct_type);
-    let mirror_method = get_mirror_method(&replica_name, &properties, &struct_type);
+    let clone_method = get_clone_method(&replica_path, &enum_name, &properties, &struct_type);
+    let mirror_method = get_mirror_method(&replica_path, &properties, &struct_type);
     let set_mutator_method = get_set_mutator_method(&properties, &struct_type);
     let read_apply_update_method = get_read_apply_update_method(&properties, &struct_type);
     let read_apply_field_update_method =
         get_read_apply_field_update_method(&properties, &struct_type);
-    let write_method = get_write_method(&properties, &struct_type);
-    let write_update_method = get_write_update_method(&enum_name, &properties, &struct_type);
+    let write_method = get_write_method(&replica_name, &properties, &struct_type);
+    let write_update_method =
+        get_write_update_method(&replica_name, &enum_name, &properties, &struct_type);
     // let has_entity_properties = get_has_entity_properties_method(&properties);
     // let entities = get_entities_method(&properties, &struct_type);
     let relations_waiting_method = get_relations_waiting_method(&properties, &struct_type);
     let relations_complete_method = get_relations_complete_method(&properties, &struct_type);
-    let split_update_method = get_split_update_method(&replica_name, &properties);
+    let split_update_method = get_split_update_method(&replica_path, &properties);
+    let layout_method = get_layout_method(&properties);
+    let fields_method = get_fields_method(&properties, &struct_type, &shared_crate_name);
 
     let gen = quote! {
         mod #module_name {
@@ -103,29 +190,31 @@ pub fn replicate_impl(
 
             #property_enum_definition
 
-            struct #builder_name;
-            impl ReplicateBuilder for #builder_name {
+            struct #builder_name #impl_generics (#builder_marker) #where_clause;
+            impl #impl_generics ReplicateBuilder for #builder_name #type_generics #where_clause {
                 #read_method
                 #read_create_update_method
                 #split_update_method
+                #layout_method
+                #fields_method
             }
-            impl Named for #builder_name {
+            impl #impl_generics Named for #builder_name #type_generics #where_clause {
                 fn name(&self) -> String {
-                    return #replica_name_str.to_string();
+                    return #name_expression;
                 }
             }
 
-            impl #replica_name {
+            impl #impl_generics #replica_name #type_generics #where_clause {
                 #new_complete_method
             }
-            impl Named for #replica_name {
+            impl #impl_generics Named for #replica_name #type_generics #where_clause {
                 fn name(&self) -> String {
-                    return #replica_name_str.to_string();
+                    return #name_expression;
                 }
             }
-            impl Replicate for #replica_name {
+            impl #impl_generics Replicate for #replica_name #type_generics #where_clause {
                 fn kind(&self) -> ComponentKind {
-                    ComponentKind::of::<#replica_name>()
+                    ComponentKind::of::<Self>()
                 }
                 fn to_any(&self) -> &dyn Any {
                     self
@@ -141,6 +230,7 @@ pub fn replicate_impl(
                 }
                 fn diff_mask_size(&self) -> u8 { #diff_mask_size }
                 #create_builder_method
+                #protocol_name_method
                 #dyn_ref_method
                 #dyn_mut_method
                 #mirror_method
@@ -152,7 +242,7 @@ pub fn replicate_impl(
                 #relations_waiting_method
                 #relations_complete_method
             }
-            impl Clone for #replica_name {
+            impl #impl_generics Clone for #replica_name #type_generics #where_clause {
                 #clone_method
             }
         }
@@ -162,7 +252,7 @@ pub fn replicate_impl(
 }
 
 /// Create a variable name for unnamed fields
-fn get_variable_name_for_unnamed_field(index: usize, span: Span) -> Ident {
+pub(crate) fn get_variable_name_for_unnamed_field(index: usize, span: Span) -> Ident {
     Ident::new(&format!("{}{}", UNNAMED_FIELD_PREFIX, index), span)
 }
 
@@ -184,11 +274,20 @@ fn get_field_name(property: &Property, struct_type: &StructType) -> Member {
 }
 
 impl Property {
-    pub fn normal(index: usize, variable_name: Ident, inner_type: Type) -> Self {
+    pub fn normal(
+        index: usize,
+        variable_name: Ident,
+        inner_type: Type,
+        attributes: FieldAttributes,
+        shared_crate_name: &TokenStream,
+    ) -> Self {
+        let codec = PropertyCodec::new(&attributes, &inner_type, shared_crate_name);
         Self::Normal(NormalProperty {
             index,
             variable_name: variable_name.clone(),
             inner_type,
+            attributes,
+            codec,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -246,7 +345,369 @@ impl Property {
     }
 }
 
-fn get_properties(input: &DeriveInput) -> Vec<Property> {
+// Field generators, shared by structs & the variants of enums. A field is given
+// as an expression of a reference to it, such as `&self.field` in a struct, or
+// a binding of the field in a pattern matching an enum variant.
+impl Property {
+    /// Get the type a field is reported as in deserialization errors
+    pub(crate) fn error_type(&self) -> TokenStream {
+        match self {
+            Self::Normal(property) => {
+                let field_type = &property.inner_type;
+                quote! { #field_type }
+            }
+            Self::Entity(_) => quote! { EntityProperty },
+            Self::NonReplicated(property) => {
+                let field_type = &property.field_type;
+                quote! { #field_type }
+            }
+        }
+    }
+
+    /// Get the entry of the mutator index enum naming this field
+    pub(crate) fn mutator_index_variant(&self) -> TokenStream {
+        let index = syn::Index::from(self.index());
+        let uppercase_variant_name = self.uppercase_variable_name();
+        quote! { #uppercase_variant_name = #index as u8, }
+    }
+
+    /// Get the argument taken for this field by the constructors of the
+    /// Replica, if it takes one
+    pub(crate) fn constructor_arg(&self) -> Option<TokenStream> {
+        match self {
+            Self::Normal(property) => {
+                let field_name = &property.variable_name;
+                let field_type = &property.inner_type;
+                Some(quote! { #field_name: #field_type })
+            }
+            Self::NonReplicated(property) => {
+                let field_name = &property.variable_name;
+                let field_type = &property.field_type;
+                Some(quote! { #field_name: #field_type })
+            }
+            Self::Entity(_) => None,
+        }
+    }
+
+    /// Get the value of this field in a new host-owned Replica, from the
+    /// expression of its value. An EntityProperty starts out unset.
+    pub(crate) fn host_owned_value(&self, enum_name: &Ident, value: TokenStream) -> TokenStream {
+        match self {
+            Self::Normal(property) => {
+                let field_type = &property.inner_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    Property::<#field_type>::host_owned(#value, #enum_name::#uppercase_variant_name as u8)
+                }
+            }
+            Self::Entity(property) => {
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    EntityProperty::with_mutator(#enum_name::#uppercase_variant_name as u8)
+                }
+            }
+            Self::NonReplicated(_) => value,
+        }
+    }
+
+    /// Get a host-owned copy of this field, without a mutator
+    pub(crate) fn clone_value(&self, enum_name: &Ident, field: TokenStream) -> TokenStream {
+        match self {
+            Self::Normal(_) => self.host_owned_value(enum_name, quote! { (**#field).clone() }),
+            Self::Entity(_) => {
+                let new_property = self.host_owned_value(enum_name, quote! {});
+                quote! {
+                    {
+                        let mut new_property = #new_property;
+                        new_property.mirror(#field);
+                        new_property
+                    }
+                }
+            }
+            Self::NonReplicated(_) => quote! { Clone::clone(#field) },
+        }
+    }
+
+    pub(crate) fn mirror(&self, field: TokenStream, other_field: TokenStream) -> TokenStream {
+        quote! { #field.mirror(#other_field); }
+    }
+
+    pub(crate) fn set_mutator(&self, field: TokenStream) -> TokenStream {
+        quote! { #field.set_mutator(mutator); }
+    }
+
+    /// Get statements reading a new value of this field into a variable of
+    /// its name, in the given probability context
+    pub(crate) fn new_read(&self, field_path: &str, context: Option<u32>) -> TokenStream {
+        let error_type = self.error_type();
+        let set_context = context.map(|context| quote! { reader.set_context(#context); });
+        match self {
+            Self::Normal(property) => {
+                let field_name = &property.variable_name;
+                let new_read = property.new_read_call();
+                quote! {
+                    #set_context
+                    let #field_name = #new_read
+                        .map_err(|err| err.in_field::<#error_type>(#field_path))?;
+                }
+            }
+            Self::Entity(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    #set_context
+                    let #field_name = EntityProperty::new_read(reader, converter)
+                        .map_err(|err| err.in_field::<#error_type>(#field_path))?;
+                }
+            }
+            Self::NonReplicated(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    let #field_name = <#error_type>::default();
+                }
+            }
+        }
+    }
+
+    /// Get statements copying an update of this field from `reader` into
+    /// `update_writer`
+    pub(crate) fn read_create_update(&self, context: u32) -> TokenStream {
+        let read_write = match self {
+            Self::Normal(property) => property.read_write_call(quote! { &mut update_writer }),
+            Self::Entity(_) => quote! { EntityProperty::read_write(reader, &mut update_writer) },
+            Self::NonReplicated(_) => unreachable!(),
+        };
+        quote! {
+            {
+                reader.set_context(#context);
+                let should_read = bool::de(reader)?;
+                should_read.ser(&mut update_writer);
+                if should_read {
+                    #read_write?;
+                }
+            }
+        }
+    }
+
+    /// Get statements copying an update of this field from `reader` into
+    /// `ready_writer`, or into `waiting_updates` if it is an EntityProperty
+    /// waiting on an entity. In a Replica which can switch variant, a waiting
+    /// EntityProperty is written unset when `switched`, as a new variant needs
+    /// every field.
+    pub(crate) fn split_update(&self, can_switch: bool) -> TokenStream {
+        match self {
+            Self::Normal(property) => {
+                let read_write = property.read_write_call(quote! { &mut ready_writer });
+                quote! {
+                    let should_read = bool::de(reader)?;
+                    should_read.ser(&mut ready_writer);
+                    if should_read {
+                        #read_write?;
+                        ready_did_write = true;
+                    }
+                }
+            }
+            Self::Entity(property) => {
+                let index = property.index as u8;
+                let waiting_ready_write = if can_switch {
+                    quote! {
+                        if switched {
+                            true.ser(&mut ready_writer);
+                        }
+                        false.ser(&mut ready_writer);
+                    }
+                } else {
+                    quote! {
+                        false.ser(&mut ready_writer);
+                    }
+                };
+                quote! {
+                    let should_read = bool::de(reader)?;
+                    if should_read {
+                        // copy property to read whether it is waiting or not
+                        let prop_copy = EntityProperty::new_read(reader, converter)?;
+
+                        // get waiting local entity from copy after read
+                        let waiting_entity_opt = prop_copy.waiting_local_entity();
+                        if let Some(waiting_entity) = waiting_entity_opt {
+                            waiting_did_write = true;
+
+                            // property is waiting on waiting_entity, write into the waiting_writer
+                            let mut waiting_writer = BitWriter::new();
+                            true.ser(&mut waiting_writer);
+                            waiting_entity.owned_ser(&mut waiting_writer);
+                            waiting_updates.push((waiting_entity, ComponentFieldUpdate::new(#index, waiting_writer.to_owned_reader())));
+
+                            #waiting_ready_write
+                        } else {
+                            ready_did_write = true;
+
+                            // write ready update into ready writer
+                            true.ser(&mut ready_writer);
+                            prop_copy.write_local_entity(converter, &mut ready_writer);
+                        }
+                    } else {
+                        // Neither writer gets an update here
+                        false.ser(&mut ready_writer);
+                    }
+                }
+            }
+            Self::NonReplicated(_) => unreachable!(),
+        }
+    }
+
+    /// Get statements reading an update of this field into it, if one was
+    /// written
+    pub(crate) fn read_apply_update(&self, field: TokenStream, field_path: &str) -> TokenStream {
+        let error_type = self.error_type();
+        let read = match self {
+            Self::Normal(property) => property.read_into_call(field),
+            Self::Entity(_) => quote! { EntityProperty::read(#field, reader, converter) },
+            Self::NonReplicated(_) => unreachable!(),
+        };
+        quote! {
+            if bool::de(reader)? {
+                #read
+                    .map_err(|err| err.in_field::<#error_type>(#field_path))?;
+            }
+        }
+    }
+
+    /// Get a statement reading the update of an EntityProperty which was
+    /// waiting on its entity
+    pub(crate) fn read_apply_field_update(&self, field: TokenStream) -> TokenStream {
+        quote! { EntityProperty::read(#field, reader, converter)?; }
+    }
+
+    fn write_call(&self, field: TokenStream) -> TokenStream {
+        match self {
+            Self::Normal(property) => property.write_from_call(field),
+            Self::Entity(_) => quote! { EntityProperty::write(#field, writer, converter) },
+            Self::NonReplicated(_) => unreachable!(),
+        }
+    }
+
+    pub(crate) fn write(&self, field: TokenStream, context: u32) -> TokenStream {
+        let write = self.write_call(field);
+        quote! {
+            writer.set_context(#context);
+            #write;
+        }
+    }
+
+    /// Get statements writing this field if its bit of the DiffMask is set,
+    /// or always when `switched`, in a Replica which can switch variant
+    pub(crate) fn write_update(
+        &self,
+        field: TokenStream,
+        context: u32,
+        enum_name: &Ident,
+        can_switch: bool,
+    ) -> TokenStream {
+        let uppercase_variant_name = self.uppercase_variable_name();
+        let mutated =
+            quote! { diff_mask.bit(#enum_name::#uppercase_variant_name as u8) == Some(true) };
+        let condition = if can_switch {
+            quote! { switched || #mutated }
+        } else {
+            mutated
+        };
+        let write = self.write_call(field);
+        quote! {
+            writer.set_context(#context);
+            if #condition {
+                true.ser(writer);
+                #write;
+            } else {
+                false.ser(writer);
+            }
+        }
+    }
+
+    pub(crate) fn relation_waiting(&self, field: TokenStream) -> TokenStream {
+        quote! {
+            if let Some(local_entity) = #field.waiting_local_entity() {
+                output.insert(local_entity);
+            }
+        }
+    }
+
+    pub(crate) fn relation_complete(&self, field: TokenStream) -> TokenStream {
+        quote! {
+            if #field.waiting_local_entity().is_some() {
+                #field.waiting_complete(converter);
+            }
+        }
+    }
+
+    /// Describe this field in the layout of the Replica
+    pub(crate) fn layout(&self) -> Option<String> {
+        match self {
+            Self::Normal(property) => {
+                let inner_type = &property.inner_type;
+                Some(format!(
+                    "{}: Property<{}>",
+                    property.variable_name,
+                    quote! { #inner_type }
+                ))
+            }
+            Self::Entity(property) => Some(format!("{}: EntityProperty", property.variable_name)),
+            Self::NonReplicated(_) => None,
+        }
+    }
+
+    pub(crate) fn schema(&self, field_path: &str, shared_crate_name: &TokenStream) -> TokenStream {
+        match self {
+            Self::Normal(property) => get_field_schema(
+                field_path,
+                "Property",
+                &property.inner_type,
+                &property.attributes,
+                shared_crate_name,
+            ),
+            Self::Entity(_) => get_entity_property_schema(field_path, shared_crate_name),
+            Self::NonReplicated(_) => unreachable!(),
+        }
+    }
+}
+
+/// Get an expression building a Replica, or a variant of one, at `path`, with
+/// the value of each field given by `value`
+pub(crate) fn get_construction<'a>(
+    path: &TokenStream,
+    struct_type: &StructType,
+    properties: impl Iterator<Item = &'a Property>,
+    value: impl Fn(&Property) -> TokenStream,
+) -> TokenStream {
+    match struct_type {
+        StructType::Struct => {
+            let fields = properties.map(|property| {
+                let field_name = property.variable_name();
+                let field_value = value(property);
+                quote! { #field_name: #field_value }
+            });
+            quote! { #path { #(#fields),* } }
+        }
+        StructType::TupleStruct => {
+            let fields = properties.map(value);
+            quote! { #path(#(#fields),*) }
+        }
+        StructType::UnitStruct => quote! { #path },
+    }
+}
+
+// Get the options given to a Property field through `#[naia(..)]` attributes
+pub(crate) fn get_property_attributes(attrs: &[Attribute]) -> FieldAttributes {
+    let attributes = FieldAttributes::parse(attrs);
+    if attributes.max_len.is_some() {
+        panic!("`max_len` can't be given to a Property");
+    }
+    if let FieldCodec::Skip(_) = attributes.codec {
+        panic!("`skip` can't be given to a Property, use a non-Property field instead");
+    }
+    attributes
+}
+
+fn get_properties(input: &DeriveInput, shared_crate_name: &TokenStream) -> Vec<Property> {
     let mut fields = Vec::new();
 
     if let Data::Struct(data_struct) = &input.data {
@@ -276,6 +737,8 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                                                 fields.len(),
                                                 variable_name.clone(),
                                                 inner_type.clone(),
+                                                get_property_attributes(&field.attrs),
+                                                shared_crate_name,
                                             ));
                                             continue;
                                         }
@@ -312,6 +775,8 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                                         fields.len(),
                                         variable_name,
                                         inner_type.clone(),
+                                        get_property_attributes(&field.attrs),
+                                        shared_crate_name,
                                     ));
                                     continue;
                                 }
@@ -323,7 +788,7 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
             Fields::Unit => {}
         }
     } else {
-        panic!("Can only derive Replicate on a struct");
+        panic!("Can only derive Replicate on a struct or an enum");
     }
 
     fields
@@ -338,26 +803,15 @@ fn get_property_enum_definition(enum_name: &Ident, properties: &[Property]) -> T
 
     let hashtag = Punct::new('#', Spacing::Alone);
 
-    let mut variant_list = quote! {};
-
-    for (_, property) in properties.iter().filter(|p| p.is_replicated()).enumerate() {
-        let index = syn::Index::from(property.index());
-        let uppercase_variant_name = property.uppercase_variable_name();
-
-        let new_output_right = quote! {
-            #uppercase_variant_name = #index as u8,
-        };
-        let new_output_result = quote! {
-            #variant_list
-            #new_output_right
-        };
-        variant_list = new_output_result;
-    }
+    let variant_list = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(Property::mutator_index_variant);
 
     quote! {
         #hashtag[repr(u8)]
         enum #enum_name {
-            #variant_list
+            #(#variant_list)*
         }
     }
 }
@@ -379,81 +833,40 @@ pub fn get_dyn_mut_method() -> TokenStream {
 }
 
 fn get_clone_method(
-    replica_name: &Ident,
+    replica_name: &TokenStream,
+    enum_name: &Ident,
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut output = quote! {};
-    let mut entity_property_output = quote! {};
-
-    for property in properties.iter() {
+    let construction = get_construction(replica_name, struct_type, properties.iter(), |property| {
         let field_name = get_field_name(property, struct_type);
-        match property {
-            Property::Normal(_) => {
-                let new_output_right = quote! {
-                    (*self.#field_name).clone(),
-                };
-                let new_output_result = quote! {
-                    #output
-                    #new_output_right
-                };
-                output = new_output_result;
-            }
-            Property::NonReplicated(_) => {
-                let new_output_right = quote! {
-                    (self.#field_name).clone(),
-                };
-                let new_output_result = quote! {
-                    #output
-                    #new_output_right
-                };
-                output = new_output_result;
-            }
-            Property::Entity(_) => {
-                let new_output_right = quote! {
-                    new_clone.#field_name.mirror(&self.#field_name);
-                };
-                let new_output_result = quote! {
-                    #entity_property_output
-                    #new_output_right
-                };
-                entity_property_output = new_output_result;
-            }
-        };
-    }
+        property.clone_value(enum_name, quote! { &self.#field_name })
+    });
 
     quote! {
         fn clone(&self) -> #replica_name {
-            let mut new_clone = #replica_name::new_complete(#output);
-            #entity_property_output
-            return new_clone;
+            #construction
         }
     }
 }
 
 fn get_mirror_method(
-    replica_name: &Ident,
+    replica_name: &TokenStream,
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut output = quote! {};
-
-    for property in properties.iter().filter(|p| p.is_replicated()) {
-        let field_name = get_field_name(property, struct_type);
-        let new_output_right = quote! {
-            self.#field_name.mirror(&replica.#field_name);
-        };
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
-    }
+    let mirrors = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_name = get_field_name(property, struct_type);
+            property.mirror(quote! { self.#field_name }, quote! { &replica.#field_name })
+        });
 
     quote! {
         fn mirror(&mut self, other: &dyn Replicate) {
             if let Some(replica) = other.to_any().downcast_ref::<#replica_name>() {
-                #output
+                #(#mirrors)*
             } else {
                 panic!("cannot mirror: other Component is of another type!");
             }
@@ -462,301 +875,95 @@ fn get_mirror_method(
 }
 
 fn get_set_mutator_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
-    let mut output = quote! {};
-
-    for property in properties.iter().filter(|p| p.is_replicated()) {
-        let field_name = get_field_name(property, struct_type);
-        let new_output_right = quote! {
-                self.#field_name.set_mutator(mutator);
-        };
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
-    }
+    let set_mutators = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_name = get_field_name(property, struct_type);
+            property.set_mutator(quote! { self.#field_name })
+        });
 
     quote! {
         fn set_mutator(&mut self, mutator: &PropertyMutator) {
-            #output
+            #(#set_mutators)*
         }
     }
 }
 
 pub fn get_new_complete_method(
-    replica_name: &Ident,
+    replica_name: &TokenStream,
     enum_name: &Ident,
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut args = quote! {};
-    for property in properties.iter() {
-        match property {
-            Property::Normal(property) => {
-                let field_name = &property.variable_name;
-                let field_type = &property.inner_type;
-
-                let new_output_right = quote! {
-                    #field_name: #field_type,
-                };
-
-                let new_output_result = quote! {
-                    #args #new_output_right
-                };
-                args = new_output_result;
-            }
-            Property::NonReplicated(property) => {
-                let field_name = &property.variable_name;
-                let field_type = &property.field_type;
-
-                let new_output_right = quote! {
-                    #field_name: #field_type,
-                };
-
-                let new_output_result = quote! {
-                    #args #new_output_right
-                };
-                args = new_output_result;
-            }
-            Property::Entity(_) => {
-                continue;
-            }
-        };
-    }
-
-    let mut fields = quote! {};
-    for property in properties.iter() {
-        let new_output_right = match property {
-            Property::Normal(property) => {
-                let field_name = &property.variable_name;
-                let field_type = &property.inner_type;
-                let uppercase_variant_name = &property.uppercase_variable_name;
-
-                match *struct_type {
-                    StructType::Struct => {
-                        quote! {
-                            #field_name: Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
-                        }
-                    }
-                    StructType::TupleStruct => {
-                        quote! {
-                            Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
-                        }
-                    }
-                    _ => {
-                        quote! {}
-                    }
-                }
-            }
-            Property::Entity(property) => {
-                let field_name = &property.variable_name;
-                let uppercase_variant_name = &property.uppercase_variable_name;
-
-                match *struct_type {
-                    StructType::Struct => {
-                        quote! {
-                             #field_name: EntityProperty::with_mutator(#enum_name::#uppercase_variant_name as u8)
-                        }
-                    }
-                    StructType::TupleStruct => {
-                        quote! {
-                            EntityProperty::with_mutator(#enum_name::#uppercase_variant_name as u8)
-                        }
-                    }
-                    _ => {
-                        quote! {}
-                    }
-                }
-            }
-            Property::NonReplicated(property) => {
-                let field_name = &property.variable_name;
-                match *struct_type {
-                    StructType::Struct => {
-                        quote! {
-                             #field_name: #field_name
-                        }
-                    }
-                    StructType::TupleStruct => {
-                        quote! {
-                            #field_name
-                        }
-                    }
-                    _ => {
-                        quote! {}
-                    }
-                }
-            }
-        };
-
-        let new_output_result = quote! {
-            #fields
-            #new_output_right,
-        };
-        fields = new_output_result;
-    }
-
-    let fn_inner = match *struct_type {
-        StructType::Struct => {
-            quote! {
-                #replica_name {
-                    #fields
-                }
-            }
-        }
-        StructType::TupleStruct => {
-            quote! {
-                #replica_name (
-                    #fields
-                )
-            }
-        }
-        StructType::UnitStruct => {
-            quote! {
-                #replica_name
-            }
-        }
-    };
+    let args = properties.iter().filter_map(Property::constructor_arg);
+    let construction = get_construction(replica_name, struct_type, properties.iter(), |property| {
+        let field_name = property.variable_name();
+        property.host_owned_value(enum_name, quote! { #field_name })
+    });
 
     quote! {
-        pub fn new_complete(#args) -> #replica_name {
-            #fn_inner
+        pub fn new_complete(#(#args),*) -> #replica_name {
+            #construction
         }
     }
 }
 
-pub fn get_create_builder_method(builder_name: &Ident) -> TokenStream {
+pub fn get_create_builder_method(builder_name: &TokenStream) -> TokenStream {
     quote! {
         fn create_builder() -> Box<dyn ReplicateBuilder> where Self:Sized {
-            Box::new(#builder_name)
+            Box::new(#builder_name(std::marker::PhantomData))
         }
     }
 }
 
 pub fn get_read_method(
-    replica_name: &Ident,
+    type_name: &Ident,
+    replica_name: &TokenStream,
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut prop_names = quote! {};
-    for property in properties.iter() {
-        let field_name = property.variable_name();
-        let new_output_right = quote! {
-            #field_name
-        };
-        let new_output_result = quote! {
-            #prop_names
-            #new_output_right,
-        };
-        prop_names = new_output_result;
-    }
-
-    let mut prop_reads = quote! {};
-    for property in properties.iter() {
-        let field_name = property.variable_name();
-        let new_output_right = match property {
-            Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
-                quote! {
-                    let #field_name = Property::<#field_type>::new_read(reader)?;
-                }
-            }
-            Property::Entity(_) => {
-                quote! {
-                    let #field_name = EntityProperty::new_read(reader, converter)?;
-                }
-            }
-            Property::NonReplicated(inner_property) => {
-                let field_name = &inner_property.variable_name;
-                let field_type = &inner_property.field_type;
-                quote! {
-                    let #field_name = <#field_type>::default();
-                }
-            }
-        };
-
-        let new_output_result = quote! {
-            #prop_reads
-            #new_output_right
-        };
-        prop_reads = new_output_result;
-    }
-
-    let replica_build = match *struct_type {
-        StructType::Struct => {
-            quote! {
-                #replica_name {
-                    #prop_names
-                }
-            }
-        }
-        StructType::TupleStruct => {
-            quote! {
-                #replica_name (
-                    #prop_names
-                )
-            }
-        }
-        StructType::UnitStruct => {
-            quote! {
-                #replica_name
-            }
-        }
-    };
+    let prop_reads = properties.iter().map(|property| {
+        let field_path = get_field_path(&get_field_name(property, struct_type));
+        let context = get_field_context(&type_name.to_string(), &field_path);
+        property.new_read(&field_path, Some(context))
+    });
+    let replica_build =
+        get_construction(replica_name, struct_type, properties.iter(), |property| {
+            let field_name = property.variable_name();
+            quote! { #field_name }
+        });
 
     quote! {
         fn read(&self, reader: &mut BitReader, converter: &dyn LocalEntityAndGlobalEntityConverter) -> Result<Box<dyn Replicate>, SerdeErr> {
-            #prop_reads
+            #(#prop_reads)*
 
             return Ok(Box::new(#replica_build));
         }
     }
 }
 
-pub fn get_read_create_update_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
-    let mut prop_read_writes = quote! {};
-    for property in properties.iter() {
-        let new_output_right = match property {
-            Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
-                quote! {
-                    {
-                        let should_read = bool::de(reader)?;
-                        should_read.ser(&mut update_writer);
-                        if should_read {
-                            Property::<#field_type>::read_write(reader, &mut update_writer)?;
-                        }
-                    }
-                }
-            }
-            Property::Entity(_) => {
-                quote! {
-                    {
-                        let should_read = bool::de(reader)?;
-                        should_read.ser(&mut update_writer);
-                        if should_read {
-                            EntityProperty::read_write(reader, &mut update_writer)?;
-                        }
-                    }
-                }
-            }
-            Property::NonReplicated(_) => {
-                continue;
-            }
-        };
-
-        let new_output_result = quote! {
-            #prop_read_writes
-            #new_output_right
-        };
-        prop_read_writes = new_output_result;
-    }
+pub fn get_read_create_update_method(
+    type_name: &Ident,
+    replica_name: &TokenStream,
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let prop_read_writes = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_path = get_field_path(&get_field_name(property, struct_type));
+            let context = get_field_context(&type_name.to_string(), &field_path);
+            property.read_create_update(context)
+        });
 
     quote! {
         fn read_create_update(&self, reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
 
             let mut update_writer = BitWriter::new();
 
-            #prop_read_writes
+            #(#prop_read_writes)*
 
             let owned_reader = update_writer.to_owned_reader();
 
@@ -765,63 +972,45 @@ pub fn get_read_create_update_method(replica_name: &Ident, properties: &[Propert
     }
 }
 
-fn get_split_update_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
-    let mut output = quote! {};
+fn get_layout_method(properties: &[Property]) -> TokenStream {
+    let layout = properties
+        .iter()
+        .filter_map(Property::layout)
+        .collect::<Vec<String>>()
+        .join(", ");
 
-    for property in properties.iter() {
-        let new_output_right = match property {
-            Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
-                quote! {
-                    let should_read = bool::de(reader)?;
-                    should_read.ser(&mut ready_writer);
-                    if should_read {
-                        Property::<#field_type>::read_write(reader, &mut ready_writer)?;
-                        ready_did_write = true;
-                    }
-                }
-            }
-            Property::Entity(inner_property) => {
-                let index = inner_property.index as u8;
-                quote! {
-                    let should_read = bool::de(reader)?;
-                    if should_read {
-                        // copy property to read whether it is waiting or not
-                        let prop_copy = EntityProperty::new_read(reader, converter)?;
-
-                        // get waiting local entity from copy after read
-                        let waiting_entity_opt = prop_copy.waiting_local_entity();
-                        if let Some(waiting_entity) = waiting_entity_opt {
-                            waiting_did_write = true;
-
-                            // property is waiting on waiting_entity, write into the waiting_writer
-                            let mut waiting_writer = BitWriter::new();
-                            waiting_entity.owned_ser(&mut waiting_writer);
-                            waiting_updates.push((waiting_entity, ComponentFieldUpdate::new(#index, waiting_writer.to_owned_reader())));
-                        } else {
-                            ready_did_write = true;
+    quote! {
+        fn layout(&self) -> &'static str {
+            #layout
+        }
+    }
+}
 
-                            // write ready update into ready writer
-                            true.ser(&mut ready_writer);
-                            prop_copy.write_local_entity(converter, &mut ready_writer);
-                        }
-                    } else {
-                        // Neither writer gets an update here
-                        false.ser(&mut ready_writer);
-                    }
-                }
-            }
-            Property::NonReplicated(_) => {
-                continue;
-            }
-        };
+fn get_fields_method(
+    properties: &[Property],
+    struct_type: &StructType,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let field_schemas = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_path = get_field_path(&get_field_name(property, struct_type));
+            property.schema(&field_path, shared_crate_name)
+        });
 
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
+    quote! {
+        fn fields(&self) -> Vec<#shared_crate_name::schema::FieldSchema> {
+            vec![#(#field_schemas),*]
+        }
     }
+}
+
+fn get_split_update_method(replica_name: &TokenStream, properties: &[Property]) -> TokenStream {
+    let splits = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| property.split_update(false));
 
     quote! {
         fn split_update(
@@ -841,7 +1030,7 @@ fn get_split_update_method(replica_name: &Ident, properties: &[Property]) -> Tok
             let mut ready_writer = BitWriter::new();
             let mut ready_did_write = false;
 
-            #output
+            #(#splits)*
 
             let waiting_result = {
                 if waiting_did_write {
@@ -864,41 +1053,19 @@ fn get_split_update_method(replica_name: &Ident, properties: &[Property]) -> Tok
 }
 
 fn get_read_apply_update_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
-    let mut output = quote! {};
-
-    for property in properties.iter() {
-        let field_name = get_field_name(property, struct_type);
-        let new_output_right = match property {
-            Property::Normal(_) => {
-                quote! {
-                    if bool::de(reader)? {
-                        Property::read(&mut self.#field_name, reader)?;
-                    }
-                }
-            }
-            Property::Entity(_) => {
-                quote! {
-                    if bool::de(reader)? {
-                        EntityProperty::read(&mut self.#field_name, reader, converter)?;
-                    }
-                }
-            }
-            Property::NonReplicated(_) => {
-                continue;
-            }
-        };
-
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
-    }
+    let reads = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_name = get_field_name(property, struct_type);
+            let field_path = get_field_path(&field_name);
+            property.read_apply_update(quote! { &mut self.#field_name }, &field_path)
+        });
 
     quote! {
         fn read_apply_update(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter, mut update: ComponentUpdate) -> Result<(), SerdeErr> {
             let reader = &mut update.reader();
-            #output
+            #(#reads)*
             Ok(())
         }
     }
@@ -908,36 +1075,25 @@ fn get_read_apply_field_update_method(
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut output = quote! {};
-
-    for property in properties.iter() {
+    let arms = properties.iter().filter_map(|property| {
+        let Property::Entity(inner_property) = property else {
+            return None;
+        };
+        let index = inner_property.index as u8;
         let field_name = get_field_name(property, struct_type);
-        let new_output_right = match property {
-            Property::Normal(_) | Property::NonReplicated(_) => {
-                continue;
-            }
-            Property::Entity(inner_property) => {
-                let index = inner_property.index as u8;
-                quote! {
-                    #index => {
-                        EntityProperty::read(&mut self.#field_name, reader, converter)?;
-                    }
-                }
+        let read = property.read_apply_field_update(quote! { &mut self.#field_name });
+        Some(quote! {
+            #index => {
+                #read
             }
-        };
-
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
-    }
+        })
+    });
 
     quote! {
         fn read_apply_field_update(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter, mut update: ComponentFieldUpdate) -> Result<(), SerdeErr> {
             let reader = &mut update.reader();
             match update.field_id() {
-                #output
+                #(#arms)*
                 _ => {}
             }
             Ok(())
@@ -945,89 +1101,46 @@ fn get_read_apply_field_update_method(
     }
 }
 
-fn get_write_method(properties: &[Property], struct_type: &StructType) -> TokenStream {
-    let mut property_writes = quote! {};
-
-    for property in properties.iter() {
-        let field_name = get_field_name(property, struct_type);
-        let new_output_right = match property {
-            Property::Normal(_) => {
-                quote! {
-                    Property::write(&self.#field_name, writer);
-                }
-            }
-            Property::Entity(_) => {
-                quote! {
-                    EntityProperty::write(&self.#field_name, writer, converter);
-                }
-            }
-            Property::NonReplicated(_) => {
-                continue;
-            }
-        };
-
-        let new_output_result = quote! {
-            #property_writes
-            #new_output_right
-        };
-        property_writes = new_output_result;
-    }
+fn get_write_method(
+    type_name: &Ident,
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let property_writes = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_name = get_field_name(property, struct_type);
+            let context = get_field_context(&type_name.to_string(), &get_field_path(&field_name));
+            property.write(quote! { &self.#field_name }, context)
+        });
 
     quote! {
         fn write(&self, component_kinds: &ComponentKinds, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
             self.kind().ser(component_kinds, writer);
-            #property_writes
+            #(#property_writes)*
         }
     }
 }
 
 fn get_write_update_method(
+    type_name: &Ident,
     enum_name: &Ident,
     properties: &[Property],
     struct_type: &StructType,
 ) -> TokenStream {
-    let mut output = quote! {};
-
-    for property in properties.iter() {
-        let field_name = get_field_name(property, struct_type);
-        let new_output_right = match property {
-            Property::Normal(property) => {
-                let uppercase_variant_name = &property.uppercase_variable_name;
-                quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
-                        true.ser(writer);
-                        Property::write(&self.#field_name, writer);
-                    } else {
-                        false.ser(writer);
-                    }
-                }
-            }
-            Property::Entity(property) => {
-                let uppercase_variant_name = &property.uppercase_variable_name;
-                quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
-                        true.ser(writer);
-                        EntityProperty::write(&self.#field_name, writer, converter);
-                    } else {
-                        false.ser(writer);
-                    }
-                }
-            }
-            Property::NonReplicated(_) => {
-                continue;
-            }
-        };
-
-        let new_output_result = quote! {
-            #output
-            #new_output_right
-        };
-        output = new_output_result;
-    }
+    let writes = properties
+        .iter()
+        .filter(|property| property.is_replicated())
+        .map(|property| {
+            let field_name = get_field_name(property, struct_type);
+            let context = get_field_context(&type_name.to_string(), &get_field_path(&field_name));
+            property.write_update(quote! { &self.#field_name }, context, enum_name, false)
+        });
 
     quote! {
         fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
-            #output
+            #(#writes)*
         }
     }
 }
@@ -1079,28 +1192,18 @@ fn get_write_update_method(
 // }
 
 fn get_relations_waiting_method(fields: &[Property], struct_type: &StructType) -> TokenStream {
-    let mut body = quote! {};
-
-    for field in fields.iter() {
-        if let Property::Entity(_) = field {
+    let inserts = fields
+        .iter()
+        .filter(|field| matches!(field, Property::Entity(_)))
+        .map(|field| {
             let field_name = get_field_name(field, struct_type);
-            let body_add_right = quote! {
-                if let Some(local_entity) = self.#field_name.waiting_local_entity() {
-                    output.insert(local_entity);
-                }
-            };
-            let new_body = quote! {
-                #body
-                #body_add_right
-            };
-            body = new_body;
-        }
-    }
+            field.relation_waiting(quote! { self.#field_name })
+        });
 
     quote! {
         fn relations_waiting(&self) -> Option<HashSet<LocalEntity>> {
             let mut output = HashSet::new();
-            #body
+            #(#inserts)*
             if output.is_empty() {
                 return None;
             }
@@ -1110,25 +1213,17 @@ fn get_relations_waiting_method(fields: &[Property], struct_type: &StructType) -
 }
 
 fn get_relations_complete_method(fields: &[Property], struct_type: &StructType) -> TokenStream {
-    let mut body = quote! {};
-
-    for field in fields.iter() {
-        if let Property::Entity(_) = field {
+    let completes = fields
+        .iter()
+        .filter(|field| matches!(field, Property::Entity(_)))
+        .map(|field| {
             let field_name = get_field_name(field, struct_type);
-            let body_add_right = quote! {
-                self.#field_name.waiting_complete(converter);
-            };
-            let new_body = quote! {
-                #body
-                #body_add_right
-            };
-            body = new_body;
-        }
-    }
+            field.relation_complete(quote! { self.#field_name })
+        });
 
     quote! {
         fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
-            #body
+            #(#completes)*
         }
     }
 }
@@ -0,0 +1,916 @@
+use proc_macro2::{Punct, Spacing, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{DataEnum, DeriveInput, Fields, GenericArgument, Ident, LitStr, PathArguments, Type};
+
+use crate::{
+    replicate::{
+        get_construction, get_property_attributes, get_variable_name_for_unnamed_field, Property,
+    },
+    shared::{
+        get_field_context, get_generics, get_name_expression, get_protocol_name_method,
+        StructType,
+    },
+};
+
+// An enum is replicated as the Component `EnumComponent` of it, which holds
+// its PropertyMutator across switches of variant. It is written as the index
+// of its variant, followed by the Properties of that variant. The first bit of
+// its DiffMask marks a switch of variant, which rewrites the Component in
+// full, and the Properties of every variant follow, so that a change within
+// the same variant is only written field by field.
+
+/// A field of an enum variant, with its name as it appears in deserialization
+/// errors & the Protocol schema, and the probability context it is coded in
+struct VariantField {
+    property: Property,
+    path: String,
+    context: u32,
+}
+
+struct ReplicaVariant {
+    name: Ident,
+    index: u16,
+    fields_type: StructType,
+    fields: Vec<VariantField>,
+}
+
+impl ReplicaVariant {
+    fn replicated_fields(&self) -> impl Iterator<Item = &VariantField> {
+        self.fields
+            .iter()
+            .filter(|field| field.property.is_replicated())
+    }
+
+    /// Get a pattern matching this variant, binding the fields for which
+    /// `bind` gives a name, and ignoring the rest
+    fn pattern(
+        &self,
+        path: &TokenStream,
+        bind: impl Fn(&Property) -> Option<Ident>,
+    ) -> TokenStream {
+        let name = &self.name;
+        match self.fields_type {
+            StructType::Struct => {
+                let bindings = self.fields.iter().filter_map(|field| {
+                    let binding = bind(&field.property)?;
+                    let field_name = field.property.variable_name();
+                    if binding == *field_name {
+                        return Some(quote! { #binding });
+                    }
+                    Some(quote! { #field_name: #binding })
+                });
+                quote! { #path::#name { #(#bindings,)* .. } }
+            }
+            StructType::TupleStruct => {
+                let bindings = self.fields.iter().map(|field| match bind(&field.property) {
+                    Some(binding) => quote! { #binding },
+                    None => quote! { _ },
+                });
+                quote! { #path::#name(#(#bindings),*) }
+            }
+            StructType::UnitStruct => quote! { #path::#name },
+        }
+    }
+
+    /// Get an expression building this variant, with the value of each field
+    /// given by `value`
+    fn construction(
+        &self,
+        path: &TokenStream,
+        value: impl Fn(&Property) -> TokenStream,
+    ) -> TokenStream {
+        let name = &self.name;
+        get_construction(
+            &quote! { #path::#name },
+            &self.fields_type,
+            self.fields.iter().map(|field| &field.property),
+            value,
+        )
+    }
+}
+
+/// Get an expression of the field bound to its own name
+fn bound_field(property: &Property) -> TokenStream {
+    let field_name = property.variable_name();
+    quote! { #field_name }
+}
+
+fn bind_replicated(property: &Property) -> Option<Ident> {
+    property
+        .is_replicated()
+        .then(|| property.variable_name().clone())
+}
+
+fn bind_entities(property: &Property) -> Option<Ident> {
+    match property {
+        Property::Entity(property) => Some(property.variable_name.clone()),
+        Property::Normal(_) | Property::NonReplicated(_) => None,
+    }
+}
+
+fn bind_all(property: &Property) -> Option<Ident> {
+    Some(property.variable_name().clone())
+}
+
+pub fn replicate_enum_impl(
+    input: &DeriveInput,
+    data_enum: &DataEnum,
+    shared_crate_name: TokenStream,
+) -> proc_macro::TokenStream {
+    // Helper Properties
+    let variants = get_variants(&input.ident, data_enum, &shared_crate_name);
+    let variant_bits = get_variant_bits(variants.len());
+    let variant_context = get_field_context(&input.ident.to_string(), "variant");
+    let generics = get_generics(input, &shared_crate_name);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let turbofish = type_generics.as_turbofish();
+
+    // Names
+    let replica_name = input.ident.clone();
+    let replica_name_str = LitStr::new(&replica_name.to_string(), replica_name.span());
+    let lowercase_replica_name = Ident::new(
+        replica_name.to_string().to_lowercase().as_str(),
+        Span::call_site(),
+    );
+    let module_name = format_ident!("define_{}", lowercase_replica_name);
+    let enum_name = format_ident!("{}Property", replica_name);
+    let replica_path = quote! { #replica_name #turbofish };
+    let name_expression =
+        get_name_expression(&replica_name_str, &generics, &shared_crate_name);
+    let protocol_name_method =
+        get_protocol_name_method(&replica_name_str, &generics, &shared_crate_name);
+
+    // Definitions
+    let property_enum_definition = get_property_enum_definition(&enum_name, &variants);
+    let diff_mask_size = {
+        let len = 1 + variants
+            .iter()
+            .map(|variant| variant.replicated_fields().count())
+            .sum::<usize>();
+        ((len - 1) / 8) + 1
+    } as u8;
+
+    // Methods
+    let new_methods = get_new_methods(&enum_name, &variants);
+    let read_method = get_read_method(&replica_path, &variants, variant_bits, variant_context);
+    let read_create_update_method =
+        get_read_create_update_method(&replica_path, &variants, variant_bits, variant_context);
+    let split_update_method = get_split_update_method(&replica_path, &variants, variant_bits);
+    let layout_method = get_layout_method(&variants);
+    let fields_method = get_fields_method(&variants, variant_bits, &shared_crate_name);
+    let clone_method = get_clone_method(&enum_name, &variants);
+    let mirror_method = get_mirror_method(&variants);
+    let set_mutator_method = get_set_mutator_method(&enum_name, &variants);
+    let write_method = get_write_method(&variants, variant_bits, variant_context);
+    let write_update_method =
+        get_write_update_method(&enum_name, &variants, variant_bits, variant_context);
+    let read_apply_update_method =
+        get_read_apply_update_method(&replica_path, &variants, variant_bits);
+    let read_apply_field_update_method = get_read_apply_field_update_method(&variants);
+    let relations_waiting_method = get_relations_waiting_method(&variants);
+    let relations_complete_method = get_relations_complete_method(&variants);
+
+    let gen = quote! {
+        mod #module_name {
+
+            use std::collections::HashSet;
+            use #shared_crate_name::{
+                DiffMask, PropertyMutate, PropertyMutator, ComponentUpdate,
+                LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, ComponentKind,
+                BitReader, BitWrite, BitWriter, OwnedBitReader, SerdeErr, SerdeErrReason, Serde, UnsignedInteger, LocalEntity,
+                EntityProperty, GlobalEntity, Property, ComponentFieldUpdate, EnumComponent, ReplicateEnum,
+            };
+            use super::*;
+
+            #property_enum_definition
+
+            impl #impl_generics #replica_name #type_generics #where_clause {
+                #new_methods
+            }
+            impl #impl_generics ReplicateEnum for #replica_name #type_generics #where_clause {
+                fn name() -> String {
+                    return #name_expression;
+                }
+                #protocol_name_method
+                fn diff_mask_size() -> u8 { #diff_mask_size }
+                #layout_method
+                #fields_method
+                #read_method
+                #read_create_update_method
+                #split_update_method
+                #mirror_method
+                #set_mutator_method
+                #write_method
+                #write_update_method
+                #read_apply_update_method
+                #read_apply_field_update_method
+                #relations_waiting_method
+                #relations_complete_method
+            }
+            impl #impl_generics Clone for #replica_name #type_generics #where_clause {
+                #clone_method
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(gen)
+}
+
+fn get_variants(
+    type_name: &Ident,
+    data_enum: &DataEnum,
+    shared_crate_name: &TokenStream,
+) -> Vec<ReplicaVariant> {
+    if data_enum.variants.is_empty() {
+        panic!("Can't derive Replicate on an enum without variants");
+    }
+
+    // Index 0 of the DiffMask is taken by the variant
+    let mut mutator_index = 1;
+    let mut variants = Vec::new();
+
+    for (index, variant) in data_enum.variants.iter().enumerate() {
+        let fields_type = match &variant.fields {
+            Fields::Named(_) => StructType::Struct,
+            Fields::Unnamed(_) => StructType::TupleStruct,
+            Fields::Unit => StructType::UnitStruct,
+        };
+
+        let mut fields = Vec::new();
+        for (position, field) in variant.fields.iter().enumerate() {
+            let (variable_name, path) = match &field.ident {
+                Some(ident) => (ident.clone(), format!("{}.{}", variant.ident, ident)),
+                None => (
+                    get_variable_name_for_unnamed_field(position, variant.ident.span()),
+                    format!("{}.{}", variant.ident, position),
+                ),
+            };
+
+            let mut property = match get_property_type(&field.ty) {
+                Some(PropertyType::Entity) => Property::entity(mutator_index, variable_name),
+                Some(PropertyType::Normal(inner_type)) => Property::normal(
+                    mutator_index,
+                    variable_name,
+                    inner_type,
+                    get_property_attributes(&field.attrs),
+                    shared_crate_name,
+                ),
+                None => Property::nonreplicated(variable_name, field.ty.clone()),
+            };
+
+            // Fields of different variants may share a name, so their
+            // mutator indices are named after their variant too
+            let uppercase_variable_name = format_ident!(
+                "{}_{}",
+                variant.ident.to_string().to_uppercase(),
+                property.variable_name().to_string().to_uppercase()
+            );
+            match &mut property {
+                Property::Normal(property) => {
+                    property.uppercase_variable_name = uppercase_variable_name;
+                }
+                Property::Entity(property) => {
+                    property.uppercase_variable_name = uppercase_variable_name;
+                }
+                Property::NonReplicated(_) => {}
+            }
+
+            if property.is_replicated() {
+                mutator_index += 1;
+                if mutator_index > usize::from(u8::MAX) + 1 {
+                    panic!("Can't derive Replicate on an enum with more than 255 Properties");
+                }
+            }
+            let context = get_field_context(&type_name.to_string(), &path);
+            fields.push(VariantField {
+                property,
+                path,
+                context,
+            });
+        }
+
+        variants.push(ReplicaVariant {
+            name: variant.ident.clone(),
+            index: index as u16,
+            fields_type,
+            fields,
+        });
+    }
+
+    variants
+}
+
+#[allow(clippy::large_enum_variant)]
+enum PropertyType {
+    Normal(Type),
+    Entity,
+}
+
+fn get_property_type(field_type: &Type) -> Option<PropertyType> {
+    let Type::Path(type_path) = field_type else {
+        return None;
+    };
+    let property_seg = type_path.path.segments.first()?;
+    if property_seg.ident == "EntityProperty" {
+        return Some(PropertyType::Entity);
+    }
+    if property_seg.ident != "Property" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(angle_args) = &property_seg.arguments else {
+        return None;
+    };
+    match angle_args.args.first() {
+        Some(GenericArgument::Type(inner_type)) => Some(PropertyType::Normal(inner_type.clone())),
+        _ => None,
+    }
+}
+
+/// Get the number of bits the index of a variant is written with
+fn get_variant_bits(variant_count: usize) -> u8 {
+    let max_index = variant_count - 1;
+    (usize::BITS - max_index.leading_zeros()).max(1) as u8
+}
+
+/// Get the name of a variant in snake case, e.g. `moving_to` for `MovingTo`
+fn get_snake_case_name(variant_name: &Ident) -> String {
+    let mut snake_case = String::new();
+    let mut after_lowercase = false;
+    for character in variant_name.to_string().chars() {
+        if character.is_uppercase() && after_lowercase {
+            snake_case.push('_');
+        }
+        after_lowercase = character.is_lowercase() || character.is_ascii_digit();
+        snake_case.extend(character.to_lowercase());
+    }
+    snake_case
+}
+
+fn get_property_enum_definition(enum_name: &Ident, variants: &[ReplicaVariant]) -> TokenStream {
+    let hashtag = Punct::new('#', Spacing::Alone);
+
+    let variant_list = variants
+        .iter()
+        .flat_map(|variant| variant.replicated_fields())
+        .map(|field| field.property.mutator_index_variant());
+
+    quote! {
+        #hashtag[repr(u8)]
+        enum #enum_name {
+            VARIANT = 0,
+            #(#variant_list)*
+        }
+    }
+}
+
+fn get_invalid_variant_error(replica_name: &TokenStream) -> TokenStream {
+    quote! {
+        SerdeErr::new(
+            SerdeErrReason::InvalidEnumVariant(index as u64),
+            reader.bit_offset(),
+        )
+        .expecting::<#replica_name>()
+    }
+}
+
+fn get_new_methods(enum_name: &Ident, variants: &[ReplicaVariant]) -> TokenStream {
+    let methods = variants.iter().map(|variant| {
+        let method_name = format_ident!("new_{}", get_snake_case_name(&variant.name));
+        let args = variant
+            .fields
+            .iter()
+            .filter_map(|field| field.property.constructor_arg());
+        let construction = variant.construction(&quote! { Self }, |property| {
+            property.host_owned_value(enum_name, bound_field(property))
+        });
+
+        quote! {
+            pub fn #method_name(#(#args),*) -> Self {
+                #construction
+            }
+        }
+    });
+
+    quote! {
+        #(#methods)*
+    }
+}
+
+fn get_clone_method(enum_name: &Ident, variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(&quote! { Self }, bind_all);
+        let construction = variant.construction(&quote! { Self }, |property| {
+            property.clone_value(enum_name, bound_field(property))
+        });
+        quote! {
+            #pattern => #construction,
+        }
+    });
+
+    quote! {
+        fn clone(&self) -> Self {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn get_mirror_method(variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(&quote! { Self }, bind_replicated);
+        let other_pattern = variant.pattern(&quote! { Self }, |property| {
+            property
+                .is_replicated()
+                .then(|| format_ident!("other_{}", property.variable_name()))
+        });
+        let mirrors = variant.replicated_fields().map(|field| {
+            let other_field_name = format_ident!("other_{}", field.property.variable_name());
+            field
+                .property
+                .mirror(bound_field(&field.property), quote! { #other_field_name })
+        });
+        quote! {
+            (#pattern, #other_pattern) => {
+                #(#mirrors)*
+            }
+        }
+    });
+    // Both sides are known to be the same variant, so the other pairs can
+    // only be reached when there is more than one
+    let other_arms = (variants.len() > 1).then(|| quote! { _ => {} });
+
+    quote! {
+        fn mirror(&mut self, other: &Self) {
+            if std::mem::discriminant(self) != std::mem::discriminant(other) {
+                // A switch of variant, which is rewritten in full once the
+                // EnumComponent gives the new Properties its mutator
+                *self = other.clone();
+                return;
+            }
+            match (self, other) {
+                #(#arms)*
+                #other_arms
+            }
+        }
+    }
+}
+
+fn get_set_mutator_method(enum_name: &Ident, variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(&quote! { Self }, bind_replicated);
+        let set_mutators = variant
+            .replicated_fields()
+            .map(|field| field.property.set_mutator(bound_field(&field.property)));
+        quote! {
+            #pattern => {
+                #(#set_mutators)*
+            }
+        }
+    });
+
+    quote! {
+        fn set_mutator(&mut self, mutator: &PropertyMutator) {
+            // A mutator is set when the Component is inserted, and again each
+            // time it switches variant. Either way, its next update rewrites
+            // it in full
+            let mut variant_mutator = mutator.clone_new();
+            variant_mutator.mutate(#enum_name::VARIANT as u8);
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn get_write_method(
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+    variant_context: u32,
+) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let pattern = variant.pattern(&quote! { Self }, bind_replicated);
+        let writes = variant.replicated_fields().map(|field| {
+            field
+                .property
+                .write(bound_field(&field.property), field.context)
+        });
+        quote! {
+            #pattern => {
+                writer.set_context(#variant_context);
+                UnsignedInteger::<#variant_bits>::new(#index).ser(writer);
+                #(#writes)*
+            }
+        }
+    });
+
+    quote! {
+        fn write(&self, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn get_write_update_method(
+    enum_name: &Ident,
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+    variant_context: u32,
+) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let pattern = variant.pattern(&quote! { Self }, bind_replicated);
+        let writes = variant.replicated_fields().map(|field| {
+            field.property.write_update(
+                bound_field(&field.property),
+                field.context,
+                enum_name,
+                true,
+            )
+        });
+        quote! {
+            #pattern => {
+                writer.set_context(#variant_context);
+                UnsignedInteger::<#variant_bits>::new(#index).ser(writer);
+                switched.ser(writer);
+                #(#writes)*
+            }
+        }
+    });
+
+    quote! {
+        fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
+            let switched = diff_mask.bit(#enum_name::VARIANT as u8) == Some(true);
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn get_read_method(
+    replica_name: &TokenStream,
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+    variant_context: u32,
+) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let reads = variant
+            .fields
+            .iter()
+            .map(|field| field.property.new_read(&field.path, Some(field.context)));
+        let construction = variant.construction(replica_name, bound_field);
+        quote! {
+            #index => {
+                #(#reads)*
+                #construction
+            }
+        }
+    });
+    let invalid_variant_error = get_invalid_variant_error(replica_name);
+
+    quote! {
+        fn read(reader: &mut BitReader, converter: &dyn LocalEntityAndGlobalEntityConverter) -> Result<Self, SerdeErr> {
+            reader.set_context(#variant_context);
+            let variant: UnsignedInteger<#variant_bits> = Serde::de(reader)?;
+            let replica = match variant.get() as u16 {
+                #(#arms)*
+                index => return Err(#invalid_variant_error),
+            };
+
+            return Ok(replica);
+        }
+    }
+}
+
+fn get_read_create_update_method(
+    replica_name: &TokenStream,
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+    variant_context: u32,
+) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let read_writes = variant
+            .replicated_fields()
+            .map(|field| field.property.read_create_update(field.context));
+        quote! {
+            #index => {
+                #(#read_writes)*
+            }
+        }
+    });
+    let invalid_variant_error = get_invalid_variant_error(replica_name);
+
+    quote! {
+        fn read_create_update(reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
+
+            let mut update_writer = BitWriter::new();
+
+            reader.set_context(#variant_context);
+            let variant: UnsignedInteger<#variant_bits> = Serde::de(reader)?;
+            variant.ser(&mut update_writer);
+            bool::de(reader)?.ser(&mut update_writer);
+
+            match variant.get() as u16 {
+                #(#arms)*
+                index => return Err(#invalid_variant_error),
+            }
+
+            let owned_reader = update_writer.to_owned_reader();
+
+            return Ok(ComponentUpdate::new(ComponentKind::of::<EnumComponent<Self>>(), owned_reader));
+        }
+    }
+}
+
+fn get_split_update_method(
+    replica_name: &TokenStream,
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let splits = variant
+            .replicated_fields()
+            .map(|field| field.property.split_update(true));
+        quote! {
+            #index => {
+                #(#splits)*
+            }
+        }
+    });
+    let invalid_variant_error = get_invalid_variant_error(replica_name);
+
+    quote! {
+        fn split_update(
+            converter: &dyn LocalEntityAndGlobalEntityConverter,
+            update: ComponentUpdate
+        ) -> Result<(
+            Option<Vec<(LocalEntity, ComponentFieldUpdate)>>,
+            Option<ComponentUpdate>
+        ), SerdeErr> {
+            let component_kind = ComponentKind::of::<EnumComponent<Self>>();
+            let reader = &mut update.reader();
+
+            let mut waiting_did_write = false;
+            let mut waiting_updates: Vec<(LocalEntity, ComponentFieldUpdate)> = Vec::new();
+
+            let mut ready_writer = BitWriter::new();
+
+            let variant: UnsignedInteger<#variant_bits> = Serde::de(reader)?;
+            variant.ser(&mut ready_writer);
+            let switched = bool::de(reader)?;
+            switched.ser(&mut ready_writer);
+
+            // a switch of variant is always ready, even if some of its
+            // EntityProperties are waiting
+            let mut ready_did_write = switched;
+
+            match variant.get() as u16 {
+                #(#arms)*
+                index => return Err(#invalid_variant_error),
+            }
+
+            let waiting_result = {
+                if waiting_did_write {
+                    Some(waiting_updates)
+                } else {
+                    None
+                }
+            };
+            let ready_result = {
+                if ready_did_write {
+                    Some(ComponentUpdate::new(component_kind, ready_writer.to_owned_reader()))
+                } else {
+                    None
+                }
+            };
+
+            return Ok((waiting_result, ready_result));
+        }
+    }
+}
+
+fn get_read_apply_update_method(
+    replica_name: &TokenStream,
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+) -> TokenStream {
+    let switch_arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let reads = variant.fields.iter().map(|field| {
+            let new_read = field.property.new_read(&field.path, None);
+            if !field.property.is_replicated() {
+                return new_read;
+            }
+            let field_path = &field.path;
+            let error_type = field.property.error_type();
+            quote! {
+                if !bool::de(reader)? {
+                    return Err(SerdeErr::new(
+                        SerdeErrReason::Other("a switch of variant must write every field".into()),
+                        reader.bit_offset(),
+                    )
+                    .in_field::<#error_type>(#field_path));
+                }
+                #new_read
+            }
+        });
+        let construction = variant.construction(replica_name, bound_field);
+        quote! {
+            #index => {
+                #(#reads)*
+                #construction
+            }
+        }
+    });
+    let invalid_variant_error = get_invalid_variant_error(replica_name);
+
+    let update_arms = variants.iter().map(|variant| {
+        let index = variant.index;
+        let pattern = variant.pattern(&quote! { Self }, bind_replicated);
+        let reads = variant.replicated_fields().map(|field| {
+            field
+                .property
+                .read_apply_update(bound_field(&field.property), &field.path)
+        });
+        quote! {
+            (#index, #pattern) => {
+                #(#reads)*
+            }
+        }
+    });
+
+    quote! {
+        fn read_apply_update(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter, mut update: ComponentUpdate) -> Result<(), SerdeErr> {
+            let reader = &mut update.reader();
+            let variant: UnsignedInteger<#variant_bits> = Serde::de(reader)?;
+            let switched = bool::de(reader)?;
+
+            if switched {
+                *self = match variant.get() as u16 {
+                    #(#switch_arms)*
+                    index => return Err(#invalid_variant_error),
+                };
+                return Ok(());
+            }
+
+            match (variant.get() as u16, self) {
+                #(#update_arms)*
+                // an update to a variant this Component has since switched away
+                // from, or has yet to switch to, which that switch supersedes
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+}
+
+fn get_read_apply_field_update_method(variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().flat_map(|variant| {
+        variant.fields.iter().filter_map(move |field| {
+            let Property::Entity(property) = &field.property else {
+                return None;
+            };
+            let index = property.index as u8;
+            let field_name = &property.variable_name;
+            let pattern = variant.pattern(&quote! { Self }, |other_property| {
+                (other_property.variable_name() == field_name && other_property.is_replicated())
+                    .then(|| field_name.clone())
+            });
+            let read = field
+                .property
+                .read_apply_field_update(bound_field(&field.property));
+            Some(quote! {
+                (#index, #pattern) => {
+                    #read
+                }
+            })
+        })
+    });
+
+    quote! {
+        fn read_apply_field_update(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter, mut update: ComponentFieldUpdate) -> Result<(), SerdeErr> {
+            let reader = &mut update.reader();
+            match (update.field_id(), self) {
+                #(#arms)*
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+}
+
+fn get_relations_waiting_method(variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(&quote! { Self }, bind_entities);
+        let inserts = variant.fields.iter().filter_map(|field| {
+            let field_name = bind_entities(&field.property)?;
+            Some(field.property.relation_waiting(quote! { #field_name }))
+        });
+        quote! {
+            #pattern => {
+                #(#inserts)*
+            }
+        }
+    });
+
+    quote! {
+        fn relations_waiting(&self) -> Option<HashSet<LocalEntity>> {
+            let mut output = HashSet::new();
+            match self {
+                #(#arms)*
+            }
+            if output.is_empty() {
+                return None;
+            }
+            return Some(output);
+        }
+    }
+}
+
+fn get_relations_complete_method(variants: &[ReplicaVariant]) -> TokenStream {
+    let arms = variants.iter().map(|variant| {
+        let pattern = variant.pattern(&quote! { Self }, bind_entities);
+        let completes = variant.fields.iter().filter_map(|field| {
+            let field_name = bind_entities(&field.property)?;
+            Some(field.property.relation_complete(quote! { #field_name }))
+        });
+        quote! {
+            #pattern => {
+                #(#completes)*
+            }
+        }
+    });
+
+    quote! {
+        fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
+            match self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+fn get_layout_method(variants: &[ReplicaVariant]) -> TokenStream {
+    let layout = variants
+        .iter()
+        .map(|variant| {
+            let fields = variant
+                .fields
+                .iter()
+                .filter_map(|field| field.property.layout())
+                .collect::<Vec<String>>();
+            if fields.is_empty() {
+                variant.name.to_string()
+            } else {
+                format!("{} {{ {} }}", variant.name, fields.join(", "))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" | ");
+
+    quote! {
+        fn layout() -> &'static str {
+            #layout
+        }
+    }
+}
+
+fn get_fields_method(
+    variants: &[ReplicaVariant],
+    variant_bits: u8,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let schema = quote! { #shared_crate_name::schema };
+    let variant_names = variants
+        .iter()
+        .map(|variant| variant.name.to_string())
+        .collect::<Vec<String>>()
+        .join(" | ");
+    let field_schemas = variants
+        .iter()
+        .flat_map(|variant| variant.replicated_fields())
+        .map(|field| field.property.schema(&field.path, shared_crate_name));
+
+    quote! {
+        fn fields() -> Vec<#schema::FieldSchema> {
+            vec![
+                #schema::FieldSchema {
+                    name: "variant",
+                    kind: #schema::FieldKind::Variant,
+                    type_name: #variant_names,
+                    encoding: #schema::FieldEncoding::Bits(#variant_bits),
+                    bit_length: Some(#variant_bits as u32),
+                    max_len: None,
+                },
+                #(#field_schemas),*
+            ]
+        }
+    }
+}
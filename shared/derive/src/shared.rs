@@ -1,4 +1,11 @@
-use syn::{Data, DeriveInput, Fields};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Data, DeriveInput, Fields, GenericParam, Generics, Ident, LitStr, Member, Type,
+};
+
+use naia_serde_derive_internals::signed_int_tokens;
+pub(crate) use naia_serde_derive_internals::{FieldAttributes, FieldCodec};
 
 pub enum StructType {
     Struct,
@@ -17,3 +24,211 @@ pub(crate) fn get_struct_type(input: &DeriveInput) -> StructType {
     }
     panic!("Can only derive on a struct")
 }
+
+/// Get the generics of the struct, requiring each of its type parameters to be
+/// `Send + Sync + 'static`, as Messages & Components must be, and to have a
+/// `ProtocolName` to name the struct after
+pub(crate) fn get_generics(input: &DeriveInput, shared_crate_name: &TokenStream) -> Generics {
+    let mut generics = input.generics.clone();
+    if generics.lifetimes().next().is_some() {
+        panic!("Can't derive on a struct with lifetime parameters, it must be 'static");
+    }
+    let type_params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for type_param in type_params {
+        where_clause.predicates.push(parse_quote! {
+            #type_param: Send + Sync + 'static + #shared_crate_name::ProtocolName
+        });
+    }
+    generics
+}
+
+/// Get the type of the field through which a builder holds on to the type
+/// parameters of the struct it builds
+pub(crate) fn get_builder_marker(generics: &Generics) -> TokenStream {
+    let type_params = generics.type_params().map(|param| &param.ident);
+    quote! { std::marker::PhantomData<fn() -> (#(#type_params,)*)> }
+}
+
+/// Get an expression giving the name of the struct. A generic struct is named
+/// after the `ProtocolName` of its arguments too, so that each of its instances
+/// reads as its own kind in errors & the Protocol schema
+pub(crate) fn get_name_expression(
+    name: &LitStr,
+    generics: &Generics,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let arguments = get_generic_arguments(generics, shared_crate_name);
+    if arguments.is_empty() {
+        return quote! { #name.to_string() };
+    }
+    quote! { format!("{}<{}>", #name, [#(#arguments),*].join(", ")) }
+}
+
+/// Get the `protocol_name()` method of a struct whose impls are generated
+/// within a module of their own: the path of the module the struct is defined
+/// in, its name, and any generic arguments
+pub(crate) fn get_protocol_name_method(
+    name: &LitStr,
+    generics: &Generics,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let arguments = get_generic_arguments(generics, shared_crate_name);
+    let format_name = if arguments.is_empty() {
+        quote! { format!("{}::{}", module_path, #name) }
+    } else {
+        quote! { format!("{}::{}<{}>", module_path, #name, [#(#arguments),*].join(", ")) }
+    };
+    quote! {
+        fn protocol_name() -> String where Self: Sized {
+            // leave out the module generated around this impl
+            let (module_path, _) = module_path!().rsplit_once("::").unwrap();
+            return #format_name;
+        }
+    }
+}
+
+// Get expressions giving the name of each generic argument of the struct
+fn get_generic_arguments(generics: &Generics, shared_crate_name: &TokenStream) -> Vec<TokenStream> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(quote! { <#ident as #shared_crate_name::ProtocolName>::protocol_name() })
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                Some(quote! { #ident.to_string() })
+            }
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+/// Get the name of a field as it should appear in a deserialization error's path
+pub(crate) fn get_field_path(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Get the probability context in which a field is written by an arithmetic
+/// coding BitWriter, which is a hash of the type & field names, so that the
+/// field is modeled apart from others
+pub(crate) fn get_field_context(type_name: &str, field_path: &str) -> u32 {
+    // FNV-1a
+    let mut hash: u32 = 0x811C_9DC5;
+    for byte in type_name.bytes().chain([b'.']).chain(field_path.bytes()) {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+/// Get an expression building the `FieldSchema` which describes a field in
+/// `Protocol::schema()`
+pub(crate) fn get_field_schema(
+    field_name: &str,
+    field_kind: &str,
+    field_type: &Type,
+    attributes: &FieldAttributes,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let schema = quote! { #shared_crate_name::schema };
+    let field_kind = format_ident!("{}", field_kind);
+    let type_name = get_type_name(field_type);
+    let (encoding, bit_length) = match &attributes.codec {
+        FieldCodec::Serde => (
+            quote! { Serde },
+            quote! {
+                {
+                    use #schema::{ConstBitWidth as _, VariableBitWidth as _};
+                    (&#schema::BitWidthProbe::<#field_type>::new()).bit_width()
+                }
+            },
+        ),
+        FieldCodec::Bits(bits) => (
+            quote! { Bits(#bits) },
+            quote! {
+                Some(#bits as u32 + u32::from(<#field_type as #shared_crate_name::codecs::IntegerField>::SIGNED))
+            },
+        ),
+        FieldCodec::Range(min, max) => {
+            let span = max.wrapping_sub(*min) as u128;
+            let bits = u128::BITS - span.leading_zeros();
+            let min = signed_int_tokens(*min);
+            let max = signed_int_tokens(*max);
+            (
+                quote! { Range { min: #min, max: #max } },
+                quote! { Some(#bits) },
+            )
+        }
+        FieldCodec::With(path) => {
+            let path = quote! { #path }.to_string().replace(' ', "");
+            (quote! { With(#path) }, quote! { None })
+        }
+        FieldCodec::SerdeRs => (quote! { SerdeRs }, quote! { None }),
+        FieldCodec::Skip(_) => (quote! { Skip }, quote! { Some(0) }),
+    };
+    let max_len = match attributes.max_len {
+        Some(max_len) => quote! { Some(#max_len) },
+        None => quote! { None },
+    };
+    quote! {
+        #schema::FieldSchema {
+            name: #field_name,
+            kind: #schema::FieldKind::#field_kind,
+            type_name: #type_name,
+            encoding: #schema::FieldEncoding::#encoding,
+            bit_length: #bit_length,
+            max_len: #max_len,
+        }
+    }
+}
+
+/// Get an expression building the `FieldSchema` which describes an
+/// EntityProperty in `Protocol::schema()`
+pub(crate) fn get_entity_property_schema(
+    field_name: &str,
+    shared_crate_name: &TokenStream,
+) -> TokenStream {
+    let schema = quote! { #shared_crate_name::schema };
+    quote! {
+        #schema::FieldSchema {
+            name: #field_name,
+            kind: #schema::FieldKind::EntityProperty,
+            type_name: "EntityProperty",
+            encoding: #schema::FieldEncoding::Entity,
+            bit_length: None,
+            max_len: None,
+        }
+    }
+}
+
+/// Get a type as it would be written in source, without the spaces `quote`
+/// puts between every token
+fn get_type_name(field_type: &Type) -> String {
+    let tokens = quote! { #field_type }.to_string();
+    let mut output = String::with_capacity(tokens.len());
+    let mut characters = tokens.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character == ' ' {
+            let after_open = output.ends_with(['<', ':', '(', '[', '&']);
+            let before_close = match characters.peek() {
+                Some(next) => "<>:,;)]".contains(*next),
+                None => true,
+            };
+            if after_open || before_close {
+                continue;
+            }
+        }
+        output.push(character);
+    }
+    output
+}
@@ -1,3 +1,6 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+
 mod enumeration;
 mod structure;
 mod tuple_structure;
@@ -5,3 +8,19 @@ mod tuple_structure;
 pub use enumeration::*;
 pub use structure::*;
 pub use tuple_structure::*;
+
+/// Get the impl of `ProtocolName` for a type whose impls are generated within
+/// a module of their own: the path of the module the type is defined in,
+/// followed by its name
+pub fn get_protocol_name_impl(type_name: &Ident, serde_crate_name: &TokenStream) -> TokenStream {
+    let type_name_str = type_name.to_string();
+    quote! {
+        impl #serde_crate_name::ProtocolName for #type_name {
+            fn protocol_name() -> String {
+                // leave out the module generated around this impl
+                let (module_path, _) = module_path!().rsplit_once("::").unwrap();
+                format!("{}::{}", module_path, #type_name_str)
+            }
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::{DataEnum, Fields};
+use syn::{DataEnum, Field, Fields, Variant};
+
+use naia_serde_derive_internals::{FieldAttributes, FieldCodec};
+
+use crate::impls::get_protocol_name_impl;
 
 fn bits_needed_for(max_value: usize) -> u8 {
     let mut bits = 1;
@@ -22,9 +26,9 @@ pub fn derive_serde_enum(
     let variant_number = enum_.variants.len();
     let bits_needed = bits_needed_for(variant_number);
 
-    let ser_method = get_ser_method(enum_, bits_needed);
-    let de_method = get_de_method(enum_, bits_needed);
-    let bit_length_method = get_bit_length_method(enum_, bits_needed);
+    let ser_method = get_ser_method(enum_, bits_needed, &serde_crate_name);
+    let de_method = get_de_method(enum_, bits_needed, &serde_crate_name);
+    let bit_length_method = get_bit_length_method(enum_, bits_needed, &serde_crate_name);
 
     let lowercase_enum_name = Ident::new(
         enum_name.to_string().to_lowercase().as_str(),
@@ -32,14 +36,15 @@ pub fn derive_serde_enum(
     );
     let module_name = format_ident!("define_{}", lowercase_enum_name);
 
-    let import_types =
-        quote! { Serde, BitWrite, UnsignedInteger, BitReader, SerdeErr, ConstBitLength, };
+    let import_types = quote! { Serde, BitWrite, UnsignedInteger, BitReader, SerdeErr, SerdeErrReason, ConstBitLength, };
     let imports = quote! { use #serde_crate_name::{#import_types}; };
+    let protocol_name_impl = get_protocol_name_impl(enum_name, &serde_crate_name);
 
     quote! {
         mod #module_name {
             #imports
-            use super::#enum_name;
+            use super::*;
+            #protocol_name_impl
 
             impl Serde for #enum_name {
                 #ser_method
@@ -50,77 +55,77 @@ pub fn derive_serde_enum(
     }
 }
 
-fn get_ser_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
-    let mut ser = quote! {};
-    for (index, variant) in enum_.variants.iter().enumerate() {
-        let variant_index = index as u16;
-        let variant_name = &variant.ident;
-        let base = match &variant.fields {
-            Fields::Unit => {
-                quote! {
-                    Self::#variant_name => {
-                        let index = UnsignedInteger::<#bits_needed>::new(#variant_index);
-                        index.ser(writer);
-                    }
+// Returns the pattern matching the variant, binding each of its fields which
+// isn't skipped to a reference, along with each field's binding name
+fn get_variant_pattern(variant: &Variant) -> (TokenStream, Vec<(Ident, &Field)>) {
+    let variant_name = &variant.ident;
+    match &variant.fields {
+        Fields::Unit => (quote! { Self::#variant_name }, Vec::new()),
+        Fields::Named(fields) => {
+            let mut bindings = Vec::new();
+            let mut has_skipped = false;
+            for field in fields.named.iter() {
+                if let FieldCodec::Skip(_) = FieldAttributes::parse(&field.attrs).codec {
+                    has_skipped = true;
+                    continue;
                 }
+                let field_name = field.ident.clone().expect("expected field to have a name.");
+                bindings.push((field_name, field));
             }
-            Fields::Named(fields) => {
-                let names: Vec<&Ident> = fields
-                    .named
-                    .iter()
-                    .map(|field| {
-                        field
-                            .ident
-                            .as_ref()
-                            .expect("expected field to have a name.")
-                    })
-                    .collect();
-                let left = quote! { Self::#variant_name{ #(#names),* } };
-                let mut right = quote! {
-                    let index = UnsignedInteger::<#bits_needed>::new(#variant_index);
-                    index.ser(writer);
-                };
-                for field in fields.named.iter() {
-                    let field_name = field
-                        .ident
-                        .as_ref()
-                        .expect("expected field to have a name.");
-                    right = quote! {
-                        #right
-                        #field_name.ser(writer);
-                    }
-                }
-                quote! {
-                    #left => { #right }
+            let names = bindings.iter().map(|(name, _)| name);
+            let rest = if has_skipped {
+                quote! { .. }
+            } else {
+                quote! {}
+            };
+            (
+                quote! { Self::#variant_name{ #(#names,)* #rest } },
+                bindings,
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let mut bindings = Vec::new();
+            let mut names = Vec::new();
+            for (i, field) in fields.unnamed.iter().enumerate() {
+                if let FieldCodec::Skip(_) = FieldAttributes::parse(&field.attrs).codec {
+                    names.push(quote! { _ });
+                    continue;
                 }
+                let field_name = format_ident!("f{}", i);
+                names.push(quote! { #field_name });
+                bindings.push((field_name, field));
             }
-            Fields::Unnamed(fields) => {
-                let names: Vec<Ident> = fields
-                    .unnamed
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| format_ident!("f{}", i))
-                    .collect();
-                let left = quote! { Self::#variant_name( #(#names),* ) };
+            (quote! { Self::#variant_name( #(#names),* ) }, bindings)
+        }
+    }
+}
 
-                let mut right = quote! {
-                    let index = UnsignedInteger::<#bits_needed>::new(#variant_index);
-                    index.ser(writer);
-                };
-                for field_name in names {
-                    right = quote! {
-                        #right
-                        #field_name.ser(writer);
-                    }
-                }
-                quote! {
-                    #left => { #right }
-                }
-            }
+fn get_ser_method(
+    enum_: &DataEnum,
+    bits_needed: u8,
+    serde_crate_name: &TokenStream,
+) -> TokenStream {
+    let mut ser = quote! {};
+    for (index, variant) in enum_.variants.iter().enumerate() {
+        let variant_index = index as u16;
+        let (left, bindings) = get_variant_pattern(variant);
+        let mut right = quote! {
+            UnsignedInteger::<#bits_needed>::new(#variant_index).ser(writer);
         };
+        for (field_name, field) in bindings {
+            let field_ser = FieldAttributes::parse(&field.attrs).ser_stmt(
+                quote! { #field_name },
+                &field.ty,
+                serde_crate_name,
+            );
+            right = quote! {
+                #right
+                #field_ser
+            }
+        }
         ser = quote! {
             #ser
-            #base
+            #left => { #right }
         }
     }
     quote! {
@@ -132,7 +137,7 @@ fn get_ser_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
     }
 }
 
-fn get_de_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
+fn get_de_method(enum_: &DataEnum, bits_needed: u8, serde_crate_name: &TokenStream) -> TokenStream {
     let mut de = quote! {};
 
     for (index, variant) in enum_.variants.iter().enumerate() {
@@ -152,9 +157,15 @@ fn get_de_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
                         .ident
                         .as_ref()
                         .expect("expected field to have a name.");
+                    let field_path = format!("{}.{}", variant_name, field_name);
+                    let field_de = FieldAttributes::parse(&field.attrs).de_expr(
+                        &field_path,
+                        &field.ty,
+                        serde_crate_name,
+                    );
                     base = quote! {
                         #base
-                        #field_name: Serde::de(reader)?,
+                        #field_name: #field_de,
                     }
                 }
                 de = quote! {
@@ -166,10 +177,16 @@ fn get_de_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
             }
             Fields::Unnamed(fields) => {
                 let mut base = quote! {};
-                for _ in fields.unnamed.iter() {
+                for (field_index, field) in fields.unnamed.iter().enumerate() {
+                    let field_path = format!("{}.{}", variant_name, field_index);
+                    let field_de = FieldAttributes::parse(&field.attrs).de_expr(
+                        &field_path,
+                        &field.ty,
+                        serde_crate_name,
+                    );
                     base = quote! {
                         #base
-                        Serde::de(reader)?,
+                        #field_de,
                     }
                 }
                 de = quote! {
@@ -182,84 +199,49 @@ fn get_de_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
         }
     }
     quote! {
-        fn de(reader: &mut BitReader) -> std::result::Result<Self, SerdeErr> {
-            let index: UnsignedInteger<#bits_needed> = Serde::de(reader)?;
+        fn de(reader: &mut BitReader) -> core::result::Result<Self, SerdeErr> {
+            let index: UnsignedInteger<#bits_needed> =
+                Serde::de(reader).map_err(SerdeErr::expecting::<Self>)?;
             let index_u16: u16 = index.get() as u16;
             Ok(match index_u16 {
                 #de
-                _ => return Err(SerdeErr)
+                _ => {
+                    return Err(SerdeErr::new(
+                        SerdeErrReason::InvalidEnumVariant(index_u16 as u64),
+                        reader.bit_offset(),
+                    )
+                    .expecting::<Self>())
+                }
             })
         }
     }
 }
 
-fn get_bit_length_method(enum_: &DataEnum, bits_needed: u8) -> TokenStream {
+fn get_bit_length_method(
+    enum_: &DataEnum,
+    bits_needed: u8,
+    serde_crate_name: &TokenStream,
+) -> TokenStream {
     let mut bit_length = quote! {};
-    for (_, variant) in enum_.variants.iter().enumerate() {
-        let variant_name = &variant.ident;
-        let base = match &variant.fields {
-            Fields::Unit => {
-                quote! {
-                    Self::#variant_name => {
-                        output += <UnsignedInteger::<#bits_needed> as ConstBitLength>::const_bit_length();
-                    }
-                }
-            }
-            Fields::Named(fields) => {
-                let names: Vec<&Ident> = fields
-                    .named
-                    .iter()
-                    .map(|field| {
-                        field
-                            .ident
-                            .as_ref()
-                            .expect("expected field to have a name.")
-                    })
-                    .collect();
-                let left = quote! { Self::#variant_name{ #(#names),* } };
-                let mut right = quote! {
-                    output += <UnsignedInteger::<#bits_needed> as ConstBitLength>::const_bit_length();
-                };
-                for field in fields.named.iter() {
-                    let field_name = field
-                        .ident
-                        .as_ref()
-                        .expect("expected field to have a name.");
-                    right = quote! {
-                        #right
-                        output += #field_name.bit_length();
-                    }
-                }
-                quote! {
-                    #left => { #right }
-                }
-            }
-            Fields::Unnamed(fields) => {
-                let names: Vec<Ident> = fields
-                    .unnamed
-                    .iter()
-                    .enumerate()
-                    .map(|(i, _)| format_ident!("f{}", i))
-                    .collect();
-                let left = quote! { Self::#variant_name( #(#names),* ) };
-
-                let mut right = quote! {
-                    output += <UnsignedInteger::<#bits_needed> as ConstBitLength>::const_bit_length();
-                };
-                for field_name in names {
-                    right = quote! {
-                        #right
-                        output += #field_name.bit_length();
-                    }
-                }
-                quote! {
-                    #left => { #right }
-                }
-            }
+    for variant in enum_.variants.iter() {
+        let (left, bindings) = get_variant_pattern(variant);
+        let mut right = quote! {
+            output += <UnsignedInteger::<#bits_needed> as ConstBitLength>::const_bit_length();
         };
+        for (field_name, field) in bindings {
+            let field_bit_length = FieldAttributes::parse(&field.attrs).bit_length_expr(
+                quote! { #field_name },
+                &field.ty,
+                serde_crate_name,
+            );
+            right = quote! {
+                #right
+                output += #field_bit_length;
+            }
+        }
         bit_length = quote! {
             #bit_length
-            #base
+            #left => { #right }
         }
     }
     quote! {
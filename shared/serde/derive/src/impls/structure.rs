@@ -2,6 +2,10 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::DataStruct;
 
+use naia_serde_derive_internals::FieldAttributes;
+
+use crate::impls::get_protocol_name_impl;
+
 #[allow(clippy::format_push_string)]
 pub fn derive_serde_struct(
     struct_: &DataStruct,
@@ -14,17 +18,23 @@ pub fn derive_serde_struct(
 
     for field in &struct_.fields {
         let field_name = field.ident.as_ref().expect("expected field to have a name");
+        let attributes = FieldAttributes::parse(&field.attrs);
+        let field_ser =
+            attributes.ser_stmt(quote! { &self.#field_name }, &field.ty, &serde_crate_name);
+        let field_de = attributes.de_expr(&field_name.to_string(), &field.ty, &serde_crate_name);
+        let field_bit_length =
+            attributes.bit_length_expr(quote! { &self.#field_name }, &field.ty, &serde_crate_name);
         ser_body = quote! {
             #ser_body
-            self.#field_name.ser(writer);
+            #field_ser
         };
         de_body = quote! {
             #de_body
-            #field_name: Serde::de(reader)?,
+            #field_name: #field_de,
         };
         bit_length_body = quote! {
             #bit_length_body
-            output += self.#field_name.bit_length();
+            output += #field_bit_length;
         };
     }
 
@@ -36,11 +46,13 @@ pub fn derive_serde_struct(
 
     let import_types = quote! { Serde, BitWrite, ConstBitLength, BitReader, SerdeErr };
     let imports = quote! { use #serde_crate_name::{#import_types}; };
+    let protocol_name_impl = get_protocol_name_impl(struct_name, &serde_crate_name);
 
     quote! {
         mod #module_name {
             #imports
-            use super::#struct_name;
+            use super::*;
+            #protocol_name_impl
             impl Serde for #struct_name {
                  fn ser(&self, writer: &mut dyn BitWrite) {
                     #ser_body
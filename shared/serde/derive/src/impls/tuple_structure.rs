@@ -1,6 +1,10 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::DataStruct;
+use syn::{DataStruct, Index};
+
+use naia_serde_derive_internals::FieldAttributes;
+
+use crate::impls::get_protocol_name_impl;
 
 #[allow(clippy::format_push_string)]
 pub fn derive_serde_tuple_struct(
@@ -12,19 +16,25 @@ pub fn derive_serde_tuple_struct(
     let mut de_body = quote! {};
     let mut bit_length_body = quote! {};
 
-    for (i, _) in struct_.fields.iter().enumerate() {
-        let field_index = i;
+    for (i, field) in struct_.fields.iter().enumerate() {
+        let field_index = Index::from(i);
+        let attributes = FieldAttributes::parse(&field.attrs);
+        let field_ser =
+            attributes.ser_stmt(quote! { &self.#field_index }, &field.ty, &serde_crate_name);
+        let field_de = attributes.de_expr(&i.to_string(), &field.ty, &serde_crate_name);
+        let field_bit_length =
+            attributes.bit_length_expr(quote! { &self.#field_index }, &field.ty, &serde_crate_name);
         ser_body = quote! {
             #ser_body
-            self.#field_index.ser(writer);
+            #field_ser
         };
         de_body = quote! {
             #de_body
-            #field_index: Serde::de(reader)?,
+            #field_index: #field_de,
         };
         bit_length_body = quote! {
             #bit_length_body
-            output += self.#field_index.bit_length();
+            output += #field_bit_length;
         };
     }
 
@@ -36,11 +46,13 @@ pub fn derive_serde_tuple_struct(
 
     let import_types = quote! {BitWrite, Serde, ConstBitLength, BitReader, SerdeErr};
     let imports = quote! { use #serde_crate_name::{#import_types}; };
+    let protocol_name_impl = get_protocol_name_impl(struct_name, &serde_crate_name);
 
     quote! {
         mod #module_name {
             #imports
-            use super::#struct_name;
+            use super::*;
+            #protocol_name_impl
             impl Serde for #struct_name {
                  fn ser(&self, writer: &mut dyn BitWrite) {
                     #ser_body
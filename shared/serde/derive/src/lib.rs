@@ -4,25 +4,25 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields};
 mod impls;
 use impls::*;
 
-#[proc_macro_derive(Serde)]
+#[proc_macro_derive(Serde, attributes(naia))]
 pub fn derive_serde(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_shared };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeInternal)]
+#[proc_macro_derive(SerdeInternal, attributes(naia))]
 pub fn derive_serde_internal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_serde };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeBevy)]
+#[proc_macro_derive(SerdeBevy, attributes(naia))]
 pub fn derive_serde_bevy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_bevy_shared };
     derive_serde_common(input, serde_crate_name)
 }
 
-#[proc_macro_derive(SerdeHecs)]
+#[proc_macro_derive(SerdeHecs, attributes(naia))]
 pub fn derive_serde_hecs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let serde_crate_name = quote! { naia_hecs_shared };
     derive_serde_common(input, serde_crate_name)
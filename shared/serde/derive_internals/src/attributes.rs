@@ -0,0 +1,268 @@
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, LitInt, LitStr, Path, Token, Type,
+};
+
+/// How a single field is written to & read from the bit stream
+pub enum FieldCodec {
+    /// Through the field type's own `Serde` impl
+    Serde,
+    /// `#[naia(bits = N)]`
+    Bits(u8),
+    /// `#[naia(range = MIN..=MAX)]`
+    Range(i128, i128),
+    /// `#[naia(with = "module")]`
+    With(Path),
+    /// `#[naia(serde_rs)]`, through the field type's serde-rs impls
+    SerdeRs,
+    /// `#[naia(skip)]`, read as `Default::default()` or through the function
+    /// given with `#[naia(skip, default = "function")]`
+    Skip(Option<Path>),
+}
+
+/// Options given to a single field through `#[naia(..)]` attributes
+pub struct FieldAttributes {
+    /// Maximum number of elements accepted when reading a collection field
+    pub max_len: Option<usize>,
+    pub codec: FieldCodec,
+}
+
+enum FieldAttribute {
+    MaxLen(usize),
+    Bits(u8),
+    Range(i128, i128),
+    With(Path),
+    SerdeRs,
+    Skip,
+    Default(Option<Path>),
+}
+
+impl Parse for FieldAttribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let attribute = match name.to_string().as_str() {
+            "max_len" => {
+                input.parse::<Token![=]>()?;
+                Self::MaxLen(input.parse::<LitInt>()?.base10_parse()?)
+            }
+            "bits" => {
+                input.parse::<Token![=]>()?;
+                Self::Bits(input.parse::<LitInt>()?.base10_parse()?)
+            }
+            "range" => {
+                input.parse::<Token![=]>()?;
+                let min = parse_signed_int(input)?;
+                let max = if input.peek(Token![..=]) {
+                    input.parse::<Token![..=]>()?;
+                    parse_signed_int(input)?
+                } else {
+                    input.parse::<Token![..]>()?;
+                    parse_signed_int(input)? - 1
+                };
+                if min > max {
+                    return Err(input.error("`range` must not be empty"));
+                }
+                Self::Range(min, max)
+            }
+            "with" => {
+                input.parse::<Token![=]>()?;
+                Self::With(input.parse::<LitStr>()?.parse()?)
+            }
+            "serde_rs" => Self::SerdeRs,
+            "skip" => Self::Skip,
+            "default" => {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    Self::Default(Some(input.parse::<LitStr>()?.parse()?))
+                } else {
+                    Self::Default(None)
+                }
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "unknown naia attribute, expected one of `max_len = N`, `bits = N`, \
+                    `range = MIN..=MAX`, `with = \"module\"`, `serde_rs`, `skip` or \
+                    `default`",
+                ))
+            }
+        };
+        Ok(attribute)
+    }
+}
+
+fn parse_signed_int(input: ParseStream) -> syn::Result<i128> {
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    let value: i128 = input.parse::<LitInt>()?.base10_parse()?;
+    Ok(if negative { -value } else { value })
+}
+
+/// Returns an integer literal, negated for a negative value, which can be
+/// given as a const generic argument
+pub fn signed_int_tokens(value: i128) -> TokenStream {
+    let magnitude = Literal::u128_unsuffixed(value.unsigned_abs());
+    if value < 0 {
+        quote! { { -#magnitude } }
+    } else {
+        quote! { #magnitude }
+    }
+}
+
+impl FieldAttributes {
+    /// Parses the `#[naia(..)]` attributes of a field, panicking on any
+    /// invalid combination
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut max_len = None;
+        let mut codec = None;
+        let mut skip = false;
+        let mut default = None;
+
+        for attr in attrs {
+            if !attr.path.is_ident("naia") {
+                continue;
+            }
+            let nested = attr
+                .parse_args_with(Punctuated::<FieldAttribute, Token![,]>::parse_terminated)
+                .unwrap_or_else(|err| panic!("invalid `#[naia(..)]` attribute: {}", err));
+            for attribute in nested {
+                let new_codec = match attribute {
+                    FieldAttribute::MaxLen(value) => {
+                        max_len = Some(value);
+                        continue;
+                    }
+                    FieldAttribute::Skip => {
+                        skip = true;
+                        continue;
+                    }
+                    FieldAttribute::Default(function) => {
+                        default = Some(function);
+                        continue;
+                    }
+                    FieldAttribute::Bits(bits) => {
+                        if bits == 0 || bits > 127 {
+                            panic!("`bits` must be between 1 and 127");
+                        }
+                        FieldCodec::Bits(bits)
+                    }
+                    FieldAttribute::Range(min, max) => FieldCodec::Range(min, max),
+                    FieldAttribute::With(path) => FieldCodec::With(path),
+                    FieldAttribute::SerdeRs => FieldCodec::SerdeRs,
+                };
+                if codec.replace(new_codec).is_some() {
+                    panic!(
+                        "only one of `bits`, `range`, `with` or `serde_rs` may be given to a field"
+                    );
+                }
+            }
+        }
+
+        let codec = if skip {
+            if codec.is_some() || max_len.is_some() {
+                panic!("a `skip` field can't be given any other naia attribute but `default`");
+            }
+            FieldCodec::Skip(default.flatten())
+        } else {
+            if default.is_some() {
+                panic!("`default` can only be given to a `skip` field");
+            }
+            codec.unwrap_or(FieldCodec::Serde)
+        };
+        if max_len.is_some() && !matches!(codec, FieldCodec::Serde) {
+            panic!(
+                "`max_len` can't be given to a field with `bits`, `range`, `with` or `serde_rs`"
+            );
+        }
+
+        Self { max_len, codec }
+    }
+
+    /// Returns the path of the codec's function with the given name (`ser`,
+    /// `de` or `bit_length`), or None if the field uses its `Serde` impl
+    pub fn codec_function(
+        &self,
+        function_name: &str,
+        field_type: &Type,
+        serde_crate_name: &TokenStream,
+    ) -> Option<TokenStream> {
+        let function_name = format_ident!("{}", function_name);
+        match &self.codec {
+            FieldCodec::Serde | FieldCodec::Skip(_) => None,
+            FieldCodec::Bits(bits) => Some(quote! {
+                #serde_crate_name::codecs::bits::#function_name::<#bits, #field_type>
+            }),
+            FieldCodec::Range(min, max) => {
+                let min = signed_int_tokens(*min);
+                let max = signed_int_tokens(*max);
+                Some(quote! {
+                    #serde_crate_name::codecs::range::#function_name::<#min, #max, #field_type>
+                })
+            }
+            FieldCodec::With(path) => Some(quote! { #path::#function_name }),
+            FieldCodec::SerdeRs => Some(quote! {
+                #serde_crate_name::serde_bridge::#function_name::<#field_type>
+            }),
+        }
+    }
+
+    /// Returns the statement writing the field, given an expression of a
+    /// reference to it
+    pub fn ser_stmt(
+        &self,
+        field_ref: TokenStream,
+        field_type: &Type,
+        serde_crate_name: &TokenStream,
+    ) -> TokenStream {
+        if let FieldCodec::Skip(_) = self.codec {
+            return quote! {};
+        }
+        match self.codec_function("ser", field_type, serde_crate_name) {
+            Some(function) => quote! { #function(#field_ref, writer); },
+            None => quote! { Serde::ser(#field_ref, writer); },
+        }
+    }
+
+    /// Returns the expression used to read this field from `reader`, recording
+    /// the field's path & type on any error
+    pub fn de_expr(
+        &self,
+        field_path: &str,
+        field_type: &Type,
+        serde_crate_name: &TokenStream,
+    ) -> TokenStream {
+        let read = match &self.codec {
+            FieldCodec::Skip(Some(function)) => return quote! { #function() },
+            FieldCodec::Skip(None) => return quote! { <#field_type as Default>::default() },
+            FieldCodec::Serde => match self.max_len {
+                Some(max_len) => quote! { reader.read_with_max_len::<#field_type>(#max_len) },
+                None => quote! { <#field_type as Serde>::de(reader) },
+            },
+            _ => {
+                let function = self.codec_function("de", field_type, serde_crate_name);
+                quote! { #function(reader) }
+            }
+        };
+        quote! {
+            #read.map_err(|err| err.in_field::<#field_type>(#field_path))?
+        }
+    }
+
+    /// Returns the expression of the number of bits the field is written
+    /// with, given an expression of a reference to it
+    pub fn bit_length_expr(
+        &self,
+        field_ref: TokenStream,
+        field_type: &Type,
+        serde_crate_name: &TokenStream,
+    ) -> TokenStream {
+        if let FieldCodec::Skip(_) = self.codec {
+            return quote! { 0 };
+        }
+        match self.codec_function("bit_length", field_type, serde_crate_name) {
+            Some(function) => quote! { #function(#field_ref) },
+            None => quote! { Serde::bit_length(#field_ref) },
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! The `#[naia(..)]` field attribute parser, shared by `naia-serde-derive`
+//! and `naia-derive` so that every derive reads a field the same way
+
+mod attributes;
+
+pub use attributes::{signed_int_tokens, FieldAttributes, FieldCodec};
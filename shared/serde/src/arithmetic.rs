@@ -0,0 +1,339 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{BitWrite, SerdeErrReason};
+
+// Probabilities are the chance of a `false` bit, out of 1 << PROBABILITY_BITS
+const PROBABILITY_BITS: u32 = 11;
+const PROBABILITY_ONE: u16 = 1 << PROBABILITY_BITS;
+// How quickly a probability moves towards the bits it sees. Models start over
+// with every packet, so they must learn fast
+const ADAPTATION_SHIFT: u16 = 4;
+// Each context holds a probability for each of its first bits, the bits past
+// those share the last one
+const CONTEXT_BITS: u32 = 7;
+const POSITION_BITS: u32 = 5;
+const LAST_POSITION: usize = (1 << POSITION_BITS) - 1;
+// The range is renormalized whenever it falls below this
+const RANGE_TOP: u32 = 1 << 24;
+// The coded bytes past the end of the buffer, which are zeroes, that a decoder
+// may read before giving up on a malformed stream
+const MAX_PADDING_BYTES: u8 = 8;
+
+/// How the body of a data packet is written to the wire
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PacketCoding {
+    /// Every bit is written as is
+    #[default]
+    Plain,
+    /// Bits are coded with adaptive binary arithmetic coding, which shrinks
+    /// skewed data such as booleans and small deltas. A packet falls back to
+    /// plain bits whenever coding would not make it any smaller
+    Arithmetic,
+}
+
+// ContextModel
+
+/// The adaptive probabilities shared by an arithmetic encoder & decoder. Each
+/// bit is coded with the probability of the context last selected, at the
+/// position of the bit within that context.
+struct ContextModel {
+    probabilities: Vec<u16>,
+    context_start: usize,
+    position: usize,
+}
+
+impl ContextModel {
+    fn new() -> Self {
+        Self {
+            probabilities: vec![PROBABILITY_ONE / 2; 1 << (CONTEXT_BITS + POSITION_BITS)],
+            context_start: 0,
+            position: 0,
+        }
+    }
+
+    fn set_context(&mut self, context: u32) {
+        // spread the bits of the context before keeping the top ones, so that
+        // contexts differing only in their low bits don't collide
+        let bucket = context.wrapping_mul(0x9E37_79B1) >> (32 - CONTEXT_BITS);
+        self.context_start = (bucket as usize) << POSITION_BITS;
+        self.position = 0;
+    }
+
+    fn next_probability(&mut self) -> &mut u16 {
+        let index = self.context_start + self.position;
+        if self.position < LAST_POSITION {
+            self.position += 1;
+        }
+        &mut self.probabilities[index]
+    }
+}
+
+fn adapt(probability: &mut u16, bit: bool) {
+    if bit {
+        *probability -= *probability >> ADAPTATION_SHIFT;
+    } else {
+        *probability += (PROBABILITY_ONE - *probability) >> ADAPTATION_SHIFT;
+    }
+}
+
+// ArithmeticBitWriter
+
+/// A BitWrite which codes every bit with adaptive binary arithmetic (range)
+/// coding, given the probability context selected through `set_context()`.
+/// Its output must be read with `BitReader::new_arithmetic()`.
+pub struct ArithmeticBitWriter {
+    model: ContextModel,
+    low: u64,
+    range: u32,
+    cache: u8,
+    cache_size: u32,
+    output: Vec<u8>,
+    // the first byte out of the coder is always zero, so it is never written
+    skip_byte: bool,
+}
+
+impl ArithmeticBitWriter {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            model: ContextModel::new(),
+            low: 0,
+            range: u32::MAX,
+            cache: 0,
+            cache_size: 1,
+            output: Vec::new(),
+            skip_byte: true,
+        }
+    }
+
+    pub fn to_bytes(mut self) -> Box<[u8]> {
+        // any value within the final range is read back the same, so pick the
+        // one with the most trailing zero bits, which need not be written
+        let high = self.low + u64::from(self.range) - 1;
+        let mut mask = u64::from(u32::MAX);
+        while high & !mask < self.low {
+            mask >>= 1;
+        }
+        self.low = high & !mask;
+
+        for _ in 0..5 {
+            self.shift_low();
+        }
+
+        // the decoder reads zeroes past the end of the buffer
+        while self.output.last() == Some(&0) {
+            self.output.pop();
+        }
+        self.output.into_boxed_slice()
+    }
+
+    fn shift_low(&mut self) {
+        if (self.low as u32) < 0xFF00_0000 || (self.low >> 32) != 0 {
+            let carry = (self.low >> 32) as u8;
+            let mut byte = self.cache;
+            loop {
+                self.push_byte(byte.wrapping_add(carry));
+                byte = 0xFF;
+                self.cache_size -= 1;
+                if self.cache_size == 0 {
+                    break;
+                }
+            }
+            self.cache = (self.low >> 24) as u8;
+        }
+        self.cache_size += 1;
+        self.low = (self.low & 0x00FF_FFFF) << 8;
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        if self.skip_byte {
+            self.skip_byte = false;
+            return;
+        }
+        self.output.push(byte);
+    }
+}
+
+impl BitWrite for ArithmeticBitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        let probability = self.model.next_probability();
+        let bound = (self.range >> PROBABILITY_BITS) * u32::from(*probability);
+        if bit {
+            self.low += u64::from(bound);
+            self.range -= bound;
+        } else {
+            self.range = bound;
+        }
+        adapt(probability, bit);
+
+        while self.range < RANGE_TOP {
+            self.range <<= 8;
+            self.shift_low();
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) {
+        let mut temp = byte;
+        for _ in 0..8 {
+            self.write_bit(temp & 1 != 0);
+            temp >>= 1;
+        }
+    }
+
+    fn write_bits(&mut self, _: u32) {
+        panic!("This method should not be called for ArithmeticBitWriter!");
+    }
+
+    fn is_counter(&self) -> bool {
+        false
+    }
+
+    fn set_context(&mut self, context: u32) {
+        self.model.set_context(context);
+    }
+
+    fn is_coding(&self) -> bool {
+        true
+    }
+}
+
+// ArithmeticDecoder
+
+/// Decodes the bits written by an ArithmeticBitWriter, pulling coded bytes
+/// from the given source, which returns None past the end of its buffer
+pub(crate) struct ArithmeticDecoder {
+    model: ContextModel,
+    range: u32,
+    code: u32,
+    padding_bytes: u8,
+}
+
+impl ArithmeticDecoder {
+    pub(crate) fn new(mut next_byte: impl FnMut() -> Option<u8>) -> Self {
+        let mut decoder = Self {
+            model: ContextModel::new(),
+            range: u32::MAX,
+            code: 0,
+            padding_bytes: 0,
+        };
+        for _ in 0..4 {
+            let byte = decoder.pull_byte(&mut next_byte);
+            decoder.code = (decoder.code << 8) | u32::from(byte);
+        }
+        decoder
+    }
+
+    pub(crate) fn set_context(&mut self, context: u32) {
+        self.model.set_context(context);
+    }
+
+    pub(crate) fn decode_bit(
+        &mut self,
+        mut next_byte: impl FnMut() -> Option<u8>,
+    ) -> Result<bool, SerdeErrReason> {
+        if self.padding_bytes > MAX_PADDING_BYTES {
+            return Err(SerdeErrReason::OutOfBits);
+        }
+
+        let probability = self.model.next_probability();
+        let bound = (self.range >> PROBABILITY_BITS) * u32::from(*probability);
+        let bit = self.code >= bound;
+        if bit {
+            self.code -= bound;
+            self.range -= bound;
+        } else {
+            self.range = bound;
+        }
+        adapt(probability, bit);
+
+        while self.range < RANGE_TOP {
+            self.range <<= 8;
+            let byte = self.pull_byte(&mut next_byte);
+            self.code = (self.code << 8) | u32::from(byte);
+        }
+
+        Ok(bit)
+    }
+
+    fn pull_byte(&mut self, next_byte: &mut impl FnMut() -> Option<u8>) -> u8 {
+        next_byte().unwrap_or_else(|| {
+            self.padding_bytes = self.padding_bytes.saturating_add(1);
+            0
+        })
+    }
+}
+
+mod tests {
+    #[test]
+    fn read_write_bits() {
+        use crate::{arithmetic::ArithmeticBitWriter, bit_reader::BitReader, BitWrite};
+
+        let bits: Vec<bool> = (0..200_u32).map(|index| index % 3 == 0).collect();
+
+        let mut writer = ArithmeticBitWriter::new();
+        for bit in &bits {
+            writer.write_bit(*bit);
+        }
+        let buffer = writer.to_bytes();
+
+        let mut reader = BitReader::new_arithmetic(&buffer);
+        for bit in &bits {
+            assert_eq!(reader.read_bit().unwrap(), *bit);
+        }
+    }
+
+    #[test]
+    fn read_write_in_contexts() {
+        use crate::{arithmetic::ArithmeticBitWriter, bit_reader::BitReader, BitWrite, Serde};
+
+        let mut writer = ArithmeticBitWriter::new();
+        for index in 0..50_u16 {
+            writer.set_context(1);
+            (index % 7 == 0).ser(&mut writer);
+            writer.set_context(2);
+            (index * 31).ser(&mut writer);
+        }
+        let buffer = writer.to_bytes();
+
+        let mut reader = BitReader::new_arithmetic(&buffer);
+        for index in 0..50_u16 {
+            reader.set_context(1);
+            assert_eq!(bool::de(&mut reader).unwrap(), index % 7 == 0);
+            reader.set_context(2);
+            assert_eq!(u16::de(&mut reader).unwrap(), index * 31);
+        }
+    }
+
+    #[test]
+    fn skewed_bits_shrink() {
+        use crate::{arithmetic::ArithmeticBitWriter, BitWrite};
+
+        let mut writer = ArithmeticBitWriter::new();
+        for index in 0..800_u32 {
+            writer.write_bit(index % 100 == 0);
+        }
+        let buffer = writer.to_bytes();
+
+        // 800 plain bits take 100 bytes
+        assert!(buffer.len() < 25);
+    }
+
+    #[test]
+    fn reading_past_the_end_fails() {
+        use crate::bit_reader::BitReader;
+
+        let mut reader = BitReader::new_arithmetic(&[0xA5; 3]);
+        let mut bits_read = 0;
+        while reader.read_bit().is_ok() {
+            bits_read += 1;
+            assert!(bits_read < 100_000);
+        }
+    }
+
+    #[test]
+    fn empty_writer_writes_nothing() {
+        use crate::arithmetic::ArithmeticBitWriter;
+
+        assert!(ArithmeticBitWriter::new().to_bytes().is_empty());
+    }
+}
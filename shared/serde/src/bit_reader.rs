@@ -1,14 +1,23 @@
 // BitReader
 
-use crate::SerdeErr;
+use alloc::boxed::Box;
+
+use crate::{arithmetic::ArithmeticDecoder, RemoteStrings, Serde, SerdeErr, SerdeErrReason};
 
 pub struct BitReader<'b> {
     state: BitReaderState,
     buffer: &'b [u8],
+    budget: ReadBudget,
+    decoder: Option<Box<ArithmeticDecoder>>,
+    remote_strings: Option<RemoteStrings>,
 }
 
 impl<'b> BitReader<'b> {
     pub fn new(buffer: &'b [u8]) -> Self {
+        Self::with_limits(buffer, ReadLimits::default())
+    }
+
+    pub fn with_limits(buffer: &'b [u8], limits: ReadLimits) -> Self {
         Self {
             state: BitReaderState {
                 scratch: 0,
@@ -16,35 +25,121 @@ impl<'b> BitReader<'b> {
                 buffer_index: 0,
             },
             buffer,
+            budget: ReadBudget::new(limits),
+            decoder: None,
+            remote_strings: None,
+        }
+    }
+
+    /// Creates a reader of the bits written by an `ArithmeticBitWriter`
+    pub fn new_arithmetic(buffer: &'b [u8]) -> Self {
+        let mut reader = Self::new(buffer);
+        reader.decoder = Some(Box::new(reader.new_decoder()));
+        reader
+    }
+
+    /// Reads the rest of the buffer as written after
+    /// `BitWriter::begin_coding()`, decoding it if it was arithmetically coded
+    pub fn begin_decoding(&mut self) -> Result<(), SerdeErr> {
+        if self.read_bit()? {
+            self.decoder = Some(Box::new(self.new_decoder()));
         }
+        Ok(())
+    }
+
+    /// Selects the probability context of the bits read next, which must be
+    /// the one they were written in. Has no effect unless decoding.
+    pub fn set_context(&mut self, context: u32) {
+        if let Some(decoder) = &mut self.decoder {
+            decoder.set_context(context);
+        }
+    }
+
+    fn new_decoder(&mut self) -> ArithmeticDecoder {
+        let buffer = self.buffer;
+        let state = &mut self.state;
+        ArithmeticDecoder::new(|| state.read_byte(buffer))
+    }
+
+    /// Reads InternedStrings against the given dictionary, until detached
+    pub fn attach_strings(&mut self, remote_strings: RemoteStrings) {
+        self.remote_strings = Some(remote_strings);
+    }
+
+    pub fn detach_strings(&mut self) -> Option<RemoteStrings> {
+        self.remote_strings.take()
     }
 
+    pub(crate) fn remote_strings(&mut self) -> Option<&mut RemoteStrings> {
+        self.remote_strings.as_mut()
+    }
+
+    /// Copies the state of the reader into an OwnedBitReader, which does not
+    /// carry on any decoding begun through `begin_decoding()`
     pub fn to_owned(&self) -> OwnedBitReader {
         OwnedBitReader {
             state: self.state,
             buffer: self.buffer.into(),
+            limits: self.budget.limits,
         }
     }
 
-    pub(crate) fn read_bit(&mut self) -> Result<bool, SerdeErr> {
-        if self.state.scratch_index == 0 {
-            if self.state.buffer_index == self.buffer.len() {
-                return Err(SerdeErr);
-            }
-
-            self.state.scratch = self.buffer[self.state.buffer_index];
+    /// Returns the number of bits which have been read so far
+    pub fn bit_offset(&self) -> usize {
+        (self.state.buffer_index * 8) - (self.state.scratch_index as usize)
+    }
 
-            self.state.buffer_index += 1;
-            self.state.scratch_index += 8;
-        }
+    pub fn limits(&self) -> &ReadLimits {
+        &self.budget.limits
+    }
 
-        let value = self.state.scratch & 1;
+    /// Reads from `buffer` with a reader bound by the same limits, whose
+    /// reservations count against this reader's own, e.g. for a Message
+    /// carried inside of another
+    pub fn read_nested<T>(
+        &mut self,
+        buffer: &[u8],
+        read: impl FnOnce(&mut BitReader) -> Result<T, SerdeErr>,
+    ) -> Result<T, SerdeErr> {
+        let mut nested = BitReader::with_limits(buffer, self.budget.limits);
+        nested.budget.reserved_bytes = self.budget.reserved_bytes;
+        let result = read(&mut nested);
+        self.budget.reserved_bytes = nested.budget.reserved_bytes;
+        result
+    }
 
-        self.state.scratch >>= 1;
+    /// Reads a value, restricting the first collection read within it to at
+    /// most `max_len` elements (on top of the reader's own limits)
+    pub fn read_with_max_len<T: Serde>(&mut self, max_len: usize) -> Result<T, SerdeErr> {
+        self.budget.next_max_len = Some(max_len);
+        let result = T::de(self);
+        self.budget.next_max_len = None;
+        result
+    }
 
-        self.state.scratch_index -= 1;
+    /// Must be called by collection impls before allocating storage for
+    /// `length` elements of type `T`, where `length` has been read off the
+    /// wire. Returns an error if this would exceed the reader's limits.
+    pub fn reserve_collection<T>(&mut self, length: usize) -> Result<(), SerdeErr> {
+        self.budget
+            .reserve(length, core::mem::size_of::<T>())
+            .map_err(|reason| SerdeErr::new(reason, self.bit_offset()))
+    }
 
-        Ok(value != 0)
+    pub(crate) fn read_bit(&mut self) -> Result<bool, SerdeErr> {
+        let bit_offset = self.bit_offset();
+        let result = match &mut self.decoder {
+            Some(decoder) => {
+                let buffer = self.buffer;
+                let state = &mut self.state;
+                decoder.decode_bit(|| state.read_byte(buffer))
+            }
+            None => self
+                .state
+                .read_bit(self.buffer)
+                .ok_or(SerdeErrReason::OutOfBits),
+        };
+        result.map_err(|reason| SerdeErr::new(reason, bit_offset))
     }
 
     pub(crate) fn read_byte(&mut self) -> Result<u8, SerdeErr> {
@@ -67,10 +162,15 @@ impl<'b> BitReader<'b> {
 pub struct OwnedBitReader {
     state: BitReaderState,
     buffer: Box<[u8]>,
+    limits: ReadLimits,
 }
 
 impl OwnedBitReader {
     pub fn new(buffer: &[u8]) -> Self {
+        Self::with_limits(buffer, ReadLimits::default())
+    }
+
+    pub fn with_limits(buffer: &[u8], limits: ReadLimits) -> Self {
         Self {
             state: BitReaderState {
                 scratch: 0,
@@ -78,6 +178,7 @@ impl OwnedBitReader {
                 buffer_index: 0,
             },
             buffer: buffer.into(),
+            limits,
         }
     }
 
@@ -85,7 +186,81 @@ impl OwnedBitReader {
         BitReader {
             state: self.state,
             buffer: &self.buffer,
+            budget: ReadBudget::new(self.limits),
+            decoder: None,
+            remote_strings: None,
+        }
+    }
+}
+
+// ReadLimits
+
+/// Limits on the allocations a BitReader will make while deserializing, so
+/// that lengths read from untrusted input cannot request unbounded memory
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// Maximum number of elements in any single collection (Vec, VecDeque,
+    /// String, HashMap, HashSet)
+    pub max_collection_len: usize,
+    /// Maximum number of bytes which may be reserved for collections over the
+    /// lifetime of a single reader
+    pub max_total_bytes: usize,
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        Self {
+            max_collection_len: 1 << 20,
+            max_total_bytes: 1 << 26,
+        }
+    }
+}
+
+// ReadBudget
+
+struct ReadBudget {
+    limits: ReadLimits,
+    reserved_bytes: usize,
+    next_max_len: Option<usize>,
+}
+
+impl ReadBudget {
+    fn new(limits: ReadLimits) -> Self {
+        Self {
+            limits,
+            reserved_bytes: 0,
+            next_max_len: None,
+        }
+    }
+
+    fn reserve(&mut self, length: usize, element_size: usize) -> Result<(), SerdeErrReason> {
+        let mut max_len = self.limits.max_collection_len;
+        if let Some(next_max_len) = self.next_max_len.take() {
+            max_len = max_len.min(next_max_len);
+        }
+        if length > max_len {
+            return Err(SerdeErrReason::LengthLimit {
+                length,
+                max_length: max_len,
+            });
+        }
+
+        let reserved_bytes = self
+            .reserved_bytes
+            .saturating_add(length.saturating_mul(element_size));
+        if reserved_bytes > self.limits.max_total_bytes {
+            return Err(SerdeErrReason::LengthLimit {
+                length,
+                max_length: self
+                    .limits
+                    .max_total_bytes
+                    .saturating_sub(self.reserved_bytes)
+                    / element_size.max(1),
+            });
         }
+        self.reserved_bytes = reserved_bytes;
+
+        Ok(())
     }
 }
 
@@ -96,3 +271,40 @@ struct BitReaderState {
     scratch_index: u8,
     buffer_index: usize,
 }
+
+impl BitReaderState {
+    fn read_bit(&mut self, buffer: &[u8]) -> Option<bool> {
+        if self.scratch_index == 0 {
+            if self.buffer_index == buffer.len() {
+                return None;
+            }
+
+            self.scratch = buffer[self.buffer_index];
+
+            self.buffer_index += 1;
+            self.scratch_index += 8;
+        }
+
+        let value = self.scratch & 1;
+
+        self.scratch >>= 1;
+
+        self.scratch_index -= 1;
+
+        Some(value != 0)
+    }
+
+    // Reads the next 8 bits, padded with zeroes past the end of the buffer, or
+    // None if there are no bits left at all
+    fn read_byte(&mut self, buffer: &[u8]) -> Option<u8> {
+        let mut output = 0;
+        for index in 0..8 {
+            match self.read_bit(buffer) {
+                Some(bit) => output |= u8::from(bit) << index,
+                None if index == 0 => return None,
+                None => break,
+            }
+        }
+        Some(output)
+    }
+}
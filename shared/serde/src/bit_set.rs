@@ -0,0 +1,311 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, UnsignedVariableInteger};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+fn word_count(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS)
+}
+
+fn get_bit(words: &[u64], index: usize) -> bool {
+    words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+}
+
+fn set_bit(words: &mut [u64], index: usize, value: bool) {
+    let mask = 1 << (index % WORD_BITS);
+    if value {
+        words[index / WORD_BITS] |= mask;
+    } else {
+        words[index / WORD_BITS] &= !mask;
+    }
+}
+
+fn write_words(writer: &mut dyn BitWrite, words: &[u64], bits: usize) {
+    for index in 0..bits {
+        writer.write_bit(get_bit(words, index));
+    }
+}
+
+fn read_words<T>(reader: &mut BitReader, bits: usize) -> Result<Vec<u64>, SerdeErr> {
+    let mut words = vec![0; word_count(bits)];
+    for index in 0..bits {
+        if reader.read_bit().map_err(SerdeErr::expecting::<T>)? {
+            set_bit(&mut words, index, true);
+        }
+    }
+    Ok(words)
+}
+
+// BitSet
+
+/// A fixed set of `N` flags, written as exactly `N` bits
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitSet<const N: usize> {
+    words: Vec<u64>,
+}
+
+impl<const N: usize> BitSet<N> {
+    /// Creates a BitSet with every flag cleared
+    pub fn new() -> Self {
+        Self {
+            words: vec![0; word_count(N)],
+        }
+    }
+
+    /// # Panics
+    /// If `index` is not less than `N`
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < N, "index {} out of range for BitSet<{}>", index, N);
+        get_bit(&self.words, index)
+    }
+
+    /// # Panics
+    /// If `index` is not less than `N`
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < N, "index {} out of range for BitSet<{}>", index, N);
+        set_bit(&mut self.words, index, value);
+    }
+
+    /// Clears every flag
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the number of flags which are set
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Returns the indices of the flags which are set, in ascending order
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..N).filter(|index| get_bit(&self.words, *index))
+    }
+}
+
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Serde for BitSet<N> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        write_words(writer, &self.words, N);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let words = read_words::<Self>(reader, N)?;
+        Ok(Self { words })
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl<const N: usize> ConstBitLength for BitSet<N> {
+    fn const_bit_length() -> u32 {
+        N as u32
+    }
+}
+
+// BitVec
+
+/// A growable list of flags, written as its length followed by one bit per
+/// flag
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a BitVec of `len` cleared flags
+    pub fn with_len(len: usize) -> Self {
+        Self {
+            words: vec![0; word_count(len)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// # Panics
+    /// If `index` is not less than the BitVec's length
+    pub fn get(&self, index: usize) -> bool {
+        assert!(
+            index < self.len,
+            "index {} out of range for BitVec of length {}",
+            index,
+            self.len
+        );
+        get_bit(&self.words, index)
+    }
+
+    /// # Panics
+    /// If `index` is not less than the BitVec's length
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(
+            index < self.len,
+            "index {} out of range for BitVec of length {}",
+            index,
+            self.len
+        );
+        set_bit(&mut self.words, index, value);
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len == self.words.len() * WORD_BITS {
+            self.words.push(0);
+        }
+        self.len += 1;
+        set_bit(&mut self.words, self.len - 1, value);
+    }
+
+    pub fn pop(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = get_bit(&self.words, self.len - 1);
+        // keep unused bits cleared, so that equal BitVecs compare equal
+        set_bit(&mut self.words, self.len - 1, false);
+        self.len -= 1;
+        self.words.truncate(word_count(self.len));
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+
+    /// Returns the number of flags which are set
+    pub fn count_ones(&self) -> u32 {
+        self.words.iter().map(|word| word.count_ones()).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|index| get_bit(&self.words, index))
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut output = Self::new();
+        for value in iter {
+            output.push(value);
+        }
+        output
+    }
+}
+
+impl Serde for BitVec {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        let length = UnsignedVariableInteger::<5>::new(self.len as u64);
+        length.ser(writer);
+        write_words(writer, &self.words, self.len);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
+        let len = length_int.get() as usize;
+        reader
+            .reserve_collection::<u64>(word_count(len))
+            .map_err(SerdeErr::expecting::<Self>)?;
+        let words = read_words::<Self>(reader, len)?;
+        Ok(Self { words, len })
+    }
+
+    fn bit_length(&self) -> u32 {
+        let length = UnsignedVariableInteger::<5>::new(self.len as u64);
+        length.bit_length() + self.len as u32
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{BitReader, BitSet, BitVec, BitWriter, ConstBitLength, Serde};
+
+    #[test]
+    fn read_write_bit_set() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let mut in_1 = BitSet::<70>::new();
+        in_1.set(0, true);
+        in_1.set(3, true);
+        in_1.set(69, true);
+        let in_2 = BitSet::<3>::new();
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1: BitSet<70> = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(out_1.iter_ones().collect::<Vec<_>>(), vec![0, 3, 69]);
+        assert_eq!(reader.bit_offset(), 73);
+        assert_eq!(BitSet::<70>::const_bit_length(), 70);
+    }
+
+    #[test]
+    fn read_write_bit_vec() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: BitVec = (0..100).map(|index| index % 3 == 0).collect();
+        let in_2 = BitVec::new();
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1: BitVec = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(out_1.count_ones(), 34);
+        assert_eq!(
+            reader.bit_offset() as u32,
+            in_1.bit_length() + in_2.bit_length()
+        );
+    }
+
+    #[test]
+    fn bit_vec_pop() {
+        let mut bit_vec: BitVec = [true, false, true].into_iter().collect();
+
+        assert_eq!(bit_vec.pop(), Some(true));
+        bit_vec.push(false);
+
+        let expected: BitVec = [true, false, false].into_iter().collect();
+        assert_eq!(bit_vec, expected);
+    }
+}
@@ -1,6 +1,8 @@
+use alloc::boxed::Box;
+
 use crate::{
     constants::{MTU_SIZE_BITS, MTU_SIZE_BYTES},
-    BitCounter, OutgoingPacket, OwnedBitReader,
+    ArithmeticBitWriter, BitCounter, HostStrings, OutgoingPacket, OwnedBitReader, PacketCoding,
 };
 
 // BitWrite
@@ -9,6 +11,20 @@ pub trait BitWrite {
     fn write_byte(&mut self, byte: u8);
     fn write_bits(&mut self, bits: u32);
     fn is_counter(&self) -> bool;
+    /// Selects the probability context of the bits written next, for writers
+    /// which code them arithmetically. The same context must be selected
+    /// before reading them back, through `BitReader::set_context()`.
+    fn set_context(&mut self, _context: u32) {}
+    /// Whether the bits written are arithmetically coded, in which case they
+    /// can't be replayed from another writer's output
+    fn is_coding(&self) -> bool {
+        false
+    }
+    /// The connection's dictionary for any InternedStrings written, if one
+    /// has been attached
+    fn host_strings(&mut self) -> Option<&mut HostStrings> {
+        None
+    }
 }
 
 // BitWriter
@@ -19,6 +35,10 @@ pub struct BitWriter {
     buffer_index: usize,
     current_bits: u32,
     max_bits: u32,
+    coder: Option<Box<ArithmeticBitWriter>>,
+    // the index of the bit which tells whether the rest is coded
+    coding_start: u32,
+    host_strings: Option<HostStrings>,
 }
 
 impl BitWriter {
@@ -31,6 +51,9 @@ impl BitWriter {
             buffer_index: 0,
             current_bits: 0,
             max_bits: MTU_SIZE_BITS,
+            coder: None,
+            coding_start: 0,
+            host_strings: None,
         }
     }
 
@@ -42,10 +65,68 @@ impl BitWriter {
             buffer_index: 0,
             current_bits: 0,
             max_bits: bit_capacity,
+            coder: None,
+            coding_start: 0,
+            host_strings: None,
+        }
+    }
+
+    /// Writes the rest of the bits with the given coding, to be read after
+    /// `BitReader::begin_decoding()`. Bits are still counted (& limited) as
+    /// plain bits, arithmetic coding only ever shrinks what is written.
+    pub fn begin_coding(&mut self, coding: PacketCoding) {
+        self.coding_start = self.current_bits;
+        // whether the rest is coded, decided once all of it is written
+        self.write_bit(false);
+        if coding == PacketCoding::Arithmetic {
+            self.coder = Some(Box::new(ArithmeticBitWriter::new()));
+        }
+    }
+
+    /// Writes InternedStrings against the given dictionary, until detached
+    pub fn attach_strings(&mut self, host_strings: HostStrings) {
+        self.host_strings = Some(host_strings);
+    }
+
+    pub fn detach_strings(&mut self) -> Option<HostStrings> {
+        self.host_strings.take()
+    }
+
+    // Swaps the bits written since `begin_coding()` for their arithmetic
+    // coding, if it is any shorter
+    fn finish_coding(&mut self) {
+        let Some(coder) = self.coder.take() else {
+            return;
+        };
+        let coded = coder.to_bytes();
+        let plain_bits = self.current_bits - self.coding_start - 1;
+        if coded.len() * 8 >= plain_bits as usize {
+            return;
+        }
+
+        let mut output = Self::new();
+        output.host_strings = self.host_strings.take();
+        for index in 0..self.coding_start {
+            output.write_bit(self.bit(index));
+        }
+        output.write_bit(true);
+        for byte in coded.iter() {
+            output.write_byte(*byte);
         }
+        *self = output;
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        let byte_index = (index / 8) as usize;
+        if byte_index < self.buffer_index {
+            return self.buffer[byte_index] & (1 << (index % 8)) != 0;
+        }
+        let scratch_offset = index - (self.buffer_index as u32 * 8);
+        self.scratch & (1 << (u32::from(self.scratch_index) - 1 - scratch_offset)) != 0
     }
 
     fn finalize(&mut self) {
+        self.finish_coding();
         if self.scratch_index > 0 {
             self.buffer[self.buffer_index] =
                 (self.scratch << (8 - self.scratch_index)).reverse_bits();
@@ -107,6 +188,10 @@ impl BitWrite for BitWriter {
             self.scratch_index -= 8;
             self.scratch = 0;
         }
+
+        if let Some(coder) = &mut self.coder {
+            coder.write_bit(bit);
+        }
     }
 
     fn write_byte(&mut self, byte: u8) {
@@ -124,6 +209,20 @@ impl BitWrite for BitWriter {
     fn is_counter(&self) -> bool {
         false
     }
+
+    fn set_context(&mut self, context: u32) {
+        if let Some(coder) = &mut self.coder {
+            coder.set_context(context);
+        }
+    }
+
+    fn is_coding(&self) -> bool {
+        self.coder.is_some()
+    }
+
+    fn host_strings(&mut self) -> Option<&mut HostStrings> {
+        self.host_strings.as_mut()
+    }
 }
 
 mod tests {
@@ -347,4 +446,68 @@ mod tests {
         assert_eq!(34, reader.read_byte().unwrap());
         assert_eq!(2, reader.read_byte().unwrap());
     }
+
+    #[test]
+    fn read_write_coded_body() {
+        use crate::{
+            bit_reader::BitReader,
+            bit_writer::{BitWrite, BitWriter},
+            PacketCoding, Serde,
+        };
+
+        let mut writer = BitWriter::new();
+
+        // plain header, not aligned to a byte
+        writer.write_bit(true);
+        writer.write_byte(77);
+
+        writer.begin_coding(PacketCoding::Arithmetic);
+        for index in 0..300_u32 {
+            writer.set_context(1);
+            (index % 50 == 0).ser(&mut writer);
+        }
+
+        let buffer = writer.to_bytes();
+
+        // 9 bits of header, 1 coding bit & 300 plain bits of body
+        assert!(buffer.len() < 10);
+
+        let mut reader = BitReader::new(&buffer);
+
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(77, reader.read_byte().unwrap());
+
+        reader.begin_decoding().unwrap();
+        for index in 0..300_u32 {
+            reader.set_context(1);
+            assert_eq!(bool::de(&mut reader).unwrap(), index % 50 == 0);
+        }
+    }
+
+    #[test]
+    fn coded_body_falls_back_to_plain() {
+        use crate::{
+            bit_reader::BitReader,
+            bit_writer::{BitWrite, BitWriter},
+            PacketCoding,
+        };
+
+        let mut writer = BitWriter::new();
+
+        writer.write_byte(77);
+
+        // too short to gain anything from coding
+        writer.begin_coding(PacketCoding::Arithmetic);
+        writer.write_byte(151);
+
+        let buffer = writer.to_bytes();
+
+        assert_eq!(buffer.len(), 3);
+
+        let mut reader = BitReader::new(&buffer);
+
+        assert_eq!(77, reader.read_byte().unwrap());
+        reader.begin_decoding().unwrap();
+        assert_eq!(151, reader.read_byte().unwrap());
+    }
 }
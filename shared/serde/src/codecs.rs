@@ -0,0 +1,258 @@
+//! Codecs which derived `Serde` impls use for fields given a `#[naia(..)]`
+//! attribute. Each codec is a module with the same `ser`, `de` & `bit_length`
+//! functions a module given through `#[naia(with = "module")]` must provide.
+
+use crate::{BitReader, BitWrite, SerdeErr};
+
+/// A primitive integer which can be written with fewer bits than its size
+pub trait IntegerField: Copy {
+    const SIGNED: bool;
+
+    fn to_i128(self) -> i128;
+
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_integer_field {
+    ($signed:expr, $($type:ty),*) => {$(
+        impl IntegerField for $type {
+            const SIGNED: bool = $signed;
+
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            fn from_i128(value: i128) -> Option<Self> {
+                <$type>::try_from(value).ok()
+            }
+        }
+    )*};
+}
+
+impl_integer_field!(false, u8, u16, u32, u64, usize);
+impl_integer_field!(true, i8, i16, i32, i64, i128, isize);
+
+// a u128 can't always be held by an i128, but no codec has room for the ones
+// which can't anyway
+impl IntegerField for u128 {
+    const SIGNED: bool = false;
+
+    fn to_i128(self) -> i128 {
+        i128::try_from(self)
+            .unwrap_or_else(|_| panic!("can't encode number {} with more than 127 bits", self))
+    }
+
+    fn from_i128(value: i128) -> Option<Self> {
+        u128::try_from(value).ok()
+    }
+}
+
+pub(crate) fn write_bits(writer: &mut dyn BitWrite, mut value: u128, bits: u32) {
+    for _ in 0..bits {
+        writer.write_bit(value & 1 != 0);
+        value >>= 1;
+    }
+}
+
+pub(crate) fn read_bits<T>(reader: &mut BitReader, bits: u32) -> Result<u128, SerdeErr> {
+    let mut output: u128 = 0;
+    for index in 0..bits {
+        if reader.read_bit().map_err(SerdeErr::expecting::<T>)? {
+            output |= 1 << index;
+        }
+    }
+    Ok(output)
+}
+
+/// `#[naia(bits = N)]`: writes an integer with `N` bits, plus a sign bit for
+/// signed types
+pub mod bits {
+    use super::{read_bits, write_bits, IntegerField};
+    use crate::{BitReader, BitWrite, SerdeErr, SerdeErrReason};
+
+    pub fn ser<const BITS: u8, T: IntegerField>(value: &T, writer: &mut dyn BitWrite) {
+        let value = value.to_i128();
+        if !T::SIGNED && value < 0 {
+            panic!("can't encode a negative number with an Unsigned Integer!");
+        }
+        let magnitude = value.unsigned_abs();
+        if BITS < 128 && magnitude >= 1 << BITS {
+            panic!("with {} bits, can't encode number {}", BITS, value);
+        }
+        if T::SIGNED {
+            writer.write_bit(value < 0);
+        }
+        write_bits(writer, magnitude, BITS as u32);
+    }
+
+    pub fn de<const BITS: u8, T: IntegerField>(reader: &mut BitReader) -> Result<T, SerdeErr> {
+        let negative = T::SIGNED && reader.read_bit().map_err(SerdeErr::expecting::<T>)?;
+        let magnitude = read_bits::<T>(reader, BITS as u32)?;
+        let value = i128::try_from(magnitude)
+            .ok()
+            .map(|value| if negative { -value } else { value })
+            .and_then(T::from_i128);
+        value.ok_or_else(|| {
+            SerdeErr::new(
+                SerdeErrReason::Other("integer does not fit in its field's type".into()),
+                reader.bit_offset(),
+            )
+            .expecting::<T>()
+        })
+    }
+
+    pub fn bit_length<const BITS: u8, T: IntegerField>(_value: &T) -> u32 {
+        BITS as u32 + u32::from(T::SIGNED)
+    }
+}
+
+/// `#[naia(range = MIN..=MAX)]`: writes an integer as its offset from `MIN`,
+/// with as few bits as are needed to hold `MAX`
+pub mod range {
+    use super::{read_bits, write_bits, IntegerField};
+    use crate::{BitReader, BitWrite, SerdeErr, SerdeErrReason};
+
+    const fn range_bits(min: i128, max: i128) -> u32 {
+        let span = max.wrapping_sub(min) as u128;
+        u128::BITS - span.leading_zeros()
+    }
+
+    pub fn ser<const MIN: i128, const MAX: i128, T: IntegerField>(
+        value: &T,
+        writer: &mut dyn BitWrite,
+    ) {
+        let value = value.to_i128();
+        if value < MIN || value > MAX {
+            panic!(
+                "can't encode number {} outside of range {}..={}",
+                value, MIN, MAX
+            );
+        }
+        let offset = value.wrapping_sub(MIN) as u128;
+        write_bits(writer, offset, range_bits(MIN, MAX));
+    }
+
+    pub fn de<const MIN: i128, const MAX: i128, T: IntegerField>(
+        reader: &mut BitReader,
+    ) -> Result<T, SerdeErr> {
+        let offset = read_bits::<T>(reader, range_bits(MIN, MAX))?;
+        let value = MIN.wrapping_add(offset as i128);
+        if offset > MAX.wrapping_sub(MIN) as u128 {
+            return Err(SerdeErr::new(
+                SerdeErrReason::Other("integer outside of its field's range".into()),
+                reader.bit_offset(),
+            )
+            .expecting::<T>());
+        }
+        T::from_i128(value).ok_or_else(|| {
+            SerdeErr::new(
+                SerdeErrReason::Other("integer does not fit in its field's type".into()),
+                reader.bit_offset(),
+            )
+            .expecting::<T>()
+        })
+    }
+
+    pub fn bit_length<const MIN: i128, const MAX: i128, T: IntegerField>(_value: &T) -> u32 {
+        range_bits(MIN, MAX)
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{codecs, BitReader, BitWriter};
+
+    #[test]
+    fn read_write_bits() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        codecs::bits::ser::<5, u8>(&31, &mut writer);
+        codecs::bits::ser::<3, i16>(&-7, &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(codecs::bits::de::<5, u8>(&mut reader).unwrap(), 31);
+        assert_eq!(codecs::bits::de::<3, i16>(&mut reader).unwrap(), -7);
+        assert_eq!(reader.bit_offset(), 9);
+    }
+
+    #[test]
+    fn read_write_range() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        codecs::range::ser::<-100, 100, i32>(&-100, &mut writer);
+        codecs::range::ser::<-100, 100, i32>(&100, &mut writer);
+        codecs::range::ser::<5, 5, u8>(&5, &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(
+            codecs::range::de::<-100, 100, i32>(&mut reader).unwrap(),
+            -100
+        );
+        assert_eq!(
+            codecs::range::de::<-100, 100, i32>(&mut reader).unwrap(),
+            100
+        );
+        assert_eq!(codecs::range::de::<5, 5, u8>(&mut reader).unwrap(), 5);
+        assert_eq!(reader.bit_offset(), 16);
+    }
+
+    #[test]
+    fn read_out_of_range() {
+        // Write, with a wider range than is read
+        let mut writer = BitWriter::new();
+
+        codecs::range::ser::<0, 255, u8>(&250, &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        assert!(codecs::range::de::<0, 200, u8>(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_write_u128() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let large: u128 = (1 << 100) + 7;
+        codecs::bits::ser::<101, u128>(&large, &mut writer);
+        codecs::range::ser::<0, 1000, u128>(&999, &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        assert_eq!(codecs::bits::de::<101, u128>(&mut reader).unwrap(), large);
+        assert_eq!(
+            codecs::range::de::<0, 1000, u128>(&mut reader).unwrap(),
+            999
+        );
+        assert_eq!(reader.bit_offset(), 111);
+    }
+
+    #[test]
+    #[should_panic]
+    fn u128_above_i128_is_rejected() {
+        let mut writer = BitWriter::new();
+        codecs::bits::ser::<127, u128>(&u128::MAX, &mut writer);
+    }
+}
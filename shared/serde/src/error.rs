@@ -1,18 +1,145 @@
-/// The error message when failing to serialize/deserialize to/from the bit
-/// stream.
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+
+/// The reason deserialization from the bit stream failed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SerdeErrReason {
+    /// The reader reached the end of the buffer
+    OutOfBits,
+    /// An enum discriminant which does not correspond to any variant
+    InvalidEnumVariant(u64),
+    /// A collection length exceeded the reader's limits
+    LengthLimit { length: usize, max_length: usize },
+    /// A String did not contain valid UTF-8
+    InvalidUtf8,
+    /// A Message, Component or Channel id which is not registered in the
+    /// Protocol
+    UnknownKind(u16),
+    /// An InternedString index which the remote host has not defined
+    UnknownInternedString(u16),
+    /// Any other reason, described by the given message, e.g. one raised by a
+    /// serde-rs `Deserialize` impl
+    Other(Cow<'static, str>),
+}
+
+/// The error returned when failing to deserialize from the bit stream.
+#[derive(Clone)]
+pub struct SerdeErr {
+    // boxed, so that the many `Result`s carrying a SerdeErr stay small
+    inner: Box<SerdeErrInner>,
+}
+
 #[derive(Clone)]
-pub struct SerdeErr;
+struct SerdeErrInner {
+    reason: SerdeErrReason,
+    bit_offset: usize,
+    type_name: Option<&'static str>,
+    field_path: Vec<&'static str>,
+    kind_name: Option<String>,
+}
+
+impl SerdeErr {
+    pub fn new(reason: SerdeErrReason, bit_offset: usize) -> Self {
+        Self {
+            inner: Box::new(SerdeErrInner {
+                reason,
+                bit_offset,
+                type_name: None,
+                field_path: Vec::new(),
+                kind_name: None,
+            }),
+        }
+    }
+
+    /// Records the type which was being read when the error occurred, unless
+    /// a more specific (inner) type has already been recorded
+    pub fn expecting<T: ?Sized>(mut self) -> Self {
+        if self.inner.type_name.is_none() {
+            self.inner.type_name = Some(core::any::type_name::<T>());
+        }
+        self
+    }
+
+    /// Records that the error occurred within the given field. Called from the
+    /// innermost field outwards, as the error propagates.
+    pub fn in_field<T: ?Sized>(mut self, field_name: &'static str) -> Self {
+        self.inner.field_path.insert(0, field_name);
+        self.expecting::<T>()
+    }
+
+    /// Records the name of the Message or Component kind being read
+    pub fn in_kind(mut self, kind_name: String) -> Self {
+        if self.inner.kind_name.is_none() {
+            self.inner.kind_name = Some(kind_name);
+        }
+        self
+    }
+
+    pub fn reason(&self) -> &SerdeErrReason {
+        &self.inner.reason
+    }
+
+    /// The offset, in bits from the start of the buffer, where reading failed
+    pub fn bit_offset(&self) -> usize {
+        self.inner.bit_offset
+    }
+
+    /// The name of the type which was expected at the point of failure
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.inner.type_name
+    }
+
+    /// The path of fields leading to the point of failure, e.g. `position.x`
+    pub fn field_path(&self) -> String {
+        self.inner.field_path.join(".")
+    }
+
+    /// The name of the Message or Component kind being read, if any
+    pub fn kind_name(&self) -> Option<&str> {
+        self.inner.kind_name.as_deref()
+    }
+}
+
+impl core::fmt::Display for SerdeErrReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SerdeErrReason::OutOfBits => write!(f, "out of bits"),
+            SerdeErrReason::InvalidEnumVariant(index) => {
+                write!(f, "invalid enum variant {}", index)
+            }
+            SerdeErrReason::LengthLimit { length, max_length } => {
+                write!(f, "length {} exceeds limit of {}", length, max_length)
+            }
+            SerdeErrReason::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            SerdeErrReason::UnknownKind(net_id) => write!(f, "unknown kind id {}", net_id),
+            SerdeErrReason::UnknownInternedString(index) => {
+                write!(f, "unknown interned string index {}", index)
+            }
+            SerdeErrReason::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
 
-impl std::fmt::Debug for SerdeErr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Bin deserialize error",)
+impl core::fmt::Debug for SerdeErr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Bin deserialize error: {}", self.inner.reason)?;
+        if let Some(type_name) = self.inner.type_name {
+            write!(f, ", expected `{}`", type_name)?;
+        }
+        if let Some(kind_name) = &self.inner.kind_name {
+            write!(f, ", in `{}`", kind_name)?;
+        }
+        if !self.inner.field_path.is_empty() {
+            write!(f, ", at field `{}`", self.field_path())?;
+        }
+        write!(f, ", at bit {}", self.inner.bit_offset)
     }
 }
 
-impl std::fmt::Display for SerdeErr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(self, f)
+impl core::fmt::Display for SerdeErr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for SerdeErr {}
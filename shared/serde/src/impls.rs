@@ -1,7 +1,13 @@
 mod array;
 mod boxed;
+mod btree;
+mod duration;
+#[cfg(feature = "std")]
 mod hash;
+mod nonzero;
 mod option;
+mod range;
+mod result;
 mod scalars;
 mod string;
 mod tuple;
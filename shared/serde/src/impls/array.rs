@@ -1,7 +1,7 @@
 use crate::{
     bit_reader::BitReader,
     bit_writer::BitWrite,
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     serde::{ConstBitLength, Serde},
 };
 
@@ -12,8 +12,12 @@ impl<T: Serde> Serde for &[T] {
         }
     }
 
-    fn de(_: &mut BitReader) -> Result<Self, SerdeErr> {
-        Err(SerdeErr {})
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Err(SerdeErr::new(
+            SerdeErrReason::Other("cannot deserialize into a borrowed slice".into()),
+            reader.bit_offset(),
+        )
+        .expecting::<Self>())
     }
 
     fn bit_length(&self) -> u32 {
@@ -34,8 +38,8 @@ impl<T: Serde, const N: usize> Serde for [T; N] {
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         unsafe {
-            let mut to = std::mem::MaybeUninit::<[T; N]>::uninit();
-            let top: *mut T = &mut to as *mut std::mem::MaybeUninit<[T; N]> as *mut T;
+            let mut to = core::mem::MaybeUninit::<[T; N]>::uninit();
+            let top: *mut T = &mut to as *mut core::mem::MaybeUninit<[T; N]> as *mut T;
             for c in 0..N {
                 top.add(c).write(Serde::de(reader)?);
             }
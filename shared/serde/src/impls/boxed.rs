@@ -1,3 +1,5 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc, vec::Vec};
+
 use crate::{
     bit_reader::BitReader,
     bit_writer::BitWrite,
@@ -26,6 +28,46 @@ impl<T: ConstBitLength> ConstBitLength for Box<T> {
     }
 }
 
+impl<T: Serde> Serde for Rc<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        (**self).ser(writer)
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Rc<T>, SerdeErr> {
+        Ok(Rc::new(Serde::de(reader)?))
+    }
+
+    fn bit_length(&self) -> u32 {
+        (**self).bit_length()
+    }
+}
+
+impl<T: ConstBitLength> ConstBitLength for Rc<T> {
+    fn const_bit_length() -> u32 {
+        T::const_bit_length()
+    }
+}
+
+impl<T: Serde> Serde for Arc<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        (**self).ser(writer)
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Arc<T>, SerdeErr> {
+        Ok(Arc::new(Serde::de(reader)?))
+    }
+
+    fn bit_length(&self) -> u32 {
+        (**self).bit_length()
+    }
+}
+
+impl<T: ConstBitLength> ConstBitLength for Arc<T> {
+    fn const_bit_length() -> u32 {
+        T::const_bit_length()
+    }
+}
+
 impl Serde for Box<[u8]> {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let length = UnsignedVariableInteger::<9>::new(self.len() as u64);
@@ -39,9 +81,12 @@ impl Serde for Box<[u8]> {
     fn de(reader: &mut BitReader) -> Result<Box<[u8]>, SerdeErr> {
         let length_int = UnsignedVariableInteger::<9>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<u8>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut bytes: Vec<u8> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
-            bytes.push(reader.read_byte()?);
+            bytes.push(reader.read_byte().map_err(SerdeErr::expecting::<Self>)?);
         }
 
         Ok(bytes.into_boxed_slice())
@@ -60,6 +105,8 @@ impl Serde for Box<[u8]> {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{rc::Rc, sync::Arc};
+
     use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
 
     #[test]
@@ -84,4 +131,27 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_write_shared() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = Rc::new("Hello rc!".to_string());
+        let in_2 = Arc::new(-42_i32);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = Rc::<String>::de(&mut reader).unwrap();
+        let out_2 = Arc::<i32>::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
 }
@@ -0,0 +1,86 @@
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::{SerdeErr, SerdeErrReason},
+    serde::{ConstBitLength, Serde},
+};
+use core::time::Duration;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+impl Serde for Duration {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.as_secs().ser(writer);
+        self.subsec_nanos().ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let secs = u64::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+        let nanos = u32::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(SerdeErr::new(
+                SerdeErrReason::Other("Duration has more than a second of nanoseconds".into()),
+                reader.bit_offset(),
+            )
+            .expecting::<Self>());
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn bit_length(&self) -> u32 {
+        <Self as ConstBitLength>::const_bit_length()
+    }
+}
+
+impl ConstBitLength for Duration {
+    fn const_bit_length() -> u32 {
+        <u64 as ConstBitLength>::const_bit_length() + <u32 as ConstBitLength>::const_bit_length()
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use core::time::Duration;
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = Duration::from_millis(1500);
+        let in_2 = Duration::new(u64::MAX, 999_999_999);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = Duration::de(&mut reader).unwrap();
+        let out_2 = Duration::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
+    #[test]
+    fn read_invalid_nanos() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        5_u64.ser(&mut writer);
+        1_000_000_000_u32.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        assert!(Duration::de(&mut reader).is_err());
+    }
+}
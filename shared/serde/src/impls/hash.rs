@@ -19,6 +19,9 @@ impl<K: Serde + Eq + Hash> Serde for HashSet<K> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<K>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut output: HashSet<K> = HashSet::new();
         for _ in 0..length_usize {
             let value = K::de(reader)?;
@@ -51,6 +54,9 @@ impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<(K, V)>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut output: HashMap<K, V> = HashMap::new();
         for _ in 0..length_usize {
             let key = K::de(reader)?;
@@ -0,0 +1,100 @@
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::{SerdeErr, SerdeErrReason},
+    serde::{ConstBitLength, Serde},
+};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+macro_rules! impl_serde_for_nonzero {
+    ($impl_type:ident, $inner_type:ident) => {
+        impl Serde for $impl_type {
+            fn ser(&self, writer: &mut dyn BitWrite) {
+                self.get().ser(writer);
+            }
+
+            fn de(reader: &mut BitReader) -> Result<$impl_type, SerdeErr> {
+                let value = $inner_type::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+                $impl_type::new(value).ok_or_else(|| {
+                    SerdeErr::new(
+                        SerdeErrReason::Other("non-zero integer was zero".into()),
+                        reader.bit_offset(),
+                    )
+                    .expecting::<Self>()
+                })
+            }
+
+            fn bit_length(&self) -> u32 {
+                <Self as ConstBitLength>::const_bit_length()
+            }
+        }
+
+        impl ConstBitLength for $impl_type {
+            fn const_bit_length() -> u32 {
+                <$inner_type as ConstBitLength>::const_bit_length()
+            }
+        }
+    };
+}
+
+impl_serde_for_nonzero!(NonZeroU8, u8);
+impl_serde_for_nonzero!(NonZeroU16, u16);
+impl_serde_for_nonzero!(NonZeroU32, u32);
+impl_serde_for_nonzero!(NonZeroU64, u64);
+impl_serde_for_nonzero!(NonZeroU128, u128);
+impl_serde_for_nonzero!(NonZeroUsize, usize);
+impl_serde_for_nonzero!(NonZeroI8, i8);
+impl_serde_for_nonzero!(NonZeroI16, i16);
+impl_serde_for_nonzero!(NonZeroI32, i32);
+impl_serde_for_nonzero!(NonZeroI64, i64);
+impl_serde_for_nonzero!(NonZeroI128, i128);
+impl_serde_for_nonzero!(NonZeroIsize, isize);
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use core::num::{NonZeroI32, NonZeroU8};
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = NonZeroU8::new(255).unwrap();
+        let in_2 = NonZeroI32::new(-7).unwrap();
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = NonZeroU8::de(&mut reader).unwrap();
+        let out_2 = NonZeroI32::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+
+    #[test]
+    fn read_zero() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        0_u8.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        assert!(NonZeroU8::de(&mut reader).is_err());
+    }
+}
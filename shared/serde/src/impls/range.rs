@@ -0,0 +1,83 @@
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::SerdeErr,
+    serde::{ConstBitLength, Serde},
+};
+use core::ops::{Range, RangeInclusive};
+
+impl<T: Serde> Serde for Range<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.start.ser(writer);
+        self.end.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let start = T::de(reader)?;
+        let end = T::de(reader)?;
+        Ok(start..end)
+    }
+
+    fn bit_length(&self) -> u32 {
+        self.start.bit_length() + self.end.bit_length()
+    }
+}
+
+impl<T: ConstBitLength> ConstBitLength for Range<T> {
+    fn const_bit_length() -> u32 {
+        T::const_bit_length() * 2
+    }
+}
+
+impl<T: Serde> Serde for RangeInclusive<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.start().ser(writer);
+        self.end().ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let start = T::de(reader)?;
+        let end = T::de(reader)?;
+        Ok(start..=end)
+    }
+
+    fn bit_length(&self) -> u32 {
+        self.start().bit_length() + self.end().bit_length()
+    }
+}
+
+impl<T: ConstBitLength> ConstBitLength for RangeInclusive<T> {
+    fn const_bit_length() -> u32 {
+        T::const_bit_length() * 2
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = -5_i16..300;
+        let in_2 = 'a'..='z';
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+}
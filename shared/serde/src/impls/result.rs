@@ -0,0 +1,63 @@
+use crate::{bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde};
+
+impl<T: Serde, E: Serde> Serde for Result<T, E> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            Ok(value) => {
+                writer.write_bit(true);
+                value.ser(writer);
+            }
+            Err(error) => {
+                writer.write_bit(false);
+                error.ser(writer);
+            }
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Result<T, E>, SerdeErr> {
+        if reader.read_bit()? {
+            Ok(Ok(T::de(reader)?))
+        } else {
+            Ok(Err(E::de(reader)?))
+        }
+    }
+
+    fn bit_length(&self) -> u32 {
+        let mut output = 1;
+        match self {
+            Ok(value) => output += value.bit_length(),
+            Err(error) => output += error.bit_length(),
+        }
+        output
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: Result<u8, String> = Ok(123);
+        let in_2: Result<u8, String> = Err("failed".to_string());
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        //Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = Result::<u8, String>::de(&mut reader).unwrap();
+        let out_2 = Result::<u8, String>::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+    }
+}
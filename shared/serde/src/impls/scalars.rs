@@ -1,7 +1,7 @@
 use crate::{
     bit_reader::BitReader,
     bit_writer::BitWrite,
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     serde::{ConstBitLength, Serde},
 };
 
@@ -59,7 +59,7 @@ impl Serde for bool {
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        reader.read_bit()
+        reader.read_bit().map_err(SerdeErr::expecting::<Self>)
     }
 
     fn bit_length(&self) -> u32 {
@@ -108,7 +108,7 @@ mod bool_tests {
 impl Serde for char {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let u32char = *self as u32;
-        let bytes = unsafe { std::mem::transmute::<&u32, &[u8; 4]>(&u32char) };
+        let bytes = unsafe { core::mem::transmute::<&u32, &[u8; 4]>(&u32char) };
         for byte in bytes {
             writer.write_byte(*byte);
         }
@@ -117,11 +117,11 @@ impl Serde for char {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let mut bytes = [0_u8; 4];
         for byte in &mut bytes {
-            *byte = reader.read_byte()?;
+            *byte = reader.read_byte().map_err(SerdeErr::expecting::<Self>)?;
         }
         let mut container = [0_u32];
         unsafe {
-            std::ptr::copy_nonoverlapping(
+            core::ptr::copy_nonoverlapping(
                 bytes.as_ptr().offset(0_isize) as *const u32,
                 container.as_mut_ptr() as *mut u32,
                 1,
@@ -131,7 +131,10 @@ impl Serde for char {
         if let Some(inner_char) = char::from_u32(container[0]) {
             Ok(inner_char)
         } else {
-            Err(SerdeErr {})
+            Err(
+                SerdeErr::new(SerdeErrReason::Other("invalid char".into()), reader.bit_offset())
+                    .expecting::<Self>(),
+            )
         }
     }
 
@@ -183,7 +186,7 @@ macro_rules! impl_serde_for {
         impl Serde for $impl_type {
             fn ser(&self, writer: &mut dyn BitWrite) {
                 let du8 = unsafe {
-                    std::mem::transmute::<&$impl_type, &[u8; std::mem::size_of::<$impl_type>()]>(
+                    core::mem::transmute::<&$impl_type, &[u8; core::mem::size_of::<$impl_type>()]>(
                         &self,
                     )
                 };
@@ -193,14 +196,14 @@ macro_rules! impl_serde_for {
             }
 
             fn de(reader: &mut BitReader) -> Result<$impl_type, SerdeErr> {
-                const BYTES_LENGTH: usize = std::mem::size_of::<$impl_type>();
+                const BYTES_LENGTH: usize = core::mem::size_of::<$impl_type>();
                 let mut byte_array = [0_u8; BYTES_LENGTH];
                 for index in 0..BYTES_LENGTH {
-                    byte_array[index] = reader.read_byte()?;
+                    byte_array[index] = reader.read_byte().map_err(SerdeErr::expecting::<Self>)?;
                 }
                 let mut container = [0 as $impl_type];
                 unsafe {
-                    std::ptr::copy_nonoverlapping(
+                    core::ptr::copy_nonoverlapping(
                         byte_array.as_ptr() as *const $impl_type,
                         container.as_mut_ptr() as *mut $impl_type,
                         1,
@@ -215,7 +218,7 @@ macro_rules! impl_serde_for {
         }
         impl ConstBitLength for $impl_type {
             fn const_bit_length() -> u32 {
-                const BYTES_LENGTH: u32 = std::mem::size_of::<$impl_type>() as u32;
+                const BYTES_LENGTH: u32 = core::mem::size_of::<$impl_type>() as u32;
                 return BYTES_LENGTH * 8;
             }
         }
@@ -226,9 +229,11 @@ macro_rules! impl_serde_for {
 impl_serde_for!(u16);
 impl_serde_for!(u32);
 impl_serde_for!(u64);
+impl_serde_for!(u128);
 impl_serde_for!(i16);
 impl_serde_for!(i32);
 impl_serde_for!(i64);
+impl_serde_for!(i128);
 impl_serde_for!(f32);
 impl_serde_for!(f64);
 
@@ -239,7 +244,7 @@ impl Serde for u8 {
     }
 
     fn de(reader: &mut BitReader) -> Result<u8, SerdeErr> {
-        reader.read_byte()
+        reader.read_byte().map_err(SerdeErr::expecting::<Self>)
     }
 
     fn bit_length(&self) -> u32 {
@@ -256,15 +261,15 @@ impl ConstBitLength for u8 {
 // i8
 impl Serde for i8 {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let du8 = unsafe { std::mem::transmute::<&i8, &u8>(self) };
+        let du8 = unsafe { core::mem::transmute::<&i8, &u8>(self) };
         writer.write_byte(*du8);
     }
 
     fn de(reader: &mut BitReader) -> Result<i8, SerdeErr> {
-        let byte = [reader.read_byte()?];
+        let byte = [reader.read_byte().map_err(SerdeErr::expecting::<Self>)?];
         let mut container = [0_i8];
         unsafe {
-            std::ptr::copy_nonoverlapping(
+            core::ptr::copy_nonoverlapping(
                 byte.as_ptr() as *const i8,
                 container.as_mut_ptr() as *mut i8,
                 1,
@@ -288,7 +293,7 @@ impl ConstBitLength for i8 {
 impl Serde for usize {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let u64usize = *self as u64;
-        let du8 = unsafe { std::mem::transmute::<&u64, &[u8; 8]>(&u64usize) };
+        let du8 = unsafe { core::mem::transmute::<&u64, &[u8; 8]>(&u64usize) };
         for byte in du8 {
             writer.write_byte(*byte);
         }
@@ -297,11 +302,11 @@ impl Serde for usize {
     fn de(reader: &mut BitReader) -> Result<usize, SerdeErr> {
         let mut byte_array = [0_u8; 8];
         for byte in &mut byte_array {
-            *byte = reader.read_byte()?;
+            *byte = reader.read_byte().map_err(SerdeErr::expecting::<Self>)?;
         }
         let mut container = [0_u64];
         unsafe {
-            std::ptr::copy_nonoverlapping(
+            core::ptr::copy_nonoverlapping(
                 byte_array.as_ptr().offset(0_isize) as *const u64,
                 container.as_mut_ptr() as *mut u64,
                 1,
@@ -325,7 +330,7 @@ impl ConstBitLength for usize {
 impl Serde for isize {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let u64usize = *self as u64;
-        let du8 = unsafe { std::mem::transmute::<&u64, &[u8; 8]>(&u64usize) };
+        let du8 = unsafe { core::mem::transmute::<&u64, &[u8; 8]>(&u64usize) };
         for byte in du8 {
             writer.write_byte(*byte);
         }
@@ -334,11 +339,11 @@ impl Serde for isize {
     fn de(reader: &mut BitReader) -> Result<isize, SerdeErr> {
         let mut byte_array = [0_u8; 8];
         for byte in &mut byte_array {
-            *byte = reader.read_byte()?;
+            *byte = reader.read_byte().map_err(SerdeErr::expecting::<Self>)?;
         }
         let mut container = [0_u64];
         unsafe {
-            std::ptr::copy_nonoverlapping(
+            core::ptr::copy_nonoverlapping(
                 byte_array.as_ptr().offset(0_isize) as *const u64,
                 container.as_mut_ptr() as *mut u64,
                 1,
@@ -390,11 +395,13 @@ mod number_tests {
     test_serde_for!(u16, test_u16);
     test_serde_for!(u32, test_u32);
     test_serde_for!(u64, test_u64);
+    test_serde_for!(u128, test_u128);
     test_serde_for!(usize, test_usize);
     test_serde_for!(i8, test_i8);
     test_serde_for!(i16, test_i16);
     test_serde_for!(i32, test_i32);
     test_serde_for!(i64, test_i64);
+    test_serde_for!(i128, test_i128);
     test_serde_for!(isize, test_isize);
     test_serde_for!(f32, test_f32);
     test_serde_for!(f64, test_f64);
@@ -1,36 +1,67 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
 use crate::{
-    bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde,
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::{SerdeErr, SerdeErrReason},
+    serde::Serde,
     UnsignedVariableInteger,
 };
 
+fn write_str(value: &str, writer: &mut dyn BitWrite) {
+    let length = UnsignedVariableInteger::<9>::new(value.len() as u64);
+    length.ser(writer);
+    let bytes = value.as_bytes();
+    for byte in bytes {
+        writer.write_byte(*byte);
+    }
+}
+
+fn str_bit_length(value: &str) -> u32 {
+    let mut output = 0;
+    let length = UnsignedVariableInteger::<9>::new(value.len() as u64);
+    output += length.bit_length();
+    output += (value.len() as u32) * 8;
+    output
+}
+
 impl Serde for String {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedVariableInteger::<9>::new(self.len() as u64);
-        length.ser(writer);
-        let bytes = self.as_bytes();
-        for byte in bytes {
-            writer.write_byte(*byte);
-        }
+        write_str(self, writer);
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<9>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<u8>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut bytes: Vec<u8> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
-            bytes.push(reader.read_byte()?);
+            bytes.push(reader.read_byte().map_err(SerdeErr::expecting::<Self>)?);
         }
 
-        let result = String::from_utf8_lossy(&bytes).into_owned();
-        Ok(result)
+        String::from_utf8(bytes).map_err(|_| {
+            SerdeErr::new(SerdeErrReason::InvalidUtf8, reader.bit_offset()).expecting::<Self>()
+        })
     }
 
     fn bit_length(&self) -> u32 {
-        let mut output = 0;
-        let length = UnsignedVariableInteger::<9>::new(self.len() as u64);
-        output += length.bit_length();
-        output += (self.len() as u32) * 8;
-        output
+        str_bit_length(self)
+    }
+}
+
+impl<'a> Serde for Cow<'a, str> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        write_str(self, writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Cow::Owned(String::de(reader)?))
+    }
+
+    fn bit_length(&self) -> u32 {
+        str_bit_length(self)
     }
 }
 
@@ -38,7 +69,15 @@ impl Serde for String {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use alloc::borrow::Cow;
+
+    use crate::{
+        bit_reader::BitReader,
+        bit_writer::{BitWrite, BitWriter},
+        error::SerdeErrReason,
+        serde::Serde,
+        UnsignedVariableInteger,
+    };
 
     #[test]
     fn read_write() {
@@ -62,4 +101,51 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_invalid_utf8() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        UnsignedVariableInteger::<9>::new(2).ser(&mut writer);
+        writer.write_byte(0xC3);
+        writer.write_byte(0x28);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let error = String::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason(), &SerdeErrReason::InvalidUtf8);
+        assert_eq!(error.type_name(), Some(core::any::type_name::<String>()));
+    }
+
+    #[test]
+    fn read_write_cow() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: Cow<str> = Cow::Borrowed("Hello cow!");
+        let in_2: Cow<str> = Cow::Owned("This is an owned cow.".to_string());
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Cow<str> = Serde::de(&mut reader).unwrap();
+        let out_2: Cow<str> = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(
+            reader.bit_offset() as u32,
+            in_1.bit_length() + in_2.bit_length()
+        );
+    }
 }
@@ -1,4 +1,10 @@
-use crate::{bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde};
+use crate::{
+    bit_reader::BitReader,
+    bit_writer::BitWrite,
+    error::SerdeErr,
+    serde::{ConstBitLength, Serde},
+};
+
 macro_rules! impl_reflect_tuple {
     {$($index:tt : $name:tt),*} => {
         impl<$($name : Serde,)*> Serde for ($($name,)*) {
@@ -14,6 +20,13 @@ macro_rules! impl_reflect_tuple {
                 output
             }
         }
+        impl<$($name : ConstBitLength,)*> ConstBitLength for ($($name,)*) {
+            fn const_bit_length() -> u32 {
+                let mut output = 0;
+                $(output += $name::const_bit_length();)*
+                output
+            }
+        }
     }
 }
 
@@ -34,7 +47,11 @@ impl_reflect_tuple! {0: A, 1: B, 2: C, 3: D, 4: E, 5: F, 6: G, 7: H, 8: I, 9: J,
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
+    use crate::{
+        bit_reader::BitReader,
+        bit_writer::BitWriter,
+        serde::{ConstBitLength, Serde},
+    };
 
     #[test]
     fn read_write() {
@@ -68,4 +85,30 @@ mod tests {
         assert_eq!(in_3, out_3);
         assert_eq!(in_4, out_4);
     }
+
+    #[test]
+    fn const_bit_length() {
+        assert_eq!(<(bool, u8, [u16; 2])>::const_bit_length(), 41);
+    }
+
+    #[test]
+    fn read_write_large_tuple() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = (
+            1_u8, 2_u16, 3_u32, 4_u64, 5_i8, 6_i16, 7_i32, 8_i64, 9_u128, 10_i128, true, 'c',
+        );
+
+        in_1.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use alloc::{collections::VecDeque, vec::Vec};
 
 use crate::{
     bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr, serde::Serde,
@@ -17,6 +17,9 @@ impl<T: Serde> Serde for Vec<T> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<T>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut output: Vec<T> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
             output.push(T::de(reader)?)
@@ -47,6 +50,9 @@ impl<T: Serde> Serde for VecDeque<T> {
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let length_int = UnsignedVariableInteger::<5>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        reader
+            .reserve_collection::<T>(length_usize)
+            .map_err(SerdeErr::expecting::<Self>)?;
         let mut output: VecDeque<T> = VecDeque::with_capacity(length_usize);
         for _ in 0..length_usize {
             output.push_back(T::de(reader)?)
@@ -69,8 +75,12 @@ impl<T: Serde> Serde for VecDeque<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{bit_reader::BitReader, bit_writer::BitWriter, serde::Serde};
-    use std::collections::VecDeque;
+    use crate::{
+        bit_reader::{BitReader, ReadLimits},
+        bit_writer::BitWriter,
+        serde::Serde,
+    };
+    use alloc::collections::VecDeque;
 
     #[test]
     fn read_write_vec() {
@@ -130,4 +140,74 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_vec_over_limits() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: Vec<u16> = vec![1, 2, 3, 4, 5];
+        in_1.ser(&mut writer);
+        in_1.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let limits = ReadLimits {
+            max_collection_len: 4,
+            ..Default::default()
+        };
+        let mut reader = BitReader::with_limits(&buffer, limits);
+        assert!(Vec::<u16>::de(&mut reader).is_err());
+
+        let limits = ReadLimits {
+            max_total_bytes: 15,
+            ..Default::default()
+        };
+        let mut reader = BitReader::with_limits(&buffer, limits);
+        assert!(Vec::<u16>::de(&mut reader).is_ok());
+        assert!(Vec::<u16>::de(&mut reader).is_err());
+    }
+
+    #[test]
+    fn read_vec_with_max_len() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: Vec<u8> = vec![1, 2, 3];
+        in_1.ser(&mut writer);
+        in_1.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let mut reader = BitReader::new(&buffer);
+
+        let out_1: Vec<u8> = reader.read_with_max_len(3).unwrap();
+        assert_eq!(in_1, out_1);
+        assert!(reader.read_with_max_len::<Vec<u8>>(2).is_err());
+    }
+
+    #[test]
+    fn read_nested_vec_within_budget() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1: Vec<u16> = vec![1, 2, 3, 4, 5];
+        in_1.ser(&mut writer);
+
+        let buffer = writer.to_bytes();
+
+        // Read
+        let limits = ReadLimits {
+            max_total_bytes: 15,
+            ..Default::default()
+        };
+        let mut reader = BitReader::with_limits(&buffer, limits);
+        let out_1: Vec<u16> = reader.read_nested(&buffer, Vec::de).unwrap();
+        assert_eq!(in_1, out_1);
+
+        // the nested read spent most of the outer reader's budget
+        assert!(Vec::<u16>::de(&mut reader).is_err());
+    }
 }
@@ -108,7 +108,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let mut negative: bool = false;
         if SIGNED {
-            negative = reader.read_bit()?;
+            negative = reader.read_bit().map_err(SerdeErr::expecting::<Self>)?;
         }
 
         if VARIABLE {
@@ -116,14 +116,14 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
             let mut output: u128 = 0;
 
             loop {
-                let proceed = reader.read_bit()?;
+                let proceed = reader.read_bit().map_err(SerdeErr::expecting::<Self>)?;
 
                 for _ in 0..BITS {
                     total_bits += 1;
 
                     output <<= 1;
 
-                    if reader.read_bit()? {
+                    if reader.read_bit().map_err(SerdeErr::expecting::<Self>)? {
                         output |= 1;
                     }
                 }
@@ -146,7 +146,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
             for _ in 0..BITS {
                 output <<= 1;
 
-                if reader.read_bit()? {
+                if reader.read_bit().map_err(SerdeErr::expecting::<Self>)? {
                     output |= 1;
                 }
             }
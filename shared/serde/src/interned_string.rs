@@ -0,0 +1,307 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use core::{fmt, ops::Deref};
+
+use crate::{
+    codecs::{read_bits, write_bits},
+    BitReader, BitWrite, Serde, SerdeErr, SerdeErrReason,
+};
+
+// Number of bits in a dictionary index, which bounds the size of a dictionary
+const INDEX_BITS: u32 = 12;
+const MAX_INTERNED: usize = 1 << INDEX_BITS;
+
+// InternedString
+
+/// A String which is sent in full until the remote host has acknowledged
+/// receiving it, after which it is sent as an index into a dictionary kept
+/// for each connection.
+///
+/// Only Messages written directly into a packet share that dictionary. Within
+/// Components, fragmented Messages, or any Message written outside of a
+/// connection the String is always sent in full, as it is once a connection
+/// has interned `4096` distinct Strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InternedString {
+    inner: String,
+}
+
+impl InternedString {
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        Self {
+            inner: value.into(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    pub fn into_string(self) -> String {
+        self.inner
+    }
+}
+
+impl Deref for InternedString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl fmt::Display for InternedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl Serde for InternedString {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        if writer.is_counter() {
+            // whether the index may be used is only known once written
+            writer.write_bits(self.bit_length());
+            return;
+        }
+
+        let entry = writer
+            .host_strings()
+            .and_then(|strings| strings.entry(&self.inner));
+        match entry {
+            Some(HostEntry::Acknowledged(index)) => {
+                // write IsDefinition bit
+                false.ser(writer);
+                write_bits(writer, index as u128, INDEX_BITS);
+            }
+            Some(HostEntry::Unacknowledged(index)) => {
+                // write IsDefinition bit
+                true.ser(writer);
+                // write HasIndex bit
+                true.ser(writer);
+                write_bits(writer, index as u128, INDEX_BITS);
+                self.inner.ser(writer);
+            }
+            None => {
+                // write IsDefinition bit
+                true.ser(writer);
+                // write HasIndex bit
+                false.ser(writer);
+                self.inner.ser(writer);
+            }
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let is_definition = bool::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+        if !is_definition {
+            let index = read_bits::<Self>(reader, INDEX_BITS)? as u16;
+            let bit_offset = reader.bit_offset();
+            let Some(inner) = reader
+                .remote_strings()
+                .and_then(|strings| strings.get(index))
+            else {
+                return Err(SerdeErr::new(
+                    SerdeErrReason::UnknownInternedString(index),
+                    bit_offset,
+                )
+                .expecting::<Self>());
+            };
+            return Ok(Self { inner });
+        }
+
+        let has_index = bool::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+        let index = if has_index {
+            Some(read_bits::<Self>(reader, INDEX_BITS)? as u16)
+        } else {
+            None
+        };
+        let inner = String::de(reader).map_err(SerdeErr::expecting::<Self>)?;
+        if let (Some(index), Some(strings)) = (index, reader.remote_strings()) {
+            strings.insert(index, inner.clone());
+        }
+        Ok(Self { inner })
+    }
+
+    /// The most bits this String may be written in, as a definition
+    fn bit_length(&self) -> u32 {
+        1 + 1 + INDEX_BITS + self.inner.bit_length()
+    }
+}
+
+// HostStrings
+
+/// The Strings a host has sent as InternedStrings over a connection, and
+/// which of them the remote host has received
+#[derive(Default)]
+pub struct HostStrings {
+    indices: BTreeMap<String, u16>,
+    // whether each index has been received, by index
+    acknowledged: Vec<bool>,
+    // indices defined since the last call to `take_written()`
+    written: Vec<u16>,
+}
+
+enum HostEntry {
+    Acknowledged(u16),
+    Unacknowledged(u16),
+}
+
+impl HostStrings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the indices of the Strings defined since last called, to be
+    /// acknowledged once the packet they were written into is delivered
+    pub fn take_written(&mut self) -> Vec<u16> {
+        core::mem::take(&mut self.written)
+    }
+
+    /// Marks the given indices as received, after which their Strings are
+    /// sent by index alone
+    pub fn acknowledge(&mut self, indices: &[u16]) {
+        for index in indices {
+            if let Some(acknowledged) = self.acknowledged.get_mut(*index as usize) {
+                *acknowledged = true;
+            }
+        }
+    }
+
+    // Returns how to write the String, assigning it an index if it has none
+    // and there is room for one, or None if it must be sent in full
+    fn entry(&mut self, value: &str) -> Option<HostEntry> {
+        let index = match self.indices.get(value) {
+            Some(index) => *index,
+            None => {
+                if self.acknowledged.len() >= MAX_INTERNED {
+                    return None;
+                }
+                let index = self.acknowledged.len() as u16;
+                self.indices.insert(value.into(), index);
+                self.acknowledged.push(false);
+                index
+            }
+        };
+        if self.acknowledged[index as usize] {
+            return Some(HostEntry::Acknowledged(index));
+        }
+        self.written.push(index);
+        Some(HostEntry::Unacknowledged(index))
+    }
+}
+
+// RemoteStrings
+
+/// The Strings a remote host has defined for its InternedStrings over a
+/// connection
+#[derive(Default)]
+pub struct RemoteStrings {
+    strings: BTreeMap<u16, String>,
+}
+
+impl RemoteStrings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, index: u16, value: String) {
+        self.strings.insert(index, value);
+    }
+
+    fn get(&self, index: u16) -> Option<String> {
+        self.strings.get(&index).cloned()
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BitReader, BitWriter, HostStrings, InternedString, RemoteStrings, Serde, SerdeErrReason,
+        MTU_SIZE_BITS,
+    };
+
+    // Writes the value against the dictionary, returning the bytes & the
+    // number of bits written
+    fn write(value: &InternedString, host_strings: &mut HostStrings) -> (Box<[u8]>, u32) {
+        let mut writer = BitWriter::new();
+        writer.attach_strings(core::mem::take(host_strings));
+        value.ser(&mut writer);
+        *host_strings = writer.detach_strings().unwrap();
+        let bits = MTU_SIZE_BITS - writer.bits_free();
+        (writer.to_bytes(), bits)
+    }
+
+    fn read(
+        bytes: &[u8],
+        remote_strings: &mut RemoteStrings,
+    ) -> Result<InternedString, SerdeErrReason> {
+        let mut reader = BitReader::new(bytes);
+        reader.attach_strings(core::mem::take(remote_strings));
+        let result = InternedString::de(&mut reader);
+        *remote_strings = reader.detach_strings().unwrap();
+        result.map_err(|error| error.reason().clone())
+    }
+
+    #[test]
+    fn read_write_without_dictionary() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = InternedString::new("fireball");
+
+        in_1.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert!(reader.bit_offset() as u32 <= in_1.bit_length());
+    }
+
+    #[test]
+    fn index_used_once_acknowledged() {
+        let value = InternedString::new("fireball");
+        let mut host_strings = HostStrings::new();
+        let mut remote_strings = RemoteStrings::new();
+
+        // The definition is written until it has been acknowledged
+        let (definition, definition_bits) = write(&value, &mut host_strings);
+        assert_eq!(host_strings.take_written(), vec![0]);
+        let (repeat, _) = write(&value, &mut host_strings);
+        assert_eq!(host_strings.take_written(), vec![0]);
+        assert_eq!(definition, repeat);
+
+        assert_eq!(read(&definition, &mut remote_strings), Ok(value.clone()));
+
+        // After which only the index is
+        host_strings.acknowledge(&[0]);
+        let (reference, reference_bits) = write(&value, &mut host_strings);
+        assert!(host_strings.take_written().is_empty());
+        assert_eq!(reference_bits, 1 + 12);
+        assert!(reference_bits < definition_bits);
+
+        assert_eq!(read(&reference, &mut remote_strings), Ok(value));
+    }
+
+    #[test]
+    fn unknown_index_is_an_error() {
+        let value = InternedString::new("fireball");
+        let mut host_strings = HostStrings::new();
+        write(&value, &mut host_strings);
+        host_strings.acknowledge(&[0]);
+
+        let (reference, _) = write(&value, &mut host_strings);
+
+        assert_eq!(
+            read(&reference, &mut RemoteStrings::new()),
+            Err(SerdeErrReason::UnknownInternedString(0))
+        );
+    }
+}
@@ -1,22 +1,45 @@
+//! # Naia Serde
+//! Bit-level de/serialization for naia. Builds without `std` (but with
+//! `alloc`) when the default `std` feature is disabled.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub use naia_serde_derive::{Serde, SerdeBevy, SerdeHecs, SerdeInternal};
 
+mod arithmetic;
 mod bit_counter;
 mod bit_reader;
+mod bit_set;
 mod bit_writer;
+pub mod codecs;
 mod constants;
 mod error;
 mod impls;
 mod integer;
+mod interned_string;
 mod outgoing_packet;
+mod protocol_name;
 mod serde;
+#[cfg(feature = "serde_bridge")]
+pub mod serde_bridge;
+mod strings;
 
+pub use arithmetic::{ArithmeticBitWriter, PacketCoding};
 pub use bit_counter::BitCounter;
-pub use bit_reader::{BitReader, OwnedBitReader};
+pub use bit_reader::{BitReader, OwnedBitReader, ReadLimits};
+pub use bit_set::{BitSet, BitVec};
 pub use bit_writer::{BitWrite, BitWriter};
 pub use constants::{MTU_SIZE_BITS, MTU_SIZE_BYTES};
-pub use error::SerdeErr;
+pub use error::{SerdeErr, SerdeErrReason};
+pub use interned_string::{HostStrings, InternedString, RemoteStrings};
 pub use integer::{SignedInteger, SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger};
 pub use outgoing_packet::OutgoingPacket;
+pub use protocol_name::ProtocolName;
 pub use serde::{
     ConstBitLength, Serde, Serde as SerdeInternal, Serde as SerdeBevy, Serde as SerdeHecs,
 };
+#[cfg(feature = "serde_bridge")]
+pub use serde_bridge::SerdeRs;
+pub use strings::{AsciiString, BoundedString};
@@ -0,0 +1,209 @@
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    ops::{Range, RangeInclusive},
+    time::Duration,
+};
+
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde_bridge")]
+use crate::SerdeRs;
+use crate::{integer::SerdeInteger, AsciiString, BitSet, BitVec, BoundedString, InternedString};
+
+// ProtocolName
+
+/// A name for a type which is the same in every build of a Protocol, unlike
+/// `std::any::type_name()`, whose output may change between compiler
+/// versions. A generic Message or Component is named after the ProtocolName
+/// of each of its type arguments, so those must implement this trait.
+///
+/// Derived for any type which derives `Serde`, as the path of the module it
+/// is defined in followed by its name.
+pub trait ProtocolName {
+    fn protocol_name() -> String;
+}
+
+macro_rules! impl_protocol_name_for {
+    ($($impl_type:ty),*) => {
+        $(
+            impl ProtocolName for $impl_type {
+                fn protocol_name() -> String {
+                    stringify!($impl_type).to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_protocol_name_for!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String,
+    Duration,
+    BitVec,
+    InternedString,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize
+);
+
+macro_rules! impl_protocol_name_for_generic {
+    ($($impl_type:ident<$($param:ident),+>),*) => {
+        $(
+            impl<$($param: ProtocolName),+> ProtocolName for $impl_type<$($param),+> {
+                fn protocol_name() -> String {
+                    let arguments: Vec<String> = Vec::from([$($param::protocol_name()),+]);
+                    format!("{}<{}>", stringify!($impl_type), arguments.join(", "))
+                }
+            }
+        )*
+    };
+}
+
+impl_protocol_name_for_generic!(
+    Option<T>,
+    Result<T, E>,
+    Box<T>,
+    Rc<T>,
+    Arc<T>,
+    Vec<T>,
+    VecDeque<T>,
+    BTreeSet<K>,
+    BTreeMap<K, V>,
+    Range<T>,
+    RangeInclusive<T>
+);
+
+#[cfg(feature = "std")]
+impl_protocol_name_for_generic!(HashSet<K>, HashMap<K, V>);
+
+#[cfg(feature = "serde_bridge")]
+impl_protocol_name_for_generic!(SerdeRs<T>);
+
+impl ProtocolName for Box<[u8]> {
+    fn protocol_name() -> String {
+        "Box<[u8]>".to_string()
+    }
+}
+
+impl<T: ProtocolName, const N: usize> ProtocolName for [T; N] {
+    fn protocol_name() -> String {
+        format!("[{}; {}]", T::protocol_name(), N)
+    }
+}
+
+macro_rules! impl_protocol_name_for_tuple {
+    ($($param:ident),+) => {
+        impl<$($param: ProtocolName),+> ProtocolName for ($($param,)+) {
+            fn protocol_name() -> String {
+                let arguments: Vec<String> = Vec::from([$($param::protocol_name()),+]);
+                if arguments.len() == 1 {
+                    return format!("({},)", arguments[0]);
+                }
+                format!("({})", arguments.join(", "))
+            }
+        }
+    };
+}
+
+impl_protocol_name_for_tuple!(A);
+impl_protocol_name_for_tuple!(A, B);
+impl_protocol_name_for_tuple!(A, B, C);
+impl_protocol_name_for_tuple!(A, B, C, D);
+impl_protocol_name_for_tuple!(A, B, C, D, E);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G, H);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_protocol_name_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> ProtocolName
+    for SerdeInteger<SIGNED, VARIABLE, BITS>
+{
+    fn protocol_name() -> String {
+        let name = match (SIGNED, VARIABLE) {
+            (false, false) => "UnsignedInteger",
+            (true, false) => "SignedInteger",
+            (false, true) => "UnsignedVariableInteger",
+            (true, true) => "SignedVariableInteger",
+        };
+        format!("{}<{}>", name, BITS)
+    }
+}
+
+impl<const N: usize> ProtocolName for BitSet<N> {
+    fn protocol_name() -> String {
+        format!("BitSet<{}>", N)
+    }
+}
+
+impl<const MAX_LEN: usize> ProtocolName for AsciiString<MAX_LEN> {
+    fn protocol_name() -> String {
+        format!("AsciiString<{}>", MAX_LEN)
+    }
+}
+
+impl<const MAX_LEN: usize> ProtocolName for BoundedString<MAX_LEN> {
+    fn protocol_name() -> String {
+        format!("BoundedString<{}>", MAX_LEN)
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use crate::{ProtocolName, UnsignedInteger};
+
+    #[test]
+    fn names_nest() {
+        assert_eq!(u32::protocol_name(), "u32");
+        assert_eq!(
+            Vec::<Option<String>>::protocol_name(),
+            "Vec<Option<String>>"
+        );
+        assert_eq!(<(bool, [u8; 4])>::protocol_name(), "(bool, [u8; 4])");
+        assert_eq!(UnsignedInteger::<7>::protocol_name(), "UnsignedInteger<7>");
+    }
+}
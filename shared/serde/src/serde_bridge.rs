@@ -0,0 +1,253 @@
+//! Writes any type implementing serde-rs' `Serialize` & `Deserialize` into the
+//! bit stream, with a compact, non-self-describing binary format: bools take a
+//! single bit, numbers & strings are written as their own `Serde` impls would,
+//! and struct field names are never written.
+//!
+//! Use either the [`SerdeRs`] wrapper, or the `#[naia(serde_rs)]` field
+//! attribute of the `Serde`, `Message` & `Replicate` derives. Both require the
+//! `serde_bridge` feature.
+//!
+//! As with other non-self-describing formats, serde-rs attributes which make a
+//! type's written fields depend on their values (e.g. `skip_serializing_if`,
+//! `flatten` or `untagged`) are not supported.
+
+mod de;
+mod ser;
+
+use core::ops::{Deref, DerefMut};
+
+use ::serde::{de::DeserializeOwned, Serialize};
+
+use crate::{BitCounter, BitReader, BitWrite, Serde, SerdeErr};
+
+use de::BitDeserializer;
+use ser::BitSerializer;
+
+/// Wraps a type implementing serde-rs' `Serialize` & `Deserialize`, so that
+/// it can be used wherever a `Serde` type is expected, e.g. in a `Property`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SerdeRs<T>(pub T);
+
+impl<T> SerdeRs<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for SerdeRs<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for SerdeRs<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for SerdeRs<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Clone + PartialEq> Serde for SerdeRs<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        ser(&self.0, writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        de(reader).map(Self)
+    }
+
+    fn bit_length(&self) -> u32 {
+        bit_length(&self.0)
+    }
+}
+
+/// Writes the value into the bit stream.
+///
+/// # Panics
+/// If the value's `Serialize` impl fails, or writes a sequence or map without
+/// giving its length up front
+pub fn ser<T: Serialize + ?Sized>(value: &T, writer: &mut dyn BitWrite) {
+    if let Err(error) = value.serialize(&mut BitSerializer::new(writer)) {
+        panic!(
+            "failed to serialize {}: {}",
+            core::any::type_name::<T>(),
+            error
+        );
+    }
+}
+
+/// Reads a value from the bit stream
+pub fn de<T: DeserializeOwned>(reader: &mut BitReader) -> Result<T, SerdeErr> {
+    let mut deserializer = BitDeserializer::new(reader);
+    let result = T::deserialize(&mut deserializer);
+    result.map_err(|error| deserializer.into_serde_err(error).expecting::<T>())
+}
+
+/// Returns the number of bits the value is written with
+pub fn bit_length<T: Serialize + ?Sized>(value: &T) -> u32 {
+    let mut counter = BitCounter::new(0, 0, u32::MAX);
+    ser(value, &mut counter);
+    counter.bits_needed()
+}
+
+/// The error passed through serde-rs while reading or writing
+#[derive(Debug)]
+pub(crate) enum BridgeError {
+    Read(SerdeErr),
+    Custom(String),
+}
+
+impl core::fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "{}", error),
+            Self::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl ::serde::ser::Error for BridgeError {
+    fn custom<M: core::fmt::Display>(message: M) -> Self {
+        Self::Custom(message.to_string())
+    }
+}
+
+impl ::serde::de::Error for BridgeError {
+    fn custom<M: core::fmt::Display>(message: M) -> Self {
+        Self::Custom(message.to_string())
+    }
+}
+
+impl From<SerdeErr> for BridgeError {
+    fn from(error: SerdeErr) -> Self {
+        Self::Read(error)
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use ::serde::{Deserialize, Serialize};
+
+    use crate::{
+        serde_bridge, BitReader, BitWriter, Serde, SerdeErrReason, SerdeRs, UnsignedVariableInteger,
+    };
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f32),
+        Rect { width: u16, height: u16 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        name: String,
+        enabled: bool,
+        ratio: f64,
+        offset: (i8, i128),
+        shapes: Vec<Shape>,
+        tags: BTreeMap<u8, Option<char>>,
+        unit: (),
+    }
+
+    fn some_config() -> Config {
+        Config {
+            name: "Hello serde!".to_string(),
+            enabled: true,
+            ratio: 0.75,
+            offset: (-3, i128::MIN),
+            shapes: vec![
+                Shape::Empty,
+                Shape::Circle(2.5),
+                Shape::Rect {
+                    width: 3,
+                    height: 4,
+                },
+            ],
+            tags: BTreeMap::from([(1, Some('x')), (2, None)]),
+            unit: (),
+        }
+    }
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = SerdeRs(some_config());
+        let in_2 = SerdeRs(Shape::Circle(-1.0));
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1: SerdeRs<Config> = Serde::de(&mut reader).unwrap();
+        let out_2: SerdeRs<Shape> = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(
+            reader.bit_offset() as u32,
+            in_1.bit_length() + in_2.bit_length()
+        );
+    }
+
+    #[test]
+    fn bools_take_one_bit() {
+        assert_eq!(serde_bridge::bit_length(&(true, false, Some(true))), 4);
+    }
+
+    #[test]
+    fn read_truncated() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        serde_bridge::ser(&some_config(), &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read, with the final bytes cut off
+
+        let mut reader = BitReader::new(&bytes[..bytes.len() - 2]);
+
+        let error = serde_bridge::de::<Config>(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason(), &SerdeErrReason::OutOfBits);
+    }
+
+    #[test]
+    fn read_invalid_variant() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        UnsignedVariableInteger::<3>::new(7_u64).ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let error = serde_bridge::de::<Shape>(&mut reader).unwrap_err();
+
+        assert!(matches!(error.reason(), SerdeErrReason::Other(_)));
+    }
+}
@@ -0,0 +1,321 @@
+use ::serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use crate::{BitReader, Serde, SerdeErr, SerdeErrReason, UnsignedVariableInteger};
+
+use super::BridgeError;
+
+/// Reads serde-rs data from the bit stream
+pub(crate) struct BitDeserializer<'r, 'b> {
+    reader: &'r mut BitReader<'b>,
+}
+
+impl<'r, 'b> BitDeserializer<'r, 'b> {
+    pub fn new(reader: &'r mut BitReader<'b>) -> Self {
+        Self { reader }
+    }
+
+    /// Converts an error from a `Deserialize` impl, recording where reading
+    /// stopped if it did not come from the bit stream itself
+    pub fn into_serde_err(self, error: BridgeError) -> SerdeErr {
+        match error {
+            BridgeError::Read(error) => error,
+            BridgeError::Custom(message) => {
+                SerdeErr::new(SerdeErrReason::Other(message.into()), self.reader.bit_offset())
+            }
+        }
+    }
+
+    fn read<T: Serde>(&mut self) -> Result<T, BridgeError> {
+        Ok(T::de(self.reader)?)
+    }
+
+    fn read_length(&mut self) -> Result<usize, BridgeError> {
+        let length = UnsignedVariableInteger::<5>::de(self.reader)?.get() as usize;
+        self.reader.reserve_collection::<u8>(length)?;
+        Ok(length)
+    }
+
+    fn unsupported(&self) -> BridgeError {
+        BridgeError::custom("can't read a value of unknown type, the format is not self-describing")
+    }
+}
+
+impl<'de, 'a, 'r, 'b> Deserializer<'de> for &'a mut BitDeserializer<'r, 'b> {
+    type Error = BridgeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, BridgeError> {
+        Err(self.unsupported())
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_bool(self.read()?)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_i8(self.read()?)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_i16(self.read()?)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_i32(self.read()?)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_i64(self.read()?)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_i128(self.read()?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_u8(self.read()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_u16(self.read()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_u32(self.read()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_u64(self.read()?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_u128(self.read()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_f32(self.read()?)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_f64(self.read()?)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_char(self.read()?)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_string(self.read::<String>()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_string(self.read::<String>()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_byte_buf(self.read::<Vec<u8>>()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_byte_buf(self.read::<Vec<u8>>()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        if self.read::<bool>()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        let remaining = self.read_length()?;
+        visitor.visit_seq(Elements {
+            deserializer: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        visitor.visit_seq(Elements {
+            deserializer: self,
+            remaining: length,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        self.deserialize_tuple(length, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, BridgeError> {
+        let remaining = self.read_length()?;
+        visitor.visit_map(Elements {
+            deserializer: self,
+            remaining,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, BridgeError> {
+        Err(self.unsupported())
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        Err(self.unsupported())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+// The elements of a sequence, tuple or map, or the fields of a struct
+struct Elements<'a, 'r, 'b> {
+    deserializer: &'a mut BitDeserializer<'r, 'b>,
+    remaining: usize,
+}
+
+impl<'de, 'a, 'r, 'b> SeqAccess<'de> for Elements<'a, 'r, 'b> {
+    type Error = BridgeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, BridgeError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 'a, 'r, 'b> MapAccess<'de> for Elements<'a, 'r, 'b> {
+    type Error = BridgeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, BridgeError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, BridgeError> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 'a, 'r, 'b> EnumAccess<'de> for &'a mut BitDeserializer<'r, 'b> {
+    type Error = BridgeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), BridgeError> {
+        let variant_index = UnsignedVariableInteger::<3>::de(self.reader)?.get();
+        let variant_index = u32::try_from(variant_index)
+            .map_err(|_| BridgeError::custom("enum variant index out of range"))?;
+        let value = seed.deserialize(IntoDeserializer::<BridgeError>::into_deserializer(
+            variant_index,
+        ))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 'a, 'r, 'b> VariantAccess<'de> for &'a mut BitDeserializer<'r, 'b> {
+    type Error = BridgeError;
+
+    fn unit_variant(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, BridgeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        length: usize,
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        self.deserialize_tuple(length, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, BridgeError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+}
@@ -0,0 +1,337 @@
+use ::serde::{
+    ser::{
+        SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Serialize, Serializer,
+};
+
+use crate::{BitWrite, Serde, UnsignedVariableInteger};
+
+use super::BridgeError;
+
+/// Writes serde-rs data into the bit stream
+pub(crate) struct BitSerializer<'w> {
+    writer: &'w mut dyn BitWrite,
+}
+
+impl<'w> BitSerializer<'w> {
+    pub fn new(writer: &'w mut dyn BitWrite) -> Self {
+        Self { writer }
+    }
+
+    fn write_length(&mut self, length: Option<usize>) -> Result<(), BridgeError> {
+        let Some(length) = length else {
+            return Err(BridgeError::Custom(
+                "sequences and maps must be given their length up front".to_string(),
+            ));
+        };
+        UnsignedVariableInteger::<5>::new(length as u64).ser(self.writer);
+        Ok(())
+    }
+
+    fn write_variant(&mut self, variant_index: u32) {
+        UnsignedVariableInteger::<3>::new(variant_index).ser(self.writer);
+    }
+}
+
+impl<'a, 'w> Serializer for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, value: bool) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), BridgeError> {
+        value.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), BridgeError> {
+        // written as a String would be
+        UnsignedVariableInteger::<9>::new(value.len() as u64).ser(self.writer);
+        for byte in value.as_bytes() {
+            self.writer.write_byte(*byte);
+        }
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), BridgeError> {
+        self.write_length(Some(value.len()))?;
+        for byte in value {
+            byte.ser(self.writer);
+        }
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), BridgeError> {
+        false.ser(self.writer);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BridgeError> {
+        true.ser(self.writer);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BridgeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), BridgeError> {
+        self.write_variant(variant_index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BridgeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), BridgeError> {
+        self.write_variant(variant_index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, length: Option<usize>) -> Result<Self, BridgeError> {
+        self.write_length(length)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _length: usize) -> Result<Self, BridgeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _length: usize,
+    ) -> Result<Self, BridgeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<Self, BridgeError> {
+        self.write_variant(variant_index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, length: Option<usize>) -> Result<Self, BridgeError> {
+        self.write_length(length)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _length: usize) -> Result<Self, BridgeError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _length: usize,
+    ) -> Result<Self, BridgeError> {
+        self.write_variant(variant_index);
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, 'w> SerializeSeq for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeTuple for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeTupleStruct for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeTupleVariant for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeMap for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), BridgeError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeStruct for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
+
+impl<'a, 'w> SerializeStructVariant for &'a mut BitSerializer<'w> {
+    type Ok = ();
+    type Error = BridgeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), BridgeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), BridgeError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use alloc::{string::String, vec::Vec};
+
+use core::{fmt, ops::Deref};
+
+use crate::{
+    codecs::{read_bits, write_bits},
+    BitReader, BitWrite, Serde, SerdeErr, SerdeErrReason,
+};
+
+// Number of bits needed to write any length up to `max_len`
+const fn length_bits(max_len: usize) -> u32 {
+    usize::BITS - max_len.leading_zeros()
+}
+
+fn write_length<const MAX_LEN: usize>(writer: &mut dyn BitWrite, length: usize) {
+    write_bits(writer, length as u128, length_bits(MAX_LEN));
+}
+
+fn read_length<T, const MAX_LEN: usize>(reader: &mut BitReader) -> Result<usize, SerdeErr> {
+    let length = read_bits::<T>(reader, length_bits(MAX_LEN))? as usize;
+    if length > MAX_LEN {
+        return Err(SerdeErr::new(
+            SerdeErrReason::LengthLimit {
+                length,
+                max_length: MAX_LEN,
+            },
+            reader.bit_offset(),
+        )
+        .expecting::<T>());
+    }
+    reader
+        .reserve_collection::<u8>(length)
+        .map_err(SerdeErr::expecting::<T>)?;
+    Ok(length)
+}
+
+// AsciiString
+
+/// A String of at most `MAX_LEN` ASCII characters, written with a length
+/// sized to `MAX_LEN` followed by 7 bits per character
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsciiString<const MAX_LEN: usize> {
+    inner: String,
+}
+
+impl<const MAX_LEN: usize> AsciiString<MAX_LEN> {
+    /// # Panics
+    /// If the value is longer than `MAX_LEN` or is not entirely ASCII
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        let inner = value.into();
+        if !inner.is_ascii() {
+            panic!(
+                "can't create an AsciiString from non-ASCII text {:?}",
+                inner
+            );
+        }
+        if inner.len() > MAX_LEN {
+            panic!(
+                "can't create an AsciiString longer than {} characters from {:?}",
+                MAX_LEN, inner
+            );
+        }
+        Self { inner }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    pub fn into_string(self) -> String {
+        self.inner
+    }
+}
+
+impl<const MAX_LEN: usize> Deref for AsciiString<MAX_LEN> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<const MAX_LEN: usize> fmt::Display for AsciiString<MAX_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl<const MAX_LEN: usize> Serde for AsciiString<MAX_LEN> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        write_length::<MAX_LEN>(writer, self.inner.len());
+        for byte in self.inner.as_bytes() {
+            write_bits(writer, *byte as u128, 7);
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let length = read_length::<Self, MAX_LEN>(reader)?;
+        let mut inner = String::with_capacity(length);
+        for _ in 0..length {
+            inner.push(read_bits::<Self>(reader, 7)? as u8 as char);
+        }
+        Ok(Self { inner })
+    }
+
+    fn bit_length(&self) -> u32 {
+        length_bits(MAX_LEN) + (self.inner.len() as u32) * 7
+    }
+}
+
+// BoundedString
+
+/// A String of at most `MAX_LEN` bytes of UTF-8, written with a length sized
+/// to `MAX_LEN` followed by its bytes
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BoundedString<const MAX_LEN: usize> {
+    inner: String,
+}
+
+impl<const MAX_LEN: usize> BoundedString<MAX_LEN> {
+    /// # Panics
+    /// If the value is longer than `MAX_LEN` bytes
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        let inner = value.into();
+        if inner.len() > MAX_LEN {
+            panic!(
+                "can't create a BoundedString longer than {} bytes from {:?}",
+                MAX_LEN, inner
+            );
+        }
+        Self { inner }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    pub fn into_string(self) -> String {
+        self.inner
+    }
+}
+
+impl<const MAX_LEN: usize> Deref for BoundedString<MAX_LEN> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<const MAX_LEN: usize> fmt::Display for BoundedString<MAX_LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.inner)
+    }
+}
+
+impl<const MAX_LEN: usize> Serde for BoundedString<MAX_LEN> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        write_length::<MAX_LEN>(writer, self.inner.len());
+        for byte in self.inner.as_bytes() {
+            writer.write_byte(*byte);
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let length = read_length::<Self, MAX_LEN>(reader)?;
+        let mut bytes: Vec<u8> = Vec::with_capacity(length);
+        for _ in 0..length {
+            bytes.push(reader.read_byte().map_err(SerdeErr::expecting::<Self>)?);
+        }
+        let inner = String::from_utf8(bytes).map_err(|_| {
+            SerdeErr::new(SerdeErrReason::InvalidUtf8, reader.bit_offset()).expecting::<Self>()
+        })?;
+        Ok(Self { inner })
+    }
+
+    fn bit_length(&self) -> u32 {
+        length_bits(MAX_LEN) + (self.inner.len() as u32) * 8
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{codecs, AsciiString, BitReader, BitWriter, BoundedString, Serde, SerdeErrReason};
+
+    #[test]
+    fn read_write_ascii() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = AsciiString::<16>::new("Hello ASCII!");
+        let in_2 = AsciiString::<16>::new("");
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(reader.bit_offset(), 5 + 12 * 7 + 5);
+        assert_eq!(
+            reader.bit_offset() as u32,
+            in_1.bit_length() + in_2.bit_length()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn create_non_ascii() {
+        AsciiString::<16>::new("héllo");
+    }
+
+    #[test]
+    fn read_write_bounded() {
+        // Write
+        let mut writer = BitWriter::new();
+
+        let in_1 = BoundedString::<32>::new("Hello bounded wörld!");
+
+        in_1.ser(&mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(reader.bit_offset() as u32, in_1.bit_length());
+    }
+
+    #[test]
+    #[should_panic]
+    fn create_too_long() {
+        BoundedString::<4>::new("Hello");
+    }
+
+    #[test]
+    fn read_too_long() {
+        // Write a length of 5, which fits in the 3 bits of the prefix
+        let mut writer = BitWriter::new();
+
+        codecs::bits::ser::<3, u8>(&5, &mut writer);
+
+        let bytes = writer.to_bytes();
+
+        // Read
+
+        let mut reader = BitReader::new(&bytes);
+
+        let error = BoundedString::<4>::de(&mut reader).unwrap_err();
+
+        assert_eq!(
+            error.reason(),
+            &SerdeErrReason::LengthLimit {
+                length: 5,
+                max_length: 4
+            }
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::{hash::Hash, net::SocketAddr};
 
-use naia_serde::{BitWriter, Serde};
+use naia_serde::{BitWriter, PacketCoding, Serde};
 use naia_socket_shared::Instant;
 
 use crate::{
@@ -32,6 +32,7 @@ pub struct BaseConnection<E: Copy + Eq + Hash + Send + Sync> {
     heartbeat_timer: Timer,
     timeout_timer: Timer,
     ack_manager: AckManager,
+    packet_coding: PacketCoding,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
@@ -53,9 +54,23 @@ impl<E: Copy + Eq + Hash + Send + Sync> BaseConnection<E> {
             remote_world_manager: RemoteWorldManager::new(),
             remote_world_reader: RemoteWorldReader::new(),
             local_world_manager: LocalWorldManager::new(user_key),
+            packet_coding: connection_config.packet_coding,
         }
     }
 
+    // Packet Coding
+
+    /// Get how the body of each outgoing data packet is written
+    pub fn packet_coding(&self) -> PacketCoding {
+        self.packet_coding
+    }
+
+    /// Set how the body of each outgoing data packet is written, from the
+    /// next packet on
+    pub fn set_packet_coding(&mut self, packet_coding: PacketCoding) {
+        self.packet_coding = packet_coding;
+    }
+
     // Heartbeats
 
     /// Record that a message has been sent (to prevent needing to send a
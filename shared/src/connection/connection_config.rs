@@ -1,5 +1,7 @@
 use std::{default::Default, time::Duration};
 
+use naia_serde::PacketCoding;
+
 /// Contains Config properties which will be used by a Server or Client
 #[derive(Clone, Debug)]
 pub struct ConnectionConfig {
@@ -12,10 +14,17 @@ pub struct ConnectionConfig {
     /// The duration over which to measure bandwidth. Set to None to avoid
     /// measure bandwidth at all.
     pub bandwidth_measure_duration: Option<Duration>,
+    /// The duration to wait for a Response to a Request, before giving up on
+    /// it and reporting a timeout
+    pub request_timeout_duration: Duration,
+    /// How the body of each data packet sent is written. Every data packet
+    /// tells how it is coded, so the remote host need not be configured alike
+    pub packet_coding: PacketCoding,
 }
 
 impl ConnectionConfig {
-    /// Creates a new ConnectionConfig, used to initialize a Connection
+    /// Creates a new ConnectionConfig, used to initialize a Connection. Other
+    /// properties keep their default values, and can be set on the result
     pub fn new(
         disconnection_timeout_duration: Duration,
         heartbeat_interval: Duration,
@@ -25,6 +34,7 @@ impl ConnectionConfig {
             disconnection_timeout_duration,
             heartbeat_interval,
             bandwidth_measure_duration,
+            ..Default::default()
         }
     }
 }
@@ -35,6 +45,8 @@ impl Default for ConnectionConfig {
             disconnection_timeout_duration: Duration::from_secs(30),
             heartbeat_interval: Duration::from_secs(4),
             bandwidth_measure_duration: None,
+            request_timeout_duration: Duration::from_secs(10),
+            packet_coding: PacketCoding::Plain,
         }
     }
 }
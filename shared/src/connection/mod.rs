@@ -8,5 +8,6 @@ pub mod encoder;
 pub mod packet_notifiable;
 pub mod packet_type;
 pub mod ping_store;
+pub mod reject_reason;
 pub mod sequence_buffer;
 pub mod standard_header;
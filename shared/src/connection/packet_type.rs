@@ -1,7 +1,9 @@
 // An enum representing the different types of packets that can be
 // sent/received
 
-use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, UnsignedInteger};
+use naia_serde::{
+    BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, SerdeErrReason, UnsignedInteger,
+};
 
 #[derive(Copy, Debug, Clone, Eq, PartialEq)]
 pub enum PacketType {
@@ -22,8 +24,11 @@ pub enum PacketType {
     // The final handshake message sent by the Server, indicating that the
     // connection has been established
     ServerConnectResponse,
-    // Indicates that the authentication payload was rejected, handshake must restart
+    // Indicates that the connection was rejected, handshake must restart
     ServerRejectResponse,
+    // Indicates that the Server is at capacity, and that the Client has been
+    // placed in the connection queue
+    ServerQueueResponse,
     // A Ping message, used to calculate RTT. Must be responded to with a Pong
     // message
     Ping,
@@ -58,6 +63,7 @@ impl Serde for PacketType {
             PacketType::Ping => 8,
             PacketType::Pong => 9,
             PacketType::Disconnect => 10,
+            PacketType::ServerQueueResponse => 11,
         };
 
         UnsignedInteger::<4>::new(index).ser(writer);
@@ -69,7 +75,8 @@ impl Serde for PacketType {
             return Ok(PacketType::Data);
         }
 
-        match UnsignedInteger::<4>::de(reader)?.get() {
+        let index = UnsignedInteger::<4>::de(reader)?.get();
+        match index {
             0 => Ok(PacketType::Heartbeat),
             1 => Ok(PacketType::ClientChallengeRequest),
             2 => Ok(PacketType::ServerChallengeResponse),
@@ -81,7 +88,12 @@ impl Serde for PacketType {
             8 => Ok(PacketType::Ping),
             9 => Ok(PacketType::Pong),
             10 => Ok(PacketType::Disconnect),
-            _ => panic!("shouldn't happen, caught above"),
+            11 => Ok(PacketType::ServerQueueResponse),
+            _ => Err(SerdeErr::new(
+                SerdeErrReason::InvalidEnumVariant(index as u64),
+                reader.bit_offset(),
+            )
+            .expecting::<Self>()),
         }
     }
 
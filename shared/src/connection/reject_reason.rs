@@ -0,0 +1,12 @@
+use naia_serde::SerdeInternal;
+
+/// The reason a Server gives for rejecting a Client's connection attempt
+#[derive(Copy, Debug, PartialEq, Eq, Clone, SerdeInternal)]
+pub enum RejectReason {
+    /// The authentication payload was rejected by the Server
+    Auth,
+    /// The Server is at capacity and its connection queue is full (or disabled)
+    ServerFull,
+    /// The Client was built with a Protocol which does not match the Server's
+    ProtocolMismatch,
+}
@@ -1,2 +1,8 @@
 pub const FRAGMENTATION_LIMIT_BYTES: usize = 400;
 pub const FRAGMENTATION_LIMIT_BITS: u32 = (FRAGMENTATION_LIMIT_BYTES as u32) * 8;
+/// The largest Message which may be sent in fragments
+pub const FRAGMENTED_MESSAGE_LIMIT_BYTES: usize = 1 << 23;
+
+/// Bits of packet space a Channel is granted per unit of weight, each time it
+/// takes a turn at writing into a packet
+pub const CHANNEL_QUANTUM_BITS: u32 = 256;
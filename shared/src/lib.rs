@@ -23,10 +23,13 @@ pub use naia_derive::{
     Channel, Message, MessageBevy, MessageHecs, Replicate, ReplicateBevy, ReplicateHecs,
 };
 pub use naia_serde::{
-    BitReader, BitWrite, BitWriter, ConstBitLength, OutgoingPacket, OwnedBitReader, Serde,
-    SerdeBevy, SerdeErr, SerdeHecs, SerdeInternal, UnsignedInteger, UnsignedVariableInteger,
-    MTU_SIZE_BITS, MTU_SIZE_BYTES,
+    codecs, ArithmeticBitWriter, AsciiString, BitReader, BitSet, BitVec, BitWrite, BitWriter,
+    BoundedString, ConstBitLength, InternedString, OutgoingPacket, OwnedBitReader, PacketCoding,
+    ProtocolName, ReadLimits, Serde, SerdeBevy, SerdeErr, SerdeErrReason, SerdeHecs, SerdeInternal,
+    UnsignedInteger, UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
 };
+#[cfg(feature = "serde_bridge")]
+pub use naia_serde::{serde_bridge, SerdeRs};
 pub use naia_socket_shared::{
     link_condition_logic, Instant, LinkConditionerConfig, Random, SocketConfig, TimeQueue,
 };
@@ -39,6 +42,7 @@ mod game_time;
 mod key_generator;
 mod messages;
 mod protocol;
+pub mod schema;
 mod sequence_list;
 mod types;
 mod world;
@@ -56,11 +60,15 @@ pub use connection::{
     packet_notifiable::PacketNotifiable,
     packet_type::PacketType,
     ping_store::{PingIndex, PingStore},
+    reject_reason::RejectReason,
     standard_header::StandardHeader,
 };
 pub use messages::{
     channels::{
-        channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings, TickBufferSettings},
+        channel::{
+            Channel, ChannelDirection, ChannelMode, FecSettings, KeyedSettings, MessageTtl,
+            ReliableSettings, StreamSettings, TickBufferSettings,
+        },
         channel_kinds::{ChannelKind, ChannelKinds},
         default_channels,
         receivers::{
@@ -69,11 +77,15 @@ pub use messages::{
         },
         senders::{channel_sender::ChannelSender, reliable_sender::ReliableSender},
     },
+    keyed_message::{KeyedMessage, MessageKey},
     message::{Message, Message as MessageBevy, Message as MessageHecs, MessageBuilder},
     message_container::MessageContainer,
+    message_handle::MessageHandle,
     message_kinds::{MessageKind, MessageKinds},
     message_manager::MessageManager,
     named::Named,
+    request::{Request, RequestId, Response, ResponseKey},
+    stream::{StreamId, StreamOptions, StreamProgress, StreamUpdate},
 };
 pub use world::{
     component::{
@@ -81,6 +93,7 @@ pub use world::{
         component_update::{ComponentFieldUpdate, ComponentUpdate},
         diff_mask::DiffMask,
         entity_property::EntityProperty,
+        enum_component::{EnumComponent, ReplicateEnum},
         property::Property,
         property_mutate::{PropertyMutate, PropertyMutator},
         replica_ref::{
@@ -122,6 +135,6 @@ pub use world::{
 pub use bigmap::{BigMap, BigMapKey};
 pub use game_time::{GameDuration, GameInstant, GAME_TIME_LIMIT};
 pub use key_generator::KeyGenerator;
-pub use protocol::{Protocol, ProtocolPlugin};
+pub use protocol::{Protocol, ProtocolError, ProtocolPlugin};
 pub use types::{HostType, MessageIndex, PacketIndex, ShortMessageIndex, Tick};
 pub use wrapping_number::{sequence_greater_than, sequence_less_than, wrapping_diff};
@@ -1,20 +1,82 @@
+use std::time::Duration;
+
+use crate::{constants::FRAGMENTATION_LIMIT_BYTES, protocol::FingerprintHasher};
+
 // Channel Trait
-pub trait Channel: 'static {}
+pub trait Channel: 'static {
+    /// The name this type is given its id by in a Protocol. Derived as the
+    /// path of the module it is defined in & its own name, which stay the
+    /// same across compilers, unlike the `std::any::type_name()` used
+    /// otherwise.
+    fn protocol_name() -> String
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>().to_string()
+    }
+}
 
 // ChannelSettings
 #[derive(Clone)]
 pub struct ChannelSettings {
     pub mode: ChannelMode,
     pub direction: ChannelDirection,
+    /// Messages in Channels with a higher priority are always written into a
+    /// packet before those in Channels with a lower priority
+    pub priority: u8,
+    /// Channels of the same priority share the space left in a packet in
+    /// proportion to their weight
+    pub weight: u16,
+    /// Forward error correction for unreliable Channels, which lets the remote
+    /// host recover Messages from a lost packet without a retransmission
+    pub fec: Option<FecSettings>,
 }
 
 impl ChannelSettings {
     pub fn new(mode: ChannelMode, direction: ChannelDirection) -> Self {
-        if mode.tick_buffered() && direction != ChannelDirection::ClientToServer {
-            panic!("TickBuffered Messages are only allowed to be sent from Client to Server");
+        if mode.tick_buffered() && direction == ChannelDirection::Bidirectional {
+            panic!("TickBuffered Messages are only allowed to be sent in one direction, from Client to Server or from Server to Client");
+        }
+        if let ChannelMode::Stream(settings) = &mode {
+            // leave room in each chunk's frame for the Stream id & action
+            if settings.chunk_size == 0 || settings.chunk_size > FRAGMENTATION_LIMIT_BYTES - 8 {
+                panic!(
+                    "Stream chunk size must be between 1 and {} bytes",
+                    FRAGMENTATION_LIMIT_BYTES - 8
+                );
+            }
+        }
+
+        Self {
+            mode,
+            direction,
+            priority: 0,
+            weight: 1,
+            fec: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u8, weight: u16) -> Self {
+        if weight == 0 {
+            panic!("Channel weight must be greater than zero");
         }
 
-        Self { mode, direction }
+        self.priority = priority;
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_fec(mut self, settings: FecSettings) -> Self {
+        match &self.mode {
+            ChannelMode::UnorderedUnreliable | ChannelMode::SequencedUnreliable => {}
+            _ => panic!("Forward error correction is only available on unreliable Channels"),
+        }
+        if settings.group_size < 2 {
+            panic!("FEC group size must be at least 2");
+        }
+
+        self.fec = Some(settings);
+        self
     }
 
     pub fn reliable(&self) -> bool {
@@ -25,6 +87,8 @@ impl ChannelSettings {
             ChannelMode::SequencedReliable(_) => true,
             ChannelMode::OrderedReliable(_) => true,
             ChannelMode::TickBuffered(_) => false,
+            ChannelMode::Stream(_) => true,
+            ChannelMode::Keyed(_) => false,
         }
     }
 
@@ -47,17 +111,83 @@ impl ChannelSettings {
             ChannelDirection::Bidirectional => true,
         }
     }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut FingerprintHasher) {
+        match &self.mode {
+            ChannelMode::UnorderedUnreliable => hasher.write(&[0]),
+            ChannelMode::SequencedUnreliable => hasher.write(&[1]),
+            ChannelMode::UnorderedReliable(settings) => {
+                hasher.write(&[2]);
+                hasher.write_u64(settings.rtt_resend_factor.to_bits() as u64);
+            }
+            ChannelMode::SequencedReliable(settings) => {
+                hasher.write(&[3]);
+                hasher.write_u64(settings.rtt_resend_factor.to_bits() as u64);
+            }
+            ChannelMode::OrderedReliable(settings) => {
+                hasher.write(&[4]);
+                hasher.write_u64(settings.rtt_resend_factor.to_bits() as u64);
+            }
+            ChannelMode::TickBuffered(settings) => {
+                hasher.write(&[5]);
+                hasher.write_u64(settings.message_capacity as u64);
+            }
+            ChannelMode::Stream(settings) => {
+                hasher.write(&[6]);
+                hasher.write_u64(settings.rtt_resend_factor.to_bits() as u64);
+                hasher.write_u64(settings.chunk_size as u64);
+            }
+            ChannelMode::Keyed(settings) => {
+                hasher.write(&[7]);
+                hasher.write_u64(settings.rtt_resend_factor.to_bits() as u64);
+            }
+        }
+        match &self.direction {
+            ChannelDirection::ClientToServer => hasher.write(&[0]),
+            ChannelDirection::ServerToClient => hasher.write(&[1]),
+            ChannelDirection::Bidirectional => hasher.write(&[2]),
+        }
+        if let Some(settings) = &self.fec {
+            hasher.write(&[settings.group_size]);
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct ReliableSettings {
     pub rtt_resend_factor: f32,
+    /// How long an undelivered Message is retried for before it is given up
+    /// on. None retries Messages until they are delivered.
+    pub ttl: Option<Duration>,
+    /// Whether sending a Message gives up on any older, undelivered Messages
+    /// of the same kind in this Channel, as they have been superseded.
+    /// Requests, Responses & Messages sent in a lane are never superseded.
+    pub supersede: bool,
 }
 
 impl ReliableSettings {
     pub const fn default() -> Self {
         Self {
             rtt_resend_factor: 1.5,
+            ttl: None,
+            supersede: false,
+        }
+    }
+}
+
+// MessageTtl
+/// How long a single reliable Message is retried for before it is given up on
+#[derive(Clone, Copy)]
+pub enum MessageTtl {
+    Duration(Duration),
+    Ticks(u16),
+}
+
+impl MessageTtl {
+    pub fn to_duration(&self, tick_interval: &Duration) -> Duration {
+        match self {
+            MessageTtl::Duration(duration) => *duration,
+            MessageTtl::Ticks(ticks) => *tick_interval * (*ticks as u32),
         }
     }
 }
@@ -77,6 +207,53 @@ impl TickBufferSettings {
     }
 }
 
+#[derive(Clone)]
+pub struct StreamSettings {
+    pub rtt_resend_factor: f32,
+    /// Maximum number of bytes carried by each chunk of a Stream
+    pub chunk_size: usize,
+    /// Maximum number of chunks which may be awaiting acknowledgement at once,
+    /// across all Streams on the Channel
+    pub max_chunks_in_flight: usize,
+}
+
+impl StreamSettings {
+    pub const fn default() -> Self {
+        Self {
+            rtt_resend_factor: 1.5,
+            chunk_size: 256,
+            max_chunks_in_flight: 32,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct KeyedSettings {
+    pub rtt_resend_factor: f32,
+}
+
+impl KeyedSettings {
+    pub const fn default() -> Self {
+        Self {
+            rtt_resend_factor: 1.5,
+        }
+    }
+}
+
+// FecSettings
+#[derive(Clone)]
+pub struct FecSettings {
+    /// Number of packets covered by each parity packet, one of which can be
+    /// recovered if lost. A group size of 4 adds 25% redundancy.
+    pub group_size: u8,
+}
+
+impl FecSettings {
+    pub const fn default() -> Self {
+        Self { group_size: 4 }
+    }
+}
+
 // ChannelMode
 #[derive(Clone)]
 pub enum ChannelMode {
@@ -86,12 +263,27 @@ pub enum ChannelMode {
     SequencedReliable(ReliableSettings),
     OrderedReliable(ReliableSettings),
     TickBuffered(TickBufferSettings),
+    /// Carries Streams of bytes opened with `open_stream`, rather than Messages
+    Stream(StreamSettings),
+    /// Carries KeyedMessages. Only the newest value sent under each key is
+    /// resent until acknowledged, and the remote host ignores older values.
+    Keyed(KeyedSettings),
 }
 
 impl ChannelMode {
     pub fn tick_buffered(&self) -> bool {
         matches!(self, ChannelMode::TickBuffered(_))
     }
+
+    /// Settings of the reliable Message modes, None for any other mode
+    pub fn reliable_settings(&self) -> Option<&ReliableSettings> {
+        match self {
+            ChannelMode::UnorderedReliable(settings)
+            | ChannelMode::SequencedReliable(settings)
+            | ChannelMode::OrderedReliable(settings) => Some(settings),
+            _ => None,
+        }
+    }
 }
 
 // ChannelDirection
@@ -1,8 +1,12 @@
 use std::{any::TypeId, collections::HashMap};
 
-use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, SerdeErrReason};
 
-use crate::messages::channels::channel::{Channel, ChannelSettings};
+use crate::{
+    messages::channels::channel::{Channel, ChannelSettings},
+    protocol::{next_net_id, sort_by_protocol_name, FingerprintHasher, ProtocolError},
+    schema::{short_type_name, ChannelSchema},
+};
 
 type NetId = u16;
 
@@ -33,7 +37,9 @@ impl ChannelKind {
 
     pub fn de(channel_kinds: &ChannelKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(channel_kinds.net_id_to_kind(&net_id))
+        channel_kinds
+            .net_id_to_kind(&net_id)
+            .ok_or_else(|| SerdeErr::new(SerdeErrReason::UnknownKind(net_id), reader.bit_offset()))
     }
 }
 
@@ -46,6 +52,7 @@ impl ConstBitLength for ChannelKind {
 // ChannelKinds
 pub struct ChannelKinds {
     current_net_id: NetId,
+    protocol_name_map: HashMap<ChannelKind, String>,
     kind_map: HashMap<ChannelKind, (NetId, ChannelSettings)>,
     net_id_map: HashMap<NetId, ChannelKind>,
 }
@@ -54,6 +61,7 @@ impl ChannelKinds {
     pub fn new() -> Self {
         Self {
             current_net_id: 0,
+            protocol_name_map: HashMap::new(),
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
         }
@@ -61,11 +69,15 @@ impl ChannelKinds {
 
     pub fn add_channel<C: Channel>(&mut self, settings: ChannelSettings) {
         let channel_kind = ChannelKind::of::<C>();
-        let net_id = self.current_net_id;
+        // registering the same Channel again keeps its id
+        let net_id = match self.kind_map.get(&channel_kind) {
+            Some((net_id, _)) => *net_id,
+            None => next_net_id(&mut self.current_net_id),
+        };
+        self.protocol_name_map
+            .insert(channel_kind, C::protocol_name());
         self.kind_map.insert(channel_kind, (net_id, settings));
         self.net_id_map.insert(net_id, channel_kind);
-        self.current_net_id += 1;
-        //TODO: check for current_id overflow?
     }
 
     pub fn channels(&self) -> Vec<(ChannelKind, ChannelSettings)> {
@@ -83,10 +95,44 @@ impl ChannelKinds {
         settings.clone()
     }
 
-    fn net_id_to_kind(&self, net_id: &NetId) -> ChannelKind {
-        return *self.net_id_map.get(net_id).expect(
-            "Must properly initialize Channel with Protocol via `add_channel()` function!",
-        );
+    /// Gives every Channel its id in order of protocol name. See
+    /// [`sort_by_protocol_name`]
+    pub(crate) fn assign_net_ids(&mut self) -> Result<(), ProtocolError> {
+        let channel_kinds = sort_by_protocol_name(&self.protocol_name_map, "Channel")?;
+        self.net_id_map.clear();
+        for (net_id, channel_kind) in channel_kinds.into_iter().enumerate() {
+            let net_id = net_id as NetId;
+            self.kind_map.get_mut(&channel_kind).unwrap().0 = net_id;
+            self.net_id_map.insert(net_id, channel_kind);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_u64(self.current_net_id as u64);
+        for net_id in 0..self.current_net_id {
+            let channel_kind = self.net_id_map.get(&net_id).unwrap();
+            let (_, settings) = self.kind_map.get(channel_kind).unwrap();
+            settings.fingerprint(hasher);
+        }
+    }
+
+    pub(crate) fn schema(&self) -> Vec<ChannelSchema> {
+        (0..self.current_net_id)
+            .map(|net_id| {
+                let channel_kind = self.net_id_map.get(&net_id).unwrap();
+                let (_, settings) = self.kind_map.get(channel_kind).unwrap();
+                ChannelSchema {
+                    id: net_id,
+                    name: short_type_name(self.protocol_name_map.get(channel_kind).unwrap()),
+                    settings: settings.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn net_id_to_kind(&self, net_id: &NetId) -> Option<ChannelKind> {
+        return self.net_id_map.get(net_id).copied();
     }
 
     fn kind_to_net_id(&self, channel_kind: &ChannelKind) -> NetId {
@@ -0,0 +1,32 @@
+use naia_serde::SerdeInternal;
+
+// FecFrame
+/// A piece of an unreliable Channel's data which is protected by forward
+/// error correction. Each Data frame holds the Messages the Channel wrote into
+/// one packet, and each Parity frame the XOR of a group of Data frames, from
+/// which any single lost frame of the group can be rebuilt.
+#[derive(Clone, PartialEq, SerdeInternal)]
+pub(crate) enum FecFrame {
+    Data {
+        group: u16,
+        position: u8,
+        chunk: Vec<u8>,
+    },
+    Parity {
+        group: u16,
+        count: u8,
+        // the XOR of the lengths of every chunk in the group
+        length: u16,
+        bytes: Vec<u8>,
+    },
+}
+
+/// XORs `chunk` into `parity`, growing `parity` as needed
+pub(crate) fn xor_into(parity: &mut Vec<u8>, chunk: &[u8]) {
+    if parity.len() < chunk.len() {
+        parity.resize(chunk.len(), 0);
+    }
+    for (parity_byte, byte) in parity.iter_mut().zip(chunk) {
+        *parity_byte ^= *byte;
+    }
+}
@@ -1,5 +1,6 @@
 pub mod channel;
 pub mod channel_kinds;
 pub mod default_channels;
+pub mod fec_frame;
 pub mod receivers;
 pub mod senders;
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use naia_serde::{BitReader, Serde, SerdeErr};
+
+use crate::{
+    messages::{
+        channels::{
+            fec_frame::{xor_into, FecFrame},
+            receivers::channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+        },
+        message_kinds::MessageKinds,
+    },
+    sequence_greater_than,
+    world::remote::entity_waitlist::EntityWaitlist,
+    LocalEntityAndGlobalEntityConverter, MessageContainer,
+};
+
+// how many groups behind the newest one are still kept for recovery
+const GROUP_HISTORY: u16 = 32;
+
+#[derive(Default)]
+struct FecGroup {
+    chunks: HashMap<u8, Vec<u8>>,
+    // number of chunks in the group, length XOR and bytes XOR
+    parity: Option<(u8, u16, Vec<u8>)>,
+}
+
+/// Wraps an unreliable Channel's receiver, passing it each chunk received, as
+/// well as any chunk rebuilt from its group's parity
+pub struct FecReceiver {
+    inner: Box<dyn MessageChannelReceiver>,
+    groups: HashMap<u16, FecGroup>,
+    newest_group: Option<u16>,
+}
+
+impl FecReceiver {
+    pub fn new(inner: Box<dyn MessageChannelReceiver>) -> Self {
+        Self {
+            inner,
+            groups: HashMap::new(),
+            newest_group: None,
+        }
+    }
+
+    // Returns the group's state, or None if it is too old to be kept
+    fn group_mut(&mut self, group: u16) -> Option<&mut FecGroup> {
+        match self.newest_group {
+            Some(newest_group) if !sequence_greater_than(group, newest_group) => {
+                if newest_group.wrapping_sub(group) >= GROUP_HISTORY {
+                    return None;
+                }
+            }
+            _ => {
+                self.newest_group = Some(group);
+                self.groups
+                    .retain(|old_group, _| group.wrapping_sub(*old_group) < GROUP_HISTORY);
+            }
+        }
+        Some(self.groups.entry(group).or_default())
+    }
+
+    // Rebuilds the group's one missing chunk from its parity, if possible
+    fn recover_chunk(group: &mut FecGroup) -> Option<Vec<u8>> {
+        let Some((count, length, bytes)) = &group.parity else {
+            return None;
+        };
+        if group.chunks.len() + 1 != *count as usize {
+            return None;
+        }
+        let missing_position = (0..*count).find(|position| !group.chunks.contains_key(position))?;
+
+        let mut chunk = bytes.clone();
+        let mut chunk_length = *length;
+        for received_chunk in group.chunks.values() {
+            xor_into(&mut chunk, received_chunk);
+            chunk_length ^= received_chunk.len() as u16;
+        }
+        chunk.truncate(chunk_length as usize);
+
+        group.chunks.insert(missing_position, chunk.clone());
+        Some(chunk)
+    }
+
+    fn read_chunk(
+        &mut self,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        chunk: &[u8],
+    ) -> Result<(), SerdeErr> {
+        let mut reader = BitReader::new(chunk);
+        self.inner
+            .read_messages(message_kinds, entity_waitlist, converter, &mut reader)
+    }
+}
+
+impl ChannelReceiver<MessageContainer> for FecReceiver {
+    fn receive_messages(
+        &mut self,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Vec<MessageContainer> {
+        self.inner.receive_messages(entity_waitlist, converter)
+    }
+}
+
+impl MessageChannelReceiver for FecReceiver {
+    fn read_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        loop {
+            let message_continue = bool::de(reader)?;
+            if !message_continue {
+                break;
+            }
+
+            let mut chunks = Vec::new();
+            match FecFrame::de(reader)? {
+                FecFrame::Data {
+                    group,
+                    position,
+                    chunk,
+                } => {
+                    let Some(group) = self.group_mut(group) else {
+                        continue;
+                    };
+                    if group.chunks.contains_key(&position) {
+                        // a duplicate, or a chunk which was already rebuilt
+                        continue;
+                    }
+                    group.chunks.insert(position, chunk.clone());
+                    chunks.push(chunk);
+                    chunks.extend(Self::recover_chunk(group));
+                }
+                FecFrame::Parity {
+                    group,
+                    count,
+                    length,
+                    bytes,
+                } => {
+                    let Some(group) = self.group_mut(group) else {
+                        continue;
+                    };
+                    if group.parity.is_some() {
+                        continue;
+                    }
+                    group.parity = Some((count, length, bytes));
+                    chunks.extend(Self::recover_chunk(group));
+                }
+            }
+
+            for chunk in chunks {
+                self.read_chunk(message_kinds, entity_waitlist, converter, &chunk)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,17 +1,63 @@
 use std::collections::HashMap;
 
-use log::info;
-
-use naia_serde::BitReader;
+use naia_serde::{BitReader, BitVec, SerdeErr, SerdeErrReason};
 
 use crate::{
-    messages::fragment::{FragmentId, FragmentedMessage},
-    LocalEntityAndGlobalEntityConverter, MessageContainer, MessageIndex, MessageKinds,
+    messages::{
+        expired_message::ExpiredMessage,
+        fragment::{FragmentId, FragmentedMessage, FRAGMENT_TOTAL_LIMIT},
+    },
+    sequence_less_than, LocalEntityAndGlobalEntityConverter, MessageContainer, MessageIndex,
+    MessageKind, MessageKinds,
 };
 
+// The fragments of a single Message received so far
+struct IncomingFragments {
+    // the MessageIndex the first fragment was sent under, as all of a
+    // Message's fragments are sent under consecutive indices
+    first_index: MessageIndex,
+    received: BitVec,
+    fragments: Vec<(usize, Box<[u8]>)>,
+}
+
+impl IncomingFragments {
+    fn new(first_index: MessageIndex, total: usize) -> Self {
+        Self {
+            first_index,
+            received: BitVec::with_len(total),
+            fragments: Vec::new(),
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.received.len()
+    }
+
+    // the MessageIndex the last fragment was sent under
+    fn last_index(&self) -> MessageIndex {
+        self.first_index.wrapping_add((self.total() - 1) as u16)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.fragments.len() == self.total()
+    }
+
+    fn concat(mut self) -> Vec<u8> {
+        self.fragments.sort_unstable_by_key(|(index, _)| *index);
+        self.fragments
+            .into_iter()
+            .flat_map(|(_, bytes)| bytes.into_vec())
+            .collect()
+    }
+}
+
 pub struct FragmentReceiver {
     current_index: MessageIndex,
-    map: HashMap<FragmentId, (u32, Vec<Box<[u8]>>)>,
+    map: HashMap<FragmentId, IncomingFragments>,
+    // indices under which the sender gave up on a Message, so no fragment will
+    // ever arrive under them
+    expired_indices: Vec<MessageIndex>,
+    oldest_index: MessageIndex,
 }
 
 impl FragmentReceiver {
@@ -19,6 +65,8 @@ impl FragmentReceiver {
         Self {
             current_index: 0,
             map: HashMap::new(),
+            expired_indices: Vec::new(),
+            oldest_index: 0,
         }
     }
 
@@ -26,15 +74,25 @@ impl FragmentReceiver {
         &mut self,
         message_kinds: &MessageKinds,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+        message_index: MessageIndex,
         message: MessageContainer,
-    ) -> Option<(MessageIndex, MessageContainer)> {
+    ) -> Result<Option<(MessageIndex, MessageContainer)>, SerdeErr> {
         // returns a new index, 1 per full message
 
+        // a fragmented Message missing one of its fragments can never complete
+        if message.kind() == MessageKind::of::<ExpiredMessage>() {
+            self.map.retain(|_, incoming| {
+                !covers(incoming.first_index, incoming.total(), message_index)
+            });
+            self.expired_indices.push(message_index);
+        }
+
         // Pass right through if not a fragment
         if !message.is_fragment() {
             let output = Some((self.current_index, message));
             self.current_index = self.current_index.wrapping_add(1);
-            return output;
+            return Ok(output);
         }
 
         // Message is a fragment, need to process
@@ -43,34 +101,86 @@ impl FragmentReceiver {
             .downcast::<FragmentedMessage>()
             .unwrap();
         let fragment_id = fragment.id();
-        let fragment_index = fragment.index();
+        let fragment_index = fragment.index().as_usize();
         let fragment_total = fragment.total().as_usize();
-        info!("fragment_total: {fragment_total}");
-        if !self.map.contains_key(&fragment_id) {
-            self.map
-                .insert(fragment_id, (0, vec![Box::new([]); fragment_total]));
+        if fragment_total == 0
+            || fragment_total > FRAGMENT_TOTAL_LIMIT as usize
+            || fragment_index >= fragment_total
+        {
+            return Err(malformed_fragment());
+        }
+        let first_index = message_index.wrapping_sub(fragment_index as u16);
+        let is_new = self
+            .map
+            .get(&fragment_id)
+            .is_none_or(|incoming| incoming.first_index != first_index);
+        if is_new && self.is_abandoned(first_index, fragment_total) {
+            return Ok(None);
         }
-        let (fragments_received, fragment_list) = self.map.get_mut(&fragment_id).unwrap();
-        fragment_list[fragment_index.as_usize()] = fragment.to_payload();
-        *fragments_received += 1;
-        if *fragments_received != fragment_total as u32 {
-            return None;
+        let incoming = self
+            .map
+            .entry(fragment_id)
+            .or_insert_with(|| IncomingFragments::new(first_index, fragment_total));
+        if incoming.first_index != first_index {
+            // the FragmentId has wrapped around onto a Message which never
+            // completed
+            *incoming = IncomingFragments::new(first_index, fragment_total);
+        }
+        if incoming.total() != fragment_total || incoming.received.get(fragment_index) {
+            return Err(malformed_fragment());
+        }
+        incoming.received.set(fragment_index, true);
+        incoming
+            .fragments
+            .push((fragment_index, fragment.to_payload()));
+        if !incoming.is_complete() {
+            return Ok(None);
         }
 
         // we have received all fragments! put it all together
-        let (_, fragment_list) = self.map.remove(&fragment_id).unwrap();
-        let concat_list = fragment_list.concat();
-        let mut reader = BitReader::new(&concat_list);
-        let full_message_result = message_kinds.read(&mut reader, converter);
-        if full_message_result.is_err() {
-            // TODO: bubble up error instead of panicking here
-            panic!("Cannot read fragmented message!");
-        }
-        let full_message = full_message_result.unwrap();
+        // read within the limits of the packet which completed it
+        let concat_list = self.map.remove(&fragment_id).unwrap().concat();
+        let full_message = reader.read_nested(&concat_list, |fragments_reader| {
+            message_kinds.read(fragments_reader, converter)
+        })?;
         let output = Some((self.current_index, full_message));
         self.current_index = self.current_index.wrapping_add(1);
-        output
+        Ok(output)
+    }
+
+    /// Drops each incomplete Message all of whose fragments were sent under
+    /// indices older than `oldest_index`, as every one of those has been
+    /// received (or given up on) already, and the Message can never complete
+    pub(crate) fn clear_stale(&mut self, oldest_index: MessageIndex) {
+        self.map
+            .retain(|_, incoming| !sequence_less_than(incoming.last_index(), oldest_index));
+        self.expired_indices
+            .retain(|message_index| !sequence_less_than(*message_index, oldest_index));
+        self.oldest_index = oldest_index;
+    }
+
+    // Whether a Message not yet being reassembled, whose fragments were sent
+    // from `first_index`, has already lost one of them to expiry
+    fn is_abandoned(&self, first_index: MessageIndex, total: usize) -> bool {
+        // an earlier fragment was received, yet no longer being reassembled
+        if sequence_less_than(first_index, self.oldest_index) {
+            return true;
+        }
+        self.expired_indices
+            .iter()
+            .any(|message_index| covers(first_index, total, *message_index))
     }
 }
 
-impl FragmentReceiver {}
+// Whether one of the `total` fragments sent from `first_index` was sent under
+// `message_index`
+fn covers(first_index: MessageIndex, total: usize, message_index: MessageIndex) -> bool {
+    (message_index.wrapping_sub(first_index) as usize) < total
+}
+
+fn malformed_fragment() -> SerdeErr {
+    SerdeErr::new(
+        SerdeErrReason::Other("fragment does not match its fragmented message".into()),
+        0,
+    )
+}
@@ -0,0 +1,109 @@
+use std::{collections::HashMap, mem};
+
+use naia_serde::{BitReader, Serde, SerdeErr};
+
+use crate::{
+    messages::{
+        channels::receivers::channel_receiver::{ChannelReceiver, MessageChannelReceiver},
+        keyed_message::MessageKey,
+        message_kinds::MessageKinds,
+    },
+    sequence_greater_than,
+    types::MessageIndex,
+    world::remote::entity_waitlist::{EntityWaitlist, WaitlistStore},
+    LocalEntityAndGlobalEntityConverter, MessageContainer,
+};
+
+/// Applies only values which are newer than the last one received under the
+/// same key
+pub struct KeyedReceiver {
+    newest_versions: HashMap<MessageKey, MessageIndex>,
+    incoming_messages: Vec<MessageContainer>,
+    waitlist_store: WaitlistStore<(MessageKey, MessageIndex, MessageContainer)>,
+}
+
+impl KeyedReceiver {
+    pub fn new() -> Self {
+        Self {
+            newest_versions: HashMap::new(),
+            incoming_messages: Vec::new(),
+            waitlist_store: WaitlistStore::new(),
+        }
+    }
+
+    pub fn buffer_message(
+        &mut self,
+        entity_waitlist: &mut EntityWaitlist,
+        key: MessageKey,
+        version: MessageIndex,
+        message: MessageContainer,
+    ) {
+        if let Some(entity_set) = message.relations_waiting() {
+            entity_waitlist.queue(
+                &entity_set,
+                &mut self.waitlist_store,
+                (key, version, message),
+            );
+            return;
+        }
+
+        self.arrange_message(key, version, message);
+    }
+
+    pub fn arrange_message(
+        &mut self,
+        key: MessageKey,
+        version: MessageIndex,
+        message: MessageContainer,
+    ) {
+        if let Some(newest_version) = self.newest_versions.get(&key) {
+            if !sequence_greater_than(version, *newest_version) {
+                // an obsolete or duplicate value
+                return;
+            }
+        }
+        self.newest_versions.insert(key, version);
+        self.incoming_messages.push(message);
+    }
+}
+
+impl ChannelReceiver<MessageContainer> for KeyedReceiver {
+    fn receive_messages(
+        &mut self,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Vec<MessageContainer> {
+        if let Some(list) = entity_waitlist.collect_ready_items(&mut self.waitlist_store) {
+            for (key, version, mut message) in list {
+                message.relations_complete(converter);
+                self.arrange_message(key, version, message);
+            }
+        }
+
+        mem::take(&mut self.incoming_messages)
+    }
+}
+
+impl MessageChannelReceiver for KeyedReceiver {
+    fn read_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        loop {
+            let message_continue = bool::de(reader)?;
+            if !message_continue {
+                break;
+            }
+
+            let key_bytes = MessageKey::de_bytes(reader)?;
+            let version = MessageIndex::de(reader)?;
+            let message = message_kinds.read(reader, converter)?;
+            let key = MessageKey::from_parts(message.kind(), key_bytes);
+            self.buffer_message(entity_waitlist, key, version, message);
+        }
+        Ok(())
+    }
+}
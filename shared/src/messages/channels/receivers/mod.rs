@@ -1,9 +1,12 @@
 pub mod channel_receiver;
+pub mod fec_receiver;
 pub mod fragment_receiver;
 pub mod indexed_message_reader;
+pub mod keyed_receiver;
 pub mod ordered_reliable_receiver;
 pub mod sequenced_reliable_receiver;
 pub mod sequenced_unreliable_receiver;
+pub mod stream_receiver;
 pub mod unordered_reliable_receiver;
 pub mod unordered_unreliable_receiver;
 
@@ -1,13 +1,23 @@
-use std::collections::VecDeque;
+use log::warn;
+use std::collections::{HashMap, VecDeque};
 
 use crate::{
-    messages::channels::receivers::reliable_message_receiver::{
-        ReceiverArranger, ReliableMessageReceiver,
+    messages::{
+        channels::receivers::reliable_message_receiver::{
+            ReceiverArranger, ReliableMessageReceiver,
+        },
+        lane_frame::LaneFrame,
+        message_kinds::MessageKind,
     },
+    sequence_greater_than,
     types::MessageIndex,
     MessageContainer,
 };
 
+// How far past the next Message of a lane a Message may be buffered, beyond
+// which it is dropped rather than let a stalled lane grow without bound
+pub(crate) const LANE_BUFFER_LIMIT: MessageIndex = 4096;
+
 // OrderedReliableReceiver
 pub type OrderedReliableReceiver = ReliableMessageReceiver<OrderedArranger>;
 
@@ -16,32 +26,41 @@ impl OrderedReliableReceiver {
         Self::with_arranger(OrderedArranger {
             oldest_received_message_index: 0,
             buffer: VecDeque::new(),
+            lanes: HashMap::new(),
         })
     }
 }
 
+enum OrderedSlot {
+    Missing,
+    Message(MessageContainer),
+    // a fragment, or a Message ordered separately within its own lane
+    Empty,
+}
+
 // OrderedArranger
 pub struct OrderedArranger {
-    buffer: VecDeque<(MessageIndex, Option<MessageContainer>)>,
+    buffer: VecDeque<(MessageIndex, OrderedSlot)>,
     oldest_received_message_index: MessageIndex,
+    lanes: HashMap<u16, LaneArranger>,
 }
 
-impl ReceiverArranger for OrderedArranger {
-    fn process(
+impl OrderedArranger {
+    fn fill(
         &mut self,
         incoming_messages: &mut Vec<(MessageIndex, MessageContainer)>,
         message_index: MessageIndex,
-        message: MessageContainer,
+        slot: OrderedSlot,
     ) {
         let mut current_index = 0;
 
         // Put message where it needs to go in buffer
         loop {
             if current_index < self.buffer.len() {
-                if let Some((old_message_index, old_message)) = self.buffer.get_mut(current_index) {
+                if let Some((old_message_index, old_slot)) = self.buffer.get_mut(current_index) {
                     if *old_message_index == message_index {
-                        if old_message.is_none() {
-                            *old_message = Some(message);
+                        if let OrderedSlot::Missing = old_slot {
+                            *old_slot = slot;
                             break;
                         }
                     }
@@ -52,10 +71,11 @@ impl ReceiverArranger for OrderedArranger {
                     .wrapping_add(current_index as u16);
 
                 if next_message_index == message_index {
-                    self.buffer.push_back((next_message_index, Some(message)));
+                    self.buffer.push_back((next_message_index, slot));
                     break;
                 } else {
-                    self.buffer.push_back((next_message_index, None));
+                    self.buffer
+                        .push_back((next_message_index, OrderedSlot::Missing));
                     // keep filling up buffer
                 }
             }
@@ -65,16 +85,97 @@ impl ReceiverArranger for OrderedArranger {
 
         // Pop messages out in order
         loop {
-            let Some((_, Some(_))) = self.buffer.front() else {
+            let Some((_, OrderedSlot::Message(_) | OrderedSlot::Empty)) = self.buffer.front()
+            else {
                 // no more messages, return
                 return;
             };
-            let Some((index, Some(message))) = self.buffer.pop_front() else {
+            let Some((index, slot)) = self.buffer.pop_front() else {
                 panic!("shouldn't be possible due to above check");
             };
 
-            incoming_messages.push((index, message));
+            if let OrderedSlot::Message(message) = slot {
+                incoming_messages.push((index, message));
+            }
             self.oldest_received_message_index = self.oldest_received_message_index.wrapping_add(1);
         }
     }
 }
+
+impl ReceiverArranger for OrderedArranger {
+    fn process(
+        &mut self,
+        incoming_messages: &mut Vec<(MessageIndex, MessageContainer)>,
+        message_index: MessageIndex,
+        message: MessageContainer,
+    ) {
+        if message.kind() != MessageKind::of::<LaneFrame>() {
+            self.fill(
+                incoming_messages,
+                message_index,
+                OrderedSlot::Message(message),
+            );
+            return;
+        }
+
+        let (frame, message) = message.into_frame::<LaneFrame>().unwrap();
+        let lane = self
+            .lanes
+            .entry(frame.lane())
+            .or_insert_with(LaneArranger::new);
+        lane.process(incoming_messages, message_index, frame.sequence(), message);
+        self.fill(incoming_messages, message_index, OrderedSlot::Empty);
+    }
+
+    fn skip(
+        &mut self,
+        incoming_messages: &mut Vec<(MessageIndex, MessageContainer)>,
+        message_index: MessageIndex,
+    ) {
+        self.fill(incoming_messages, message_index, OrderedSlot::Empty);
+    }
+}
+
+// LaneArranger
+/// Orders the Messages of a single lane, independently of any other lane
+pub(crate) struct LaneArranger {
+    next_sequence: MessageIndex,
+    buffer: HashMap<MessageIndex, (MessageIndex, MessageContainer)>,
+}
+
+impl LaneArranger {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_sequence: 0,
+            buffer: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn process(
+        &mut self,
+        incoming_messages: &mut Vec<(MessageIndex, MessageContainer)>,
+        message_index: MessageIndex,
+        sequence: MessageIndex,
+        message: MessageContainer,
+    ) {
+        if sequence != self.next_sequence {
+            if !sequence_greater_than(sequence, self.next_sequence) {
+                return;
+            }
+            if sequence.wrapping_sub(self.next_sequence) > LANE_BUFFER_LIMIT {
+                warn!("Dropping a Message too far ahead of the rest of its lane");
+                return;
+            }
+            self.buffer.insert(sequence, (message_index, message));
+            return;
+        }
+
+        incoming_messages.push((message_index, message));
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+
+        while let Some((message_index, message)) = self.buffer.remove(&self.next_sequence) {
+            incoming_messages.push((message_index, message));
+            self.next_sequence = self.next_sequence.wrapping_add(1);
+        }
+    }
+}
@@ -23,6 +23,15 @@ pub trait ReceiverArranger: Send + Sync {
         message_index: MessageIndex,
         message: MessageContainer,
     );
+
+    /// Called with the index of each fragment which does not complete its
+    /// Message, as no Message will be arranged under that index
+    fn skip(
+        &mut self,
+        _incoming_messages: &mut Vec<(MessageIndex, MessageContainer)>,
+        _message_index: MessageIndex,
+    ) {
+    }
 }
 
 // Reliable Receiver
@@ -50,25 +59,37 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         message_kinds: &MessageKinds,
         entity_waitlist: &mut EntityWaitlist,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+        message_index: MessageIndex,
         message: MessageContainer,
-    ) {
-        let Some((first_index, full_message)) =
-            self.fragment_receiver
-                .receive(message_kinds, converter, message) else {
-            return;
+    ) -> Result<(), SerdeErr> {
+        // a fragmented Message is arranged under the index of whichever of its
+        // fragments arrives last
+        let Some((_, full_message)) = self.fragment_receiver.receive(
+            message_kinds,
+            converter,
+            reader,
+            message_index,
+            message,
+        )?
+        else {
+            self.arranger
+                .skip(&mut self.incoming_messages, message_index);
+            return Ok(());
         };
 
         if let Some(entity_set) = full_message.relations_waiting() {
             entity_waitlist.queue(
                 &entity_set,
                 &mut self.waitlist_store,
-                (first_index, full_message),
+                (message_index, full_message),
             );
-            return;
+            return Ok(());
         }
 
         self.arranger
-            .process(&mut self.incoming_messages, first_index, full_message);
+            .process(&mut self.incoming_messages, message_index, full_message);
+        Ok(())
     }
 
     pub fn buffer_message(
@@ -76,15 +97,26 @@ impl<A: ReceiverArranger> ReliableMessageReceiver<A> {
         message_kinds: &MessageKinds,
         entity_waitlist: &mut EntityWaitlist,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
         message_index: MessageIndex,
         message: MessageContainer,
-    ) {
+    ) -> Result<(), SerdeErr> {
         self.reliable_receiver
             .buffer_message(message_index, message);
         let received_messages = self.reliable_receiver.receive_messages();
-        for (_, received_message) in received_messages {
-            self.push_message(message_kinds, entity_waitlist, converter, received_message)
+        for (received_index, received_message) in received_messages {
+            self.push_message(
+                message_kinds,
+                entity_waitlist,
+                converter,
+                reader,
+                received_index,
+                received_message,
+            )?;
         }
+        self.fragment_receiver
+            .clear_stale(self.reliable_receiver.oldest_index());
+        Ok(())
     }
 
     pub fn receive_messages(
@@ -128,7 +160,14 @@ impl<A: ReceiverArranger> MessageChannelReceiver for ReliableMessageReceiver<A>
     ) -> Result<(), SerdeErr> {
         let id_w_msgs = IndexedMessageReader::read_messages(message_kinds, converter, reader)?;
         for (id, message) in id_w_msgs {
-            self.buffer_message(message_kinds, entity_waitlist, converter, id, message);
+            self.buffer_message(
+                message_kinds,
+                entity_waitlist,
+                converter,
+                reader,
+                id,
+                message,
+            )?;
         }
         Ok(())
     }
@@ -87,6 +87,11 @@ impl<M> ReliableReceiver<M> {
         }
     }
 
+    /// The index of the oldest Message which has not been received yet
+    pub(crate) fn oldest_index(&self) -> MessageIndex {
+        self.oldest_received_message_index
+    }
+
     pub(crate) fn receive_messages(&mut self) -> Vec<(MessageIndex, M)> {
         std::mem::take(&mut self.incoming_messages)
     }
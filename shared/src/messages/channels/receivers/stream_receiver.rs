@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use naia_serde::{BitReader, SerdeErr, SerdeErrReason};
+
+use crate::{
+    messages::{
+        channels::receivers::{
+            channel_receiver::MessageChannelReceiver,
+            ordered_reliable_receiver::OrderedReliableReceiver,
+        },
+        message_kinds::MessageKinds,
+        stream::{StreamAction, StreamFrame, StreamId, StreamUpdate},
+    },
+    world::remote::entity_waitlist::EntityWaitlist,
+    LocalEntityAndGlobalEntityConverter,
+};
+
+struct IncomingStream {
+    received: u64,
+}
+
+/// Reassembles the frames of incoming Streams, in order, into StreamUpdates
+pub struct StreamReceiver {
+    reliable_receiver: OrderedReliableReceiver,
+    streams: HashMap<StreamId, IncomingStream>,
+    updates: Vec<StreamUpdate>,
+    rejections: Vec<StreamId>,
+}
+
+impl StreamReceiver {
+    pub fn new() -> Self {
+        Self {
+            reliable_receiver: OrderedReliableReceiver::new(),
+            streams: HashMap::new(),
+            updates: Vec::new(),
+            rejections: Vec::new(),
+        }
+    }
+
+    pub fn read_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        self.reliable_receiver
+            .read_messages(message_kinds, entity_waitlist, converter, reader)?;
+
+        let frames = self
+            .reliable_receiver
+            .receive_messages(entity_waitlist, converter);
+        for (_, message) in frames {
+            let Ok(frame) = message.to_boxed_any().downcast::<StreamFrame>() else {
+                return Err(SerdeErr::new(
+                    SerdeErrReason::Other("expected a StreamFrame on a Stream channel".into()),
+                    reader.bit_offset(),
+                ));
+            };
+            let (id, action) = frame.into_parts();
+            self.process_frame(message_kinds, converter, id, action)?;
+        }
+
+        Ok(())
+    }
+
+    /// Stops reading an incoming Stream, ignoring any of its frames still to come
+    pub fn reject_stream(&mut self, id: &StreamId) {
+        self.streams.remove(id);
+    }
+
+    pub fn take_updates(&mut self) -> Vec<StreamUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+
+    /// Outgoing Streams which the remote host has rejected
+    pub fn take_rejections(&mut self) -> Vec<StreamId> {
+        std::mem::take(&mut self.rejections)
+    }
+
+    fn process_frame(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        id: StreamId,
+        action: StreamAction,
+    ) -> Result<(), SerdeErr> {
+        match action {
+            StreamAction::Open {
+                metadata,
+                offset,
+                length,
+                content_hash,
+            } => {
+                let mut reader = BitReader::new(&metadata);
+                let metadata = message_kinds.read(&mut reader, converter)?;
+                self.streams.insert(id, IncomingStream { received: offset });
+                self.updates.push(StreamUpdate::Opened {
+                    id,
+                    metadata,
+                    offset,
+                    length,
+                    content_hash,
+                });
+            }
+            StreamAction::Data { bytes } => {
+                let Some(stream) = self.streams.get_mut(&id) else {
+                    // Stream was rejected locally
+                    return Ok(());
+                };
+                let offset = stream.received;
+                stream.received += bytes.len() as u64;
+                self.updates.push(StreamUpdate::Data { id, offset, bytes });
+            }
+            StreamAction::Finish => {
+                if self.streams.remove(&id).is_some() {
+                    self.updates.push(StreamUpdate::Finished { id });
+                }
+            }
+            StreamAction::Cancel => {
+                if self.streams.remove(&id).is_some() {
+                    self.updates.push(StreamUpdate::Cancelled { id });
+                }
+            }
+            StreamAction::Reject => {
+                self.rejections.push(id);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
 
 use crate::{
-    messages::{message_container::MessageContainer, message_kinds::MessageKinds},
+    messages::{
+        message_container::MessageContainer,
+        message_kinds::{MessageKind, MessageKinds},
+    },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut,
 };
@@ -19,12 +24,26 @@ pub trait ChannelSender<P>: Send + Sync {
 }
 
 pub trait MessageChannelSender: ChannelSender<MessageContainer> {
-    /// Gets Messages from the internal buffer and writes it to the BitWriter
+    /// Gets Messages from the internal buffer and writes it to the BitWriter.
+    /// Stops once the next Message would take the bits written past
+    /// `bit_budget`, though the first Message is written regardless.
     fn write_messages(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
+        bit_budget: u32,
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>>;
+    /// Returns the index under which the next Message sent will be
+    /// acknowledged, for Channels which report the delivery of each Message
+    fn delivery_index(&self) -> Option<MessageIndex>;
+    /// Queues a Message which, over a reliable channel, is given up on if it
+    /// remains undelivered after `ttl`
+    fn send_message_with_ttl(&mut self, message: MessageContainer, ttl: Option<Duration>);
+    /// Gives up on any Messages whose TTL has passed, returning their indices
+    fn expire_messages(&mut self, now: &Instant) -> Vec<MessageIndex>;
+    /// Gives up on any undelivered Messages of the given kind, returning their
+    /// indices
+    fn supersede_messages(&mut self, message_kind: &MessageKind) -> Vec<MessageIndex>;
 }
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use naia_serde::{BitWrite, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::{
+        channels::{
+            channel::FecSettings,
+            fec_frame::{xor_into, FecFrame},
+            senders::channel_sender::{ChannelSender, MessageChannelSender},
+        },
+        message_container::MessageContainer,
+        message_kinds::{MessageKind, MessageKinds},
+    },
+    types::MessageIndex,
+    LocalEntityAndGlobalEntityConverterMut,
+};
+
+// the most a frame's header can add to the chunk it carries
+const FRAME_OVERHEAD_BITS: u32 = 64;
+
+/// Wraps an unreliable Channel's sender, sending the Messages it writes into
+/// each packet as a single chunk, followed by a parity chunk for every group
+/// of chunks
+pub struct FecSender {
+    inner: Box<dyn MessageChannelSender>,
+    group_size: u8,
+    group: u16,
+    // number of chunks sent so far in the current group
+    group_count: u8,
+    parity: Vec<u8>,
+    parity_length: u16,
+    // parity of a completed group, sent in a later packet than its chunks
+    outgoing_parity: Option<FecFrame>,
+}
+
+impl FecSender {
+    pub fn new(inner: Box<dyn MessageChannelSender>, settings: &FecSettings) -> Self {
+        Self {
+            inner,
+            group_size: settings.group_size,
+            group: 0,
+            group_count: 0,
+            parity: Vec::new(),
+            parity_length: 0,
+            outgoing_parity: None,
+        }
+    }
+
+    fn finish_group(&mut self) {
+        self.outgoing_parity = Some(FecFrame::Parity {
+            group: self.group,
+            count: self.group_count,
+            length: self.parity_length,
+            bytes: std::mem::take(&mut self.parity),
+        });
+        self.group = self.group.wrapping_add(1);
+        self.group_count = 0;
+        self.parity_length = 0;
+    }
+
+    fn write_frame(writer: &mut dyn BitWrite, frame: &FecFrame) {
+        // write MessageContinue bit
+        true.ser(writer);
+        frame.ser(writer);
+    }
+}
+
+impl ChannelSender<MessageContainer> for FecSender {
+    fn send_message(&mut self, message: MessageContainer) {
+        self.inner.send_message(message);
+    }
+
+    fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.inner.collect_messages(now, rtt_millis);
+    }
+
+    fn has_messages(&self) -> bool {
+        // an unfinished group is closed off once there is nothing else to send
+        self.inner.has_messages() || self.outgoing_parity.is_some() || self.group_count > 0
+    }
+
+    fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
+        self.inner.notify_message_delivered(message_index);
+    }
+}
+
+impl MessageChannelSender for FecSender {
+    fn write_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        writer: &mut BitWriter,
+        bit_budget: u32,
+        has_written: &mut bool,
+    ) -> Option<Vec<MessageIndex>> {
+        let start_bits_free = writer.bits_free();
+
+        if !self.inner.has_messages() && self.outgoing_parity.is_none() && self.group_count > 0 {
+            self.finish_group();
+        }
+
+        if let Some(frame) = &self.outgoing_parity {
+            let mut counter = writer.counter();
+            Self::write_frame(&mut counter, frame);
+            if counter.overflowed() {
+                return None;
+            }
+            Self::write_frame(writer, frame);
+            *has_written = true;
+            self.outgoing_parity = None;
+        }
+
+        if !self.inner.has_messages() || writer.bits_free() <= FRAME_OVERHEAD_BITS {
+            return None;
+        }
+
+        // write the inner Channel's Messages into a chunk of their own, leaving
+        // room for the frame around it, and the chunk's MessageContinue finish bit
+        let chunk_capacity = (writer.bits_free() - FRAME_OVERHEAD_BITS) / 8 * 8;
+        let mut chunk_writer = BitWriter::with_capacity(chunk_capacity);
+        chunk_writer.reserve_bits(1);
+        let bits_written = start_bits_free - writer.bits_free();
+        let mut chunk_has_written = *has_written;
+        let message_indices = self.inner.write_messages(
+            message_kinds,
+            converter,
+            &mut chunk_writer,
+            bit_budget.saturating_sub(bits_written),
+            &mut chunk_has_written,
+        );
+        if chunk_writer.bits_free() == chunk_capacity - 1 {
+            // the next Message does not fit into this packet
+            return None;
+        }
+        chunk_writer.release_bits(1);
+        false.ser(&mut chunk_writer);
+        let chunk = chunk_writer.to_bytes().to_vec();
+
+        xor_into(&mut self.parity, &chunk);
+        self.parity_length ^= chunk.len() as u16;
+        let frame = FecFrame::Data {
+            group: self.group,
+            position: self.group_count,
+            chunk,
+        };
+        Self::write_frame(writer, &frame);
+        *has_written = true;
+
+        self.group_count += 1;
+        if self.group_count == self.group_size {
+            self.finish_group();
+        }
+
+        message_indices
+    }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        self.inner.delivery_index()
+    }
+
+    fn send_message_with_ttl(&mut self, message: MessageContainer, ttl: Option<Duration>) {
+        self.inner.send_message_with_ttl(message, ttl);
+    }
+
+    fn expire_messages(&mut self, now: &Instant) -> Vec<MessageIndex> {
+        self.inner.expire_messages(now)
+    }
+
+    fn supersede_messages(&mut self, message_kind: &MessageKind) -> Vec<MessageIndex> {
+        self.inner.supersede_messages(message_kind)
+    }
+}
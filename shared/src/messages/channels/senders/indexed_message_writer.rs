@@ -18,10 +18,12 @@ impl IndexedMessageWriter {
         outgoing_messages: &mut VecDeque<(MessageIndex, MessageContainer)>,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
+        bit_budget: u32,
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>> {
         let mut last_written_id: Option<MessageIndex> = None;
         let mut message_indices = Vec::new();
+        let start_bits_free = writer.bits_free();
 
         loop {
             if outgoing_messages.is_empty() {
@@ -50,6 +52,12 @@ impl IndexedMessageWriter {
                 break;
             }
 
+            // stay within the budget, once at least one Message has been written
+            let bits_written = start_bits_free - writer.bits_free();
+            if !message_indices.is_empty() && bits_written + counter.bits_needed() > bit_budget {
+                break;
+            }
+
             *has_written = true;
 
             // write MessageContinue bit
@@ -0,0 +1,198 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use naia_serde::{BitWrite, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::{
+        channels::{
+            channel::KeyedSettings,
+            senders::channel_sender::{ChannelSender, MessageChannelSender},
+        },
+        keyed_message::MessageKey,
+        message_container::MessageContainer,
+        message_kinds::{MessageKind, MessageKinds},
+    },
+    types::MessageIndex,
+    LocalEntityAndGlobalEntityConverterMut,
+};
+
+struct KeyedValue {
+    version: MessageIndex,
+    last_sent: Option<Instant>,
+    message: MessageContainer,
+}
+
+/// Keeps only the newest unacknowledged value under each key, and resends it
+/// until the remote host acknowledges it
+pub struct KeyedSender {
+    rtt_resend_factor: f32,
+    next_versions: HashMap<MessageKey, MessageIndex>,
+    values: HashMap<MessageKey, KeyedValue>,
+    outgoing_messages: VecDeque<(MessageKey, MessageIndex, MessageContainer)>,
+    // each write of a value is acknowledged under its own index
+    next_message_index: MessageIndex,
+    values_in_flight: HashMap<MessageIndex, (MessageKey, MessageIndex)>,
+}
+
+impl KeyedSender {
+    pub fn new(settings: &KeyedSettings) -> Self {
+        Self {
+            rtt_resend_factor: settings.rtt_resend_factor,
+            next_versions: HashMap::new(),
+            values: HashMap::new(),
+            outgoing_messages: VecDeque::new(),
+            next_message_index: 0,
+            values_in_flight: HashMap::new(),
+        }
+    }
+
+    /// Queues a value under the given key, replacing any older value which
+    /// has not yet been delivered
+    pub fn send_keyed_message(&mut self, key: MessageKey, message: MessageContainer) {
+        let next_version = self.next_versions.entry(key.clone()).or_insert(0);
+        let version = *next_version;
+        *next_version = next_version.wrapping_add(1);
+
+        self.outgoing_messages
+            .retain(|(queued_key, _, _)| *queued_key != key);
+        self.values.insert(
+            key,
+            KeyedValue {
+                version,
+                last_sent: None,
+                message,
+            },
+        );
+    }
+
+    fn write_message(
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        writer: &mut dyn BitWrite,
+        key: &MessageKey,
+        version: &MessageIndex,
+        message: &MessageContainer,
+    ) {
+        key.ser(writer);
+        version.ser(writer);
+        message.write(message_kinds, writer, converter);
+    }
+}
+
+impl ChannelSender<MessageContainer> for KeyedSender {
+    fn send_message(&mut self, _: MessageContainer) {
+        panic!("Only KeyedMessages can be sent over a Keyed Channel, use `send_keyed_message()` instead");
+    }
+
+    fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        let resend_duration = Duration::from_millis((self.rtt_resend_factor * rtt_millis) as u64);
+
+        for (key, value) in self.values.iter_mut() {
+            if let Some(last_sent) = &value.last_sent {
+                if last_sent.elapsed() < resend_duration {
+                    continue;
+                }
+            }
+            self.outgoing_messages
+                .retain(|(queued_key, _, _)| queued_key != key);
+            self.outgoing_messages
+                .push_back((key.clone(), value.version, value.message.clone()));
+            value.last_sent = Some(now.clone());
+        }
+    }
+
+    fn has_messages(&self) -> bool {
+        !self.outgoing_messages.is_empty()
+    }
+
+    fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
+        let Some((key, version)) = self.values_in_flight.remove(message_index) else {
+            return;
+        };
+        let Some(value) = self.values.get(&key) else {
+            return;
+        };
+        if value.version != version {
+            // a newer value has been sent since
+            return;
+        }
+        self.values.remove(&key);
+
+        // earlier writes of this value no longer need acknowledging
+        self.values_in_flight
+            .retain(|_, (in_flight_key, _)| *in_flight_key != key);
+    }
+}
+
+impl MessageChannelSender for KeyedSender {
+    fn write_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        writer: &mut BitWriter,
+        bit_budget: u32,
+        has_written: &mut bool,
+    ) -> Option<Vec<MessageIndex>> {
+        let mut message_indices = Vec::new();
+        let start_bits_free = writer.bits_free();
+
+        while let Some((key, version, message)) = self.outgoing_messages.front() {
+            // check that we can write the next message
+            let mut counter = writer.counter();
+            Self::write_message(
+                message_kinds,
+                converter,
+                &mut counter,
+                key,
+                version,
+                message,
+            );
+            if counter.overflowed() {
+                break;
+            }
+
+            // stay within the budget, once at least one Message has been written
+            let bits_written = start_bits_free - writer.bits_free();
+            if !message_indices.is_empty() && bits_written + counter.bits_needed() > bit_budget {
+                break;
+            }
+
+            *has_written = true;
+
+            // write MessageContinue bit
+            true.ser(writer);
+
+            // write data
+            Self::write_message(message_kinds, converter, writer, key, version, message);
+
+            let (key, version, _) = self.outgoing_messages.pop_front().unwrap();
+            let message_index = self.next_message_index;
+            self.next_message_index = self.next_message_index.wrapping_add(1);
+            self.values_in_flight.insert(message_index, (key, version));
+            message_indices.push(message_index);
+        }
+
+        Some(message_indices)
+    }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        // values are superseded rather than tracked individually
+        None
+    }
+
+    fn send_message_with_ttl(&mut self, _: MessageContainer, _: Option<Duration>) {
+        panic!("Only KeyedMessages can be sent over a Keyed Channel, use `send_keyed_message()` instead");
+    }
+
+    fn expire_messages(&mut self, _: &Instant) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+
+    fn supersede_messages(&mut self, _: &MessageKind) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+}
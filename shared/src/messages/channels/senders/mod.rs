@@ -1,6 +1,9 @@
 pub mod channel_sender;
+pub mod fec_sender;
 pub mod indexed_message_writer;
+pub mod keyed_sender;
 pub mod message_fragmenter;
 pub mod reliable_sender;
 pub mod sequenced_unreliable_sender;
+pub mod stream_sender;
 pub mod unordered_unreliable_sender;
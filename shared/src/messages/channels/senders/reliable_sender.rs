@@ -1,4 +1,8 @@
-use std::{collections::VecDeque, mem, time::Duration};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    mem,
+    time::Duration,
+};
 
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
@@ -9,11 +13,12 @@ use crate::{
             channel_sender::{ChannelSender, MessageChannelSender},
             indexed_message_writer::IndexedMessageWriter,
         },
+        expired_message::ExpiredMessage,
         message_container::MessageContainer,
-        message_kinds::MessageKinds,
+        message_kinds::{MessageKind, MessageKinds},
     },
     types::MessageIndex,
-    LocalEntityAndGlobalEntityConverterMut,
+    FakeEntityConverter, LocalEntityAndGlobalEntityConverterMut,
 };
 
 // Sender
@@ -22,6 +27,8 @@ pub struct ReliableSender<P: Send + Sync> {
     sending_messages: VecDeque<Option<(MessageIndex, Option<Instant>, P)>>,
     next_send_message_index: MessageIndex,
     outgoing_messages: VecDeque<(MessageIndex, P)>,
+    ttl: Option<Duration>,
+    expiries: HashMap<MessageIndex, Instant>,
 }
 
 impl<P: Send + Sync> ReliableSender<P> {
@@ -31,7 +38,46 @@ impl<P: Send + Sync> ReliableSender<P> {
             next_send_message_index: 0,
             sending_messages: VecDeque::new(),
             outgoing_messages: VecDeque::new(),
+            ttl: None,
+            expiries: HashMap::new(),
+        }
+    }
+
+    /// Gives up on Messages which remain undelivered for longer than `ttl`
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Queues a Message which is given up on after `ttl` instead of the
+    /// sender's own TTL
+    pub fn send_message_with_ttl(&mut self, message: P, ttl: Option<Duration>) {
+        if let Some(ttl) = ttl {
+            let mut expiry = Instant::now();
+            expiry.add_millis(ttl.as_millis() as u32);
+            self.expiries.insert(self.next_send_message_index, expiry);
+        }
+        self.sending_messages
+            .push_back(Some((self.next_send_message_index, None, message)));
+        self.next_send_message_index = self.next_send_message_index.wrapping_add(1);
+    }
+
+    // Replaces the undelivered Messages which match `predicate` with
+    // `placeholder`, returning their indices
+    fn replace_messages(
+        &mut self,
+        predicate: impl Fn(&MessageIndex, &P) -> bool,
+        placeholder: impl Fn() -> P,
+    ) -> Vec<MessageIndex> {
+        let mut replaced = Vec::new();
+        for (message_index, _, message) in self.sending_messages.iter_mut().flatten() {
+            if predicate(message_index, message) {
+                *message = placeholder();
+                self.expiries.remove(message_index);
+                replaced.push(*message_index);
+            }
         }
+        replaced
     }
 
     pub fn cleanup_sent_messages(&mut self) {
@@ -51,6 +97,11 @@ impl<P: Send + Sync> ReliableSender<P> {
         }
     }
 
+    /// Returns the index which will be given to the next Message sent
+    pub fn next_message_index(&self) -> MessageIndex {
+        self.next_send_message_index
+    }
+
     pub fn take_next_messages(&mut self) -> VecDeque<(MessageIndex, P)> {
         mem::take(&mut self.outgoing_messages)
     }
@@ -77,6 +128,7 @@ impl<P: Send + Sync> ReliableSender<P> {
                 // replace found message with nothing
                 let container = self.sending_messages.get_mut(index).unwrap();
                 let output = container.take();
+                self.expiries.remove(message_index);
 
                 self.cleanup_sent_messages();
 
@@ -91,9 +143,7 @@ impl<P: Send + Sync> ReliableSender<P> {
 
 impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
     fn send_message(&mut self, message: P) {
-        self.sending_messages
-            .push_back(Some((self.next_send_message_index, None, message)));
-        self.next_send_message_index = self.next_send_message_index.wrapping_add(1);
+        self.send_message_with_ttl(message, self.ttl);
     }
 
     fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
@@ -131,6 +181,7 @@ impl MessageChannelSender for ReliableSender<MessageContainer> {
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
+        bit_budget: u32,
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>> {
         IndexedMessageWriter::write_messages(
@@ -138,7 +189,45 @@ impl MessageChannelSender for ReliableSender<MessageContainer> {
             &mut self.outgoing_messages,
             converter,
             writer,
+            bit_budget,
             has_written,
         )
     }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        Some(self.next_send_message_index)
+    }
+
+    fn send_message_with_ttl(&mut self, message: MessageContainer, ttl: Option<Duration>) {
+        ReliableSender::send_message_with_ttl(self, message, ttl);
+    }
+
+    fn expire_messages(&mut self, now: &Instant) -> Vec<MessageIndex> {
+        let expired: HashSet<MessageIndex> = self
+            .expiries
+            .iter()
+            .filter(|(_, expiry)| **expiry <= *now)
+            .map(|(message_index, _)| *message_index)
+            .collect();
+        if expired.is_empty() {
+            return Vec::new();
+        }
+        self.replace_messages(
+            |message_index, _| expired.contains(message_index),
+            expired_message,
+        )
+    }
+
+    fn supersede_messages(&mut self, message_kind: &MessageKind) -> Vec<MessageIndex> {
+        self.replace_messages(
+            |_, message| {
+                !message.is_fragment() && !message.is_frame() && message.kind() == *message_kind
+            },
+            expired_message,
+        )
+    }
+}
+
+fn expired_message() -> MessageContainer {
+    MessageContainer::from_write(Box::new(ExpiredMessage), &mut FakeEntityConverter)
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_serde::BitWriter;
 use naia_socket_shared::Instant;
@@ -10,7 +10,7 @@ use crate::{
             indexed_message_writer::IndexedMessageWriter,
         },
         message_container::MessageContainer,
-        message_kinds::MessageKinds,
+        message_kinds::{MessageKind, MessageKinds},
     },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut,
@@ -60,6 +60,7 @@ impl MessageChannelSender for SequencedUnreliableSender {
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
+        bit_budget: u32,
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>> {
         IndexedMessageWriter::write_messages(
@@ -67,7 +68,26 @@ impl MessageChannelSender for SequencedUnreliableSender {
             &mut self.outgoing_messages,
             converter,
             writer,
+            bit_budget,
             has_written,
         )
     }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        // Messages over an unreliable channel are not acknowledged
+        None
+    }
+
+    fn send_message_with_ttl(&mut self, message: MessageContainer, _: Option<Duration>) {
+        // unreliable Messages are never retried, so have no need of a TTL
+        self.send_message(message);
+    }
+
+    fn expire_messages(&mut self, _: &Instant) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+
+    fn supersede_messages(&mut self, _: &MessageKind) -> Vec<MessageIndex> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,298 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use naia_serde::BitWriter;
+use naia_socket_shared::Instant;
+
+use crate::{
+    constants::FRAGMENTATION_LIMIT_BITS,
+    messages::{
+        channels::{
+            channel::StreamSettings,
+            senders::{
+                channel_sender::{ChannelSender, MessageChannelSender},
+                reliable_sender::ReliableSender,
+            },
+        },
+        message_container::MessageContainer,
+        message_kinds::{MessageKind, MessageKinds},
+        stream::{
+            StreamAction, StreamFrame, StreamId, StreamOptions, StreamProgress, StreamUpdate,
+        },
+    },
+    types::MessageIndex,
+    FakeEntityConverter, LocalEntityAndGlobalEntityConverterMut,
+};
+
+enum FrameInFlight {
+    Data(u64),
+    Finish,
+}
+
+struct OutgoingStream {
+    id: StreamId,
+    pending: VecDeque<u8>,
+    finishing: bool,
+    finish_sent: bool,
+    finish_delivered: bool,
+    progress: StreamProgress,
+}
+
+/// Splits the bytes written to outgoing Streams into chunks, and feeds them
+/// into a reliable sender no faster than the remote host acknowledges them
+pub struct StreamSender {
+    reliable_sender: ReliableSender<MessageContainer>,
+    chunk_size: usize,
+    max_chunks_in_flight: usize,
+    next_stream_id: StreamId,
+    streams: Vec<OutgoingStream>,
+    next_stream_index: usize,
+    frames_in_flight: HashMap<MessageIndex, (StreamId, FrameInFlight)>,
+    updates: Vec<StreamUpdate>,
+}
+
+impl StreamSender {
+    pub fn new(settings: &StreamSettings) -> Self {
+        Self {
+            reliable_sender: ReliableSender::new(settings.rtt_resend_factor),
+            chunk_size: settings.chunk_size,
+            max_chunks_in_flight: settings.max_chunks_in_flight,
+            next_stream_id: StreamId::zero(),
+            streams: Vec::new(),
+            next_stream_index: 0,
+            frames_in_flight: HashMap::new(),
+            updates: Vec::new(),
+        }
+    }
+
+    pub fn open_stream(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        metadata: MessageContainer,
+        options: StreamOptions,
+    ) -> StreamId {
+        if metadata.bit_length() > FRAGMENTATION_LIMIT_BITS {
+            panic!("Stream metadata Message `{}` is above the fragmentation size limit! Slim down the metadata, and send the rest of it through the Stream itself.", metadata.name());
+        }
+        let mut writer = BitWriter::with_capacity(FRAGMENTATION_LIMIT_BITS);
+        metadata.write(message_kinds, &mut writer, converter);
+        let metadata = writer.to_bytes().to_vec();
+
+        // once the id wraps around, skip over any still held by a long-lived
+        // Stream
+        while self
+            .streams
+            .iter()
+            .any(|stream| stream.id == self.next_stream_id)
+        {
+            self.next_stream_id.increment();
+        }
+        let id = self.next_stream_id;
+        self.next_stream_id.increment();
+
+        self.send_frame(
+            id,
+            StreamAction::Open {
+                metadata,
+                offset: options.offset,
+                length: options.length,
+                content_hash: options.content_hash,
+            },
+        );
+        self.streams.push(OutgoingStream {
+            id,
+            pending: VecDeque::new(),
+            finishing: false,
+            finish_sent: false,
+            finish_delivered: false,
+            progress: StreamProgress {
+                written: 0,
+                sent: 0,
+                acknowledged: 0,
+            },
+        });
+
+        id
+    }
+
+    pub fn write_stream(&mut self, id: &StreamId, bytes: &[u8]) {
+        let Some(stream) = self.stream_mut(id) else {
+            return;
+        };
+        if stream.finishing {
+            panic!("Cannot write to a Stream which has been finished");
+        }
+        stream.pending.extend(bytes);
+        stream.progress.written += bytes.len() as u64;
+    }
+
+    pub fn finish_stream(&mut self, id: &StreamId) {
+        if let Some(stream) = self.stream_mut(id) {
+            stream.finishing = true;
+        }
+    }
+
+    pub fn cancel_stream(&mut self, id: &StreamId) {
+        if self.remove_stream(id) {
+            self.send_frame(*id, StreamAction::Cancel);
+        }
+    }
+
+    /// Tells the remote host that we will not read an incoming Stream
+    pub fn reject_stream(&mut self, id: &StreamId) {
+        self.send_frame(*id, StreamAction::Reject);
+    }
+
+    /// Called when the remote host has rejected an outgoing Stream
+    pub fn receive_rejection(&mut self, id: &StreamId) {
+        if self.remove_stream(id) {
+            self.updates.push(StreamUpdate::Rejected { id: *id });
+        }
+    }
+
+    pub fn progress(&self, id: &StreamId) -> Option<StreamProgress> {
+        self.streams
+            .iter()
+            .find(|stream| stream.id == *id)
+            .map(|stream| stream.progress)
+    }
+
+    pub fn take_updates(&mut self) -> Vec<StreamUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+
+    fn stream_mut(&mut self, id: &StreamId) -> Option<&mut OutgoingStream> {
+        self.streams.iter_mut().find(|stream| stream.id == *id)
+    }
+
+    fn remove_stream(&mut self, id: &StreamId) -> bool {
+        let Some(index) = self.streams.iter().position(|stream| stream.id == *id) else {
+            return false;
+        };
+        self.streams.remove(index);
+        if self.next_stream_index > index {
+            self.next_stream_index -= 1;
+        }
+        true
+    }
+
+    fn send_frame(&mut self, id: StreamId, action: StreamAction) -> MessageIndex {
+        let message_index = self.reliable_sender.next_message_index();
+        let frame = StreamFrame::new(id, action);
+        let message = MessageContainer::from_write(Box::new(frame), &mut FakeEntityConverter);
+        self.reliable_sender.send_message(message);
+        message_index
+    }
+
+    // Queues chunks from each Stream in turn, until the window is full or
+    // there is nothing left to send
+    fn fill_window(&mut self) {
+        loop {
+            if self.frames_in_flight.len() >= self.max_chunks_in_flight {
+                return;
+            }
+
+            let stream_count = self.streams.len();
+            let Some(offset) = (0..stream_count).find(|offset| {
+                let stream = &self.streams[(self.next_stream_index + offset) % stream_count];
+                !stream.pending.is_empty() || (stream.finishing && !stream.finish_sent)
+            }) else {
+                return;
+            };
+            let index = (self.next_stream_index + offset) % stream_count;
+            self.next_stream_index = (index + 1) % stream_count;
+
+            let stream = &mut self.streams[index];
+            let id = stream.id;
+            let (action, frame) = if stream.pending.is_empty() {
+                stream.finish_sent = true;
+                (StreamAction::Finish, FrameInFlight::Finish)
+            } else {
+                let chunk_size = self.chunk_size.min(stream.pending.len());
+                let bytes: Vec<u8> = stream.pending.drain(..chunk_size).collect();
+                stream.progress.sent += bytes.len() as u64;
+                (
+                    StreamAction::Data { bytes },
+                    FrameInFlight::Data(chunk_size as u64),
+                )
+            };
+
+            let message_index = self.send_frame(id, action);
+            self.frames_in_flight.insert(message_index, (id, frame));
+        }
+    }
+}
+
+impl ChannelSender<MessageContainer> for StreamSender {
+    fn send_message(&mut self, _: MessageContainer) {
+        panic!("Messages cannot be sent over a Stream Channel, open a Stream instead");
+    }
+
+    fn collect_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.fill_window();
+        self.reliable_sender.collect_messages(now, rtt_millis);
+    }
+
+    fn has_messages(&self) -> bool {
+        self.reliable_sender.has_messages()
+    }
+
+    fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
+        self.reliable_sender.notify_message_delivered(message_index);
+
+        let Some((id, frame)) = self.frames_in_flight.remove(message_index) else {
+            return;
+        };
+        let Some(stream) = self.stream_mut(&id) else {
+            // Stream has since been cancelled or rejected
+            return;
+        };
+        match frame {
+            FrameInFlight::Data(length) => stream.progress.acknowledged += length,
+            FrameInFlight::Finish => stream.finish_delivered = true,
+        }
+        if stream.finish_delivered && stream.progress.acknowledged == stream.progress.written {
+            self.remove_stream(&id);
+            self.updates.push(StreamUpdate::Delivered { id });
+        }
+    }
+}
+
+impl MessageChannelSender for StreamSender {
+    fn write_messages(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        writer: &mut BitWriter,
+        bit_budget: u32,
+        has_written: &mut bool,
+    ) -> Option<Vec<MessageIndex>> {
+        self.reliable_sender.write_messages(
+            message_kinds,
+            converter,
+            writer,
+            bit_budget,
+            has_written,
+        )
+    }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        // Stream frames are acknowledged through StreamUpdates instead
+        None
+    }
+
+    fn send_message_with_ttl(&mut self, _: MessageContainer, _: Option<Duration>) {
+        panic!("Messages cannot be sent over a Stream Channel, open a Stream instead");
+    }
+
+    fn expire_messages(&mut self, _: &Instant) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+
+    fn supersede_messages(&mut self, _: &MessageKind) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+}
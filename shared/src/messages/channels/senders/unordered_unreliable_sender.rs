@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 use naia_serde::{BitWrite, BitWriter, Serde};
 use naia_socket_shared::Instant;
@@ -7,7 +7,7 @@ use crate::{
     messages::{
         channels::senders::channel_sender::{ChannelSender, MessageChannelSender},
         message_container::MessageContainer,
-        message_kinds::MessageKinds,
+        message_kinds::{MessageKind, MessageKinds},
     },
     types::MessageIndex,
     LocalEntityAndGlobalEntityConverterMut,
@@ -66,8 +66,11 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
+        bit_budget: u32,
         has_written: &mut bool,
     ) -> Option<Vec<MessageIndex>> {
+        let start_bits_free = writer.bits_free();
+        let mut has_written_any = false;
         loop {
             if self.outgoing_messages.is_empty() {
                 break;
@@ -88,7 +91,14 @@ impl MessageChannelSender for UnorderedUnreliableSender {
                 break;
             }
 
+            // stay within the budget, once at least one Message has been written
+            let bits_written = start_bits_free - writer.bits_free();
+            if has_written_any && bits_written + counter.bits_needed() > bit_budget {
+                break;
+            }
+
             *has_written = true;
+            has_written_any = true;
 
             // write MessageContinue bit
             true.ser(writer);
@@ -101,4 +111,22 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         }
         None
     }
+
+    fn delivery_index(&self) -> Option<MessageIndex> {
+        // Messages over an unreliable channel are not acknowledged
+        None
+    }
+
+    fn send_message_with_ttl(&mut self, message: MessageContainer, _: Option<Duration>) {
+        // unreliable Messages are never retried, so have no need of a TTL
+        self.send_message(message);
+    }
+
+    fn expire_messages(&mut self, _: &Instant) -> Vec<MessageIndex> {
+        Vec::new()
+    }
+
+    fn supersede_messages(&mut self, _: &MessageKind) -> Vec<MessageIndex> {
+        Vec::new()
+    }
 }
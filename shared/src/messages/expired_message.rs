@@ -0,0 +1,6 @@
+use naia_derive::MessageInternal;
+
+/// Takes the place of a reliable Message which was given up on before it was
+/// delivered, so that the remote host does not wait on its index forever
+#[derive(MessageInternal)]
+pub struct ExpiredMessage;
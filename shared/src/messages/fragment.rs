@@ -1,10 +1,14 @@
 use naia_derive::MessageFragment;
 use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, UnsignedInteger};
 
+use crate::constants::{FRAGMENTATION_LIMIT_BYTES, FRAGMENTED_MESSAGE_LIMIT_BYTES};
+
 const FRAGMENT_ID_BITS: u8 = 10;
-const FRAGMENT_ID_LIMIT: u16 = 2 ^ (FRAGMENT_ID_BITS as u16);
+const FRAGMENT_ID_LIMIT: u16 = 1 << FRAGMENT_ID_BITS;
 const FRAGMENT_INDEX_BITS: u8 = 20;
-const FRAGMENT_INDEX_LIMIT: u32 = 2 ^ (FRAGMENT_INDEX_BITS as u32);
+/// The most fragments a single Message may be split into
+pub(crate) const FRAGMENT_TOTAL_LIMIT: u32 =
+    FRAGMENTED_MESSAGE_LIMIT_BYTES.div_ceil(FRAGMENTATION_LIMIT_BYTES) as u32;
 
 // FragmentId
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
@@ -61,8 +65,8 @@ impl FragmentIndex {
 
     pub(crate) fn increment(&mut self) {
         self.inner += 1;
-        if self.inner >= FRAGMENT_INDEX_LIMIT {
-            panic!("Attempting to fragment large message, but hit fragment limit of {FRAGMENT_INDEX_LIMIT}. This means you're trying to transmit about 500 megabytes, which is a bad idea.")
+        if self.inner > FRAGMENT_TOTAL_LIMIT {
+            panic!("Attempting to fragment large message, but hit fragment limit of {FRAGMENT_TOTAL_LIMIT}. Messages larger than {FRAGMENTED_MESSAGE_LIMIT_BYTES} bytes cannot be sent.")
         }
     }
 
@@ -0,0 +1,48 @@
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+
+use crate::{messages::message_kinds::MessageKind, Message};
+
+// KeyedMessage
+/// A Message which holds the latest value of some state, identified by its
+/// key. Over a Keyed Channel, a newer value replaces any older one under the
+/// same key which has not yet been delivered.
+pub trait KeyedMessage: Message {
+    type Key: Serde;
+
+    fn key(&self) -> Self::Key;
+}
+
+// MessageKey
+/// The serialized key of a KeyedMessage, along with the kind of the Message,
+/// so that different KeyedMessages sent over the same Channel never replace
+/// each other
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct MessageKey {
+    kind: MessageKind,
+    bytes: Vec<u8>,
+}
+
+impl MessageKey {
+    pub fn of<M: KeyedMessage>(message: &M) -> Self {
+        let mut writer = BitWriter::new();
+        message.key().ser(&mut writer);
+        Self {
+            kind: MessageKind::of::<M>(),
+            bytes: writer.to_bytes().to_vec(),
+        }
+    }
+
+    // only the key itself is written, as the receiver learns the kind from the
+    // Message which follows it
+    pub(crate) fn ser(&self, writer: &mut dyn BitWrite) {
+        self.bytes.ser(writer);
+    }
+
+    pub(crate) fn de_bytes(reader: &mut BitReader) -> Result<Vec<u8>, SerdeErr> {
+        Vec::<u8>::de(reader)
+    }
+
+    pub(crate) fn from_parts(kind: MessageKind, bytes: Vec<u8>) -> Self {
+        Self { kind, bytes }
+    }
+}
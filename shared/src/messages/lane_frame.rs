@@ -0,0 +1,37 @@
+use naia_derive::MessageInternal;
+
+use crate::{messages::message_frame::MessageFrame, types::MessageIndex};
+
+// LaneFrame
+/// Carries a Message sent in a numbered lane of an Ordered Reliable Channel,
+/// tagged with its position among the other Messages of that lane
+#[derive(MessageInternal)]
+pub struct LaneFrame {
+    lane: u16,
+    sequence: MessageIndex,
+    message: Vec<u8>,
+}
+
+impl LaneFrame {
+    pub(crate) fn new(lane: u16, sequence: MessageIndex, message: Vec<u8>) -> Self {
+        Self {
+            lane,
+            sequence,
+            message,
+        }
+    }
+
+    pub(crate) fn lane(&self) -> u16 {
+        self.lane
+    }
+
+    pub(crate) fn sequence(&self) -> MessageIndex {
+        self.sequence
+    }
+}
+
+impl MessageFrame for LaneFrame {
+    fn take_message(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.message)
+    }
+}
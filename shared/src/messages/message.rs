@@ -7,18 +7,23 @@ use crate::{
         message_kinds::{MessageKind, MessageKinds},
         named::Named,
     },
+    schema::FieldSchema,
     world::entity::entity_converters::LocalEntityAndGlobalEntityConverterMut,
     LocalEntity, LocalEntityAndGlobalEntityConverter, MessageContainer,
 };
 
 // MessageBuilder
-pub trait MessageBuilder: Send + Sync {
+pub trait MessageBuilder: Send + Sync + Named {
     /// Create new Message from incoming bit stream
     fn read(
         &self,
         reader: &mut BitReader,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<MessageContainer, SerdeErr>;
+    /// Describes the fields of the Message, used in the Protocol fingerprint
+    fn layout(&self) -> &'static str;
+    /// Describes the fields of the Message, used in the Protocol schema
+    fn fields(&self) -> Vec<FieldSchema>;
 }
 
 // Message
@@ -27,6 +32,13 @@ pub trait Message: Send + Sync + Named + MessageClone + Any {
     fn kind(&self) -> MessageKind;
     fn to_boxed_any(self: Box<Self>) -> Box<dyn Any>;
     fn create_builder() -> Box<dyn MessageBuilder>
+    where
+        Self: Sized;
+    /// The name this type is given its id by in a Protocol: the path of the
+    /// module it is defined in & its own name, which stay the same across
+    /// compilers, unlike `std::any::type_name()`. The arguments of a generic
+    /// type are named by their own `ProtocolName`.
+    fn protocol_name() -> String
     where
         Self: Sized;
     fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32;
@@ -42,8 +54,8 @@ pub trait Message: Send + Sync + Named + MessageClone + Any {
     fn relations_waiting(&self) -> Option<HashSet<LocalEntity>>;
     /// Converts any LocalEntities contained within the Message's EntityProperty fields to GlobalEntities
     fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter);
-    // /// Returns whether has any EntityRelations
-    // fn has_entity_relations(&self) -> bool;
+    /// Returns whether the Message has any EntityProperty fields
+    fn has_entity_relations(&self) -> bool;
     // /// Returns a list of Entities contained within the Message's EntityRelation fields
     // fn entities(&self) -> Vec<GlobalEntity>;
 }
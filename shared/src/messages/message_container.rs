@@ -1,16 +1,27 @@
-use std::{any::Any, collections::HashSet};
+use std::{any::Any, collections::HashSet, sync::Arc};
 
-use naia_serde::BitWrite;
+use naia_serde::{BitWrite, BitWriter};
 
 use crate::{
-    world::entity::entity_converters::LocalEntityAndGlobalEntityConverterMut, LocalEntity,
-    LocalEntityAndGlobalEntityConverter, Message, MessageKind, MessageKinds,
+    messages::message_frame::MessageFrame,
+    world::entity::entity_converters::LocalEntityAndGlobalEntityConverterMut, FakeEntityConverter,
+    LocalEntity, LocalEntityAndGlobalEntityConverter, Message, MessageKind, MessageKinds,
 };
 
 #[derive(Clone)]
 pub struct MessageContainer {
-    inner: Box<dyn Message>,
+    // shared by every clone of the container, so that queueing the same
+    // Message on many connections does not copy it for each
+    inner: Arc<Box<dyn Message>>,
     bit_length: Option<u32>,
+    // the Message already serialized & the number of bits it was written in,
+    // shared by every clone of the container. `bit_length` is only an upper
+    // bound on the latter
+    serialized: Option<(Arc<[u8]>, u32)>,
+    // the Message carried by a frame which was read off the wire
+    framed: Option<Box<MessageContainer>>,
+    // whether this is a frame carrying another Message, written to be sent
+    is_frame: bool,
 }
 
 impl MessageContainer {
@@ -20,18 +31,77 @@ impl MessageContainer {
     ) -> Self {
         let bit_length = message.bit_length(converter);
         Self {
-            inner: message,
+            inner: Arc::new(message),
             bit_length: Some(bit_length),
+            serialized: None,
+            framed: None,
+            is_frame: false,
         }
     }
 
+    /// Serializes a Message once, so that it can be written to many
+    /// connections without serializing it again. The Message must not have
+    /// any EntityProperty fields, as those are converted per connection.
+    pub fn from_write_shared(message: Box<dyn Message>, message_kinds: &MessageKinds) -> Self {
+        if message.has_entity_relations() {
+            panic!(
+                "Cannot share the serialized Message `{}`, as it has EntityProperty fields",
+                message.name()
+            );
+        }
+        let bit_length = message.bit_length(&mut FakeEntityConverter);
+        let mut writer = BitWriter::with_capacity(bit_length);
+        message.write(message_kinds, &mut writer, &mut FakeEntityConverter);
+        let bits_written = bit_length - writer.bits_free();
+        Self {
+            inner: Arc::new(message),
+            bit_length: Some(bit_length),
+            serialized: Some((Arc::from(writer.to_bytes()), bits_written)),
+            framed: None,
+            is_frame: false,
+        }
+    }
+
+    /// Wraps a frame carrying another Message, already serialized, to be
+    /// sent. Frames are never superseded, as the Messages they carry can't be
+    /// told apart by kind.
+    pub(crate) fn from_write_frame(
+        frame: Box<dyn Message>,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> Self {
+        let mut container = Self::from_write(frame, converter);
+        container.is_frame = true;
+        container
+    }
+
     pub fn from_read(message: Box<dyn Message>) -> Self {
         Self {
-            inner: message,
+            inner: Arc::new(message),
             bit_length: None,
+            serialized: None,
+            framed: None,
+            is_frame: false,
         }
     }
 
+    pub(crate) fn from_read_framed(frame: Box<dyn Message>, message: MessageContainer) -> Self {
+        Self {
+            inner: Arc::new(frame),
+            bit_length: None,
+            serialized: None,
+            framed: Some(Box::new(message)),
+            is_frame: true,
+        }
+    }
+
+    /// Splits a frame which was read off the wire into the frame itself &
+    /// the Message it carries. Returns None if this is not such a frame.
+    pub(crate) fn into_frame<F: MessageFrame>(self) -> Option<(Box<F>, MessageContainer)> {
+        let framed = self.framed?;
+        let frame = into_message(self.inner).to_boxed_any().downcast::<F>().ok()?;
+        Some((frame, *framed))
+    }
+
     pub fn name(&self) -> String {
         self.inner.name()
     }
@@ -48,17 +118,32 @@ impl MessageContainer {
     ) {
         if writer.is_counter() {
             writer.write_bits(self.bit_length());
+        } else if let (Some((bytes, bit_length)), false) = (&self.serialized, writer.is_coding()) {
+            // replay the serialized bits, which are stored least significant
+            // first. A coding writer must code them in their own contexts
+            // instead, so it writes the Message anew
+            let full_bytes = (bit_length / 8) as usize;
+            for byte in &bytes[..full_bytes] {
+                writer.write_byte(*byte);
+            }
+            for bit in 0..(bit_length % 8) {
+                writer.write_bit(bytes[full_bytes] & (1 << bit) != 0);
+            }
         } else {
             self.inner.write(message_kinds, writer, converter);
         }
     }
 
+    pub(crate) fn is_frame(&self) -> bool {
+        self.is_frame
+    }
+
     pub fn is_fragment(&self) -> bool {
         return self.inner.is_fragment();
     }
 
     pub fn to_boxed_any(self) -> Box<dyn Any> {
-        return self.inner.to_boxed_any();
+        return into_message(self.inner).to_boxed_any();
     }
 
     pub fn kind(&self) -> MessageKind {
@@ -66,10 +151,29 @@ impl MessageContainer {
     }
 
     pub fn relations_waiting(&self) -> Option<HashSet<LocalEntity>> {
-        return self.inner.relations_waiting();
+        let mut relations = self.inner.relations_waiting();
+        if let Some(framed_relations) = self
+            .framed
+            .as_ref()
+            .and_then(|framed| framed.relations_waiting())
+        {
+            relations
+                .get_or_insert_with(HashSet::new)
+                .extend(framed_relations);
+        }
+        relations
     }
 
     pub fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
-        self.inner.relations_complete(converter);
+        Arc::make_mut(&mut self.inner).relations_complete(converter);
+        if let Some(framed) = &mut self.framed {
+            framed.relations_complete(converter);
+        }
     }
 }
+
+// Takes the Message out of a container, copying it only if a clone of the
+// container still shares it
+fn into_message(inner: Arc<Box<dyn Message>>) -> Box<dyn Message> {
+    Arc::try_unwrap(inner).unwrap_or_else(|inner| (*inner).clone())
+}
@@ -0,0 +1,10 @@
+use crate::Message;
+
+// MessageFrame
+/// A Message which carries another Message, already serialized, e.g. a
+/// Request tagged with its id. The carried Message is read as soon as the frame
+/// is, so that it waits on its entities like any other Message.
+pub(crate) trait MessageFrame: Message {
+    /// Takes the bytes of the carried Message out of the frame
+    fn take_message(&mut self) -> Vec<u8>;
+}
@@ -0,0 +1,16 @@
+/// Identifies a Message sent with `send_tracked_message`, so that its delivery
+/// can be matched up with the `MessageDeliveredEvent` reporting it
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MessageHandle {
+    inner: u32,
+}
+
+impl MessageHandle {
+    pub(crate) fn zero() -> Self {
+        Self { inner: 0 }
+    }
+
+    pub(crate) fn increment(&mut self) {
+        self.inner = self.inner.wrapping_add(1);
+    }
+}
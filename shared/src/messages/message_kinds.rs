@@ -1,11 +1,26 @@
 use std::{any::TypeId, collections::HashMap};
 
-use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, SerdeErrReason};
 
-use crate::{LocalEntityAndGlobalEntityConverter, Message, MessageBuilder, MessageContainer};
+use crate::{
+    messages::message_frame::MessageFrame,
+    protocol::{next_net_id, sort_by_protocol_name, FingerprintHasher, ProtocolError},
+    schema::KindSchema,
+    LocalEntityAndGlobalEntityConverter, Message, MessageBuilder, MessageContainer,
+};
 
 type NetId = u16;
 
+// Splits a frame read off the wire into the frame & the bytes of the Message
+// it carries
+type FrameSplitter = fn(MessageContainer) -> (Box<dyn Message>, Vec<u8>);
+
+fn split_frame<F: MessageFrame>(container: MessageContainer) -> (Box<dyn Message>, Vec<u8>) {
+    let mut frame = container.to_boxed_any().downcast::<F>().unwrap();
+    let bytes = frame.take_message();
+    (frame, bytes)
+}
+
 /// MessageKind - should be one unique value for each type of Message
 #[derive(Eq, Hash, Copy, Clone, PartialEq)]
 pub struct MessageKind {
@@ -33,7 +48,9 @@ impl MessageKind {
 
     pub fn de(message_kinds: &MessageKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(message_kinds.net_id_to_kind(&net_id))
+        message_kinds
+            .net_id_to_kind(&net_id)
+            .ok_or_else(|| SerdeErr::new(SerdeErrReason::UnknownKind(net_id), reader.bit_offset()))
     }
 }
 
@@ -46,28 +63,44 @@ impl ConstBitLength for MessageKind {
 // MessageKinds
 pub struct MessageKinds {
     current_net_id: NetId,
+    protocol_name_map: HashMap<MessageKind, String>,
     kind_map: HashMap<MessageKind, (NetId, Box<dyn MessageBuilder>)>,
     net_id_map: HashMap<NetId, MessageKind>,
+    frame_splitters: HashMap<MessageKind, FrameSplitter>,
 }
 
 impl MessageKinds {
     pub fn new() -> Self {
         Self {
             current_net_id: 0,
+            protocol_name_map: HashMap::new(),
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
+            frame_splitters: HashMap::new(),
         }
     }
 
     pub fn add_message<M: Message>(&mut self) {
         let message_kind = MessageKind::of::<M>();
 
-        let net_id = self.current_net_id;
+        // registering the same Message again keeps its id
+        let net_id = match self.kind_map.get(&message_kind) {
+            Some((net_id, _)) => *net_id,
+            None => next_net_id(&mut self.current_net_id),
+        };
+        self.protocol_name_map
+            .insert(message_kind, M::protocol_name());
         self.kind_map
             .insert(message_kind, (net_id, M::create_builder()));
         self.net_id_map.insert(net_id, message_kind);
-        self.current_net_id += 1;
-        //TODO: check for current_id overflow?
+    }
+
+    /// Registers a Message which carries another, so that the carried
+    /// Message is read along with it
+    pub(crate) fn add_frame<F: MessageFrame>(&mut self) {
+        self.add_message::<F>();
+        self.frame_splitters
+            .insert(MessageKind::of::<F>(), split_frame::<F>);
     }
 
     pub fn read(
@@ -76,13 +109,60 @@ impl MessageKinds {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<MessageContainer, SerdeErr> {
         let message_kind: MessageKind = MessageKind::de(self, reader)?;
-        return self.kind_to_builder(&message_kind).read(reader, converter);
+        let builder = self.kind_to_builder(&message_kind);
+        let message = builder
+            .read(reader, converter)
+            .map_err(|err| err.in_kind(builder.name()))?;
+
+        let Some(split_frame) = self.frame_splitters.get(&message_kind) else {
+            return Ok(message);
+        };
+        let (frame, bytes) = split_frame(message);
+        let framed = reader
+            .read_nested(&bytes, |frame_reader| self.read(frame_reader, converter))
+            .map_err(|err| err.in_kind(builder.name()))?;
+        Ok(MessageContainer::from_read_framed(frame, framed))
+    }
+
+    /// Gives every Message its id in order of protocol name. See
+    /// [`sort_by_protocol_name`]
+    pub(crate) fn assign_net_ids(&mut self) -> Result<(), ProtocolError> {
+        let message_kinds = sort_by_protocol_name(&self.protocol_name_map, "Message")?;
+        self.net_id_map.clear();
+        for (net_id, message_kind) in message_kinds.into_iter().enumerate() {
+            let net_id = net_id as NetId;
+            self.kind_map.get_mut(&message_kind).unwrap().0 = net_id;
+            self.net_id_map.insert(net_id, message_kind);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_u64(self.current_net_id as u64);
+        for net_id in 0..self.current_net_id {
+            let message_kind = self.net_id_map.get(&net_id).unwrap();
+            let builder = self.kind_to_builder(message_kind);
+            hasher.write_str(&builder.name());
+            hasher.write_str(builder.layout());
+        }
+    }
+
+    pub(crate) fn schema(&self) -> Vec<KindSchema> {
+        (0..self.current_net_id)
+            .map(|net_id| {
+                let message_kind = self.net_id_map.get(&net_id).unwrap();
+                let builder = self.kind_to_builder(message_kind);
+                KindSchema {
+                    id: net_id,
+                    name: builder.name(),
+                    fields: builder.fields(),
+                }
+            })
+            .collect()
     }
 
-    fn net_id_to_kind(&self, net_id: &NetId) -> MessageKind {
-        return *self.net_id_map.get(net_id).expect(
-            "Must properly initialize Message with Protocol via `add_message()` function!",
-        );
+    fn net_id_to_kind(&self, net_id: &NetId) -> Option<MessageKind> {
+        return self.net_id_map.get(net_id).copied();
     }
 
     fn kind_to_net_id(&self, message_kind: &MessageKind) -> NetId {
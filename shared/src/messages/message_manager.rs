@@ -1,34 +1,55 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::time::Duration;
 
-use naia_serde::{BitReader, BitWrite, BitWriter, ConstBitLength, Serde, SerdeErr};
+use log::warn;
+use naia_serde::{
+    BitReader, BitWrite, BitWriter, ConstBitLength, HostStrings, RemoteStrings, Serde, SerdeErr,
+    SerdeErrReason,
+};
 use naia_socket_shared::Instant;
 
 use crate::{
-    constants::FRAGMENTATION_LIMIT_BITS,
+    constants::{CHANNEL_QUANTUM_BITS, FRAGMENTATION_LIMIT_BITS},
     messages::{
         channels::{
+            channel::ChannelDirection,
             channel::ChannelMode,
             channel::ChannelSettings,
+            channel::ReliableSettings,
             channel_kinds::{ChannelKind, ChannelKinds},
             receivers::{
-                channel_receiver::MessageChannelReceiver,
-                ordered_reliable_receiver::OrderedReliableReceiver,
+                channel_receiver::MessageChannelReceiver, fec_receiver::FecReceiver,
+                keyed_receiver::KeyedReceiver, ordered_reliable_receiver::OrderedReliableReceiver,
                 sequenced_reliable_receiver::SequencedReliableReceiver,
                 sequenced_unreliable_receiver::SequencedUnreliableReceiver,
+                stream_receiver::StreamReceiver,
                 unordered_reliable_receiver::UnorderedReliableReceiver,
                 unordered_unreliable_receiver::UnorderedUnreliableReceiver,
             },
             senders::{
-                channel_sender::MessageChannelSender, message_fragmenter::MessageFragmenter,
+                channel_sender::{ChannelSender, MessageChannelSender},
+                fec_sender::FecSender,
+                keyed_sender::KeyedSender,
+                message_fragmenter::MessageFragmenter,
                 reliable_sender::ReliableSender,
                 sequenced_unreliable_sender::SequencedUnreliableSender,
+                stream_sender::StreamSender,
                 unordered_unreliable_sender::UnorderedUnreliableSender,
             },
         },
+        expired_message::ExpiredMessage,
+        keyed_message::MessageKey,
+        lane_frame::LaneFrame,
         message_container::MessageContainer,
+        message_handle::MessageHandle,
+        message_kinds::MessageKind,
+        request::{RequestFrame, RequestId},
+        stream::{StreamId, StreamOptions, StreamProgress, StreamUpdate},
+        tick_buffered_frame::TickBufferedFrame,
     },
-    types::{HostType, MessageIndex, PacketIndex},
+    types::{HostType, MessageIndex, PacketIndex, Tick},
     world::{
         entity::entity_converters::LocalEntityAndGlobalEntityConverterMut,
         remote::entity_waitlist::EntityWaitlist,
@@ -42,9 +63,40 @@ use crate::{
 pub struct MessageManager {
     channel_senders: HashMap<ChannelKind, Box<dyn MessageChannelSender>>,
     channel_receivers: HashMap<ChannelKind, Box<dyn MessageChannelReceiver>>,
+    stream_senders: HashMap<ChannelKind, StreamSender>,
+    stream_receivers: HashMap<ChannelKind, StreamReceiver>,
+    keyed_senders: HashMap<ChannelKind, KeyedSender>,
+    // the sequence of the next Message sent in each lane of each Channel
+    lane_sequences: HashMap<(ChannelKind, u16), MessageIndex>,
     channel_settings: HashMap<ChannelKind, ChannelSettings>,
+    // sending Channels grouped by priority, highest priority first
+    priority_tiers: Vec<Vec<ChannelKind>>,
+    channel_deficits: HashMap<ChannelKind, i64>,
     packet_to_message_map: HashMap<PacketIndex, Vec<(ChannelKind, Vec<MessageIndex>)>>,
     message_fragmenter: MessageFragmenter,
+    next_request_id: RequestId,
+    // outgoing Requests awaiting a Response, and when they expire
+    pending_requests: HashMap<RequestId, Instant>,
+    incoming_requests: Vec<(ChannelKind, RequestId, MessageContainer)>,
+    incoming_responses: Vec<(ChannelKind, RequestId, MessageContainer)>,
+    incoming_tick_buffered_messages: Vec<(ChannelKind, Tick, MessageContainer)>,
+    request_timeouts: Vec<RequestId>,
+    next_message_handle: MessageHandle,
+    // the indices each tracked Message (or its fragments) was sent under,
+    // removed as each is acknowledged
+    tracked_messages: HashMap<MessageHandle, HashSet<(ChannelKind, MessageIndex)>>,
+    tracked_indices: HashMap<(ChannelKind, MessageIndex), MessageHandle>,
+    delivered_messages: Vec<MessageHandle>,
+    expired_messages: Vec<MessageHandle>,
+    // Messages sent without a handle which may still be dropped before
+    // delivery, tracked internally so that they can be reported
+    untracked_messages: HashMap<MessageHandle, (ChannelKind, MessageKind)>,
+    dropped_messages: Vec<(ChannelKind, MessageKind)>,
+    // the dictionaries of InternedStrings sent & received over this connection
+    host_strings: HostStrings,
+    remote_strings: RemoteStrings,
+    // the InternedStrings defined in each packet, acknowledged on its delivery
+    packet_to_string_map: HashMap<PacketIndex, Vec<u16>>,
 }
 
 impl MessageManager {
@@ -54,6 +106,7 @@ impl MessageManager {
 
         // initialize senders
         let mut channel_senders = HashMap::<ChannelKind, Box<dyn MessageChannelSender>>::new();
+        let mut keyed_senders = HashMap::new();
         for (channel_kind, channel_settings) in channel_kinds.channels() {
             match &host_type {
                 HostType::Server => {
@@ -82,15 +135,36 @@ impl MessageManager {
                 | ChannelMode::OrderedReliable(settings) => {
                     channel_senders.insert(
                         channel_kind,
-                        Box::new(ReliableSender::<MessageContainer>::new(
-                            settings.rtt_resend_factor,
-                        )),
+                        Box::new(
+                            ReliableSender::<MessageContainer>::new(settings.rtt_resend_factor)
+                                .with_ttl(settings.ttl),
+                        ),
                     );
                 }
                 ChannelMode::TickBuffered(_) => {
-                    // Tick buffered channel uses another manager, skip
+                    if channel_settings.direction == ChannelDirection::ServerToClient {
+                        // Messages are held back by the Client until their Tick
+                        channel_senders.insert(
+                            channel_kind,
+                            Box::new(ReliableSender::<MessageContainer>::new(
+                                ReliableSettings::default().rtt_resend_factor,
+                            )),
+                        );
+                    }
+                    // otherwise, the Client's tick buffer uses another manager
+                }
+                ChannelMode::Stream(_) => {
+                    // Stream channels are initialized below, skip
+                }
+                ChannelMode::Keyed(settings) => {
+                    keyed_senders.insert(channel_kind, KeyedSender::new(settings));
                 }
             };
+
+            if let Some(settings) = &channel_settings.fec {
+                let inner = channel_senders.remove(&channel_kind).unwrap();
+                channel_senders.insert(channel_kind, Box::new(FecSender::new(inner, settings)));
+            }
         }
 
         // initialize receivers
@@ -141,9 +215,35 @@ impl MessageManager {
                     );
                 }
                 ChannelMode::TickBuffered(_) => {
-                    // Tick buffered channel uses another manager, skip
+                    if channel_settings.direction == ChannelDirection::ServerToClient {
+                        channel_receivers
+                            .insert(channel_kind, Box::new(UnorderedReliableReceiver::new()));
+                    }
+                    // otherwise, the Server's tick buffer uses another manager
+                }
+                ChannelMode::Stream(_) => {
+                    // Stream channels are initialized below, skip
+                }
+                ChannelMode::Keyed(_) => {
+                    channel_receivers.insert(channel_kind, Box::new(KeyedReceiver::new()));
                 }
             };
+
+            if channel_settings.fec.is_some() {
+                let inner = channel_receivers.remove(&channel_kind).unwrap();
+                channel_receivers.insert(channel_kind, Box::new(FecReceiver::new(inner)));
+            }
+        }
+
+        // initialize streams, which need a sender & receiver on both hosts,
+        // as rejections travel opposite to the Stream's data
+        let mut stream_senders = HashMap::new();
+        let mut stream_receivers = HashMap::new();
+        for (channel_kind, channel_settings) in channel_kinds.channels() {
+            if let ChannelMode::Stream(settings) = &channel_settings.mode {
+                stream_senders.insert(channel_kind, StreamSender::new(settings));
+                stream_receivers.insert(channel_kind, StreamReceiver::new());
+            }
         }
 
         // initialize settings
@@ -152,12 +252,58 @@ impl MessageManager {
             channel_settings_map.insert(channel_kind.clone(), channel_settings);
         }
 
+        // initialize priority tiers
+        let mut sender_kinds: Vec<(u8, ChannelKind)> = channel_senders
+            .keys()
+            .chain(stream_senders.keys())
+            .chain(keyed_senders.keys())
+            .map(|channel_kind| (channel_settings_map[channel_kind].priority, *channel_kind))
+            .collect();
+        sender_kinds.sort_by_key(|(priority, _)| Reverse(*priority));
+        let mut priority_tiers: Vec<Vec<ChannelKind>> = Vec::new();
+        let mut last_priority = None;
+        for (priority, channel_kind) in sender_kinds {
+            if last_priority != Some(priority) {
+                priority_tiers.push(Vec::new());
+                last_priority = Some(priority);
+            }
+            priority_tiers.last_mut().unwrap().push(channel_kind);
+        }
+        let channel_deficits = channel_senders
+            .keys()
+            .chain(stream_senders.keys())
+            .chain(keyed_senders.keys())
+            .map(|channel_kind| (*channel_kind, 0))
+            .collect();
+
         MessageManager {
             channel_senders,
             channel_receivers,
+            stream_senders,
+            stream_receivers,
+            keyed_senders,
+            lane_sequences: HashMap::new(),
             channel_settings: channel_settings_map,
+            priority_tiers,
+            channel_deficits,
             packet_to_message_map: HashMap::new(),
             message_fragmenter: MessageFragmenter::new(),
+            next_request_id: RequestId::zero(),
+            pending_requests: HashMap::new(),
+            incoming_requests: Vec::new(),
+            incoming_responses: Vec::new(),
+            incoming_tick_buffered_messages: Vec::new(),
+            request_timeouts: Vec::new(),
+            next_message_handle: MessageHandle::zero(),
+            tracked_messages: HashMap::new(),
+            tracked_indices: HashMap::new(),
+            delivered_messages: Vec::new(),
+            expired_messages: Vec::new(),
+            untracked_messages: HashMap::new(),
+            dropped_messages: Vec::new(),
+            host_strings: HostStrings::new(),
+            remote_strings: RemoteStrings::new(),
+            packet_to_string_map: HashMap::new(),
         }
     }
 
@@ -171,15 +317,52 @@ impl MessageManager {
         channel_kind: &ChannelKind,
         message: MessageContainer,
     ) {
+        let can_drop = self
+            .channel_settings
+            .get(channel_kind)
+            .and_then(|settings| settings.mode.reliable_settings())
+            .is_some_and(|settings| settings.ttl.is_some() || settings.supersede);
+        if !can_drop {
+            self.send_message_with_ttl(message_kinds, converter, channel_kind, message, None);
+            return;
+        }
+
+        let message_kind = message.kind();
+        let handle =
+            self.send_tracked_message(message_kinds, converter, channel_kind, message, None);
+        self.untracked_messages
+            .insert(handle, (*channel_kind, message_kind));
+    }
+
+    /// Queues a Message, dropping it if it is still undelivered once `ttl`
+    /// has elapsed. If `ttl` is None, the Channel's own TTL is used.
+    fn send_message_with_ttl(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        ttl: Option<Duration>,
+    ) {
+        if self.stream_senders.contains_key(channel_kind) {
+            panic!("Messages cannot be sent over a Stream Channel, open a Stream instead");
+        }
+        if self.keyed_senders.contains_key(channel_kind) {
+            panic!("Only KeyedMessages can be sent over a Keyed Channel, use `send_keyed_message()` instead");
+        }
+        let Some(settings) = self.channel_settings.get(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
+        let supersede = settings
+            .mode
+            .reliable_settings()
+            .is_some_and(|settings| settings.supersede);
         let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
             panic!("Channel not configured correctly! Cannot send message.");
         };
 
         let message_bit_length = message.bit_length();
         if message_bit_length > FRAGMENTATION_LIMIT_BITS {
-            let Some(settings) = self.channel_settings.get(channel_kind) else {
-                panic!("Channel not configured correctly! Cannot send message.");
-            };
             if !settings.reliable() {
                 panic!("ERROR: Attempting to send Message above the fragmentation size limit over an unreliable Message channel! Slim down the size of your Message, or send this Message through a reliable message channel.");
             }
@@ -189,17 +372,192 @@ impl MessageManager {
                 self.message_fragmenter
                     .fragment_message(message_kinds, converter, message);
             for message_fragment in messages {
-                channel.send_message(message_fragment);
+                Self::send_with_ttl(channel, message_fragment, ttl);
             }
         } else {
-            channel.send_message(message);
+            // replace any older, unacknowledged Messages of the same kind. A
+            // frame's kind says nothing of the Message it carries, so frames
+            // neither supersede nor are superseded
+            let superseded = if supersede && !message.is_frame() {
+                channel.supersede_messages(&message.kind())
+            } else {
+                Vec::new()
+            };
+            Self::send_with_ttl(channel, message, ttl);
+            self.expire_tracked_messages(channel_kind, superseded);
+        }
+    }
+
+    fn send_with_ttl(
+        channel: &mut Box<dyn MessageChannelSender>,
+        message: MessageContainer,
+        ttl: Option<Duration>,
+    ) {
+        match ttl {
+            Some(ttl) => channel.send_message_with_ttl(message, Some(ttl)),
+            None => channel.send_message(message),
+        }
+    }
+
+    /// Queues a Message over a Server to Client Tick Buffered Channel, which
+    /// the Client will receive once it reaches the given server Tick
+    pub fn send_tick_buffered_message(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        tick: &Tick,
+        message: MessageContainer,
+    ) {
+        let Some(settings) = self.channel_settings.get(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
+        if !settings.tick_buffered() {
+            panic!("Channel is not configured for Tick Buffered Messages");
+        }
+        let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
+        if message.bit_length() > FRAGMENTATION_LIMIT_BITS {
+            panic!("Tick Buffered Message `{}` is above the fragmentation size limit! Slim down the size of your Message.", message.name());
+        }
+        let mut writer = BitWriter::with_capacity(FRAGMENTATION_LIMIT_BITS);
+        message.write(message_kinds, &mut writer, converter);
+        let bytes = writer.to_bytes().to_vec();
+
+        let frame = TickBufferedFrame::new(*tick, bytes);
+        channel.send_message(MessageContainer::from_write_frame(
+            Box::new(frame),
+            converter,
+        ));
+    }
+
+    /// Queues a Message in a numbered lane of an Ordered Reliable Channel.
+    /// Messages are delivered in order within their lane, so a lost Message
+    /// only holds back later Messages of the same lane. Messages sent outside
+    /// of any lane still wait on every Message sent before them. The Channel
+    /// must not have a TTL, as a lane would wait forever on an expired Message.
+    pub fn send_message_in_lane(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        lane: u16,
+        message: MessageContainer,
+    ) {
+        let Some(settings) = self.channel_settings.get(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
+        let ChannelMode::OrderedReliable(reliable_settings) = &settings.mode else {
+            panic!("Lanes can only be used on an Ordered Reliable Channel");
+        };
+        if reliable_settings.ttl.is_some() {
+            panic!("Lanes cannot be used on a Channel with a TTL, as a lane would wait forever on an expired Message");
+        }
+        let Some(channel) = self.channel_senders.get_mut(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send message.");
+        };
+        if message.bit_length() > FRAGMENTATION_LIMIT_BITS {
+            panic!("Message `{}` sent in a lane is above the fragmentation size limit! Slim down the size of your Message.", message.name());
         }
+        let mut writer = BitWriter::with_capacity(FRAGMENTATION_LIMIT_BITS);
+        message.write(message_kinds, &mut writer, converter);
+        let bytes = writer.to_bytes().to_vec();
+
+        let next_sequence = self
+            .lane_sequences
+            .entry((*channel_kind, lane))
+            .or_insert(0);
+        let sequence = *next_sequence;
+        *next_sequence = next_sequence.wrapping_add(1);
+
+        let frame = LaneFrame::new(lane, sequence, bytes);
+        channel.send_message(MessageContainer::from_write_frame(
+            Box::new(frame),
+            converter,
+        ));
+    }
+
+    /// Queues a KeyedMessage over a Keyed Channel, replacing any older value
+    /// under the same key which has not yet been delivered
+    pub fn send_keyed_message(
+        &mut self,
+        channel_kind: &ChannelKind,
+        key: MessageKey,
+        message: MessageContainer,
+    ) {
+        let Some(channel) = self.keyed_senders.get_mut(channel_kind) else {
+            panic!("KeyedMessages can only be sent over a Keyed Channel");
+        };
+        if message.bit_length() > FRAGMENTATION_LIMIT_BITS {
+            panic!("ERROR: Attempting to send KeyedMessage `{}` above the fragmentation size limit! Keyed Channels do not fragment Messages, slim down the size of your Message.", message.name());
+        }
+        channel.send_keyed_message(key, message);
+    }
+
+    /// Queues a Message over a reliable Channel, and returns a handle which
+    /// will be reported through `receive_delivered_messages` once the remote
+    /// host has acknowledged it
+    pub fn send_tracked_message(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        message: MessageContainer,
+        ttl: Option<Duration>,
+    ) -> MessageHandle {
+        let Some(first_index) = self
+            .channel_senders
+            .get(channel_kind)
+            .and_then(|channel| channel.delivery_index())
+        else {
+            panic!("Only Messages sent over a reliable Channel can be tracked");
+        };
+
+        self.send_message_with_ttl(message_kinds, converter, channel_kind, message, ttl);
+
+        // a fragmented Message is sent under several consecutive indices
+        let end_index = self.channel_senders[channel_kind].delivery_index().unwrap();
+        let handle = self.next_message_handle;
+        self.next_message_handle.increment();
+
+        let mut indices = HashSet::new();
+        let mut message_index = first_index;
+        while message_index != end_index {
+            indices.insert((*channel_kind, message_index));
+            self.tracked_indices
+                .insert((*channel_kind, message_index), handle);
+            message_index = message_index.wrapping_add(1);
+        }
+        self.tracked_messages.insert(handle, indices);
+
+        handle
     }
 
     pub fn collect_outgoing_messages(&mut self, now: &Instant, rtt_millis: &f32) {
-        for channel in self.channel_senders.values_mut() {
+        let mut expired = Vec::new();
+        for (channel_kind, channel) in &mut self.channel_senders {
+            expired.push((*channel_kind, channel.expire_messages(now)));
+            channel.collect_messages(now, rtt_millis);
+        }
+        for (channel_kind, indices) in expired {
+            self.expire_tracked_messages(&channel_kind, indices);
+        }
+        for channel in self.stream_senders.values_mut() {
+            channel.collect_messages(now, rtt_millis);
+        }
+        for channel in self.keyed_senders.values_mut() {
             channel.collect_messages(now, rtt_millis);
         }
+
+        let request_timeouts = &mut self.request_timeouts;
+        self.pending_requests.retain(|id, expiry| {
+            if *expiry > *now {
+                return true;
+            }
+            request_timeouts.push(*id);
+            false
+        });
     }
 
     /// Returns whether the Manager has queued Messages that can be transmitted
@@ -210,9 +568,24 @@ impl MessageManager {
                 return true;
             }
         }
+        for channel in self.stream_senders.values() {
+            if channel.has_messages() {
+                return true;
+            }
+        }
+        for channel in self.keyed_senders.values() {
+            if channel.has_messages() {
+                return true;
+            }
+        }
         false
     }
 
+    /// Writes queued Messages into the packet. Channels are visited from
+    /// highest to lowest priority, and Channels of equal priority share the
+    /// remaining space by deficit round robin, in proportion to their weight.
+    /// InternedStrings are written by index once their definition has been
+    /// delivered.
     pub fn write_messages(
         &mut self,
         protocol: &Protocol,
@@ -221,54 +594,153 @@ impl MessageManager {
         packet_index: PacketIndex,
         has_written: &mut bool,
     ) {
-        for (channel_kind, channel) in &mut self.channel_senders {
-            if !channel.has_messages() {
-                continue;
-            }
+        writer.attach_strings(std::mem::take(&mut self.host_strings));
+        self.write_channel_messages(protocol, converter, writer, packet_index, has_written);
+        self.host_strings = writer.detach_strings().unwrap();
 
-            // check that we can at least write a ChannelIndex and a MessageContinue bit
-            let mut counter = writer.counter();
-            counter.write_bits(<ChannelKind as ConstBitLength>::const_bit_length());
-            counter.write_bit(false);
+        // a packet index is only reused long after its packet has been lost,
+        // so any definitions still recorded under it were never received
+        let defined_strings = self.host_strings.take_written();
+        if defined_strings.is_empty() {
+            self.packet_to_string_map.remove(&packet_index);
+        } else {
+            self.packet_to_string_map
+                .insert(packet_index, defined_strings);
+        }
+    }
 
-            if counter.overflowed() {
-                break;
-            }
+    fn write_channel_messages(
+        &mut self,
+        protocol: &Protocol,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        writer: &mut BitWriter,
+        packet_index: PacketIndex,
+        has_written: &mut bool,
+    ) {
+        // unused share does not carry over into a new packet, only overdrafts do
+        for deficit in self.channel_deficits.values_mut() {
+            *deficit = (*deficit).min(0);
+        }
 
-            // write ChannelContinue bit
-            true.ser(writer);
+        for tier in &self.priority_tiers {
+            // Channels which could not fit their next Message into this packet
+            let mut stalled_channels = HashSet::new();
 
-            // reserve MessageContinue bit
-            writer.reserve_bits(1);
+            loop {
+                let mut has_active_channel = false;
 
-            // write ChannelIndex
-            channel_kind.ser(&protocol.channel_kinds, writer);
+                for channel_kind in tier {
+                    let channel: &mut dyn MessageChannelSender =
+                        if let Some(channel) = self.channel_senders.get_mut(channel_kind) {
+                            channel.as_mut()
+                        } else if let Some(channel) = self.stream_senders.get_mut(channel_kind) {
+                            channel
+                        } else {
+                            self.keyed_senders.get_mut(channel_kind).unwrap()
+                        };
+                    if !channel.has_messages() || stalled_channels.contains(channel_kind) {
+                        continue;
+                    }
+                    has_active_channel = true;
 
-            // write Messages
-            if let Some(message_indices) =
-                channel.write_messages(&protocol.message_kinds, converter, writer, has_written)
-            {
-                self.packet_to_message_map
-                    .entry(packet_index)
-                    .or_insert_with(Vec::new);
-                let channel_list = self.packet_to_message_map.get_mut(&packet_index).unwrap();
-                channel_list.push((channel_kind.clone(), message_indices));
-            }
+                    // grant this Channel its quantum
+                    let weight = self.channel_settings[channel_kind].weight;
+                    let deficit = self.channel_deficits.get_mut(channel_kind).unwrap();
+                    *deficit += (weight as i64) * (CHANNEL_QUANTUM_BITS as i64);
+                    if *deficit <= 0 {
+                        // still paying off a previous oversized turn
+                        continue;
+                    }
+
+                    // check that we can at least write a ChannelContinue bit, a ChannelIndex
+                    // and a MessageContinue bit
+                    let mut counter = writer.counter();
+                    counter.write_bit(true);
+                    counter.write_bits(<ChannelKind as ConstBitLength>::const_bit_length());
+                    counter.write_bit(false);
+
+                    if counter.overflowed() {
+                        return;
+                    }
+
+                    let bits_free_before_channel = writer.bits_free();
 
-            // write MessageContinue finish bit, release
-            writer.release_bits(1);
-            false.ser(writer);
+                    // write ChannelContinue bit
+                    true.ser(writer);
+
+                    // reserve MessageContinue bit
+                    writer.reserve_bits(1);
+
+                    // write ChannelIndex
+                    channel_kind.ser(&protocol.channel_kinds, writer);
+
+                    // write Messages
+                    let bits_free_before_messages = writer.bits_free();
+                    if let Some(message_indices) = channel.write_messages(
+                        &protocol.message_kinds,
+                        converter,
+                        writer,
+                        *deficit as u32,
+                        has_written,
+                    ) {
+                        self.packet_to_message_map
+                            .entry(packet_index)
+                            .or_insert_with(Vec::new);
+                        let channel_list =
+                            self.packet_to_message_map.get_mut(&packet_index).unwrap();
+                        channel_list.push((channel_kind.clone(), message_indices));
+                    }
+                    let wrote_messages = writer.bits_free() != bits_free_before_messages;
+
+                    // write MessageContinue finish bit, release
+                    writer.release_bits(1);
+                    false.ser(writer);
+
+                    *deficit -= (bits_free_before_channel - writer.bits_free()) as i64;
+
+                    if !channel.has_messages() {
+                        // an idle Channel does not bank its unused share
+                        *deficit = 0;
+                    } else if !wrote_messages {
+                        // the next Message does not fit, wait for the next packet
+                        stalled_channels.insert(*channel_kind);
+                    } else if self.channel_settings[channel_kind].fec.is_some() {
+                        // a lost packet may only cost each FEC group one chunk
+                        stalled_channels.insert(*channel_kind);
+                    }
+                }
+
+                if !has_active_channel {
+                    break;
+                }
+            }
         }
     }
 
     // Incoming Messages
 
+    /// Reads the Messages in a packet. A packet must be read before any packet
+    /// sent after its delivery was acknowledged, so that InternedStrings are
+    /// defined before their index is read.
     pub fn read_messages(
         &mut self,
         protocol: &Protocol,
         entity_waitlist: &mut EntityWaitlist,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
         reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        reader.attach_strings(std::mem::take(&mut self.remote_strings));
+        let result = self.read_channel_messages(protocol, entity_waitlist, converter, reader);
+        self.remote_strings = reader.detach_strings().unwrap();
+        result
+    }
+
+    fn read_channel_messages(
+        &mut self,
+        protocol: &Protocol,
+        entity_waitlist: &mut EntityWaitlist,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        reader: &mut BitReader,
     ) -> Result<(), SerdeErr> {
         loop {
             let message_continue = bool::de(reader)?;
@@ -280,8 +752,26 @@ impl MessageManager {
             let channel_kind = ChannelKind::de(&protocol.channel_kinds, reader)?;
 
             // continue read inside channel
-            let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
+            if let Some(channel) = self.channel_receivers.get_mut(&channel_kind) {
+                channel.read_messages(
+                    &protocol.message_kinds,
+                    entity_waitlist,
+                    converter,
+                    reader,
+                )?;
+                continue;
+            }
+            let Some(channel) = self.stream_receivers.get_mut(&channel_kind) else {
+                return Err(SerdeErr::new(
+                    SerdeErrReason::Other("channel is not configured to receive messages".into()),
+                    reader.bit_offset(),
+                ));
+            };
             channel.read_messages(&protocol.message_kinds, entity_waitlist, converter, reader)?;
+            let stream_sender = self.stream_senders.get_mut(&channel_kind).unwrap();
+            for stream_id in channel.take_rejections() {
+                stream_sender.receive_rejection(&stream_id);
+            }
         }
 
         Ok(())
@@ -297,22 +787,278 @@ impl MessageManager {
         let entity_converter =
             EntityConverter::new(global_entity_converter, local_entity_converter);
         let mut output = Vec::new();
-        // TODO: shouldn't we have a priority mechanisms between channels?
+        let request_frame_kind = MessageKind::of::<RequestFrame>();
+        let expired_message_kind = MessageKind::of::<ExpiredMessage>();
         for (channel_kind, channel) in &mut self.channel_receivers {
-            let messages = channel.receive_messages(entity_waitlist, &entity_converter);
+            let mut messages = channel.receive_messages(entity_waitlist, &entity_converter);
+            // placeholders for Messages the sender gave up on carry nothing
+            messages.retain(|message| message.kind() != expired_message_kind);
+            if self.channel_settings[channel_kind].tick_buffered() {
+                for frame in messages {
+                    let Some((frame, message)) = frame.into_frame::<TickBufferedFrame>() else {
+                        warn!(
+                            "Dropping a Message sent over a Tick Buffered Channel without a Tick"
+                        );
+                        continue;
+                    };
+                    self.incoming_tick_buffered_messages.push((
+                        *channel_kind,
+                        frame.tick(),
+                        message,
+                    ));
+                }
+                continue;
+            }
+            if messages
+                .iter()
+                .any(|message| message.kind() == request_frame_kind)
+            {
+                let (frames, rest) = messages
+                    .into_iter()
+                    .partition(|message| message.kind() == request_frame_kind);
+                messages = rest;
+                for frame in frames {
+                    let (frame, message) = frame.into_frame::<RequestFrame>().unwrap();
+                    let id = frame.id();
+                    if !frame.is_response() {
+                        self.incoming_requests.push((*channel_kind, id, message));
+                    } else if self.pending_requests.remove(&id).is_some() {
+                        self.incoming_responses.push((*channel_kind, id, message));
+                    }
+                    // otherwise the Request has already timed out
+                }
+            }
             output.push((channel_kind.clone(), messages));
         }
+        // hand Messages from higher priority Channels to the application first
+        output
+            .sort_by_key(|(channel_kind, _)| Reverse(self.channel_settings[channel_kind].priority));
         output
     }
+
+    /// Retrieve all Messages received over Tick Buffered Channels, along with
+    /// the Tick at which each should be handed to the application
+    pub fn receive_tick_buffered_messages(&mut self) -> Vec<(ChannelKind, Tick, MessageContainer)> {
+        std::mem::take(&mut self.incoming_tick_buffered_messages)
+    }
+
+    /// Retrieve all Requests received from the remote host
+    pub fn receive_requests(&mut self) -> Vec<(ChannelKind, RequestId, MessageContainer)> {
+        std::mem::take(&mut self.incoming_requests)
+    }
+
+    /// Retrieve all Responses to Requests sent to the remote host
+    pub fn receive_responses(&mut self) -> Vec<(ChannelKind, RequestId, MessageContainer)> {
+        std::mem::take(&mut self.incoming_responses)
+    }
+
+    /// Retrieve all Requests sent to the remote host which have gone unanswered
+    /// for longer than their timeout
+    pub fn receive_request_timeouts(&mut self) -> Vec<RequestId> {
+        std::mem::take(&mut self.request_timeouts)
+    }
+
+    /// Retrieve all tracked Messages which the remote host has acknowledged
+    pub fn receive_delivered_messages(&mut self) -> Vec<MessageHandle> {
+        std::mem::take(&mut self.delivered_messages)
+    }
+
+    /// Retrieve all tracked Messages which were dropped before delivery, either
+    /// because their time-to-live elapsed or a newer Message superseded them
+    pub fn receive_expired_messages(&mut self) -> Vec<MessageHandle> {
+        std::mem::take(&mut self.expired_messages)
+    }
+
+    /// Retrieve the Channel and kind of each Message sent without a handle
+    /// which was dropped before delivery, either because its time-to-live
+    /// elapsed or a newer Message superseded it
+    pub fn receive_dropped_messages(&mut self) -> Vec<(ChannelKind, MessageKind)> {
+        std::mem::take(&mut self.dropped_messages)
+    }
+
+    /// Retrieve all tracked Messages still awaiting acknowledgement, and stop
+    /// tracking them. Used when the Connection closes, as these will never
+    /// be delivered.
+    pub fn take_undelivered_messages(&mut self) -> Vec<MessageHandle> {
+        self.tracked_indices.clear();
+        let untracked_messages = std::mem::take(&mut self.untracked_messages);
+        self.tracked_messages
+            .drain()
+            .map(|(handle, _)| handle)
+            .filter(|handle| !untracked_messages.contains_key(handle))
+            .collect()
+    }
+
+    /// Retrieve all updates to incoming & outgoing Streams
+    pub fn receive_stream_updates(&mut self) -> Vec<(ChannelKind, StreamUpdate)> {
+        let mut output = Vec::new();
+        for (channel_kind, channel) in &mut self.stream_senders {
+            for update in channel.take_updates() {
+                output.push((*channel_kind, update));
+            }
+        }
+        for (channel_kind, channel) in &mut self.stream_receivers {
+            for update in channel.take_updates() {
+                output.push((*channel_kind, update));
+            }
+        }
+        output
+    }
+
+    // Requests
+
+    /// Sends a Request over a bidirectional, reliable Channel. Returns the id
+    /// which the Response will be matched against.
+    pub fn send_request(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        request: MessageContainer,
+        timeout: &Duration,
+    ) -> RequestId {
+        let Some(settings) = self.channel_settings.get(channel_kind) else {
+            panic!("Channel not configured correctly! Cannot send request.");
+        };
+        if !settings.reliable() || settings.direction != ChannelDirection::Bidirectional {
+            panic!("Requests can only be sent over a bidirectional, reliable Channel, so that the Response can return the same way");
+        }
+
+        let id = self.next_request_id;
+        self.next_request_id.increment();
+
+        let mut expiry = Instant::now();
+        expiry.add_millis(timeout.as_millis() as u32);
+        self.pending_requests.insert(id, expiry);
+
+        self.send_request_frame(message_kinds, converter, channel_kind, id, false, request);
+        id
+    }
+
+    /// Sends a Response to a Request received from the remote host
+    pub fn send_response(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        id: &RequestId,
+        response: MessageContainer,
+    ) {
+        self.send_request_frame(message_kinds, converter, channel_kind, *id, true, response);
+    }
+
+    fn send_request_frame(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        id: RequestId,
+        is_response: bool,
+        message: MessageContainer,
+    ) {
+        if message.bit_length() > FRAGMENTATION_LIMIT_BITS {
+            panic!("Request or Response `{}` is above the fragmentation size limit! Slim down its size, or send large payloads through a Stream.", message.name());
+        }
+        let mut writer = BitWriter::with_capacity(FRAGMENTATION_LIMIT_BITS);
+        message.write(message_kinds, &mut writer, converter);
+        let bytes = writer.to_bytes().to_vec();
+
+        let frame = RequestFrame::new(id, is_response, bytes);
+        let frame = MessageContainer::from_write_frame(Box::new(frame), converter);
+        self.send_message(message_kinds, converter, channel_kind, frame);
+    }
+
+    // Streams
+
+    /// Opens a Stream over a Channel configured with `ChannelMode::Stream`
+    pub fn open_stream(
+        &mut self,
+        message_kinds: &MessageKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        channel_kind: &ChannelKind,
+        metadata: MessageContainer,
+        options: StreamOptions,
+    ) -> StreamId {
+        self.stream_sender(channel_kind)
+            .open_stream(message_kinds, converter, metadata, options)
+    }
+
+    /// Queues bytes to be sent over an open Stream
+    pub fn write_stream(&mut self, channel_kind: &ChannelKind, id: &StreamId, bytes: &[u8]) {
+        self.stream_sender(channel_kind).write_stream(id, bytes);
+    }
+
+    /// Marks an outgoing Stream as complete, once all bytes written so far are sent
+    pub fn finish_stream(&mut self, channel_kind: &ChannelKind, id: &StreamId) {
+        self.stream_sender(channel_kind).finish_stream(id);
+    }
+
+    /// Abandons an outgoing Stream
+    pub fn cancel_stream(&mut self, channel_kind: &ChannelKind, id: &StreamId) {
+        self.stream_sender(channel_kind).cancel_stream(id);
+    }
+
+    /// Stops reading an incoming Stream, and asks the remote host to stop sending it
+    pub fn reject_stream(&mut self, channel_kind: &ChannelKind, id: &StreamId) {
+        let Some(receiver) = self.stream_receivers.get_mut(channel_kind) else {
+            panic!("Channel is not configured as a Stream Channel!");
+        };
+        receiver.reject_stream(id);
+        self.stream_sender(channel_kind).reject_stream(id);
+    }
+
+    /// Returns the progress of an outgoing Stream, or None if it is no longer open
+    pub fn stream_progress(
+        &self,
+        channel_kind: &ChannelKind,
+        id: &StreamId,
+    ) -> Option<StreamProgress> {
+        let Some(sender) = self.stream_senders.get(channel_kind) else {
+            panic!("Channel is not configured as a Stream Channel!");
+        };
+        sender.progress(id)
+    }
+
+    fn stream_sender(&mut self, channel_kind: &ChannelKind) -> &mut StreamSender {
+        let Some(sender) = self.stream_senders.get_mut(channel_kind) else {
+            panic!("Channel is not configured as a Stream Channel!");
+        };
+        sender
+    }
 }
 
 impl MessageManager {
     /// Occurs when a packet has been notified as delivered. Stops tracking the
     /// status of Messages in that packet.
     pub fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        if let Some(defined_strings) = self.packet_to_string_map.remove(&packet_index) {
+            self.host_strings.acknowledge(&defined_strings);
+        }
+
         if let Some(channel_list) = self.packet_to_message_map.get(&packet_index) {
             for (channel_kind, message_indices) in channel_list {
                 if let Some(channel) = self.channel_senders.get_mut(channel_kind) {
+                    for message_index in message_indices {
+                        channel.notify_message_delivered(message_index);
+
+                        let key = (*channel_kind, *message_index);
+                        let Some(handle) = self.tracked_indices.remove(&key) else {
+                            continue;
+                        };
+                        let indices = self.tracked_messages.get_mut(&handle).unwrap();
+                        indices.remove(&key);
+                        if indices.is_empty() {
+                            self.tracked_messages.remove(&handle);
+                            if self.untracked_messages.remove(&handle).is_none() {
+                                self.delivered_messages.push(handle);
+                            }
+                        }
+                    }
+                } else if let Some(channel) = self.stream_senders.get_mut(channel_kind) {
+                    for message_index in message_indices {
+                        channel.notify_message_delivered(message_index);
+                    }
+                } else if let Some(channel) = self.keyed_senders.get_mut(channel_kind) {
                     for message_index in message_indices {
                         channel.notify_message_delivered(message_index);
                     }
@@ -320,4 +1066,24 @@ impl MessageManager {
             }
         }
     }
+
+    /// Stops tracking any Messages which were sent under the given, now
+    /// dropped, indices and reports them as expired
+    fn expire_tracked_messages(&mut self, channel_kind: &ChannelKind, indices: Vec<MessageIndex>) {
+        for message_index in indices {
+            let Some(handle) = self.tracked_indices.remove(&(*channel_kind, message_index)) else {
+                continue;
+            };
+            // a fragmented Message expires as a whole
+            if let Some(indices) = self.tracked_messages.remove(&handle) {
+                for key in indices {
+                    self.tracked_indices.remove(&key);
+                }
+                match self.untracked_messages.remove(&handle) {
+                    Some(dropped) => self.dropped_messages.push(dropped),
+                    None => self.expired_messages.push(handle),
+                }
+            }
+        }
+    }
 }
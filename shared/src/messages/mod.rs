@@ -1,10 +1,18 @@
 pub mod channels;
+pub mod expired_message;
 pub mod fragment;
+pub mod keyed_message;
+pub mod lane_frame;
 pub mod message;
 pub mod message_container;
+pub mod message_frame;
+pub mod message_handle;
 pub mod message_kinds;
 pub mod message_manager;
 pub mod named;
+pub mod request;
+pub mod stream;
+pub mod tick_buffered_frame;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use naia_derive::MessageInternal;
+use naia_serde::SerdeInternal;
+
+use crate::{messages::message_frame::MessageFrame, Message};
+
+// Request
+/// A Message which expects a Response from the remote host
+pub trait Request: Message {
+    type Response: Response;
+}
+
+// Response
+/// A Message sent in reply to a Request
+pub trait Response: Message {}
+
+// RequestId
+/// Identifies a Request among those sent by one side of a Connection
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, SerdeInternal)]
+pub struct RequestId {
+    inner: u32,
+}
+
+impl RequestId {
+    pub(crate) fn zero() -> Self {
+        Self { inner: 0 }
+    }
+
+    pub(crate) fn increment(&mut self) {
+        self.inner = self.inner.wrapping_add(1);
+    }
+}
+
+// ResponseKey
+/// Returned when sending a Request, used to match up the Response when it
+/// arrives, or to recognize that the Request has timed out
+pub struct ResponseKey<S: Response> {
+    id: RequestId,
+    phantom_s: PhantomData<S>,
+}
+
+impl<S: Response> ResponseKey<S> {
+    pub fn new(id: RequestId) -> Self {
+        Self {
+            id,
+            phantom_s: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> RequestId {
+        self.id
+    }
+}
+
+impl<S: Response> Clone for ResponseKey<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Response> Copy for ResponseKey<S> {}
+
+impl<S: Response> PartialEq for ResponseKey<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<S: Response> Eq for ResponseKey<S> {}
+
+// RequestFrame
+/// Carries a Request or Response, tagged with the id used to match them up
+#[derive(MessageInternal)]
+pub struct RequestFrame {
+    id: RequestId,
+    is_response: bool,
+    message: Vec<u8>,
+}
+
+impl RequestFrame {
+    pub(crate) fn new(id: RequestId, is_response: bool, message: Vec<u8>) -> Self {
+        Self {
+            id,
+            is_response,
+            message,
+        }
+    }
+
+    pub(crate) fn id(&self) -> RequestId {
+        self.id
+    }
+
+    pub(crate) fn is_response(&self) -> bool {
+        self.is_response
+    }
+}
+
+impl MessageFrame for RequestFrame {
+    fn take_message(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.message)
+    }
+}
@@ -0,0 +1,120 @@
+use naia_derive::MessageInternal;
+use naia_serde::SerdeInternal;
+
+use crate::MessageContainer;
+
+// StreamId
+/// Identifies a Stream among those opened by one side of a Connection
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, SerdeInternal)]
+pub struct StreamId {
+    inner: u16,
+}
+
+impl StreamId {
+    pub(crate) fn zero() -> Self {
+        Self { inner: 0 }
+    }
+
+    pub(crate) fn increment(&mut self) {
+        self.inner = self.inner.wrapping_add(1);
+    }
+}
+
+// StreamOptions
+#[derive(Clone)]
+pub struct StreamOptions {
+    /// Position within the overall content at which this Stream's data
+    /// begins. Used to resume a Stream which was interrupted, for example by a
+    /// reconnection, once the receiver has reported how much it already holds.
+    pub offset: u64,
+    /// Total length of the content in bytes, if known up front
+    pub length: Option<u64>,
+    /// Hash of the full content, so that the receiver can look it up in a
+    /// local cache and reject the Stream if it already has it
+    pub content_hash: Option<u64>,
+}
+
+impl StreamOptions {
+    pub const fn default() -> Self {
+        Self {
+            offset: 0,
+            length: None,
+            content_hash: None,
+        }
+    }
+}
+
+// StreamProgress
+/// Progress of an outgoing Stream, counted in bytes written since it was opened
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StreamProgress {
+    /// Bytes handed to the Stream by the application
+    pub written: u64,
+    /// Bytes which have been queued for transmission
+    pub sent: u64,
+    /// Bytes which the remote host has acknowledged
+    pub acknowledged: u64,
+}
+
+// StreamUpdate
+pub enum StreamUpdate {
+    /// The remote host opened a Stream
+    Opened {
+        id: StreamId,
+        metadata: MessageContainer,
+        offset: u64,
+        length: Option<u64>,
+        content_hash: Option<u64>,
+    },
+    /// A chunk of an incoming Stream arrived. `offset` is the position of the
+    /// chunk within the overall content, so `offset + bytes.len()` is the
+    /// amount received so far.
+    Data {
+        id: StreamId,
+        offset: u64,
+        bytes: Vec<u8>,
+    },
+    /// The remote host finished writing to an incoming Stream
+    Finished { id: StreamId },
+    /// The remote host cancelled an incoming Stream
+    Cancelled { id: StreamId },
+    /// Every byte of an outgoing Stream has been acknowledged by the remote host
+    Delivered { id: StreamId },
+    /// The remote host rejected an outgoing Stream
+    Rejected { id: StreamId },
+}
+
+// StreamAction
+#[derive(Clone, PartialEq, SerdeInternal)]
+pub(crate) enum StreamAction {
+    Open {
+        metadata: Vec<u8>,
+        offset: u64,
+        length: Option<u64>,
+        content_hash: Option<u64>,
+    },
+    Data {
+        bytes: Vec<u8>,
+    },
+    Finish,
+    Cancel,
+    Reject,
+}
+
+// StreamFrame
+/// Carries a single piece of a Stream over its Channel
+#[derive(MessageInternal)]
+pub struct StreamFrame {
+    id: StreamId,
+    action: StreamAction,
+}
+
+impl StreamFrame {
+    pub(crate) fn new(id: StreamId, action: StreamAction) -> Self {
+        Self { id, action }
+    }
+
+    pub(crate) fn into_parts(self) -> (StreamId, StreamAction) {
+        (self.id, self.action)
+    }
+}
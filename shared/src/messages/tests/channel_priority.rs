@@ -0,0 +1,147 @@
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, BitWriter, Serde};
+
+use crate::{
+    messages::channels::{
+        channel::{Channel, ChannelDirection, ChannelMode},
+        channel_kinds::ChannelKind,
+    },
+    FakeEntityConverter, HostType, MessageContainer, MessageManager, Protocol,
+};
+
+#[derive(MessageInternal)]
+pub struct NumberMessage {
+    pub inner: u32,
+}
+
+struct HighChannel;
+impl Channel for HighChannel {}
+
+struct LowChannel;
+impl Channel for LowChannel {}
+
+struct HeavyChannel;
+impl Channel for HeavyChannel {}
+
+struct LightChannel;
+impl Channel for LightChannel {}
+
+fn send_messages<C: Channel>(protocol: &Protocol, manager: &mut MessageManager, count: u32) {
+    for inner in 0..count {
+        let container = MessageContainer::from_write(
+            Box::new(NumberMessage { inner }),
+            &mut FakeEntityConverter,
+        );
+        manager.send_message(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &ChannelKind::of::<C>(),
+            container,
+        );
+    }
+}
+
+// Writes a single packet and returns how many Messages each Channel section held, in order
+fn write_packet(protocol: &Protocol, manager: &mut MessageManager) -> Vec<(ChannelKind, usize)> {
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+
+    // reserve MessageContinue finish bit, as the Connection does
+    writer.reserve_bits(1);
+    manager.write_messages(
+        protocol,
+        &mut FakeEntityConverter,
+        &mut writer,
+        0,
+        &mut has_written,
+    );
+    writer.release_bits(1);
+    false.ser(&mut writer);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let mut sections = Vec::new();
+    while bool::de(&mut reader).unwrap() {
+        let channel_kind = ChannelKind::de(&protocol.channel_kinds, &mut reader).unwrap();
+        let mut count = 0;
+        while bool::de(&mut reader).unwrap() {
+            protocol
+                .message_kinds
+                .read(&mut reader, &FakeEntityConverter)
+                .unwrap();
+            count += 1;
+        }
+        sections.push((channel_kind, count));
+    }
+    sections
+}
+
+fn total_for<C: Channel>(sections: &[(ChannelKind, usize)]) -> usize {
+    sections
+        .iter()
+        .filter(|(channel_kind, _)| *channel_kind == ChannelKind::of::<C>())
+        .map(|(_, count)| count)
+        .sum()
+}
+
+#[test]
+fn higher_priority_channel_written_first() {
+    let protocol = Protocol::builder()
+        .add_message::<NumberMessage>()
+        .add_channel_with_priority::<LowChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+            0,
+            1,
+        )
+        .add_channel_with_priority::<HighChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+            1,
+            1,
+        )
+        .build();
+    let mut manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+
+    send_messages::<LowChannel>(&protocol, &mut manager, 500);
+    send_messages::<HighChannel>(&protocol, &mut manager, 20);
+
+    let sections = write_packet(&protocol, &mut manager);
+
+    assert!(sections[0].0 == ChannelKind::of::<HighChannel>());
+    assert_eq!(total_for::<HighChannel>(&sections), 20);
+    assert!(total_for::<LowChannel>(&sections) > 0);
+}
+
+#[test]
+fn equal_priority_channels_share_by_weight() {
+    let protocol = Protocol::builder()
+        .add_message::<NumberMessage>()
+        .add_channel_with_priority::<HeavyChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+            0,
+            3,
+        )
+        .add_channel_with_priority::<LightChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+            0,
+            1,
+        )
+        .build();
+    let mut manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+
+    send_messages::<HeavyChannel>(&protocol, &mut manager, 500);
+    send_messages::<LightChannel>(&protocol, &mut manager, 500);
+
+    let mut sections = Vec::new();
+    for _ in 0..3 {
+        sections.extend(write_packet(&protocol, &mut manager));
+    }
+
+    let heavy = total_for::<HeavyChannel>(&sections);
+    let light = total_for::<LightChannel>(&sections);
+    assert!(light > 0);
+    assert!(heavy >= light * 2 && heavy <= light * 4);
+}
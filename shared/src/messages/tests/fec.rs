@@ -0,0 +1,90 @@
+use naia_derive::MessageInternal;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, FecSettings},
+    ChannelKind, FakeEntityConverter, MessageContainer, MessageManager, Protocol,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct VoiceSample {
+    pub value: u16,
+}
+
+struct VoiceChannel;
+impl Channel for VoiceChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<VoiceSample>()
+        .add_channel_with_fec::<VoiceChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+            FecSettings { group_size: 2 },
+        )
+        .build()
+}
+
+fn send_sample(protocol: &Protocol, manager: &mut MessageManager, value: u16) {
+    let message =
+        MessageContainer::from_write(Box::new(VoiceSample { value }), &mut FakeEntityConverter);
+    manager.send_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<VoiceChannel>(),
+        message,
+    );
+}
+
+// Reads a packet into `receiver`, returning the values received
+fn read_samples(protocol: &Protocol, receiver: &mut MessageManager, bytes: &[u8]) -> Vec<u16> {
+    read_packet(protocol, receiver, bytes)
+        .into_iter()
+        .map(|message| {
+            message
+                .to_boxed_any()
+                .downcast::<VoiceSample>()
+                .unwrap()
+                .value
+        })
+        .collect()
+}
+
+#[test]
+fn lost_packet_is_recovered_from_parity() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_sample(&protocol, &mut server, 100);
+    let _lost_packet = write_packet(&protocol, &mut server, 0);
+    send_sample(&protocol, &mut server, 200);
+    let packet = write_packet(&protocol, &mut server, 1);
+    send_sample(&protocol, &mut server, 300);
+    let parity_packet = write_packet(&protocol, &mut server, 2);
+
+    assert_eq!(read_samples(&protocol, &mut client, &packet), vec![200]);
+    assert_eq!(
+        read_samples(&protocol, &mut client, &parity_packet),
+        vec![100, 300]
+    );
+}
+
+#[test]
+fn unfinished_group_is_closed_off() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_sample(&protocol, &mut server, 100);
+    let _lost_packet = write_packet(&protocol, &mut server, 0);
+
+    // the group's parity is sent once there is nothing else to send
+    assert!(server.has_outgoing_messages());
+    let parity_packet = write_packet(&protocol, &mut server, 1);
+    assert!(!server.has_outgoing_messages());
+
+    assert_eq!(
+        read_samples(&protocol, &mut client, &parity_packet),
+        vec![100]
+    );
+}
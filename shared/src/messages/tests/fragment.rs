@@ -1,9 +1,14 @@
 use naia_derive::MessageInternal;
+use naia_serde::{BitReader, ReadLimits, SerdeErrReason};
 
 use crate::{
-    messages::channels::{
-        receivers::fragment_receiver::FragmentReceiver,
-        senders::message_fragmenter::MessageFragmenter,
+    messages::{
+        channels::{
+            receivers::fragment_receiver::FragmentReceiver,
+            senders::message_fragmenter::MessageFragmenter,
+        },
+        expired_message::ExpiredMessage,
+        fragment::{FragmentId, FragmentIndex, FragmentedMessage},
     },
     FakeEntityConverter, MessageContainer, MessageKinds, Protocol,
 };
@@ -24,6 +29,7 @@ impl StringMessage {
 fn setup() -> (
     MessageKinds,
     FakeEntityConverter,
+    BitReader<'static>,
     MessageFragmenter,
     FragmentReceiver,
 ) {
@@ -34,34 +40,52 @@ fn setup() -> (
     // Converter
     let converter = FakeEntityConverter;
 
+    // Reader of the packet each fragment arrives in
+    let reader = BitReader::new(&[]);
+
     // Fragmenter
     let fragmenter = MessageFragmenter::new();
 
     // Fragment Receiver
     let receiver = FragmentReceiver::new();
 
-    (protocol.message_kinds, converter, fragmenter, receiver)
+    (
+        protocol.message_kinds,
+        converter,
+        reader,
+        fragmenter,
+        receiver,
+    )
 }
 
 #[test]
 fn convert_single_fragment() {
-    let (message_kinds, converter, mut fragmenter, mut receiver) = setup();
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
 
     // Message
     let initial_message = StringMessage::new("hello");
     let outgoing_message = initial_message.clone();
 
-    let container = MessageContainer::from_write(Box::new(outgoing_message), &FakeEntityConverter);
+    let container =
+        MessageContainer::from_write(Box::new(outgoing_message), &mut FakeEntityConverter);
 
     // Fragment Message
-    let fragments = fragmenter.fragment_message(&message_kinds, &converter, container);
+    let fragments =
+        fragmenter.fragment_message(&message_kinds, &mut FakeEntityConverter, container);
     let fragment_count = fragments.len();
 
     // Receive Fragments
     let mut incoming_message_container_opt = None;
-    for fragment in fragments {
-        if let Some((_, reassembled_message)) =
-            receiver.receive(&message_kinds, &converter, fragment)
+    for (index, fragment) in fragments.into_iter().enumerate() {
+        if let Some((_, reassembled_message)) = receiver
+            .receive(
+                &message_kinds,
+                &converter,
+                &mut reader,
+                index as u16,
+                fragment,
+            )
+            .unwrap()
         {
             incoming_message_container_opt = Some(reassembled_message);
             break;
@@ -70,7 +94,10 @@ fn convert_single_fragment() {
     let Some(incoming_message_container) = incoming_message_container_opt else {
         panic!("Did not receive reassembled message!");
     };
-    let Ok(incoming_message) = incoming_message_container.to_boxed_any().downcast::<StringMessage>() else {
+    let Ok(incoming_message) = incoming_message_container
+        .to_boxed_any()
+        .downcast::<StringMessage>()
+    else {
         panic!("cannot cast message container into proper message!");
     };
 
@@ -79,12 +106,8 @@ fn convert_single_fragment() {
     assert_eq!(initial_message.inner, incoming_message.inner);
 }
 
-#[test]
-fn convert_multiple_fragments() {
-    let (message_kinds, converter, mut fragmenter, mut receiver) = setup();
-
-    // Message
-    let initial_message = StringMessage::new("Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec sed justo a mi ultricies ultrices. \
+fn long_message() -> StringMessage {
+    StringMessage::new("Lorem ipsum dolor sit amet, consectetur adipiscing elit. Donec sed justo a mi ultricies ultrices. \
             Sed porta, odio eu rhoncus venenatis, massa elit posuere nisl, sit amet venenatis mi mi at erat. \
             Vivamus non ullamcorper augue, non pharetra augue. Fusce erat ante, iaculis id maximus eu, faucibus consequat nisl. \
             Integer consequat consequat bibendum. Cras nisl est, lacinia nec ipsum vitae, pulvinar elementum orci. \
@@ -92,13 +115,32 @@ fn convert_multiple_fragments() {
             Donec ac sollicitudin nunc. Nam luctus nulla ut nisi tristique, quis scelerisque neque elementum. Etiam a quam turpis. \
             Vestibulum ultricies dui et porttitor blandit. Etiam turpis quam, pretium ac convallis a, blandit sit amet ipsum. \
             Sed ut pharetra arcu. Pellentesque id magna sapien. Suspendisse potenti. \
-            Donec ut purus venenatis, mollis est ut, sollicitudin egestas.");
+            Donec ut purus venenatis, mollis est ut, sollicitudin egestas.")
+}
+
+fn fragment_long_message(
+    message_kinds: &MessageKinds,
+    fragmenter: &mut MessageFragmenter,
+) -> Vec<MessageContainer> {
+    let container =
+        MessageContainer::from_write(Box::new(long_message()), &mut FakeEntityConverter);
+    fragmenter.fragment_message(message_kinds, &mut FakeEntityConverter, container)
+}
+
+#[test]
+fn convert_multiple_fragments() {
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
+
+    // Message
+    let initial_message = long_message();
     let outgoing_message = initial_message.clone();
 
-    let container = MessageContainer::from_write(Box::new(outgoing_message), &FakeEntityConverter);
+    let container =
+        MessageContainer::from_write(Box::new(outgoing_message), &mut FakeEntityConverter);
 
     // Fragment Message
-    let fragments = fragmenter.fragment_message(&message_kinds, &converter, container);
+    let fragments =
+        fragmenter.fragment_message(&message_kinds, &mut FakeEntityConverter, container);
     let fragment_count = fragments.len();
 
     // Receive Fragments
@@ -112,8 +154,15 @@ fn convert_multiple_fragments() {
         };
 
         let fragment = &fragments[j];
-        if let Some((_, reassembled_message)) =
-            receiver.receive(&message_kinds, &converter, fragment.clone())
+        if let Some((_, reassembled_message)) = receiver
+            .receive(
+                &message_kinds,
+                &converter,
+                &mut reader,
+                j as u16,
+                fragment.clone(),
+            )
+            .unwrap()
         {
             incoming_message_container_opt = Some(reassembled_message);
             break;
@@ -122,7 +171,10 @@ fn convert_multiple_fragments() {
     let Some(incoming_message_container) = incoming_message_container_opt else {
         panic!("Did not receive reassembled message!");
     };
-    let Ok(incoming_message) = incoming_message_container.to_boxed_any().downcast::<StringMessage>() else {
+    let Ok(incoming_message) = incoming_message_container
+        .to_boxed_any()
+        .downcast::<StringMessage>()
+    else {
         panic!("cannot cast message container into proper message!");
     };
 
@@ -130,3 +182,214 @@ fn convert_multiple_fragments() {
     assert_eq!(fragment_count, 3);
     assert_eq!(initial_message.inner, incoming_message.inner);
 }
+
+#[test]
+fn reject_repeated_fragment() {
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
+
+    let fragments = fragment_long_message(&message_kinds, &mut fragmenter);
+
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            0,
+            fragments[0].clone()
+        )
+        .unwrap()
+        .is_none());
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            0,
+            fragments[0].clone()
+        )
+        .is_err());
+}
+
+#[test]
+fn reject_fragment_without_total() {
+    let (message_kinds, converter, mut reader, _, mut receiver) = setup();
+
+    let fragment =
+        FragmentedMessage::new(FragmentId::zero(), FragmentIndex::zero(), Box::new([0; 4]));
+    let container = MessageContainer::from_write(Box::new(fragment), &mut FakeEntityConverter);
+
+    assert!(receiver
+        .receive(&message_kinds, &converter, &mut reader, 0, container)
+        .is_err());
+}
+
+#[test]
+fn clear_stale_fragments() {
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
+
+    let fragments = fragment_long_message(&message_kinds, &mut fragmenter);
+    assert_eq!(fragments.len(), 3);
+
+    // the Message's fragments are sent under indices 10, 11 & 12
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            10,
+            fragments[0].clone()
+        )
+        .unwrap()
+        .is_none());
+
+    // still waiting on indices 11 & 12
+    receiver.clear_stale(11);
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            11,
+            fragments[1].clone()
+        )
+        .unwrap()
+        .is_none());
+
+    // the window has moved past every fragment, so the Message is dropped and
+    // its last fragment no longer completes it
+    receiver.clear_stale(13);
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            12,
+            fragments[2].clone()
+        )
+        .unwrap()
+        .is_none());
+}
+
+fn expired_message() -> MessageContainer {
+    MessageContainer::from_write(Box::new(ExpiredMessage), &mut FakeEntityConverter)
+}
+
+#[test]
+fn expired_fragment_drops_its_message() {
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
+
+    let fragments = fragment_long_message(&message_kinds, &mut fragmenter);
+
+    // the Message's fragments are sent under indices 10, 11 & 12, and the
+    // sender gives up on the one under 11
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            10,
+            fragments[0].clone()
+        )
+        .unwrap()
+        .is_none());
+    let placeholder = receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            11,
+            expired_message(),
+        )
+        .unwrap();
+    assert!(placeholder.is_some());
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            12,
+            fragments[2].clone()
+        )
+        .unwrap()
+        .is_none());
+
+    // nothing of the Message is kept, so a repeat of its first fragment is
+    // not mistaken for a duplicate
+    receiver.clear_stale(10);
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            10,
+            fragments[0].clone()
+        )
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn fragment_arriving_after_expiry_is_dropped() {
+    let (message_kinds, converter, mut reader, mut fragmenter, mut receiver) = setup();
+
+    let fragments = fragment_long_message(&message_kinds, &mut fragmenter);
+
+    // the placeholder for the first fragment arrives before the others
+    assert!(receiver
+        .receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            10,
+            expired_message()
+        )
+        .unwrap()
+        .is_some());
+    receiver.clear_stale(11);
+    for (index, fragment) in fragments.into_iter().enumerate().skip(1) {
+        assert!(receiver
+            .receive(
+                &message_kinds,
+                &converter,
+                &mut reader,
+                10 + index as u16,
+                fragment
+            )
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[test]
+fn reassembled_message_is_read_within_limits() {
+    let (message_kinds, converter, _, mut fragmenter, mut receiver) = setup();
+    let limits = ReadLimits {
+        max_collection_len: 16,
+        ..ReadLimits::default()
+    };
+    let mut reader = BitReader::with_limits(&[], limits);
+
+    let fragments = fragment_long_message(&message_kinds, &mut fragmenter);
+    let fragment_count = fragments.len();
+    let mut result = Ok(None);
+    for (index, fragment) in fragments.into_iter().enumerate() {
+        result = receiver.receive(
+            &message_kinds,
+            &converter,
+            &mut reader,
+            index as u16,
+            fragment,
+        );
+        if index + 1 < fragment_count {
+            assert!(result.as_ref().unwrap().is_none());
+        }
+    }
+
+    let Err(error) = result else {
+        panic!("expected the reassembled Message to exceed the limits");
+    };
+    assert!(matches!(
+        error.reason(),
+        SerdeErrReason::LengthLimit { max_length: 16, .. }
+    ));
+}
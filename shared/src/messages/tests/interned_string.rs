@@ -0,0 +1,127 @@
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, SerdeErrReason};
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode},
+    world::remote::entity_waitlist::EntityWaitlist,
+    ChannelKind, FakeEntityConverter, InternedString, MessageContainer, MessageManager, Protocol,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct SpellCast {
+    pub spell: InternedString,
+}
+
+struct SpellChannel;
+impl Channel for SpellChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<SpellCast>()
+        .add_channel::<SpellChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedUnreliable,
+        )
+        .build()
+}
+
+fn cast(protocol: &Protocol, manager: &mut MessageManager, spell: &str) {
+    let message = MessageContainer::from_write(
+        Box::new(SpellCast {
+            spell: InternedString::new(spell),
+        }),
+        &mut FakeEntityConverter,
+    );
+    manager.send_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<SpellChannel>(),
+        message,
+    );
+}
+
+// Reads a packet into `receiver`, returning the spells cast
+fn read_spells(protocol: &Protocol, receiver: &mut MessageManager, bytes: &[u8]) -> Vec<String> {
+    read_packet(protocol, receiver, bytes)
+        .into_iter()
+        .map(|message| {
+            let cast = message.to_boxed_any().downcast::<SpellCast>().unwrap();
+            cast.spell.into_string()
+        })
+        .collect()
+}
+
+#[test]
+fn interned_string_sent_by_index_once_delivered() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    cast(&protocol, &mut server, "fireball");
+    let first_packet = write_packet(&protocol, &mut server, 0);
+    assert_eq!(
+        read_spells(&protocol, &mut client, &first_packet),
+        vec!["fireball"]
+    );
+
+    // the definition has not been acknowledged yet, so it is sent again
+    cast(&protocol, &mut server, "fireball");
+    let second_packet = write_packet(&protocol, &mut server, 1);
+    assert_eq!(second_packet.len(), first_packet.len());
+
+    server.notify_packet_delivered(0);
+    cast(&protocol, &mut server, "fireball");
+    let third_packet = write_packet(&protocol, &mut server, 2);
+    assert!(third_packet.len() < first_packet.len());
+    assert_eq!(
+        read_spells(&protocol, &mut client, &third_packet),
+        vec!["fireball"]
+    );
+}
+
+#[test]
+fn lost_definition_is_sent_again() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    cast(&protocol, &mut server, "fireball");
+    let lost_packet = write_packet(&protocol, &mut server, 0);
+
+    // only a later packet is acknowledged
+    cast(&protocol, &mut server, "frostbolt");
+    write_packet(&protocol, &mut server, 1);
+    server.notify_packet_delivered(1);
+
+    cast(&protocol, &mut server, "fireball");
+    let packet = write_packet(&protocol, &mut server, 2);
+    assert_eq!(packet.len(), lost_packet.len());
+    assert_eq!(
+        read_spells(&protocol, &mut client, &packet),
+        vec!["fireball"]
+    );
+}
+
+#[test]
+fn index_without_definition_is_an_error() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    cast(&protocol, &mut server, "fireball");
+    write_packet(&protocol, &mut server, 0);
+    server.notify_packet_delivered(0);
+    cast(&protocol, &mut server, "fireball");
+    let packet = write_packet(&protocol, &mut server, 1);
+
+    let mut reader = BitReader::new(&packet);
+    let error = client
+        .read_messages(
+            &protocol,
+            &mut EntityWaitlist::new(),
+            &FakeEntityConverter,
+            &mut reader,
+        )
+        .unwrap_err();
+
+    assert_eq!(error.reason(), &SerdeErrReason::UnknownInternedString(0));
+}
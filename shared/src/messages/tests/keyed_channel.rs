@@ -0,0 +1,151 @@
+use naia_derive::MessageInternal;
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, KeyedSettings},
+    ChannelKind, FakeEntityConverter, KeyedMessage, MessageContainer, MessageKey, MessageManager,
+    Protocol,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct ReadyState {
+    pub player: u8,
+    pub ready: bool,
+}
+
+impl KeyedMessage for ReadyState {
+    type Key = u8;
+
+    fn key(&self) -> u8 {
+        self.player
+    }
+}
+
+#[derive(MessageInternal)]
+pub struct TeamState {
+    pub player: u8,
+    pub team: u8,
+}
+
+impl KeyedMessage for TeamState {
+    type Key = u8;
+
+    fn key(&self) -> u8 {
+        self.player
+    }
+}
+
+struct KeyedChannel;
+impl Channel for KeyedChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<ReadyState>()
+        .add_message::<TeamState>()
+        .add_channel::<KeyedChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::Keyed(KeyedSettings::default()),
+        )
+        .build()
+}
+
+fn send_ready(manager: &mut MessageManager, player: u8, ready: bool) {
+    let state = ReadyState { player, ready };
+    let key = MessageKey::of(&state);
+    let message = MessageContainer::from_write(Box::new(state), &mut FakeEntityConverter);
+    manager.send_keyed_message(&ChannelKind::of::<KeyedChannel>(), key, message);
+}
+
+fn send_team(manager: &mut MessageManager, player: u8, team: u8) {
+    let state = TeamState { player, team };
+    let key = MessageKey::of(&state);
+    let message = MessageContainer::from_write(Box::new(state), &mut FakeEntityConverter);
+    manager.send_keyed_message(&ChannelKind::of::<KeyedChannel>(), key, message);
+}
+
+// Reads a packet into `receiver`, returning the values applied
+fn read_ready(protocol: &Protocol, receiver: &mut MessageManager, bytes: &[u8]) -> Vec<(u8, bool)> {
+    let mut values: Vec<(u8, bool)> = read_packet(protocol, receiver, bytes)
+        .into_iter()
+        .map(|message| {
+            let state = message.to_boxed_any().downcast::<ReadyState>().unwrap();
+            (state.player, state.ready)
+        })
+        .collect();
+    values.sort();
+    values
+}
+
+#[test]
+fn only_newest_value_per_key_is_sent() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_ready(&mut server, 1, true);
+    send_ready(&mut server, 1, false);
+    send_ready(&mut server, 2, true);
+
+    let packet = write_packet(&protocol, &mut server, 0);
+    assert_eq!(
+        read_ready(&protocol, &mut client, &packet),
+        vec![(1, false), (2, true)]
+    );
+
+    // once acknowledged, values are no longer resent
+    server.notify_packet_delivered(0);
+    server.collect_outgoing_messages(&Instant::now(), &0.0);
+    assert!(!server.has_outgoing_messages());
+}
+
+#[test]
+fn obsolete_value_is_ignored() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_ready(&mut server, 1, true);
+    let old_packet = write_packet(&protocol, &mut server, 0);
+    send_ready(&mut server, 1, false);
+    let new_packet = write_packet(&protocol, &mut server, 1);
+
+    // packets arrive out of order
+    assert_eq!(
+        read_ready(&protocol, &mut client, &new_packet),
+        vec![(1, false)]
+    );
+    assert!(read_ready(&protocol, &mut client, &old_packet).is_empty());
+
+    // acknowledging the obsolete value does not stop the newer one being resent
+    server.notify_packet_delivered(0);
+    server.collect_outgoing_messages(&Instant::now(), &0.0);
+    assert!(server.has_outgoing_messages());
+}
+
+#[test]
+fn same_key_of_different_messages_is_kept_apart() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    // both are keyed by player 1, and the ReadyState has a later version than
+    // the TeamState
+    send_ready(&mut server, 1, true);
+    send_ready(&mut server, 1, false);
+    send_team(&mut server, 1, 4);
+
+    let packet = write_packet(&protocol, &mut server, 0);
+    let mut ready_values = Vec::new();
+    let mut team_values = Vec::new();
+    for message in read_packet(&protocol, &mut client, &packet) {
+        let message = message.to_boxed_any();
+        match message.downcast::<ReadyState>() {
+            Ok(state) => ready_values.push((state.player, state.ready)),
+            Err(message) => {
+                let state = message.downcast::<TeamState>().unwrap();
+                team_values.push((state.player, state.team));
+            }
+        }
+    }
+    assert_eq!(ready_values, vec![(1, false)]);
+    assert_eq!(team_values, vec![(1, 4)]);
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use naia_derive::MessageInternal;
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings},
+    ChannelKind, FakeEntityConverter, MessageContainer, MessageHandle, MessageKind,
+    MessageManager, Protocol,
+};
+
+use super::{managers, read_packet, write_packet_at};
+
+#[derive(MessageInternal)]
+pub struct PositionMessage {
+    pub label: String,
+}
+
+struct OrderedChannel;
+impl Channel for OrderedChannel {}
+
+struct SupersedeChannel;
+impl Channel for SupersedeChannel {}
+
+fn protocol() -> Protocol {
+    let mut supersede_settings = ReliableSettings::default();
+    supersede_settings.supersede = true;
+
+    Protocol::builder()
+        .add_message::<PositionMessage>()
+        .add_channel::<OrderedChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .add_channel::<SupersedeChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedReliable(supersede_settings),
+        )
+        .build()
+}
+
+fn send_position<C: Channel>(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    label: &str,
+    ttl: Option<Duration>,
+) -> MessageHandle {
+    let message = MessageContainer::from_write(
+        Box::new(PositionMessage {
+            label: label.to_string(),
+        }),
+        &mut FakeEntityConverter,
+    );
+    manager.send_tracked_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<C>(),
+        message,
+        ttl,
+    )
+}
+
+fn send_untracked_position<C: Channel>(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    label: &str,
+) {
+    let message = MessageContainer::from_write(
+        Box::new(PositionMessage {
+            label: label.to_string(),
+        }),
+        &mut FakeEntityConverter,
+    );
+    manager.send_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<C>(),
+        message,
+    );
+}
+
+// Writes a single packet from `sender` at the given time and reads it into
+// `receiver`, then returns the labels of the Messages received
+fn transfer(
+    protocol: &Protocol,
+    sender: &mut MessageManager,
+    receiver: &mut MessageManager,
+    now: &Instant,
+) -> Vec<String> {
+    let bytes = write_packet_at(protocol, sender, now, 0);
+    read_packet(protocol, receiver, &bytes)
+        .into_iter()
+        .map(|message| {
+            let message = message
+                .to_boxed_any()
+                .downcast::<PositionMessage>()
+                .unwrap();
+            message.label
+        })
+        .collect()
+}
+
+#[test]
+fn expired_message_is_reported_and_skipped() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    let expiring = send_position::<OrderedChannel>(
+        &protocol,
+        &mut server,
+        "stale",
+        Some(Duration::from_millis(100)),
+    );
+    send_position::<OrderedChannel>(&protocol, &mut server, "fresh", None);
+
+    let mut later = Instant::now();
+    later.add_millis(200);
+    let received = transfer(&protocol, &mut server, &mut client, &later);
+
+    assert_eq!(server.receive_expired_messages(), vec![expiring]);
+    // the Ordered Channel does not wait on the dropped Message
+    assert_eq!(received, vec!["fresh".to_string()]);
+}
+
+#[test]
+fn newer_message_supersedes_unacknowledged_one() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    let first = send_position::<SupersedeChannel>(&protocol, &mut server, "first", None);
+    let second = send_position::<SupersedeChannel>(&protocol, &mut server, "second", None);
+    assert_eq!(server.receive_expired_messages(), vec![first]);
+
+    let received = transfer(&protocol, &mut server, &mut client, &Instant::now());
+    assert_eq!(received, vec!["second".to_string()]);
+
+    server.notify_packet_delivered(0);
+    assert_eq!(server.receive_delivered_messages(), vec![second]);
+}
+
+#[test]
+fn dropped_untracked_message_is_reported() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_untracked_position::<SupersedeChannel>(&protocol, &mut server, "first");
+    send_untracked_position::<SupersedeChannel>(&protocol, &mut server, "second");
+
+    let dropped = server.receive_dropped_messages();
+    assert_eq!(dropped.len(), 1);
+    let (channel_kind, message_kind) = dropped[0];
+    assert!(channel_kind == ChannelKind::of::<SupersedeChannel>());
+    assert!(message_kind == MessageKind::of::<PositionMessage>());
+    assert!(server.receive_expired_messages().is_empty());
+
+    let received = transfer(&protocol, &mut server, &mut client, &Instant::now());
+    assert_eq!(received, vec!["second".to_string()]);
+
+    // a Message sent without a handle is not reported once delivered
+    server.notify_packet_delivered(0);
+    assert!(server.receive_delivered_messages().is_empty());
+    assert!(server.receive_dropped_messages().is_empty());
+}
@@ -1 +1,123 @@
+use naia_serde::{BitReader, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    world::remote::entity_waitlist::EntityWaitlist, EntityAndGlobalEntityConverter,
+    EntityDoesNotExistError, FakeEntityConverter, GlobalEntity, HostType, LocalEntity,
+    LocalEntityAndGlobalEntityConverter, LocalEntityConverter, MessageContainer, MessageManager,
+    Protocol,
+};
+
+mod channel_priority;
+mod fec;
 mod fragment;
+mod interned_string;
+mod keyed_channel;
+mod message_ttl;
+mod ordering_lanes;
+mod request;
+mod shared_message;
+mod stream;
+mod tick_buffered;
+mod tracked_message;
+
+// No Entities exist in these tests
+struct NoEntities;
+
+impl EntityAndGlobalEntityConverter<u32> for NoEntities {
+    fn global_entity_to_entity(&self, _: &GlobalEntity) -> Result<u32, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn entity_to_global_entity(&self, _: &u32) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
+
+impl LocalEntityConverter<u32> for NoEntities {
+    fn entity_to_local_entity(&self, _: &u32) -> Result<LocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn local_entity_to_entity(&self, _: &LocalEntity) -> Result<u32, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverter for NoEntities {
+    fn global_entity_to_local_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<LocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn local_entity_to_global_entity(
+        &self,
+        _: &LocalEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+}
+
+// A server & a client MessageManager for the given Protocol
+fn managers(protocol: &Protocol) -> (MessageManager, MessageManager) {
+    (
+        MessageManager::new(HostType::Server, &protocol.channel_kinds),
+        MessageManager::new(HostType::Client, &protocol.channel_kinds),
+    )
+}
+
+// Writes a single packet under the given index, returning its bytes
+fn write_packet(protocol: &Protocol, manager: &mut MessageManager, packet_index: u16) -> Box<[u8]> {
+    write_packet_at(protocol, manager, &Instant::now(), packet_index)
+}
+
+// Writes a single packet under the given index at the given time, returning
+// its bytes. The RTT is large enough that nothing is resent between packets.
+fn write_packet_at(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    now: &Instant,
+    packet_index: u16,
+) -> Box<[u8]> {
+    manager.collect_outgoing_messages(now, &10000.0);
+
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    writer.reserve_bits(1);
+    manager.write_messages(
+        protocol,
+        &mut FakeEntityConverter,
+        &mut writer,
+        packet_index,
+        &mut has_written,
+    );
+    writer.release_bits(1);
+    false.ser(&mut writer);
+    writer.to_bytes()
+}
+
+// Reads a packet into `receiver`, returning the Messages it hands to the
+// application
+fn read_packet(
+    protocol: &Protocol,
+    receiver: &mut MessageManager,
+    bytes: &[u8],
+) -> Vec<MessageContainer> {
+    let mut reader = BitReader::new(bytes);
+    let mut entity_waitlist = EntityWaitlist::new();
+    receiver
+        .read_messages(
+            protocol,
+            &mut entity_waitlist,
+            &FakeEntityConverter,
+            &mut reader,
+        )
+        .unwrap();
+    receiver
+        .receive_messages(&NoEntities, &NoEntities, &mut entity_waitlist)
+        .into_iter()
+        .flat_map(|(_, messages)| messages)
+        .collect()
+}
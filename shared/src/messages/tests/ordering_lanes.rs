@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use naia_derive::MessageInternal;
+
+use crate::{
+    messages::channels::{
+        channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings},
+        receivers::ordered_reliable_receiver::{LaneArranger, LANE_BUFFER_LIMIT},
+    },
+    ChannelKind, FakeEntityConverter, MessageContainer, MessageManager, Protocol,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct ChatLine {
+    pub value: u8,
+}
+
+struct ChatChannel;
+impl Channel for ChatChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<ChatLine>()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+fn chat_line(value: u8) -> MessageContainer {
+    MessageContainer::from_write(Box::new(ChatLine { value }), &mut FakeEntityConverter)
+}
+
+// Sends a Message in the given lane, or outside of any lane if None
+fn send_chat(protocol: &Protocol, manager: &mut MessageManager, lane: Option<u16>, value: u8) {
+    let channel_kind = ChannelKind::of::<ChatChannel>();
+    match lane {
+        Some(lane) => manager.send_message_in_lane(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &channel_kind,
+            lane,
+            chat_line(value),
+        ),
+        None => manager.send_message(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &channel_kind,
+            chat_line(value),
+        ),
+    }
+}
+
+// Reads a packet into `receiver`, returning the values received, in order
+fn read_chat(protocol: &Protocol, receiver: &mut MessageManager, bytes: &[u8]) -> Vec<u8> {
+    read_packet(protocol, receiver, bytes)
+        .into_iter()
+        .map(|message| message.to_boxed_any().downcast::<ChatLine>().unwrap().value)
+        .collect()
+}
+
+#[test]
+fn delayed_message_only_blocks_its_own_lane() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_chat(&protocol, &mut server, Some(1), 10);
+    let delayed_packet = write_packet(&protocol, &mut server, 0);
+    send_chat(&protocol, &mut server, Some(2), 20);
+    send_chat(&protocol, &mut server, None, 30);
+    send_chat(&protocol, &mut server, Some(1), 11);
+    let packet = write_packet(&protocol, &mut server, 1);
+
+    // the later Message of lane 1 waits, other lanes do not, while a
+    // Message outside of any lane waits on everything sent before it
+    assert_eq!(read_chat(&protocol, &mut client, &packet), vec![20]);
+    assert_eq!(
+        read_chat(&protocol, &mut client, &delayed_packet),
+        vec![10, 11, 30]
+    );
+}
+
+#[test]
+fn unlaned_messages_stay_in_order() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_chat(&protocol, &mut server, None, 1);
+    let delayed_packet = write_packet(&protocol, &mut server, 0);
+    send_chat(&protocol, &mut server, Some(1), 10);
+    send_chat(&protocol, &mut server, None, 2);
+    let packet = write_packet(&protocol, &mut server, 1);
+
+    assert_eq!(read_chat(&protocol, &mut client, &packet), vec![10]);
+    assert_eq!(
+        read_chat(&protocol, &mut client, &delayed_packet),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn lane_sequence_wraps_around() {
+    let mut lane = LaneArranger::new();
+    let mut incoming = Vec::new();
+    for sequence in 0..u16::MAX {
+        lane.process(&mut incoming, sequence, sequence, chat_line(0));
+    }
+    assert_eq!(incoming.len(), u16::MAX as usize);
+    incoming.clear();
+
+    // the last sequence before the wraparound arrives after those following it
+    lane.process(&mut incoming, 1, 1, chat_line(3));
+    lane.process(&mut incoming, 0, 0, chat_line(2));
+    assert!(incoming.is_empty());
+    lane.process(&mut incoming, u16::MAX, u16::MAX, chat_line(1));
+
+    // a duplicate from before the wraparound is ignored
+    lane.process(&mut incoming, u16::MAX - 1, u16::MAX - 1, chat_line(0));
+
+    let values: Vec<u8> = incoming
+        .into_iter()
+        .map(|(_, message)| message.to_boxed_any().downcast::<ChatLine>().unwrap().value)
+        .collect();
+    assert_eq!(values, vec![1, 2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn lanes_cannot_be_used_with_ttl() {
+    let mut ttl_settings = ReliableSettings::default();
+    ttl_settings.ttl = Some(Duration::from_secs(1));
+    let protocol = Protocol::builder()
+        .add_message::<ChatLine>()
+        .add_channel::<ChatChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ttl_settings),
+        )
+        .build();
+    let (mut server, _) = managers(&protocol);
+
+    send_chat(&protocol, &mut server, Some(1), 10);
+}
+
+#[test]
+fn lane_buffer_is_capped() {
+    let mut lane = LaneArranger::new();
+    let mut incoming = Vec::new();
+
+    // while the first Message of the lane is missing, only so many later
+    // Messages are held on to
+    lane.process(&mut incoming, 0, LANE_BUFFER_LIMIT + 1, chat_line(2));
+    lane.process(&mut incoming, 0, LANE_BUFFER_LIMIT, chat_line(1));
+    for sequence in 1..LANE_BUFFER_LIMIT {
+        lane.process(&mut incoming, sequence, sequence, chat_line(0));
+    }
+    assert!(incoming.is_empty());
+    lane.process(&mut incoming, 0, 0, chat_line(0));
+
+    assert_eq!(incoming.len(), LANE_BUFFER_LIMIT as usize + 1);
+    let (_, last) = incoming.pop().unwrap();
+    assert_eq!(last.to_boxed_any().downcast::<ChatLine>().unwrap().value, 1);
+}
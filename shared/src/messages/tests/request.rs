@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::{
+        channels::channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings},
+        message_kinds::MessageKind,
+        request::RequestFrame,
+    },
+    ChannelKind, EntityProperty, FakeEntityConverter, LocalEntity, MessageContainer,
+    MessageManager, Protocol, Request, RequestId, Response,
+};
+
+use super::{managers, read_packet, write_packet, NoEntities};
+
+#[derive(MessageInternal)]
+pub struct PingRequest {
+    pub value: u32,
+}
+
+#[derive(MessageInternal)]
+pub struct PongResponse {
+    pub value: u32,
+}
+
+impl Request for PingRequest {
+    type Response = PongResponse;
+}
+
+impl Response for PongResponse {}
+
+#[derive(MessageInternal)]
+pub struct FollowRequest {
+    pub target: EntityProperty,
+}
+
+impl Request for FollowRequest {
+    type Response = PongResponse;
+}
+
+struct RpcChannel;
+impl Channel for RpcChannel {}
+
+struct SupersedeChannel;
+impl Channel for SupersedeChannel {}
+
+fn protocol() -> Protocol {
+    let mut supersede_settings = ReliableSettings::default();
+    supersede_settings.supersede = true;
+
+    Protocol::builder()
+        .add_message::<PingRequest>()
+        .add_message::<PongResponse>()
+        .add_message::<FollowRequest>()
+        .add_channel::<RpcChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedReliable(ReliableSettings::default()),
+        )
+        .add_channel::<SupersedeChannel>(
+            ChannelDirection::Bidirectional,
+            ChannelMode::UnorderedReliable(supersede_settings),
+        )
+        .build()
+}
+
+// Writes a single packet from `sender` and reads it into `receiver`, then
+// hands the received Messages to the application
+fn transfer(protocol: &Protocol, sender: &mut MessageManager, receiver: &mut MessageManager) {
+    let bytes = write_packet(protocol, sender, 0);
+    assert!(read_packet(protocol, receiver, &bytes).is_empty());
+}
+
+fn send_ping(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    value: u32,
+    timeout: &Duration,
+) -> RequestId {
+    send_ping_over::<RpcChannel>(protocol, manager, value, timeout)
+}
+
+fn send_ping_over<C: Channel>(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    value: u32,
+    timeout: &Duration,
+) -> RequestId {
+    let request =
+        MessageContainer::from_write(Box::new(PingRequest { value }), &mut FakeEntityConverter);
+    manager.send_request(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<C>(),
+        request,
+        timeout,
+    )
+}
+
+#[test]
+fn response_is_matched_to_request() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    let timeout = Duration::from_secs(10);
+    send_ping(&protocol, &mut client, 1, &timeout);
+    let second_id = send_ping(&protocol, &mut client, 2, &timeout);
+    transfer(&protocol, &mut client, &mut server);
+
+    let requests = server.receive_requests();
+    assert_eq!(requests.len(), 2);
+
+    // answer only the second Request
+    let (channel_kind, id, request) = requests
+        .into_iter()
+        .find(|(_, id, _)| *id == second_id)
+        .unwrap();
+    let request = request.to_boxed_any().downcast::<PingRequest>().unwrap();
+    let response = MessageContainer::from_write(
+        Box::new(PongResponse {
+            value: request.value * 10,
+        }),
+        &mut FakeEntityConverter,
+    );
+    server.send_response(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &channel_kind,
+        &id,
+        response,
+    );
+    transfer(&protocol, &mut server, &mut client);
+
+    let mut responses = client.receive_responses();
+    assert_eq!(responses.len(), 1);
+    let (_, id, response) = responses.pop().unwrap();
+    assert_eq!(id, second_id);
+    let response = response.to_boxed_any().downcast::<PongResponse>().unwrap();
+    assert_eq!(response.value, 20);
+    assert!(client.receive_request_timeouts().is_empty());
+}
+
+#[test]
+fn unanswered_request_times_out() {
+    let protocol = protocol();
+    let (_, mut client) = managers(&protocol);
+
+    let id = send_ping(&protocol, &mut client, 1, &Duration::from_millis(100));
+
+    client.collect_outgoing_messages(&Instant::now(), &0.0);
+    assert!(client.receive_request_timeouts().is_empty());
+
+    let mut later = Instant::now();
+    later.add_millis(200);
+    client.collect_outgoing_messages(&later, &0.0);
+    assert_eq!(client.receive_request_timeouts(), vec![id]);
+}
+
+#[test]
+fn requests_are_not_superseded() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    // both Requests are framed as the same kind, but neither replaces the other
+    let timeout = Duration::from_secs(10);
+    let first_id = send_ping_over::<SupersedeChannel>(&protocol, &mut client, 1, &timeout);
+    let second_id = send_ping_over::<SupersedeChannel>(&protocol, &mut client, 2, &timeout);
+    transfer(&protocol, &mut client, &mut server);
+
+    let mut requests: Vec<(RequestId, u32)> = server
+        .receive_requests()
+        .into_iter()
+        .map(|(_, id, request)| {
+            let request = request.to_boxed_any().downcast::<PingRequest>().unwrap();
+            (id, request.value)
+        })
+        .collect();
+    requests.sort_by_key(|(_, value)| *value);
+    assert_eq!(requests, vec![(first_id, 1), (second_id, 2)]);
+    assert!(client.receive_dropped_messages().is_empty());
+}
+
+// Writes a RequestFrame carrying `message` as it would go over the wire
+fn framed_bytes(protocol: &Protocol, message: Vec<u8>) -> Box<[u8]> {
+    let frame = RequestFrame::new(RequestId::zero(), false, message);
+    let container = MessageContainer::from_write(Box::new(frame), &mut FakeEntityConverter);
+    let mut writer = BitWriter::new();
+    container.write(
+        &protocol.message_kinds,
+        &mut writer,
+        &mut FakeEntityConverter,
+    );
+    writer.to_bytes()
+}
+
+#[test]
+fn malformed_request_is_an_error() {
+    let protocol = protocol();
+
+    let bytes = framed_bytes(&protocol, Vec::new());
+
+    let mut reader = BitReader::new(&bytes);
+    assert!(protocol
+        .message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .is_err());
+}
+
+#[test]
+fn request_waits_on_its_entities() {
+    let protocol = protocol();
+
+    // a FollowRequest targeting an Entity the receiver has not seen yet
+    let mut writer = BitWriter::new();
+    MessageKind::of::<FollowRequest>().ser(&protocol.message_kinds, &mut writer);
+    true.ser(&mut writer);
+    LocalEntity::Host(7).owned_ser(&mut writer);
+    let bytes = framed_bytes(&protocol, writer.to_bytes().to_vec());
+
+    let mut reader = BitReader::new(&bytes);
+    let frame = protocol
+        .message_kinds
+        .read(&mut reader, &NoEntities)
+        .unwrap();
+    assert_eq!(frame.relations_waiting().map(|set| set.len()), Some(1));
+
+    let (frame, request) = frame.into_frame::<RequestFrame>().unwrap();
+    assert!(!frame.is_response());
+    assert!(request.kind() == MessageKind::of::<FollowRequest>());
+}
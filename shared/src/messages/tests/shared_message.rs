@@ -0,0 +1,151 @@
+use naia_derive::MessageInternal;
+use naia_serde::BitWriter;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings},
+    ChannelKind, EntityProperty, FakeEntityConverter, InternedString, Message, MessageContainer,
+    Protocol,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct Announcement {
+    pub round: u16,
+    pub text: String,
+    pub urgent: bool,
+}
+
+#[derive(MessageInternal)]
+pub struct Follow {
+    pub target: EntityProperty,
+}
+
+#[derive(MessageInternal)]
+pub struct SpellCast {
+    pub spell: InternedString,
+}
+
+struct BroadcastChannel;
+impl Channel for BroadcastChannel {}
+
+fn written_bytes(protocol: &Protocol, message: &MessageContainer) -> Box<[u8]> {
+    let mut writer = BitWriter::new();
+    message.write(
+        &protocol.message_kinds,
+        &mut writer,
+        &mut FakeEntityConverter,
+    );
+    writer.to_bytes()
+}
+
+#[test]
+fn shared_message_writes_same_bits() {
+    let protocol = Protocol::builder().add_message::<Announcement>().build();
+    let announcement = || {
+        Box::new(Announcement {
+            round: 3,
+            text: "sudden death".to_string(),
+            urgent: true,
+        })
+    };
+
+    let shared = MessageContainer::from_write_shared(announcement(), &protocol.message_kinds);
+    let owned = MessageContainer::from_write(announcement(), &mut FakeEntityConverter);
+
+    assert_eq!(shared.bit_length(), owned.bit_length());
+    assert_eq!(
+        written_bytes(&protocol, &shared),
+        written_bytes(&protocol, &owned)
+    );
+}
+
+#[test]
+#[should_panic]
+fn entity_message_cannot_be_shared() {
+    let protocol = Protocol::builder().add_message::<Follow>().build();
+    let follow = Follow {
+        target: EntityProperty::new(),
+    };
+    assert!(follow.has_entity_relations());
+
+    MessageContainer::from_write_shared(Box::new(follow), &protocol.message_kinds);
+}
+
+#[test]
+fn clones_share_the_message() {
+    let protocol = Protocol::builder().add_message::<Announcement>().build();
+    let shared = MessageContainer::from_write_shared(
+        Box::new(Announcement {
+            round: 3,
+            text: "sudden death".to_string(),
+            urgent: true,
+        }),
+        &protocol.message_kinds,
+    );
+    let queued = shared.clone();
+
+    // the Message is copied out only while another clone still holds it
+    let first = shared.to_boxed_any().downcast::<Announcement>().unwrap();
+    let last = queued.to_boxed_any().downcast::<Announcement>().unwrap();
+    assert_eq!(first.text, "sudden death");
+    assert_eq!(last.text, "sudden death");
+}
+
+#[test]
+fn shared_message_replays_only_bits_written() {
+    let protocol = Protocol::builder()
+        .add_message::<SpellCast>()
+        .add_message::<Announcement>()
+        .add_channel::<BroadcastChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .build();
+    let (mut server, mut client) = managers(&protocol);
+
+    // an InternedString is written in fewer bits than its bit_length allows,
+    // so the Message queued after it must still be read from the right place
+    let cast = MessageContainer::from_write_shared(
+        Box::new(SpellCast {
+            spell: InternedString::new("fireball"),
+        }),
+        &protocol.message_kinds,
+    );
+    let announcement = MessageContainer::from_write_shared(
+        Box::new(Announcement {
+            round: 3,
+            text: "sudden death".to_string(),
+            urgent: true,
+        }),
+        &protocol.message_kinds,
+    );
+    for message in [cast, announcement] {
+        server.send_message(
+            &protocol.message_kinds,
+            &mut FakeEntityConverter,
+            &ChannelKind::of::<BroadcastChannel>(),
+            message,
+        );
+    }
+    let packet = write_packet(&protocol, &mut server, 0);
+
+    let mut received = read_packet(&protocol, &mut client, &packet).into_iter();
+    let cast = received
+        .next()
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<SpellCast>()
+        .unwrap();
+    assert_eq!(cast.spell.as_str(), "fireball");
+    let announcement = received
+        .next()
+        .unwrap()
+        .to_boxed_any()
+        .downcast::<Announcement>()
+        .unwrap();
+    assert_eq!(announcement.round, 3);
+    assert_eq!(announcement.text, "sudden death");
+    assert!(announcement.urgent);
+    assert!(received.next().is_none());
+}
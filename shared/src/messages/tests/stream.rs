@@ -0,0 +1,202 @@
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, BitWriter, Serde};
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, StreamSettings},
+    world::remote::entity_waitlist::EntityWaitlist,
+    ChannelKind, FakeEntityConverter, MessageContainer, MessageManager, Protocol, StreamId,
+    StreamOptions, StreamUpdate,
+};
+
+use super::managers;
+
+#[derive(MessageInternal)]
+pub struct AssetMessage {
+    pub name: String,
+}
+
+struct AssetChannel;
+impl Channel for AssetChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<AssetMessage>()
+        .add_channel::<AssetChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::Stream(StreamSettings {
+                rtt_resend_factor: 1.5,
+                chunk_size: 100,
+                max_chunks_in_flight: 2,
+            }),
+        )
+        .build()
+}
+
+// Writes a single packet from `sender` and reads it into `receiver`
+fn transfer(
+    protocol: &Protocol,
+    sender: &mut MessageManager,
+    receiver: &mut MessageManager,
+    packet_index: u16,
+) {
+    sender.collect_outgoing_messages(&Instant::now(), &0.0);
+
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    writer.reserve_bits(1);
+    sender.write_messages(
+        protocol,
+        &mut FakeEntityConverter,
+        &mut writer,
+        packet_index,
+        &mut has_written,
+    );
+    writer.release_bits(1);
+    false.ser(&mut writer);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let mut entity_waitlist = EntityWaitlist::new();
+    receiver
+        .read_messages(
+            protocol,
+            &mut entity_waitlist,
+            &FakeEntityConverter,
+            &mut reader,
+        )
+        .unwrap();
+}
+
+fn open_asset_stream(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    options: StreamOptions,
+) -> StreamId {
+    let metadata = MessageContainer::from_write(
+        Box::new(AssetMessage {
+            name: "map.bin".to_string(),
+        }),
+        &mut FakeEntityConverter,
+    );
+    manager.open_stream(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<AssetChannel>(),
+        metadata,
+        options,
+    )
+}
+
+#[test]
+fn stream_respects_window_and_reports_delivery() {
+    let protocol = protocol();
+    let channel_kind = ChannelKind::of::<AssetChannel>();
+    let (mut server, mut client) = managers(&protocol);
+
+    let id = open_asset_stream(&protocol, &mut server, StreamOptions::default());
+    let content: Vec<u8> = (0..450).map(|i| i as u8).collect();
+    server.write_stream(&channel_kind, &id, &content);
+    server.finish_stream(&channel_kind, &id);
+
+    // no more than the window of chunks is sent
+    transfer(&protocol, &mut server, &mut client, 0);
+    let progress = server.stream_progress(&channel_kind, &id).unwrap();
+    assert_eq!(progress.written, 450);
+    assert_eq!(progress.sent, 200);
+    assert_eq!(progress.acknowledged, 0);
+
+    // nothing further is queued until the window is acknowledged
+    transfer(&protocol, &mut server, &mut client, 1);
+    let progress = server.stream_progress(&channel_kind, &id).unwrap();
+    assert_eq!(progress.sent, 200);
+
+    let mut packet_index = 1;
+    let mut received = Vec::new();
+    let mut finished = false;
+    let mut delivered = false;
+    for _ in 0..10 {
+        server.notify_packet_delivered(packet_index);
+        packet_index += 1;
+        transfer(&protocol, &mut server, &mut client, packet_index);
+
+        for (_, update) in client.receive_stream_updates() {
+            match update {
+                StreamUpdate::Opened { metadata, .. } => {
+                    let metadata = metadata.to_boxed_any().downcast::<AssetMessage>().unwrap();
+                    assert_eq!(metadata.name, "map.bin");
+                }
+                StreamUpdate::Data { offset, bytes, .. } => {
+                    assert_eq!(offset, received.len() as u64);
+                    received.extend(bytes);
+                }
+                StreamUpdate::Finished { .. } => finished = true,
+                _ => panic!("unexpected update on the receiving side"),
+            }
+        }
+        for (_, update) in server.receive_stream_updates() {
+            if let StreamUpdate::Delivered { id: delivered_id } = update {
+                assert_eq!(delivered_id, id);
+                delivered = true;
+            }
+        }
+    }
+
+    assert_eq!(received, content);
+    assert!(finished);
+    assert!(delivered);
+    assert!(server.stream_progress(&channel_kind, &id).is_none());
+}
+
+#[test]
+fn rejected_stream_stops_sending() {
+    let protocol = protocol();
+    let channel_kind = ChannelKind::of::<AssetChannel>();
+    let (mut server, mut client) = managers(&protocol);
+
+    let mut options = StreamOptions::default();
+    options.content_hash = Some(0xfeed);
+    let id = open_asset_stream(&protocol, &mut server, options);
+    server.write_stream(&channel_kind, &id, &[0; 1000]);
+    transfer(&protocol, &mut server, &mut client, 0);
+
+    let mut opened = None;
+    for (_, update) in client.receive_stream_updates() {
+        if let StreamUpdate::Opened {
+            id, content_hash, ..
+        } = update
+        {
+            opened = Some((id, content_hash));
+        }
+    }
+    assert_eq!(opened, Some((id, Some(0xfeed))));
+
+    // receiver already has this content cached
+    client.reject_stream(&channel_kind, &id);
+    transfer(&protocol, &mut client, &mut server, 0);
+
+    let rejected = server
+        .receive_stream_updates()
+        .into_iter()
+        .any(|(_, update)| matches!(update, StreamUpdate::Rejected { .. }));
+    assert!(rejected);
+    assert!(server.stream_progress(&channel_kind, &id).is_none());
+}
+
+#[test]
+fn stream_id_in_use_is_skipped_after_wraparound() {
+    let protocol = protocol();
+    let channel_kind = ChannelKind::of::<AssetChannel>();
+    let (mut server, _) = managers(&protocol);
+
+    let long_lived = open_asset_stream(&protocol, &mut server, StreamOptions::default());
+    for _ in 0..u16::MAX {
+        let id = open_asset_stream(&protocol, &mut server, StreamOptions::default());
+        assert_ne!(id, long_lived);
+        server.cancel_stream(&channel_kind, &id);
+    }
+
+    let id = open_asset_stream(&protocol, &mut server, StreamOptions::default());
+    assert_ne!(id, long_lived);
+    assert!(server.stream_progress(&channel_kind, &long_lived).is_some());
+}
@@ -0,0 +1,90 @@
+use naia_derive::MessageInternal;
+use naia_serde::BitReader;
+
+use crate::{
+    messages::channels::channel::{
+        Channel, ChannelDirection, ChannelMode, ChannelSettings, TickBufferSettings,
+    },
+    world::remote::entity_waitlist::EntityWaitlist,
+    ChannelKind, FakeEntityConverter, MessageContainer, MessageManager, Protocol, Tick,
+};
+
+use super::{managers, read_packet, write_packet};
+
+#[derive(MessageInternal)]
+pub struct RoundStart {
+    pub round: u8,
+}
+
+struct ScheduledChannel;
+impl Channel for ScheduledChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<RoundStart>()
+        .add_channel::<ScheduledChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::TickBuffered(TickBufferSettings::default()),
+        )
+        .build()
+}
+
+fn send_round(protocol: &Protocol, manager: &mut MessageManager, tick: Tick, round: u8) {
+    let message =
+        MessageContainer::from_write(Box::new(RoundStart { round }), &mut FakeEntityConverter);
+    manager.send_tick_buffered_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<ScheduledChannel>(),
+        &tick,
+        message,
+    );
+}
+
+#[test]
+fn message_is_received_with_its_tick() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_round(&protocol, &mut server, 42, 3);
+
+    // the Message is held back rather than handed over straight away
+    let packet = write_packet(&protocol, &mut server, 0);
+    assert!(read_packet(&protocol, &mut client, &packet).is_empty());
+
+    let mut scheduled = client.receive_tick_buffered_messages();
+    assert_eq!(scheduled.len(), 1);
+    let (channel_kind, tick, message) = scheduled.pop().unwrap();
+    assert!(channel_kind == ChannelKind::of::<ScheduledChannel>());
+    assert_eq!(tick, 42);
+    let message = message.to_boxed_any().downcast::<RoundStart>().unwrap();
+    assert_eq!(message.round, 3);
+}
+
+#[test]
+fn truncated_packet_is_an_error() {
+    let protocol = protocol();
+    let (mut server, mut client) = managers(&protocol);
+
+    send_round(&protocol, &mut server, 42, 3);
+    let packet = write_packet(&protocol, &mut server, 0);
+
+    let mut reader = BitReader::new(&packet[..packet.len() - 1]);
+    let result = client.read_messages(
+        &protocol,
+        &mut EntityWaitlist::new(),
+        &FakeEntityConverter,
+        &mut reader,
+    );
+    assert!(result.is_err());
+    assert!(client.receive_tick_buffered_messages().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn bidirectional_tick_buffered_channel_is_rejected() {
+    ChannelSettings::new(
+        ChannelMode::TickBuffered(TickBufferSettings::default()),
+        ChannelDirection::Bidirectional,
+    );
+}
@@ -0,0 +1,111 @@
+use naia_derive::MessageInternal;
+use naia_serde::BitWriter;
+use naia_socket_shared::Instant;
+
+use crate::{
+    messages::channels::channel::{Channel, ChannelDirection, ChannelMode, ReliableSettings},
+    ChannelKind, FakeEntityConverter, HostType, MessageContainer, MessageHandle, MessageManager,
+    Protocol,
+};
+
+#[derive(MessageInternal)]
+pub struct PurchaseMessage {
+    pub receipt: String,
+}
+
+struct ReliableChannel;
+impl Channel for ReliableChannel {}
+
+fn protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<PurchaseMessage>()
+        .add_channel::<ReliableChannel>(
+            ChannelDirection::ServerToClient,
+            ChannelMode::UnorderedReliable(ReliableSettings::default()),
+        )
+        .build()
+}
+
+fn send_purchase(
+    protocol: &Protocol,
+    manager: &mut MessageManager,
+    receipt: &str,
+) -> MessageHandle {
+    let message = MessageContainer::from_write(
+        Box::new(PurchaseMessage {
+            receipt: receipt.to_string(),
+        }),
+        &mut FakeEntityConverter,
+    );
+    manager.send_tracked_message(
+        &protocol.message_kinds,
+        &mut FakeEntityConverter,
+        &ChannelKind::of::<ReliableChannel>(),
+        message,
+        None,
+    )
+}
+
+// Writes a single packet under the given index, then returns whether anything
+// was written to it
+fn write_packet(protocol: &Protocol, manager: &mut MessageManager, packet_index: u16) -> bool {
+    manager.collect_outgoing_messages(&Instant::now(), &10000.0);
+
+    let mut writer = BitWriter::new();
+    let mut has_written = false;
+    writer.reserve_bits(1);
+    manager.write_messages(
+        protocol,
+        &mut FakeEntityConverter,
+        &mut writer,
+        packet_index,
+        &mut has_written,
+    );
+    has_written
+}
+
+#[test]
+fn tracked_message_reported_once_delivered() {
+    let protocol = protocol();
+    let mut manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+
+    let first = send_purchase(&protocol, &mut manager, "sword");
+    write_packet(&protocol, &mut manager, 0);
+    let second = send_purchase(&protocol, &mut manager, "shield");
+    write_packet(&protocol, &mut manager, 1);
+    assert_ne!(first, second);
+
+    manager.notify_packet_delivered(1);
+    assert_eq!(manager.receive_delivered_messages(), vec![second]);
+
+    // a duplicate acknowledgement is not reported again
+    manager.notify_packet_delivered(1);
+    assert!(manager.receive_delivered_messages().is_empty());
+
+    assert_eq!(manager.take_undelivered_messages(), vec![first]);
+    manager.notify_packet_delivered(0);
+    assert!(manager.receive_delivered_messages().is_empty());
+}
+
+#[test]
+fn fragmented_message_delivered_with_last_fragment() {
+    let protocol = protocol();
+    let mut manager = MessageManager::new(HostType::Server, &protocol.channel_kinds);
+
+    let receipt = "x".repeat(1500);
+    let handle = send_purchase(&protocol, &mut manager, &receipt);
+
+    let mut packet_index = 0;
+    while write_packet(&protocol, &mut manager, packet_index) {
+        packet_index += 1;
+    }
+    assert!(packet_index > 1);
+
+    for index in 0..packet_index - 1 {
+        manager.notify_packet_delivered(index);
+    }
+    assert!(manager.receive_delivered_messages().is_empty());
+
+    manager.notify_packet_delivered(packet_index - 1);
+    assert_eq!(manager.receive_delivered_messages(), vec![handle]);
+}
@@ -0,0 +1,28 @@
+use naia_derive::MessageInternal;
+
+use crate::{messages::message_frame::MessageFrame, Tick};
+
+// TickBufferedFrame
+/// Carries a Message sent from the Server over a Tick Buffered Channel, tagged
+/// with the server Tick at which the Client should receive it
+#[derive(MessageInternal)]
+pub struct TickBufferedFrame {
+    tick: Tick,
+    message: Vec<u8>,
+}
+
+impl TickBufferedFrame {
+    pub(crate) fn new(tick: Tick, message: Vec<u8>) -> Self {
+        Self { tick, message }
+    }
+
+    pub(crate) fn tick(&self) -> Tick {
+        self.tick
+    }
+}
+
+impl MessageFrame for TickBufferedFrame {
+    fn take_message(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.message)
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, error::Error, fmt, hash::Hash, time::Duration};
 
 use naia_socket_shared::{LinkConditionerConfig, SocketConfig};
 
@@ -6,17 +6,145 @@ use crate::{
     connection::compression_config::CompressionConfig,
     messages::{
         channels::{
-            channel::{Channel, ChannelDirection, ChannelMode, ChannelSettings},
+            channel::{Channel, ChannelDirection, ChannelMode, ChannelSettings, FecSettings},
             channel_kinds::ChannelKinds,
             default_channels::DefaultChannelsPlugin,
         },
+        expired_message::ExpiredMessage,
         fragment::FragmentedMessage,
+        lane_frame::LaneFrame,
         message::Message,
         message_kinds::MessageKinds,
+        request::RequestFrame,
+        stream::StreamFrame,
+        tick_buffered_frame::TickBufferedFrame,
     },
+    schema::ProtocolSchema,
     world::component::{component_kinds::ComponentKinds, replicate::Replicate},
 };
 
+// Fingerprint Hasher
+
+/// A 64-bit FNV-1a hasher. Unlike `DefaultHasher`, its output is guaranteed to
+/// be the same across builds and platforms, so Client and Server agree on it.
+pub(crate) struct FingerprintHasher {
+    state: u64,
+}
+
+impl FingerprintHasher {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    pub fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Writes the length before the string, so that adjacent strings can't
+    /// run into each other
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+// Protocol Error
+
+/// Why the kinds registered in a Protocol can't be given ids
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtocolError {
+    /// More kinds of one sort were registered than there are ids for
+    TooManyKinds {
+        kind_label: &'static str,
+        count: usize,
+    },
+    /// Two different kinds of one sort share a protocol name
+    DuplicateProtocolName {
+        kind_label: &'static str,
+        protocol_name: String,
+    },
+}
+
+impl Error for ProtocolError {}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooManyKinds { kind_label, count } => write!(
+                f,
+                "Can't register more than {} {} kinds in a Protocol, {} were registered!",
+                u16::MAX,
+                kind_label,
+                count
+            ),
+            Self::DuplicateProtocolName {
+                kind_label,
+                protocol_name,
+            } => write!(
+                f,
+                "Two different {} kinds share the protocol name `{}`, so they can't be given ids!",
+                kind_label, protocol_name
+            ),
+        }
+    }
+}
+
+// Kind Ids
+
+/// Hands out the next id to a newly registered kind. Ids are reassigned once
+/// the Protocol is built, which fails if too many kinds were registered.
+pub(crate) fn next_net_id(current_net_id: &mut u16) -> u16 {
+    let net_id = *current_net_id;
+    *current_net_id = net_id.wrapping_add(1);
+    net_id
+}
+
+/// Orders the registered kinds by their protocol names, so that Client and
+/// Server give them the same ids whatever order they were registered in, and
+/// whichever compiler built them. Fails if there are more kinds than ids, or
+/// if two different kinds have the same protocol name.
+pub(crate) fn sort_by_protocol_name<K: Copy + Eq + Hash>(
+    protocol_names: &HashMap<K, String>,
+    kind_label: &'static str,
+) -> Result<Vec<K>, ProtocolError> {
+    if protocol_names.len() > u16::MAX as usize {
+        return Err(ProtocolError::TooManyKinds {
+            kind_label,
+            count: protocol_names.len(),
+        });
+    }
+    let mut kinds: Vec<(&str, K)> = protocol_names
+        .iter()
+        .map(|(kind, protocol_name)| (protocol_name.as_str(), *kind))
+        .collect();
+    kinds.sort_by_key(|(protocol_name, _)| *protocol_name);
+    for pair in kinds.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            return Err(ProtocolError::DuplicateProtocolName {
+                kind_label,
+                protocol_name: pair[0].0.to_string(),
+            });
+        }
+    }
+    Ok(kinds.into_iter().map(|(_, kind)| kind).collect())
+}
+
 // Protocol Plugin
 pub trait ProtocolPlugin {
     fn build(&self, protocol: &mut Protocol);
@@ -35,6 +163,7 @@ pub struct Protocol {
     pub compression: Option<CompressionConfig>,
     /// Whether or not Client Authoritative Entities will be allowed
     pub client_authoritative_entities: bool,
+    fingerprint: u64,
     locked: bool,
 }
 
@@ -42,6 +171,11 @@ impl Default for Protocol {
     fn default() -> Self {
         let mut message_kinds = MessageKinds::new();
         message_kinds.add_message::<FragmentedMessage>();
+        message_kinds.add_message::<StreamFrame>();
+        message_kinds.add_frame::<RequestFrame>();
+        message_kinds.add_message::<ExpiredMessage>();
+        message_kinds.add_frame::<TickBufferedFrame>();
+        message_kinds.add_frame::<LaneFrame>();
         Self {
             channel_kinds: ChannelKinds::new(),
             message_kinds,
@@ -50,6 +184,7 @@ impl Default for Protocol {
             tick_interval: Duration::from_millis(50),
             compression: None,
             client_authoritative_entities: false,
+            fingerprint: 0,
             locked: false,
         }
     }
@@ -114,6 +249,36 @@ impl Protocol {
         self
     }
 
+    /// Adds a Channel which shares packet space with other Channels according
+    /// to the given priority and weight. See [`ChannelSettings`].
+    pub fn add_channel_with_priority<C: Channel>(
+        &mut self,
+        direction: ChannelDirection,
+        mode: ChannelMode,
+        priority: u8,
+        weight: u16,
+    ) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds.add_channel::<C>(
+            ChannelSettings::new(mode, direction).with_priority(priority, weight),
+        );
+        self
+    }
+
+    /// Adds an unreliable Channel protected by forward error correction. See
+    /// [`FecSettings`].
+    pub fn add_channel_with_fec<C: Channel>(
+        &mut self,
+        direction: ChannelDirection,
+        mode: ChannelMode,
+        settings: FecSettings,
+    ) -> &mut Self {
+        self.check_lock();
+        self.channel_kinds
+            .add_channel::<C>(ChannelSettings::new(mode, direction).with_fec(settings));
+        self
+    }
+
     pub fn add_message<M: Message>(&mut self) -> &mut Self {
         self.check_lock();
         self.message_kinds.add_message::<M>();
@@ -126,8 +291,14 @@ impl Protocol {
         self
     }
 
+    /// # Panics
+    /// If the registered kinds can't be given ids, see [`ProtocolError`]
     pub fn lock(&mut self) {
         self.check_lock();
+        if let Err(error) = self.assign_net_ids() {
+            panic!("{}", error);
+        }
+        self.fingerprint = self.compute_fingerprint();
         self.locked = true;
     }
 
@@ -137,7 +308,54 @@ impl Protocol {
         }
     }
 
+    /// # Panics
+    /// If the registered kinds can't be given ids, see [`Protocol::try_build`]
     pub fn build(&mut self) -> Self {
-        std::mem::take(self)
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the Protocol, or returns why the registered kinds can't be
+    /// given ids
+    pub fn try_build(&mut self) -> Result<Self, ProtocolError> {
+        let mut protocol = std::mem::take(self);
+        protocol.assign_net_ids()?;
+        protocol.fingerprint = protocol.compute_fingerprint();
+        Ok(protocol)
+    }
+
+    /// A hash over everything which affects how Client and Server read each
+    /// other's packets. Client and Server must agree on this to connect.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Describes every registered Channel, Message & Component, for tooling
+    /// which reads naia packets outside of Rust. See [`ProtocolSchema`].
+    pub fn schema(&self) -> ProtocolSchema {
+        ProtocolSchema {
+            fingerprint: self.compute_fingerprint(),
+            channels: self.channel_kinds.schema(),
+            messages: self.message_kinds.schema(),
+            components: self.component_kinds.schema(),
+        }
+    }
+
+    /// Gives every Channel, Message & Component its id on the wire, in order
+    /// of protocol name
+    fn assign_net_ids(&mut self) -> Result<(), ProtocolError> {
+        self.channel_kinds.assign_net_ids()?;
+        self.message_kinds.assign_net_ids()?;
+        self.component_kinds.assign_net_ids()?;
+        Ok(())
+    }
+
+    fn compute_fingerprint(&self) -> u64 {
+        let mut hasher = FingerprintHasher::new();
+        self.channel_kinds.fingerprint(&mut hasher);
+        self.message_kinds.fingerprint(&mut hasher);
+        self.component_kinds.fingerprint(&mut hasher);
+        hasher.write_u64(self.tick_interval.as_nanos() as u64);
+        hasher.write(&[self.client_authoritative_entities as u8]);
+        hasher.finish()
     }
 }
@@ -0,0 +1,344 @@
+//! A machine-readable description of a Protocol, for tooling written in other
+//! languages (e.g. generated decoders or packet dissectors).
+//!
+//! Get one with `Protocol::schema()`, and write it out with
+//! [`ProtocolSchema::to_json`]. The field metadata comes from the `Message` &
+//! `Replicate` derives.
+
+use std::{fmt::Write, marker::PhantomData};
+
+use naia_serde::ConstBitLength;
+
+use crate::messages::channels::channel::{ChannelDirection, ChannelMode, ChannelSettings};
+
+/// Every Channel, Message & Component registered in a Protocol, in the order
+/// of their ids on the wire
+pub struct ProtocolSchema {
+    pub fingerprint: u64,
+    pub channels: Vec<ChannelSchema>,
+    pub messages: Vec<KindSchema>,
+    pub components: Vec<KindSchema>,
+}
+
+pub struct ChannelSchema {
+    pub id: u16,
+    pub name: String,
+    pub settings: ChannelSettings,
+}
+
+/// A registered Message or Component
+pub struct KindSchema {
+    pub id: u16,
+    pub name: String,
+    /// The fields in the order they are written. For an enum Component, its
+    /// variant followed by the fields of every variant, of which only those of
+    /// the written variant are present
+    pub fields: Vec<FieldSchema>,
+}
+
+pub struct FieldSchema {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    /// The field's type as written in its struct. For a Property, the type
+    /// inside of it
+    pub type_name: &'static str,
+    pub encoding: FieldEncoding,
+    /// The number of bits the field is always written with, or None if that
+    /// depends on its value
+    pub bit_length: Option<u32>,
+    /// The length limit given through `#[naia(max_len = N)]`, if any
+    pub max_len: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A plain field of a Message
+    Field,
+    Property,
+    EntityProperty,
+    /// The variant of an enum Component, written before the fields of that
+    /// variant. Its `type_name` lists the variants in the order of their
+    /// indices
+    Variant,
+}
+
+/// How a field is written, as given through `#[naia(..)]` attributes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldEncoding {
+    /// The type's own `Serde` impl
+    Serde,
+    /// `#[naia(bits = N)]`: a sign bit for signed types, then `N` bits
+    Bits(u8),
+    /// `#[naia(range = MIN..=MAX)]`: the offset from `MIN`
+    Range { min: i128, max: i128 },
+    /// `#[naia(with = path)]`: the given module's functions
+    With(&'static str),
+    /// `#[naia(serde_rs)]`: the type's serde-rs impls, through `serde_bridge`
+    SerdeRs,
+    /// `#[naia(skip)]`: never written
+    Skip,
+    /// An EntityProperty, written as a possibly unset entity
+    Entity,
+}
+
+impl ProtocolSchema {
+    /// Writes the schema as a JSON object. The fingerprint is written as a
+    /// hex string, as it doesn't fit in a JSON number without losing precision
+    pub fn to_json(&self) -> String {
+        let mut output = String::new();
+        output.push('{');
+        write!(output, "\"fingerprint\":\"{:016x}\"", self.fingerprint).unwrap();
+
+        output.push_str(",\"channels\":[");
+        for (index, channel) in self.channels.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+            channel.write_json(&mut output);
+        }
+
+        output.push_str("],\"messages\":[");
+        for (index, message) in self.messages.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+            message.write_json(&mut output);
+        }
+
+        output.push_str("],\"components\":[");
+        for (index, component) in self.components.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+            component.write_json(&mut output);
+        }
+        output.push_str("]}");
+
+        output
+    }
+}
+
+impl ChannelSchema {
+    fn write_json(&self, output: &mut String) {
+        let settings = &self.settings;
+        write!(
+            output,
+            "{{\"id\":{},\"name\":{},\"mode\":\"{}\",\"direction\":\"{}\",\"priority\":{},\"weight\":{},\"fec_group_size\":",
+            self.id,
+            json_string(&self.name),
+            mode_name(&settings.mode),
+            direction_name(&settings.direction),
+            settings.priority,
+            settings.weight,
+        )
+        .unwrap();
+        match &settings.fec {
+            Some(fec) => write!(output, "{}", fec.group_size).unwrap(),
+            None => output.push_str("null"),
+        }
+        output.push('}');
+    }
+}
+
+impl KindSchema {
+    fn write_json(&self, output: &mut String) {
+        write!(
+            output,
+            "{{\"id\":{},\"name\":{},\"fields\":[",
+            self.id,
+            json_string(&self.name)
+        )
+        .unwrap();
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+            field.write_json(output);
+        }
+        output.push_str("]}");
+    }
+}
+
+impl FieldSchema {
+    fn write_json(&self, output: &mut String) {
+        let kind = match self.kind {
+            FieldKind::Field => "Field",
+            FieldKind::Property => "Property",
+            FieldKind::EntityProperty => "EntityProperty",
+            FieldKind::Variant => "Variant",
+        };
+        write!(
+            output,
+            "{{\"name\":{},\"kind\":\"{}\",\"type\":{},\"encoding\":",
+            json_string(self.name),
+            kind,
+            json_string(self.type_name)
+        )
+        .unwrap();
+        match self.encoding {
+            FieldEncoding::Serde => output.push_str("{\"type\":\"serde\"}"),
+            FieldEncoding::Bits(bits) => {
+                write!(output, "{{\"type\":\"bits\",\"bits\":{}}}", bits).unwrap()
+            }
+            FieldEncoding::Range { min, max } => write!(
+                output,
+                "{{\"type\":\"range\",\"min\":{},\"max\":{}}}",
+                min, max
+            )
+            .unwrap(),
+            FieldEncoding::With(path) => write!(
+                output,
+                "{{\"type\":\"with\",\"path\":{}}}",
+                json_string(path)
+            )
+            .unwrap(),
+            FieldEncoding::SerdeRs => output.push_str("{\"type\":\"serde_rs\"}"),
+            FieldEncoding::Skip => output.push_str("{\"type\":\"skip\"}"),
+            FieldEncoding::Entity => output.push_str("{\"type\":\"entity\"}"),
+        }
+        output.push_str(",\"bit_length\":");
+        write_json_option(output, self.bit_length);
+        output.push_str(",\"max_len\":");
+        write_json_option(output, self.max_len);
+        output.push('}');
+    }
+}
+
+fn mode_name(mode: &ChannelMode) -> &'static str {
+    match mode {
+        ChannelMode::UnorderedUnreliable => "UnorderedUnreliable",
+        ChannelMode::SequencedUnreliable => "SequencedUnreliable",
+        ChannelMode::UnorderedReliable(_) => "UnorderedReliable",
+        ChannelMode::SequencedReliable(_) => "SequencedReliable",
+        ChannelMode::OrderedReliable(_) => "OrderedReliable",
+        ChannelMode::TickBuffered(_) => "TickBuffered",
+        ChannelMode::Stream(_) => "Stream",
+        ChannelMode::Keyed(_) => "Keyed",
+    }
+}
+
+fn direction_name(direction: &ChannelDirection) -> &'static str {
+    match direction {
+        ChannelDirection::ClientToServer => "ClientToServer",
+        ChannelDirection::ServerToClient => "ServerToClient",
+        ChannelDirection::Bidirectional => "Bidirectional",
+    }
+}
+
+fn write_json_option<T: std::fmt::Display>(output: &mut String, value: Option<T>) {
+    match value {
+        Some(value) => write!(output, "{}", value).unwrap(),
+        None => output.push_str("null"),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                write!(output, "\\u{:04x}", character as u32).unwrap()
+            }
+            character => output.push(character),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Strips the module path from a type's name, keeping any generic arguments
+/// as they are
+pub(crate) fn short_type_name(full_name: &str) -> String {
+    let path_end = full_name.find('<').unwrap_or(full_name.len());
+    let name_start = full_name[..path_end]
+        .rfind("::")
+        .map_or(0, |index| index + 2);
+    full_name[name_start..].to_string()
+}
+
+// Bit Width
+
+// Used by the derives to find the fixed bit length of a field's type, where
+// it has one. Calling `(&BitWidthProbe::<T>::new()).bit_width()` picks the
+// `ConstBitWidth` impl if `T: ConstBitLength`, and `VariableBitWidth`
+// otherwise.
+
+#[doc(hidden)]
+pub struct BitWidthProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> BitWidthProbe<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Default for BitWidthProbe<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ConstBitWidth {
+    fn bit_width(&self) -> Option<u32>;
+}
+
+impl<T: ConstBitLength> ConstBitWidth for BitWidthProbe<T> {
+    fn bit_width(&self) -> Option<u32> {
+        Some(T::const_bit_length())
+    }
+}
+
+#[doc(hidden)]
+pub trait VariableBitWidth {
+    fn bit_width(&self) -> Option<u32>;
+}
+
+impl<T: ?Sized> VariableBitWidth for &BitWidthProbe<T> {
+    fn bit_width(&self) -> Option<u32> {
+        None
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::{json_string, short_type_name, BitWidthProbe, ConstBitWidth, VariableBitWidth};
+
+    struct SomeChannel;
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(
+            json_string("a \"quoted\"\\path\n\u{1}"),
+            "\"a \\\"quoted\\\"\\\\path\\n\\u0001\""
+        );
+    }
+
+    #[test]
+    fn short_names() {
+        assert_eq!(
+            short_type_name(std::any::type_name::<SomeChannel>()),
+            "SomeChannel"
+        );
+        assert_eq!(
+            short_type_name("alloc::vec::Vec<my_game::Item>"),
+            "Vec<my_game::Item>"
+        );
+    }
+
+    #[test]
+    fn bit_widths() {
+        assert_eq!((&BitWidthProbe::<u16>::new()).bit_width(), Some(16));
+        assert_eq!((&BitWidthProbe::<(bool, u8)>::new()).bit_width(), Some(9));
+        assert_eq!((&BitWidthProbe::<String>::new()).bit_width(), None);
+    }
+}
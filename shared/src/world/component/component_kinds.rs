@@ -1,8 +1,10 @@
 use std::{any::TypeId, collections::HashMap};
 
-use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, ConstBitLength, Serde, SerdeErr, SerdeErrReason};
 
 use crate::{
+    protocol::{next_net_id, sort_by_protocol_name, FingerprintHasher, ProtocolError},
+    schema::KindSchema,
     ComponentFieldUpdate, ComponentUpdate, LocalEntity, LocalEntityAndGlobalEntityConverter,
     Replicate, ReplicateBuilder,
 };
@@ -39,7 +41,9 @@ impl ComponentKind {
 
     pub fn de(component_kinds: &ComponentKinds, reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let net_id: NetId = NetId::de(reader)?;
-        Ok(component_kinds.net_id_to_kind(&net_id))
+        component_kinds
+            .net_id_to_kind(&net_id)
+            .ok_or_else(|| SerdeErr::new(SerdeErrReason::UnknownKind(net_id), reader.bit_offset()))
     }
 }
 
@@ -52,6 +56,7 @@ impl ConstBitLength for ComponentKind {
 /// A map to hold all component types
 pub struct ComponentKinds {
     current_net_id: NetId,
+    protocol_name_map: HashMap<ComponentKind, String>,
     kind_map: HashMap<ComponentKind, (NetId, Box<dyn ReplicateBuilder>)>,
     net_id_map: HashMap<NetId, ComponentKind>,
 }
@@ -60,6 +65,7 @@ impl ComponentKinds {
     pub fn new() -> Self {
         Self {
             current_net_id: 0,
+            protocol_name_map: HashMap::new(),
             kind_map: HashMap::new(),
             net_id_map: HashMap::new(),
         }
@@ -68,12 +74,16 @@ impl ComponentKinds {
     pub fn add_component<C: Replicate>(&mut self) {
         let component_kind = ComponentKind::of::<C>();
 
-        let net_id = self.current_net_id;
+        // registering the same Component again keeps its id
+        let net_id = match self.kind_map.get(&component_kind) {
+            Some((net_id, _)) => *net_id,
+            None => next_net_id(&mut self.current_net_id),
+        };
+        self.protocol_name_map
+            .insert(component_kind, C::protocol_name());
         self.kind_map
             .insert(component_kind, (net_id, C::create_builder()));
         self.net_id_map.insert(net_id, component_kind);
-        self.current_net_id += 1;
-        //TODO: check for current_id overflow?
     }
 
     pub fn read(
@@ -82,16 +92,18 @@ impl ComponentKinds {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<Box<dyn Replicate>, SerdeErr> {
         let component_kind: ComponentKind = ComponentKind::de(self, reader)?;
-        return self
-            .kind_to_builder(&component_kind)
-            .read(reader, converter);
+        let builder = self.kind_to_builder(&component_kind);
+        return builder
+            .read(reader, converter)
+            .map_err(|err| err.in_kind(builder.name()));
     }
 
     pub fn read_create_update(&self, reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
         let component_kind: ComponentKind = ComponentKind::de(self, reader)?;
-        return self
-            .kind_to_builder(&component_kind)
-            .read_create_update(reader);
+        let builder = self.kind_to_builder(&component_kind);
+        return builder
+            .read_create_update(reader)
+            .map_err(|err| err.in_kind(builder.name()));
     }
 
     pub fn split_update(
@@ -106,19 +118,55 @@ impl ComponentKinds {
         ),
         SerdeErr,
     > {
-        return self
-            .kind_to_builder(component_kind)
-            .split_update(converter, update);
+        let builder = self.kind_to_builder(component_kind);
+        return builder
+            .split_update(converter, update)
+            .map_err(|err| err.in_kind(builder.name()));
     }
 
     pub fn kind_to_name(&self, component_kind: &ComponentKind) -> String {
         return self.kind_to_builder(component_kind).name();
     }
 
-    fn net_id_to_kind(&self, net_id: &NetId) -> ComponentKind {
-        return *self.net_id_map.get(net_id).expect(
-            "Must properly initialize Component with Protocol via `add_component()` function!",
-        );
+    /// Gives every Component its id in order of protocol name. See
+    /// [`sort_by_protocol_name`]
+    pub(crate) fn assign_net_ids(&mut self) -> Result<(), ProtocolError> {
+        let component_kinds = sort_by_protocol_name(&self.protocol_name_map, "Component")?;
+        self.net_id_map.clear();
+        for (net_id, component_kind) in component_kinds.into_iter().enumerate() {
+            let net_id = net_id as NetId;
+            self.kind_map.get_mut(&component_kind).unwrap().0 = net_id;
+            self.net_id_map.insert(net_id, component_kind);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn fingerprint(&self, hasher: &mut FingerprintHasher) {
+        hasher.write_u64(self.current_net_id as u64);
+        for net_id in 0..self.current_net_id {
+            let component_kind = self.net_id_map.get(&net_id).unwrap();
+            let builder = self.kind_to_builder(component_kind);
+            hasher.write_str(&builder.name());
+            hasher.write_str(builder.layout());
+        }
+    }
+
+    pub(crate) fn schema(&self) -> Vec<KindSchema> {
+        (0..self.current_net_id)
+            .map(|net_id| {
+                let component_kind = self.net_id_map.get(&net_id).unwrap();
+                let builder = self.kind_to_builder(component_kind);
+                KindSchema {
+                    id: net_id,
+                    name: builder.name(),
+                    fields: builder.fields(),
+                }
+            })
+            .collect()
+    }
+
+    fn net_id_to_kind(&self, net_id: &NetId) -> Option<ComponentKind> {
+        return self.net_id_map.get(net_id).copied();
     }
 
     fn kind_to_net_id(&self, component_kind: &ComponentKind) -> NetId {
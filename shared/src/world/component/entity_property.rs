@@ -1,7 +1,7 @@
 use log::warn;
 use std::hash::Hash;
 
-use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason};
 
 use crate::{
     world::entity::{
@@ -62,6 +62,15 @@ impl EntityProperty {
         }
     }
 
+    /// Get the PropertyMutator tracking changes to the EntityProperty, if one
+    /// has been set
+    pub fn mutator(&self) -> Option<&PropertyMutator> {
+        match &self.inner {
+            EntityRelation::HostOwned(inner) => inner.mutator.as_ref(),
+            EntityRelation::RemoteOwned(_) | EntityRelation::RemoteWaiting(_) => None,
+        }
+    }
+
     // Serialization / deserialization
 
     pub fn write(
@@ -142,7 +151,10 @@ impl EntityProperty {
         converter: &dyn LocalEntityAndGlobalEntityConverter,
     ) -> Result<(), SerdeErr> {
         if self.inner.is_host_owned() {
-            panic!("HostOwned EntityProperty should never read.");
+            return Err(SerdeErr::new(
+                SerdeErrReason::Other("cannot read into a host-owned EntityProperty".into()),
+                reader.bit_offset(),
+            ));
         }
         let exists = bool::de(reader)?;
         let new_inner = {
@@ -0,0 +1,291 @@
+use std::{
+    any::Any,
+    collections::HashSet,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+use naia_serde::{BitReader, BitWrite, SerdeErr};
+
+use crate::{
+    messages::named::Named,
+    schema::FieldSchema,
+    world::{
+        component::{
+            component_kinds::{ComponentKind, ComponentKinds},
+            component_update::ComponentUpdate,
+            diff_mask::DiffMask,
+            property_mutate::PropertyMutator,
+            replica_ref::{ReplicaDynMut, ReplicaDynRef},
+            replicate::{Replicate, ReplicateBuilder},
+        },
+        entity::entity_converters::LocalEntityAndGlobalEntityConverter,
+    },
+    ComponentFieldUpdate, LocalEntity, LocalEntityAndGlobalEntityConverterMut,
+};
+
+/// Implemented by `#[derive(Replicate)]` on an enum, which is replicated as
+/// the Component `EnumComponent` of it. Follows `Replicate` &
+/// `ReplicateBuilder`, other than leaving the ComponentKind to the
+/// `EnumComponent`.
+#[doc(hidden)]
+pub trait ReplicateEnum: Clone + Send + Sync + 'static {
+    fn name() -> String;
+    fn protocol_name() -> String
+    where
+        Self: Sized;
+    fn diff_mask_size() -> u8;
+    fn layout() -> &'static str;
+    fn fields() -> Vec<FieldSchema>;
+    fn read(
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Self, SerdeErr>;
+    fn read_create_update(reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr>;
+    #[allow(clippy::type_complexity)]
+    fn split_update(
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentUpdate,
+    ) -> Result<
+        (
+            Option<Vec<(LocalEntity, ComponentFieldUpdate)>>,
+            Option<ComponentUpdate>,
+        ),
+        SerdeErr,
+    >;
+    /// Sets the value to that of another, which may be of another variant
+    fn mirror(&mut self, other: &Self);
+    /// Gives the Properties of the current variant the PropertyMutator, and
+    /// marks the variant to be rewritten in full
+    fn set_mutator(&mut self, mutator: &PropertyMutator);
+    fn write(
+        &self,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    );
+    fn write_update(
+        &self,
+        diff_mask: &DiffMask,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    );
+    fn read_apply_update(
+        &mut self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentUpdate,
+    ) -> Result<(), SerdeErr>;
+    fn read_apply_field_update(
+        &mut self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentFieldUpdate,
+    ) -> Result<(), SerdeErr>;
+    fn relations_waiting(&self) -> Option<HashSet<LocalEntity>>;
+    fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter);
+}
+
+/// The Component of an enum deriving Replicate. It holds on to the
+/// PropertyMutator it is given when inserted, so that it can switch to
+/// another variant in place through [`EnumComponent::set`], whether or not
+/// the current variant has Properties of its own.
+///
+/// Derefs to the enum, through which the Properties of the current variant
+/// can be changed as those of a struct Component. Assigning a new variant
+/// through it is not replicated.
+pub struct EnumComponent<E: ReplicateEnum> {
+    value: E,
+    mutator: Option<PropertyMutator>,
+}
+
+impl<E: ReplicateEnum> EnumComponent<E> {
+    pub fn new(value: E) -> Self {
+        Self {
+            value,
+            mutator: None,
+        }
+    }
+
+    /// Switches the Component to another value, which is replicated in full
+    pub fn set(&mut self, value: E) {
+        self.value = value;
+        self.give_mutator();
+    }
+
+    pub fn into_inner(self) -> E {
+        self.value
+    }
+
+    fn give_mutator(&mut self) {
+        if let Some(mutator) = &self.mutator {
+            self.value.set_mutator(mutator);
+        }
+    }
+}
+
+impl<E: ReplicateEnum> Deref for EnumComponent<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.value
+    }
+}
+
+impl<E: ReplicateEnum> DerefMut for EnumComponent<E> {
+    fn deref_mut(&mut self) -> &mut E {
+        &mut self.value
+    }
+}
+
+impl<E: ReplicateEnum> Clone for EnumComponent<E> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<E: ReplicateEnum> Named for EnumComponent<E> {
+    fn name(&self) -> String {
+        E::name()
+    }
+}
+
+impl<E: ReplicateEnum> Replicate for EnumComponent<E> {
+    fn kind(&self) -> ComponentKind {
+        ComponentKind::of::<Self>()
+    }
+    fn to_any(&self) -> &dyn Any {
+        self
+    }
+    fn to_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn to_boxed_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+    fn copy_to_box(&self) -> Box<dyn Replicate> {
+        Box::new(self.clone())
+    }
+    fn create_builder() -> Box<dyn ReplicateBuilder>
+    where
+        Self: Sized,
+    {
+        Box::new(EnumComponentBuilder::<E>(PhantomData))
+    }
+    fn protocol_name() -> String
+    where
+        Self: Sized,
+    {
+        E::protocol_name()
+    }
+    fn diff_mask_size(&self) -> u8 {
+        E::diff_mask_size()
+    }
+    fn dyn_ref(&self) -> ReplicaDynRef<'_> {
+        ReplicaDynRef::new(self)
+    }
+    fn dyn_mut(&mut self) -> ReplicaDynMut<'_> {
+        ReplicaDynMut::new(self)
+    }
+    fn mirror(&mut self, other: &dyn Replicate) {
+        let Some(other) = other.to_any().downcast_ref::<Self>() else {
+            panic!("cannot mirror: other Component is of another type!");
+        };
+        let switched = mem::discriminant(&self.value) != mem::discriminant(&other.value);
+        self.value.mirror(&other.value);
+        if switched {
+            // the Properties of the new variant have yet to be given a mutator
+            self.give_mutator();
+        }
+    }
+    fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.mutator = Some(mutator.clone_new());
+        self.value.set_mutator(mutator);
+    }
+    fn write(
+        &self,
+        component_kinds: &ComponentKinds,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        self.kind().ser(component_kinds, writer);
+        self.value.write(writer, converter);
+    }
+    fn write_update(
+        &self,
+        diff_mask: &DiffMask,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        self.value.write_update(diff_mask, writer, converter);
+    }
+    fn read_apply_update(
+        &mut self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentUpdate,
+    ) -> Result<(), SerdeErr> {
+        self.value.read_apply_update(converter, update)
+    }
+    fn read_apply_field_update(
+        &mut self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentFieldUpdate,
+    ) -> Result<(), SerdeErr> {
+        self.value.read_apply_field_update(converter, update)
+    }
+    fn relations_waiting(&self) -> Option<HashSet<LocalEntity>> {
+        self.value.relations_waiting()
+    }
+    fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
+        self.value.relations_complete(converter);
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "bevy_support")]
+    {
+        use bevy_ecs::component::{Component, TableStorage};
+
+        impl<E: ReplicateEnum> Component for EnumComponent<E> {
+            type Storage = TableStorage;
+        }
+    }
+}
+
+struct EnumComponentBuilder<E: ReplicateEnum>(PhantomData<fn() -> E>);
+
+impl<E: ReplicateEnum> Named for EnumComponentBuilder<E> {
+    fn name(&self) -> String {
+        E::name()
+    }
+}
+
+impl<E: ReplicateEnum> ReplicateBuilder for EnumComponentBuilder<E> {
+    fn read(
+        &self,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Box<dyn Replicate>, SerdeErr> {
+        Ok(Box::new(EnumComponent::new(E::read(reader, converter)?)))
+    }
+    fn read_create_update(&self, reader: &mut BitReader) -> Result<ComponentUpdate, SerdeErr> {
+        E::read_create_update(reader)
+    }
+    fn split_update(
+        &self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        update: ComponentUpdate,
+    ) -> Result<
+        (
+            Option<Vec<(LocalEntity, ComponentFieldUpdate)>>,
+            Option<ComponentUpdate>,
+        ),
+        SerdeErr,
+    > {
+        E::split_update(converter, update)
+    }
+    fn layout(&self) -> &'static str {
+        E::layout()
+    }
+    fn fields(&self) -> Vec<FieldSchema> {
+        E::fields()
+    }
+}
@@ -2,6 +2,7 @@ pub mod component_kinds;
 pub mod component_update;
 pub mod diff_mask;
 pub mod entity_property;
+pub mod enum_component;
 pub mod property;
 pub mod property_mutate;
 pub mod replica_ref;
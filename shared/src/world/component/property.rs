@@ -1,9 +1,16 @@
 use std::ops::{Deref, DerefMut};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason};
 
 use crate::world::component::property_mutate::PropertyMutator;
 
+/// A codec function writing a Property's value, such as those in
+/// [`codecs`](naia_serde::codecs)
+pub type SerFn<T> = fn(&T, &mut dyn BitWrite);
+
+/// A codec function reading a Property's value
+pub type DeFn<T> = fn(&mut BitReader) -> Result<T, SerdeErr>;
+
 #[derive(Clone)]
 enum PropertyImpl<T: Serde> {
     HostOwned(HostOwnedProperty<T>),
@@ -38,13 +45,28 @@ impl<T: Serde> Property<T> {
         }
     }
 
+    /// Get the PropertyMutator tracking changes to the Property, if one has
+    /// been set
+    pub fn mutator(&self) -> Option<&PropertyMutator> {
+        match &self.inner {
+            PropertyImpl::HostOwned(inner) => inner.mutator.as_ref(),
+            PropertyImpl::RemoteOwned(_) => None,
+        }
+    }
+
     // Serialization / deserialization
 
     /// Writes contained value into outgoing byte stream
     pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.write_with(writer, T::ser);
+    }
+
+    /// Writes contained value into outgoing byte stream, through the given
+    /// codec function rather than the value's `Serde` impl
+    pub fn write_with(&self, writer: &mut dyn BitWrite, ser: SerFn<T>) {
         match &self.inner {
             PropertyImpl::HostOwned(inner) => {
-                inner.write(writer);
+                ser(&inner.inner, writer);
             }
             PropertyImpl::RemoteOwned(_) => {
                 panic!("Remote Property should never be written.");
@@ -55,7 +77,13 @@ impl<T: Serde> Property<T> {
     /// Given a cursor into incoming packet data, initializes the Property with
     /// the synced value
     pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let inner_value = Self::read_inner(reader)?;
+        Self::new_read_with(reader, T::de)
+    }
+
+    /// Initializes the Property with the synced value, read through the given
+    /// codec function
+    pub fn new_read_with(reader: &mut BitReader, de: DeFn<T>) -> Result<Self, SerdeErr> {
+        let inner_value = de(reader)?;
 
         Ok(Self {
             inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
@@ -65,28 +93,44 @@ impl<T: Serde> Property<T> {
     /// Reads from a stream and immediately writes to a stream
     /// Used to buffer updates for later
     pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
-        T::de(reader)?.ser(writer);
+        Self::read_write_with(reader, writer, T::de, T::ser)
+    }
+
+    /// Reads from a stream and immediately writes to a stream, through the
+    /// given codec functions
+    pub fn read_write_with(
+        reader: &mut BitReader,
+        writer: &mut BitWriter,
+        de: DeFn<T>,
+        ser: SerFn<T>,
+    ) -> Result<(), SerdeErr> {
+        ser(&de(reader)?, writer);
         Ok(())
     }
 
     /// Given a cursor into incoming packet data, updates the Property with the
     /// synced value
     pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.read_with(reader, T::de)
+    }
+
+    /// Updates the Property with the synced value, read through the given
+    /// codec function
+    pub fn read_with(&mut self, reader: &mut BitReader, de: DeFn<T>) -> Result<(), SerdeErr> {
         match &mut self.inner {
             PropertyImpl::HostOwned(_) => {
-                panic!("Host Property should never read.");
+                return Err(SerdeErr::new(
+                    SerdeErrReason::Other("cannot read into a host-owned Property".into()),
+                    reader.bit_offset(),
+                ));
             }
             PropertyImpl::RemoteOwned(inner) => {
-                inner.read(reader)?;
+                inner.inner = de(reader)?;
             }
         }
         Ok(())
     }
 
-    fn read_inner(reader: &mut BitReader) -> Result<T, SerdeErr> {
-        T::de(reader)
-    }
-
     // Comparison
 
     fn inner(&self) -> &T {
@@ -162,10 +206,6 @@ impl<T: Serde> HostOwnedProperty<T> {
         self.mutator = Some(mutator.clone_new());
     }
 
-    pub fn write(&self, writer: &mut dyn BitWrite) {
-        self.inner.ser(writer);
-    }
-
     pub fn mirror(&mut self, other: &T) {
         self.mutate();
         self.inner = other.clone();
@@ -188,9 +228,4 @@ impl<T: Serde> RemoteOwnedProperty<T> {
     pub fn new(value: T) -> Self {
         Self { inner: value }
     }
-
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        self.inner = Property::read_inner(reader)?;
-        Ok(())
-    }
 }
@@ -4,6 +4,7 @@ use naia_serde::{BitReader, BitWrite, SerdeErr};
 
 use crate::{
     messages::named::Named,
+    schema::FieldSchema,
     world::{
         component::{
             component_kinds::{ComponentKind, ComponentKinds},
@@ -38,6 +39,12 @@ pub trait ReplicateBuilder: Send + Sync + Named {
         ),
         SerdeErr,
     >;
+    /// Describes the replicated fields of the Component, used in the Protocol
+    /// fingerprint
+    fn layout(&self) -> &'static str;
+    /// Describes the replicated fields of the Component, used in the Protocol
+    /// schema
+    fn fields(&self) -> Vec<FieldSchema>;
 }
 
 /// A struct that implements Replicate is a Component, or otherwise,
@@ -51,6 +58,13 @@ pub trait Replicate: ReplicateInner + Named + Any {
     fn to_boxed_any(self: Box<Self>) -> Box<dyn Any>;
     fn copy_to_box(&self) -> Box<dyn Replicate>;
     fn create_builder() -> Box<dyn ReplicateBuilder>
+    where
+        Self: Sized;
+    /// The name this type is given its id by in a Protocol: the path of the
+    /// module it is defined in & its own name, which stay the same across
+    /// compilers, unlike `std::any::type_name()`. The arguments of a generic
+    /// type are named by their own `ProtocolName`.
+    fn protocol_name() -> String
     where
         Self: Sized;
     /// Gets the number of bytes of the Component's DiffMask
@@ -108,6 +108,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> HostWorldManager<E> {
         self.world_channel.entity_channel_is_open(entity)
     }
 
+    // Delivery
+
+    /// Entities whose spawn the remote host has acknowledged since the last
+    /// call
+    pub fn take_delivered_spawns(&mut self) -> Vec<E> {
+        self.world_channel.take_delivered_spawns()
+    }
+
+    /// Components whose insertion the remote host has acknowledged since the
+    /// last call
+    pub fn take_delivered_inserts(&mut self) -> Vec<(E, ComponentKind)> {
+        self.world_channel.take_delivered_inserts()
+    }
+
     // Messages
 
     pub fn collect_outgoing_messages(&mut self, rtt_millis: &f32) {
@@ -6,3 +6,6 @@ pub mod user_diff_handler;
 pub mod world_channel;
 
 mod entity_action_event;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,145 @@
+use std::{
+    any::TypeId,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
+
+use naia_socket_shared::Instant;
+
+use crate::{
+    world::{
+        host::{
+            entity_action_event::EntityActionEvent,
+            global_diff_handler::GlobalDiffHandler,
+            host_world_manager::{ActionId, HostWorldManager},
+            mut_channel::{MutChannelType, MutReceiver},
+        },
+        local_world_manager::LocalWorldManager,
+    },
+    BigMapKey, ComponentKind, EntityAction, EntityAndGlobalEntityConverter,
+    EntityDoesNotExistError, GlobalEntity, GlobalWorldManagerType, PacketIndex,
+};
+
+struct Position;
+
+fn position_kind() -> ComponentKind {
+    ComponentKind::from(TypeId::of::<Position>())
+}
+
+struct TestMutChannel {
+    diff_mask_length: u8,
+}
+
+impl MutChannelType for TestMutChannel {
+    fn new_receiver(&mut self, _: &Option<SocketAddr>) -> Option<MutReceiver> {
+        Some(MutReceiver::new(self.diff_mask_length))
+    }
+
+    fn send(&self, _: u8) {}
+}
+
+// A world in which every Entity is its own GlobalEntity
+struct TestWorld {
+    diff_handler: Arc<RwLock<GlobalDiffHandler<u32>>>,
+}
+
+impl EntityAndGlobalEntityConverter<u32> for TestWorld {
+    fn global_entity_to_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<u32, EntityDoesNotExistError> {
+        Ok(global_entity.to_u64() as u32)
+    }
+
+    fn entity_to_global_entity(
+        &self,
+        entity: &u32,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Ok(GlobalEntity::from_u64(*entity as u64))
+    }
+}
+
+impl GlobalWorldManagerType<u32> for TestWorld {
+    fn component_kinds(&self, _: &u32) -> Option<Vec<ComponentKind>> {
+        None
+    }
+
+    fn to_global_entity_converter(&self) -> &dyn EntityAndGlobalEntityConverter<u32> {
+        self
+    }
+
+    fn entity_can_relate_to_user(&self, _: &u32, _: &u64) -> bool {
+        true
+    }
+
+    fn new_mut_channel(&self, diff_mask_length: u8) -> Arc<RwLock<dyn MutChannelType>> {
+        Arc::new(RwLock::new(TestMutChannel { diff_mask_length }))
+    }
+
+    fn diff_handler(&self) -> Arc<RwLock<GlobalDiffHandler<u32>>> {
+        self.diff_handler.clone()
+    }
+
+    fn remote_spawn_entity(&mut self, _: &u32, _: &u64) {}
+
+    fn remote_despawn_entity(&mut self, _: &u32) {}
+}
+
+// Takes the next action queued for sending, and records it as written into
+// the given packet, as the HostWorldWriter does
+fn send_next_action(
+    host: &mut HostWorldManager<u32>,
+    packet_index: PacketIndex,
+) -> EntityActionEvent<u32> {
+    let mut actions = host
+        .take_outgoing_events(&Instant::now(), &10000.0)
+        .next_send_actions;
+    assert_eq!(actions.len(), 1);
+    let (action_id, event): (ActionId, EntityActionEvent<u32>) = actions.pop_front().unwrap();
+    let action = match &event {
+        EntityActionEvent::SpawnEntity(entity) => EntityAction::SpawnEntity(*entity, Vec::new()),
+        EntityActionEvent::InsertComponent(entity, component_kind) => {
+            EntityAction::InsertComponent(*entity, *component_kind)
+        }
+        _ => panic!("unexpected action"),
+    };
+    host.sent_action_packets
+        .insert_scan_from_back(packet_index, (Instant::now(), vec![(action_id, action)]));
+    event
+}
+
+#[test]
+fn spawn_and_insert_are_reported_once_delivered() {
+    let world = TestWorld {
+        diff_handler: Arc::new(RwLock::new(GlobalDiffHandler::new())),
+    };
+    let mut local_world_manager = LocalWorldManager::new(0);
+    let mut host = HostWorldManager::new(&None, &world);
+
+    let entity = 7;
+    world
+        .diff_handler
+        .write()
+        .unwrap()
+        .register_component(&world, &entity, &position_kind(), 1);
+
+    host.spawn_entity(&mut local_world_manager, &entity);
+    host.insert_component(&entity, &position_kind());
+
+    // the Entity is spawned first, and its Component inserted once the spawn
+    // has been delivered
+    assert!(send_next_action(&mut host, 0) == EntityActionEvent::SpawnEntity(entity));
+    assert!(host.take_delivered_spawns().is_empty());
+
+    host.notify_packet_delivered(0, &mut local_world_manager);
+    assert_eq!(host.take_delivered_spawns(), vec![entity]);
+    assert!(host.take_delivered_inserts().is_empty());
+
+    assert!(
+        send_next_action(&mut host, 1)
+            == EntityActionEvent::InsertComponent(entity, position_kind())
+    );
+    host.notify_packet_delivered(1, &mut local_world_manager);
+    assert!(host.take_delivered_inserts() == vec![(entity, position_kind())]);
+    assert!(host.take_delivered_spawns().is_empty());
+}
@@ -0,0 +1 @@
+mod delivered_actions;
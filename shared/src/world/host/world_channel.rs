@@ -48,6 +48,11 @@ pub struct WorldChannel<E: Copy + Eq + Hash + Send + Sync> {
     entity_channels: CheckedMap<E, EntityChannel>,
     outgoing_actions: ReliableSender<EntityActionEvent<E>>,
     delivered_actions: EntityActionReceiver<E>,
+    /// Entities whose spawn the remote host has acknowledged, not yet taken
+    delivered_spawns: Vec<E>,
+    /// Components whose insertion the remote host has acknowledged, not yet
+    /// taken
+    delivered_inserts: Vec<(E, ComponentKind)>,
 
     address: Option<SocketAddr>,
     pub diff_handler: UserDiffHandler<E>,
@@ -64,6 +69,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
             entity_channels: CheckedMap::new(),
             outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
             delivered_actions: EntityActionReceiver::new(),
+            delivered_spawns: Vec::new(),
+            delivered_inserts: Vec::new(),
 
             address: *address,
             diff_handler: UserDiffHandler::new(global_world_manager),
@@ -412,12 +419,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
                     let component_set: HashSet<ComponentKind> =
                         components.iter().copied().collect();
                     self.remote_spawn_entity(&entity, &component_set);
+                    self.delivered_spawns.push(entity);
+                    // Components spawned along with the Entity arrive with it
+                    for component in components {
+                        self.delivered_inserts.push((entity, component));
+                    }
                 }
                 EntityAction::DespawnEntity(entity) => {
                     self.remote_despawn_entity(local_world_manager, &entity);
                 }
                 EntityAction::InsertComponent(entity, component) => {
                     self.remote_insert_component(&entity, &component);
+                    self.delivered_inserts.push((entity, component));
                 }
                 EntityAction::RemoveComponent(entity, component) => {
                     self.remote_remove_component(&entity, &component);
@@ -429,6 +442,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldChannel<E> {
         }
     }
 
+    /// Entities whose spawn the remote host has acknowledged since the last
+    /// call
+    pub fn take_delivered_spawns(&mut self) -> Vec<E> {
+        std::mem::take(&mut self.delivered_spawns)
+    }
+
+    /// Components whose insertion the remote host has acknowledged since the
+    /// last call
+    pub fn take_delivered_inserts(&mut self) -> Vec<(E, ComponentKind)> {
+        std::mem::take(&mut self.delivered_inserts)
+    }
+
     // Collect
 
     pub fn take_next_actions(
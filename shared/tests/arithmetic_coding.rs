@@ -0,0 +1,150 @@
+mod some_coded_types {
+    use naia_shared::{Message, Property, Replicate};
+
+    #[derive(Message)]
+    pub struct Input {
+        pub jump: bool,
+        pub crouch: bool,
+        pub delta: i8,
+    }
+
+    #[derive(Replicate)]
+    pub struct Health {
+        pub current: Property<u16>,
+        pub regenerating: Property<bool>,
+    }
+
+    impl Health {
+        pub fn new(current: u16) -> Self {
+            Self::new_complete(current, false)
+        }
+    }
+}
+
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, FakeEntityConverter, Message, MessageContainer, PacketCoding,
+    Protocol, Replicate, Serde,
+};
+
+use some_coded_types::{Health, Input};
+
+fn some_protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Input>()
+        .add_component::<Health>()
+        .build()
+}
+
+fn some_input(index: u8) -> Input {
+    Input {
+        jump: index % 16 == 0,
+        crouch: false,
+        delta: (index % 3) as i8 - 1,
+    }
+}
+
+fn write_inputs(coding: PacketCoding) -> Box<[u8]> {
+    let message_kinds = some_protocol().message_kinds;
+
+    let mut writer = BitWriter::new();
+    // a plain header
+    7_u16.ser(&mut writer);
+    writer.begin_coding(coding);
+    for index in 0..64 {
+        some_input(index).write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+    }
+    writer.to_bytes()
+}
+
+#[test]
+fn read_write_coded_messages() {
+    let message_kinds = some_protocol().message_kinds;
+
+    let bytes = write_inputs(PacketCoding::Arithmetic);
+
+    let mut reader = BitReader::new(&bytes);
+    assert_eq!(u16::de(&mut reader).unwrap(), 7);
+    reader.begin_decoding().unwrap();
+    for index in 0..64 {
+        let message = message_kinds
+            .read(&mut reader, &FakeEntityConverter)
+            .expect("should deserialize correctly")
+            .to_boxed_any();
+        let input = message.downcast_ref::<Input>().unwrap();
+        let expected = some_input(index);
+        assert_eq!(input.jump, expected.jump);
+        assert_eq!(input.crouch, expected.crouch);
+        assert_eq!(input.delta, expected.delta);
+    }
+}
+
+#[test]
+fn coding_shrinks_skewed_messages() {
+    let plain = write_inputs(PacketCoding::Plain);
+    let coded = write_inputs(PacketCoding::Arithmetic);
+
+    assert!(coded.len() * 2 < plain.len());
+}
+
+#[test]
+fn shared_message_is_written_in_its_contexts() {
+    let message_kinds = some_protocol().message_kinds;
+
+    let container = MessageContainer::from_write_shared(Box::new(some_input(0)), &message_kinds);
+
+    let mut writer = BitWriter::new();
+    writer.begin_coding(PacketCoding::Arithmetic);
+    for _ in 0..32 {
+        container.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+    }
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    reader.begin_decoding().unwrap();
+    for _ in 0..32 {
+        let message = message_kinds
+            .read(&mut reader, &FakeEntityConverter)
+            .expect("should deserialize correctly")
+            .to_boxed_any();
+        assert!(message.downcast_ref::<Input>().unwrap().jump);
+    }
+}
+
+#[test]
+fn read_write_coded_component_update() {
+    let component_kinds = some_protocol().component_kinds;
+
+    let created = Health::new(80);
+    let updated = Health::new(60);
+
+    let mut diff_mask = DiffMask::new(updated.diff_mask_size());
+    diff_mask.set_bit(0, true);
+
+    let mut writer = BitWriter::new();
+    writer.begin_coding(PacketCoding::Arithmetic);
+    created.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    for _ in 0..16 {
+        updated.write_update(&diff_mask, &mut writer, &mut FakeEntityConverter);
+    }
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    reader.begin_decoding().unwrap();
+    let mut remote = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any()
+        .downcast::<Health>()
+        .unwrap();
+    assert_eq!(*remote.current, 80);
+    for _ in 0..16 {
+        let update = Health::create_builder()
+            .read_create_update(&mut reader)
+            .expect("should deserialize correctly");
+        remote
+            .read_apply_update(&FakeEntityConverter, update)
+            .expect("should apply correctly");
+    }
+    assert_eq!(*remote.current, 60);
+    assert!(!*remote.regenerating);
+}
@@ -28,9 +28,26 @@ mod some_enum_2 {
     }
 }
 
-use naia_shared::{BitReader, BitWriter, Serde};
+mod some_enum_3 {
+    use naia_serde as serde;
+    use serde::Serde;
+
+    #[derive(Debug, PartialEq, Clone, Serde)]
+    pub enum PackedEnum {
+        Move {
+            #[naia(range = -1..=1)]
+            direction: i8,
+            #[naia(bits = 10)]
+            index: u16,
+        },
+        Rename(#[naia(skip)] String, #[naia(bits = 4)] u8),
+    }
+}
+
+use naia_shared::{BitReader, BitWriter, Serde, SerdeErrReason, UnsignedInteger};
 use some_enum::SomeEnum;
 use some_enum_2::SomeEnum2;
+use some_enum_3::PackedEnum;
 
 #[test]
 fn read_write_enum() {
@@ -95,3 +112,56 @@ fn read_write_enum_2() {
     assert_eq!(in_2, out_2);
     assert_eq!(in_3, out_3);
 }
+
+#[test]
+fn read_invalid_enum_variant() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    UnsignedInteger::<2>::new(3).ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let error = SomeEnum2::de(&mut reader).unwrap_err();
+
+    assert_eq!(error.reason(), &SerdeErrReason::InvalidEnumVariant(3));
+    assert_eq!(error.type_name(), Some(std::any::type_name::<SomeEnum2>()));
+    assert_eq!(error.bit_offset(), 2);
+}
+
+#[test]
+fn read_write_enum_with_field_attributes() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = PackedEnum::Move {
+        direction: -1,
+        index: 1000,
+    };
+    let in_2 = PackedEnum::Rename("Hello enum!".to_string(), 9);
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    // 2 bits for the variant, 2 bits for the 3 possible values & 10 bits
+    assert_eq!(in_1.bit_length(), 14);
+    // 2 bits for the variant, nothing for the skipped field & 4 bits
+    assert_eq!(in_2.bit_length(), 6);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1: PackedEnum = Serde::de(&mut reader).unwrap();
+    let out_2: PackedEnum = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(in_1, out_1);
+    assert_eq!(out_2, PackedEnum::Rename(String::new(), 9));
+    assert_eq!(reader.bit_offset(), 20);
+}
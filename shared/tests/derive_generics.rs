@@ -0,0 +1,177 @@
+mod some_generic_types {
+    use naia_shared::{Message, Property, ProtocolName, Replicate, Serde};
+
+    #[derive(Message)]
+    pub struct Envelope<T: Serde> {
+        pub sender: u16,
+        pub contents: T,
+    }
+
+    #[derive(Clone, PartialEq, Serde)]
+    pub struct Coin {
+        pub value: u16,
+    }
+
+    #[derive(Message)]
+    pub struct Batch<T: Serde, const N: usize>(pub [T; N]);
+
+    #[derive(Replicate)]
+    pub struct Stat<T: Serde> {
+        pub value: Property<T>,
+        pub max: Property<T>,
+    }
+
+    impl<T: Serde + ProtocolName + Send + Sync + 'static> Stat<T> {
+        pub fn new(value: T, max: T) -> Self {
+            Self::new_complete(value, max)
+        }
+    }
+}
+
+use naia_shared::{BitReader, BitWriter, FakeEntityConverter, Message, Protocol, Replicate};
+
+use some_generic_types::{Batch, Coin, Envelope, Stat};
+
+fn some_protocol() -> Protocol {
+    Protocol::builder()
+        .add_message::<Envelope<String>>()
+        .add_message::<Envelope<u32>>()
+        .add_message::<Batch<bool, 3>>()
+        .add_component::<Stat<u8>>()
+        .add_component::<Stat<f32>>()
+        .build()
+}
+
+#[test]
+fn each_instance_is_its_own_kind() {
+    let protocol = some_protocol();
+    let schema = protocol.schema();
+
+    let message_names: Vec<&str> = schema
+        .messages
+        .iter()
+        .map(|message| message.name.as_str())
+        .collect();
+    assert!(message_names.contains(&"Envelope<String>"));
+    assert!(message_names.contains(&"Envelope<u32>"));
+    assert!(message_names.contains(&"Batch<bool, 3>"));
+
+    let component_names: Vec<&str> = schema
+        .components
+        .iter()
+        .map(|component| component.name.as_str())
+        .collect();
+    assert_eq!(component_names, vec!["Stat<f32>", "Stat<u8>"]);
+}
+
+#[test]
+fn instances_have_their_own_protocol_names() {
+    assert_eq!(
+        Envelope::<u32>::protocol_name(),
+        "derive_generics::some_generic_types::Envelope<u32>"
+    );
+    assert_eq!(
+        Batch::<bool, 3>::protocol_name(),
+        "derive_generics::some_generic_types::Batch<bool, 3>"
+    );
+    assert_eq!(
+        Stat::<u8>::protocol_name(),
+        "derive_generics::some_generic_types::Stat<u8>"
+    );
+}
+
+#[test]
+fn arguments_are_named_by_module_path() {
+    assert_eq!(
+        Envelope::<Coin>::protocol_name(),
+        "derive_generics::some_generic_types::Envelope<derive_generics::some_generic_types::Coin>"
+    );
+    assert_eq!(
+        Envelope::<Vec<Option<Coin>>>::protocol_name(),
+        "derive_generics::some_generic_types::Envelope<Vec<Option<derive_generics::some_generic_types::Coin>>>"
+    );
+}
+
+#[test]
+fn read_write_generic_message() {
+    // Protocol
+    let protocol = some_protocol();
+    let message_kinds = protocol.message_kinds;
+
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = Envelope {
+        sender: 7,
+        contents: 1234_u32,
+    };
+    let in_2 = Envelope {
+        sender: 8,
+        contents: "hello".to_string(),
+    };
+
+    in_1.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+    in_2.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+    let out_2 = message_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+
+    let typed_out_1 = out_1.downcast_ref::<Envelope<u32>>().unwrap();
+    assert_eq!(typed_out_1.sender, 7);
+    assert_eq!(typed_out_1.contents, 1234);
+
+    let typed_out_2 = out_2.downcast_ref::<Envelope<String>>().unwrap();
+    assert_eq!(typed_out_2.sender, 8);
+    assert_eq!(typed_out_2.contents, "hello");
+}
+
+#[test]
+fn read_write_generic_replica() {
+    // Protocol
+    let protocol = some_protocol();
+    let component_kinds = protocol.component_kinds;
+
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = Stat::new(3_u8, 10_u8);
+    let in_2 = Stat::new(0.5_f32, 1.0_f32);
+
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    in_2.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+    let out_2 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+
+    let typed_out_1 = out_1.downcast_ref::<Stat<u8>>().unwrap();
+    assert_eq!(*typed_out_1.value, 3);
+    assert_eq!(*typed_out_1.max, 10);
+
+    let typed_out_2 = out_2.downcast_ref::<Stat<f32>>().unwrap();
+    assert_eq!(*typed_out_2.value, 0.5);
+    assert_eq!(*typed_out_2.max, 1.0);
+}
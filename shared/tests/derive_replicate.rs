@@ -53,6 +53,22 @@ mod some_entity_replica {
     }
 }
 
+mod some_target_replica {
+    use naia_shared::{EntityProperty, Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct TargetHolder {
+        pub target: EntityProperty,
+        pub distance: Property<u8>,
+    }
+
+    impl TargetHolder {
+        pub fn new(distance: u8) -> Self {
+            TargetHolder::new_complete(distance)
+        }
+    }
+}
+
 mod some_nonreplicated_replica {
     use naia_shared::{Property, Replicate};
 
@@ -72,15 +88,37 @@ mod some_nonreplicated_replica {
     }
 }
 
+mod some_packed_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    pub struct PackedHolder {
+        #[naia(bits = 4)]
+        pub level: Property<u8>,
+        #[naia(range = -500..=500)]
+        pub offset: Property<i32>,
+        pub name: Property<String>,
+    }
+
+    impl PackedHolder {
+        pub fn new(level: u8, offset: i32, name: &str) -> Self {
+            return PackedHolder::new_complete(level, offset, name.to_string());
+        }
+    }
+}
+
 use naia_shared::{
-    BigMapKey, BitReader, BitWriter, EntityAndGlobalEntityConverter, EntityDoesNotExistError,
-    FakeEntityConverter, GlobalEntity, LocalEntity, LocalEntityAndGlobalEntityConverter, Protocol,
+    BigMapKey, BitReader, BitWriter, DiffMask, EntityAndGlobalEntityConverter,
+    EntityDoesNotExistError, FakeEntityConverter, GlobalEntity, LocalEntity,
+    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, Protocol,
     Replicate,
 };
 
 use some_entity_replica::EntityPropertyHolder;
 use some_named_replica::NamedStringHolder;
 use some_nonreplicated_replica::MixedReplicationHolder;
+use some_packed_replica::PackedHolder;
+use some_target_replica::TargetHolder;
 use some_tuple_replica::TupleStringHolder;
 use some_unit_replica::UnitHolder;
 
@@ -95,7 +133,7 @@ fn read_write_unit_replica() {
 
     let in_1 = UnitHolder::new();
 
-    in_1.write(&component_kinds, &mut writer, &FakeEntityConverter);
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
 
     let bytes = writer.to_bytes();
 
@@ -123,7 +161,7 @@ fn read_write_named_replica() {
 
     let in_1 = NamedStringHolder::new("hello world", "goodbye world");
 
-    in_1.write(&component_kinds, &mut writer, &FakeEntityConverter);
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
 
     let bytes = writer.to_bytes();
 
@@ -158,7 +196,7 @@ fn read_write_tuple_replica() {
 
     let in_1 = TupleStringHolder::new("hello world", "goodbye world");
 
-    in_1.write(&component_kinds, &mut writer, &FakeEntityConverter);
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
 
     let bytes = writer.to_bytes();
 
@@ -213,6 +251,14 @@ fn read_write_entity_replica() {
             Ok(GlobalEntity::from_u64(local_entity_value as u64))
         }
     }
+    impl LocalEntityAndGlobalEntityConverterMut for TestEntityConverter {
+        fn get_or_reserve_host_entity(
+            &mut self,
+            global_entity: &GlobalEntity,
+        ) -> Result<LocalEntity, EntityDoesNotExistError> {
+            self.global_entity_to_local_entity(global_entity)
+        }
+    }
 
     // Protocol
     let protocol = Protocol::builder()
@@ -224,7 +270,7 @@ fn read_write_entity_replica() {
     let mut writer = BitWriter::new();
     let mut in_1 = EntityPropertyHolder::new();
     in_1.entity_1.set(&TestEntityConverter, &1);
-    in_1.write(&component_kinds, &mut writer, &TestEntityConverter);
+    in_1.write(&component_kinds, &mut writer, &mut TestEntityConverter);
     let bytes = writer.to_bytes();
 
     // Read
@@ -235,14 +281,10 @@ fn read_write_entity_replica() {
         .to_boxed_any();
 
     let typed_out_1 = out_1.downcast_ref::<EntityPropertyHolder>().unwrap();
-    assert!(in_1.entity_1.equals(&typed_out_1.entity_1));
-    let global_entitys = Vec::<GlobalEntity>::from([GlobalEntity::from_u64(1)]);
-    assert_eq!(in_1.entities(), global_entitys);
-    assert_eq!(typed_out_1.entities(), global_entitys);
+    // the Entity was already known, so the Component isn't left waiting on it
+    assert!(typed_out_1.relations_waiting().is_none());
     assert_eq!(in_1.entity_1.get(&TestEntityConverter).unwrap(), 1);
-    assert_eq!(in_1.entity_1.global_entity().unwrap().to_u64(), 1);
     assert_eq!(typed_out_1.entity_1.get(&TestEntityConverter).unwrap(), 1);
-    assert_eq!(typed_out_1.entity_1.global_entity().unwrap().to_u64(), 1);
 }
 
 #[test]
@@ -258,7 +300,7 @@ fn read_write_nonreplicated_replica() {
 
     let in_1 = MixedReplicationHolder::new("hello world", "goodbye world");
 
-    in_1.write(&component_kinds, &mut writer, &FakeEntityConverter);
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
 
     let bytes = writer.to_bytes();
 
@@ -278,3 +320,133 @@ fn read_write_nonreplicated_replica() {
     assert_eq!(*typed_out_1.string_1, "hello world".to_string());
     assert_eq!(*typed_out_1.string_2, "".to_string());
 }
+
+#[test]
+fn read_write_packed_replica() {
+    // Protocol
+    let protocol = Protocol::builder().add_component::<PackedHolder>().build();
+    let component_kinds = protocol.component_kinds;
+
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = PackedHolder::new(15, -500, "hello world");
+
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+
+    let typed_out_1 = out_1.downcast_ref::<PackedHolder>().unwrap();
+    assert_eq!(*typed_out_1.level, 15);
+    assert_eq!(*typed_out_1.offset, -500);
+    assert_eq!(*typed_out_1.name, "hello world".to_string());
+}
+
+// Knows of every Entity, unless it has yet to arrive
+struct LateEntityConverter {
+    arrived: bool,
+}
+
+impl EntityAndGlobalEntityConverter<u64> for LateEntityConverter {
+    fn global_entity_to_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<u64, EntityDoesNotExistError> {
+        Ok(global_entity.to_u64())
+    }
+    fn entity_to_global_entity(
+        &self,
+        entity: &u64,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Ok(GlobalEntity::from_u64(*entity))
+    }
+}
+impl LocalEntityAndGlobalEntityConverter for LateEntityConverter {
+    fn global_entity_to_local_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<LocalEntity, EntityDoesNotExistError> {
+        Ok(LocalEntity::new_host(global_entity.to_u64() as u16))
+    }
+    fn local_entity_to_global_entity(
+        &self,
+        local_entity: &LocalEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        if !self.arrived {
+            return Err(EntityDoesNotExistError);
+        }
+        Ok(GlobalEntity::from_u64(local_entity.value() as u64))
+    }
+}
+impl LocalEntityAndGlobalEntityConverterMut for LateEntityConverter {
+    fn get_or_reserve_host_entity(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Result<LocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_local_entity(global_entity)
+    }
+}
+
+#[test]
+fn update_waiting_on_entity_is_split() {
+    let protocol = Protocol::builder().add_component::<TargetHolder>().build();
+    let component_kinds = protocol.component_kinds;
+    let mut converter = LateEntityConverter { arrived: true };
+
+    let mut host = TargetHolder::new(3);
+    let mut writer = BitWriter::new();
+    host.write(&component_kinds, &mut writer, &mut converter);
+    let bytes = writer.to_bytes();
+    let mut reader = BitReader::new(&bytes);
+    let remote = component_kinds
+        .read(&mut reader, &converter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+    let mut remote = *remote.downcast::<TargetHolder>().unwrap();
+
+    // Update both fields, pointing the target at an Entity the remote has yet
+    // to receive
+    host.target.set(&converter, &5);
+    *host.distance = 9;
+    let mut diff_mask = DiffMask::new(host.diff_mask_size());
+    diff_mask.set_bit(0, true);
+    diff_mask.set_bit(1, true);
+    let mut writer = BitWriter::new();
+    host.write_update(&diff_mask, &mut writer, &mut converter);
+    let bytes = writer.to_bytes();
+
+    let builder = TargetHolder::create_builder();
+    let mut reader = BitReader::new(&bytes);
+    let update = builder
+        .read_create_update(&mut reader)
+        .expect("should deserialize correctly");
+    converter.arrived = false;
+    let (waiting, ready) = builder
+        .split_update(&converter, update)
+        .expect("should split correctly");
+
+    // The distance is applied at once
+    remote
+        .read_apply_update(&converter, ready.expect("distance should be ready"))
+        .expect("should apply correctly");
+    assert_eq!(*remote.distance, 9);
+    assert!(remote.target.get(&converter).is_none());
+
+    // & the target once its Entity arrives
+    converter.arrived = true;
+    for (_, field_update) in waiting.expect("target should be waiting") {
+        remote
+            .read_apply_field_update(&converter, field_update)
+            .expect("should apply correctly");
+    }
+    assert_eq!(remote.target.get(&converter), Some(5));
+}
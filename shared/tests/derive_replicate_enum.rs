@@ -0,0 +1,231 @@
+mod some_enum_replica {
+    use naia_shared::{EntityProperty, Property, Replicate};
+
+    #[derive(Replicate)]
+    pub enum AiState {
+        Idle,
+        Chasing {
+            target: EntityProperty,
+            speed: Property<f32>,
+        },
+        Wandering(Property<u16>, u8),
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    BitReader, BitWriter, DiffMask, EnumComponent, FakeEntityConverter, Property, PropertyMutate,
+    PropertyMutator, Protocol, Replicate,
+};
+
+use some_enum_replica::AiState;
+
+// Collects the mutations of a host-owned Component into a DiffMask, as the
+// Server would
+#[derive(Clone)]
+struct MaskMutator(Arc<Mutex<DiffMask>>);
+
+impl PropertyMutate for MaskMutator {
+    fn mutate(&mut self, property_index: u8) {
+        self.0.lock().unwrap().set_bit(property_index, true);
+    }
+}
+
+fn host_with_mask(state: AiState) -> (EnumComponent<AiState>, Arc<Mutex<DiffMask>>) {
+    let mut host = EnumComponent::new(state);
+    let mask = Arc::new(Mutex::new(DiffMask::new(host.diff_mask_size())));
+    host.set_mutator(&PropertyMutator::new(MaskMutator(mask.clone())));
+    mask.lock().unwrap().clear();
+    (host, mask)
+}
+
+fn replicate(host: &EnumComponent<AiState>) -> EnumComponent<AiState> {
+    let protocol = Protocol::builder()
+        .add_component::<EnumComponent<AiState>>()
+        .build();
+    let component_kinds = protocol.component_kinds;
+
+    let mut writer = BitWriter::new();
+    host.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let remote = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+    *remote.downcast::<EnumComponent<AiState>>().unwrap()
+}
+
+fn send_update(
+    host: &EnumComponent<AiState>,
+    mask: &Arc<Mutex<DiffMask>>,
+    remote: &mut EnumComponent<AiState>,
+) {
+    let mut writer = BitWriter::new();
+    host.write_update(&mask.lock().unwrap(), &mut writer, &mut FakeEntityConverter);
+    mask.lock().unwrap().clear();
+    let bytes = writer.to_bytes();
+
+    let mut reader = BitReader::new(&bytes);
+    let update = EnumComponent::<AiState>::create_builder()
+        .read_create_update(&mut reader)
+        .expect("should deserialize correctly");
+    remote
+        .read_apply_update(&FakeEntityConverter, update)
+        .expect("should apply correctly");
+}
+
+fn wandering(state: &mut AiState) -> &mut Property<u16> {
+    match state {
+        AiState::Wandering(heading, _) => heading,
+        _ => panic!("expected the Wandering variant"),
+    }
+}
+
+#[test]
+fn read_write_variants() {
+    let idle = replicate(&EnumComponent::new(AiState::new_idle()));
+    assert!(matches!(*idle, AiState::Idle));
+
+    let chasing = replicate(&EnumComponent::new(AiState::new_chasing(2.5)));
+    let AiState::Chasing { speed, .. } = chasing.into_inner() else {
+        panic!("expected the Chasing variant");
+    };
+    assert_eq!(*speed, 2.5);
+
+    // The non-replicated field is left at its default
+    let wandering = replicate(&EnumComponent::new(AiState::new_wandering(90, 7)));
+    let AiState::Wandering(heading, seed) = wandering.into_inner() else {
+        panic!("expected the Wandering variant");
+    };
+    assert_eq!(*heading, 90);
+    assert_eq!(seed, 0);
+}
+
+#[test]
+fn update_within_variant() {
+    let (mut host, mask) = host_with_mask(AiState::new_wandering(90, 7));
+    let mut remote = replicate(&host);
+
+    **wandering(&mut host) = 180;
+    assert_eq!(mask.lock().unwrap().bit(0), Some(false));
+
+    send_update(&host, &mask, &mut remote);
+    assert_eq!(**wandering(&mut remote), 180);
+}
+
+#[test]
+fn switch_variant_rewrites_in_full() {
+    let (mut host, mask) = host_with_mask(AiState::new_idle());
+    let mut remote = replicate(&host);
+
+    // Insert the new state over the old one, as the Server does
+    host.mirror(&EnumComponent::new(AiState::new_chasing(4.0)));
+    assert_eq!(mask.lock().unwrap().bit(0), Some(true));
+
+    send_update(&host, &mask, &mut remote);
+    let AiState::Chasing { speed, .. } = &*remote else {
+        panic!("expected the Chasing variant");
+    };
+    assert_eq!(**speed, 4.0);
+}
+
+#[test]
+fn set_variant_keeps_mutator() {
+    let (mut host, mask) = host_with_mask(AiState::new_wandering(90, 7));
+    let mut remote = replicate(&host);
+
+    host.set(AiState::new_chasing(4.0));
+    assert_eq!(mask.lock().unwrap().bit(0), Some(true));
+
+    send_update(&host, &mask, &mut remote);
+    let AiState::Chasing { speed, .. } = &*remote else {
+        panic!("expected the Chasing variant");
+    };
+    assert_eq!(**speed, 4.0);
+
+    // Changes within the new variant are still tracked
+    let AiState::Chasing { speed, .. } = &mut *host else {
+        panic!("expected the Chasing variant");
+    };
+    **speed = 6.0;
+    assert!(!mask.lock().unwrap().is_clear());
+
+    send_update(&host, &mask, &mut remote);
+    let AiState::Chasing { speed, .. } = &*remote else {
+        panic!("expected the Chasing variant");
+    };
+    assert_eq!(**speed, 6.0);
+}
+
+#[test]
+fn assigned_variant_is_not_tracked() {
+    let (mut host, mask) = host_with_mask(AiState::new_wandering(90, 7));
+
+    // Assigning through the enum drops the mutator, which is why
+    // `EnumComponent::set()` exists
+    *host = AiState::new_chasing(4.0);
+    let AiState::Chasing { speed, .. } = &mut *host else {
+        panic!("expected the Chasing variant");
+    };
+    **speed = 6.0;
+
+    assert!(mask.lock().unwrap().is_clear());
+}
+
+#[test]
+fn set_variant_from_variant_without_properties() {
+    let (mut host, mask) = host_with_mask(AiState::new_idle());
+    let mut remote = replicate(&host);
+
+    host.set(AiState::new_chasing(4.0));
+    send_update(&host, &mask, &mut remote);
+    let AiState::Chasing { speed, .. } = &*remote else {
+        panic!("expected the Chasing variant");
+    };
+    assert_eq!(**speed, 4.0);
+
+    // And back again, through a variant without Properties
+    host.set(AiState::new_idle());
+    send_update(&host, &mask, &mut remote);
+    assert!(matches!(*remote, AiState::Idle));
+
+    host.set(AiState::new_wandering(90, 7));
+    send_update(&host, &mask, &mut remote);
+    assert_eq!(**wandering(&mut remote), 90);
+}
+
+#[test]
+fn update_to_other_variant_is_ignored() {
+    let (mut host, mask) = host_with_mask(AiState::new_wandering(90, 7));
+    let mut remote = replicate(&EnumComponent::new(AiState::new_idle()));
+
+    **wandering(&mut host) = 180;
+    send_update(&host, &mask, &mut remote);
+
+    assert!(matches!(*remote, AiState::Idle));
+}
+
+#[test]
+fn schema_lists_every_variant() {
+    let protocol = Protocol::builder()
+        .add_component::<EnumComponent<AiState>>()
+        .build();
+    let schema = protocol.schema();
+
+    let field_names: Vec<&str> = schema.components[0]
+        .fields
+        .iter()
+        .map(|field| field.name)
+        .collect();
+    assert_eq!(
+        field_names,
+        vec!["variant", "Chasing.target", "Chasing.speed", "Wandering.0"]
+    );
+    assert_eq!(
+        schema.components[0].fields[0].type_name,
+        "Idle | Chasing | Wandering"
+    );
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "serde_bridge")]
+
+mod some_serde_rs_types {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    pub struct Transform {
+        pub position: [f32; 3],
+        pub scale: Option<f32>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    pub enum Team {
+        Red,
+        Blue { captain: String },
+    }
+}
+
+mod some_struct {
+    use naia_shared::Serde;
+
+    use super::some_serde_rs_types::{Team, Transform};
+
+    #[derive(Clone, Debug, PartialEq, Serde)]
+    pub struct BridgedStruct {
+        pub some_int: u16,
+        #[naia(serde_rs)]
+        pub transform: Transform,
+        #[naia(serde_rs)]
+        pub teams: Vec<Team>,
+    }
+}
+
+mod some_replica {
+    use naia_shared::{Property, Replicate, SerdeRs};
+
+    use super::some_serde_rs_types::{Team, Transform};
+
+    #[derive(Replicate)]
+    pub struct BridgedHolder {
+        pub transform: Property<SerdeRs<Transform>>,
+        pub team: Property<SerdeRs<Team>>,
+    }
+
+    impl BridgedHolder {
+        pub fn new(transform: Transform, team: Team) -> Self {
+            BridgedHolder::new_complete(SerdeRs(transform), SerdeRs(team))
+        }
+    }
+}
+
+use naia_shared::{BitReader, BitWriter, FakeEntityConverter, Protocol, Replicate, Serde};
+
+use some_replica::BridgedHolder;
+use some_serde_rs_types::{Team, Transform};
+use some_struct::BridgedStruct;
+
+fn some_transform() -> Transform {
+    Transform {
+        position: [1.0, -2.5, 300.0],
+        scale: None,
+    }
+}
+
+#[test]
+fn read_write_struct_with_serde_rs_fields() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = BridgedStruct {
+        some_int: 42,
+        transform: some_transform(),
+        teams: vec![
+            Team::Red,
+            Team::Blue {
+                captain: "Ferris".to_string(),
+            },
+        ],
+    };
+
+    in_1.ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(in_1, out_1);
+    assert_eq!(reader.bit_offset() as u32, in_1.bit_length());
+}
+
+#[test]
+fn read_write_serde_rs_replica() {
+    // Protocol
+    let protocol = Protocol::builder().add_component::<BridgedHolder>().build();
+    let component_kinds = protocol.component_kinds;
+
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = BridgedHolder::new(
+        some_transform(),
+        Team::Blue {
+            captain: "Ferris".to_string(),
+        },
+    );
+
+    in_1.write(&component_kinds, &mut writer, &mut FakeEntityConverter);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = component_kinds
+        .read(&mut reader, &FakeEntityConverter)
+        .expect("should deserialize correctly")
+        .to_boxed_any();
+
+    let typed_out_1 = out_1.downcast_ref::<BridgedHolder>().unwrap();
+    assert_eq!(**typed_out_1.transform, some_transform());
+    assert_eq!(
+        **typed_out_1.team,
+        Team::Blue {
+            captain: "Ferris".to_string()
+        }
+    );
+}
@@ -7,11 +7,71 @@ mod some_struct {
         pub some_int: i16,
         pub some_bool: bool,
     }
+
+    #[derive(Clone, Debug, PartialEq, Serde)]
+    pub struct BoundedStruct {
+        #[naia(max_len = 8)]
+        pub some_string: String,
+        #[naia(max_len = 2)]
+        pub some_list: Vec<u8>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serde)]
+    pub struct PackedStruct {
+        #[naia(bits = 5)]
+        pub some_small_int: u32,
+        #[naia(range = -100..=100)]
+        pub some_ranged_int: i16,
+        #[naia(with = "fixed_point")]
+        pub some_float: f32,
+        #[naia(skip, default)]
+        pub some_local_state: Vec<u8>,
+        #[naia(skip, default = "default_name")]
+        pub some_name: String,
+    }
+
+    fn default_name() -> String {
+        "unnamed".to_string()
+    }
+
+    // writes an f32 as a number of hundredths
+    mod fixed_point {
+        use naia_shared::{BitReader, BitWrite, Serde, SerdeErr};
+
+        pub fn ser(value: &f32, writer: &mut dyn BitWrite) {
+            ((*value * 100.0).round() as i32).ser(writer);
+        }
+
+        pub fn de(reader: &mut BitReader) -> Result<f32, SerdeErr> {
+            Ok(i32::de(reader)? as f32 / 100.0)
+        }
+
+        pub fn bit_length(_value: &f32) -> u32 {
+            32
+        }
+    }
+
+    // serde-rs attributes live alongside naia's, in their own namespace
+    #[derive(Clone, Debug, PartialEq, Serde, serde::Serialize, serde::Deserialize)]
+    pub struct DualStruct {
+        #[serde(rename = "hp")]
+        #[naia(bits = 7)]
+        pub health: u8,
+        #[serde(default)]
+        #[naia(max_len = 16)]
+        pub name: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serde)]
+    pub struct OuterStruct {
+        pub some_int: u32,
+        pub inner: SomeStruct,
+    }
 }
 
-use naia_shared::{BitReader, BitWriter, Serde};
+use naia_shared::{BitReader, BitWriter, Serde, SerdeErrReason};
 
-use some_struct::SomeStruct;
+use some_struct::{BoundedStruct, DualStruct, OuterStruct, PackedStruct, SomeStruct};
 
 #[test]
 fn read_write_struct() {
@@ -44,3 +104,143 @@ fn read_write_struct() {
     assert_eq!(in_1, out_1);
     assert_eq!(in_2, out_2);
 }
+
+#[test]
+fn read_struct_over_max_len() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = BoundedStruct {
+        some_string: "Hello!".to_string(),
+        some_list: vec![1, 2],
+    };
+    let in_2 = BoundedStruct {
+        some_string: "Hello!".to_string(),
+        some_list: vec![1, 2, 3],
+    };
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1: BoundedStruct = Serde::de(&mut reader).unwrap();
+    let out_2: Result<BoundedStruct, _> = Serde::de(&mut reader);
+
+    assert_eq!(in_1, out_1);
+    assert_eq!(
+        out_2.unwrap_err().reason(),
+        &SerdeErrReason::LengthLimit {
+            length: 3,
+            max_length: 2
+        }
+    );
+}
+
+#[test]
+fn read_truncated_struct_error_path() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = OuterStruct {
+        some_int: 7,
+        inner: SomeStruct {
+            some_string: "Hello world!".to_string(),
+            some_int: 42,
+            some_bool: true,
+        },
+    };
+
+    in_1.ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read, with the final bytes cut off
+
+    let mut reader = BitReader::new(&bytes[..bytes.len() - 2]);
+
+    let error = OuterStruct::de(&mut reader).unwrap_err();
+
+    assert_eq!(error.reason(), &SerdeErrReason::OutOfBits);
+    assert_eq!(error.field_path(), "inner.some_int");
+    assert_eq!(error.type_name(), Some("i16"));
+}
+
+#[test]
+fn read_write_struct_with_field_attributes() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = PackedStruct {
+        some_small_int: 31,
+        some_ranged_int: -100,
+        some_float: 1.25,
+        some_local_state: vec![1, 2, 3],
+        some_name: "Hello world!".to_string(),
+    };
+
+    in_1.ser(&mut writer);
+
+    // 5 bits, 8 bits for the 201 possible values, 32 bits, and nothing for the
+    // skipped fields
+    assert_eq!(in_1.bit_length(), 45);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = PackedStruct::de(&mut reader).unwrap();
+
+    assert_eq!(reader.bit_offset(), 45);
+    assert_eq!(out_1.some_small_int, 31);
+    assert_eq!(out_1.some_ranged_int, -100);
+    assert_eq!(out_1.some_float, 1.25);
+    assert!(out_1.some_local_state.is_empty());
+    assert_eq!(out_1.some_name, "unnamed");
+}
+
+#[test]
+#[should_panic]
+fn write_struct_outside_of_range() {
+    let mut writer = BitWriter::new();
+
+    let in_1 = PackedStruct {
+        some_small_int: 0,
+        some_ranged_int: 101,
+        some_float: 0.0,
+        some_local_state: Vec::new(),
+        some_name: String::new(),
+    };
+
+    in_1.ser(&mut writer);
+}
+
+#[test]
+fn read_write_struct_with_serde_rs_derives() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = DualStruct {
+        health: 100,
+        name: "Ferris".to_string(),
+    };
+
+    in_1.ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1 = DualStruct::de(&mut reader).unwrap();
+
+    assert_eq!(in_1, out_1);
+    assert_eq!(reader.bit_offset() as u32, in_1.bit_length());
+}
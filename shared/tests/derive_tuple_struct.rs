@@ -3,10 +3,17 @@ mod some_struct {
 
     #[derive(Clone, Debug, PartialEq, Serde)]
     pub struct SomeStruct(pub String, pub i16, pub bool);
+
+    #[derive(Clone, Debug, PartialEq, Serde)]
+    pub struct PackedStruct(
+        #[naia(bits = 3)] pub i8,
+        #[naia(skip)] pub bool,
+        #[naia(range = 1000..1004)] pub u16,
+    );
 }
 
 use naia_shared::{BitReader, BitWriter, Serde};
-use some_struct::SomeStruct;
+use some_struct::{PackedStruct, SomeStruct};
 
 #[test]
 fn read_write_tuple_struct() {
@@ -31,3 +38,29 @@ fn read_write_tuple_struct() {
     assert_eq!(in_1, out_1);
     assert_eq!(in_2, out_2);
 }
+
+#[test]
+fn read_write_tuple_struct_with_field_attributes() {
+    // Write
+    let mut writer = BitWriter::new();
+
+    let in_1 = PackedStruct(-7, true, 1003);
+    let in_2 = PackedStruct(5, false, 1000);
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    let bytes = writer.to_bytes();
+
+    // Read
+
+    let mut reader = BitReader::new(&bytes);
+
+    let out_1: PackedStruct = Serde::de(&mut reader).unwrap();
+    let out_2: PackedStruct = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(out_1, PackedStruct(-7, false, 1003));
+    assert_eq!(out_2, in_2);
+    // a sign bit & 3 bits, then 2 bits for the 4 possible values
+    assert_eq!(reader.bit_offset(), 12);
+}
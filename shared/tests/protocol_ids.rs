@@ -0,0 +1,167 @@
+mod some_protocol {
+    use naia_shared::{
+        BitReader, BitWrite, Channel, Message, Property, ProtocolName, Replicate, Serde, SerdeErr,
+    };
+
+    #[derive(Channel)]
+    pub struct ChatChannel;
+
+    #[derive(Channel)]
+    pub struct InputChannel;
+
+    #[derive(Message)]
+    pub struct Chat {
+        pub text: String,
+    }
+
+    #[derive(Message)]
+    pub struct Input {
+        pub keys: u8,
+    }
+
+    #[derive(Replicate)]
+    pub struct Health {
+        pub value: Property<u8>,
+    }
+
+    #[derive(Replicate)]
+    pub struct Name {
+        pub value: Property<String>,
+    }
+
+    #[derive(Message)]
+    pub struct Parcel<T: Serde> {
+        pub contents: T,
+    }
+
+    /// Gives every unit the same protocol name
+    #[derive(Clone, PartialEq)]
+    pub struct Reading<const UNIT: u8>(pub u8);
+
+    impl<const UNIT: u8> Serde for Reading<UNIT> {
+        fn ser(&self, writer: &mut dyn BitWrite) {
+            self.0.ser(writer);
+        }
+
+        fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+            Ok(Self(u8::de(reader)?))
+        }
+
+        fn bit_length(&self) -> u32 {
+            self.0.bit_length()
+        }
+    }
+
+    impl<const UNIT: u8> ProtocolName for Reading<UNIT> {
+        fn protocol_name() -> String {
+            "Reading".to_string()
+        }
+    }
+}
+
+use naia_shared::{
+    Channel, ChannelDirection, ChannelMode, Message, Protocol, ProtocolError, ReliableSettings,
+    Replicate,
+};
+
+use some_protocol::{Chat, ChatChannel, Health, Input, InputChannel, Name, Parcel, Reading};
+
+fn add_chat_channel(protocol: &mut Protocol) {
+    protocol.add_channel::<ChatChannel>(
+        ChannelDirection::Bidirectional,
+        ChannelMode::OrderedReliable(ReliableSettings::default()),
+    );
+}
+
+fn add_input_channel(protocol: &mut Protocol) {
+    protocol.add_channel::<InputChannel>(
+        ChannelDirection::ClientToServer,
+        ChannelMode::UnorderedUnreliable,
+    );
+}
+
+#[test]
+fn ids_ignore_registration_order() {
+    let mut protocol_1 = Protocol::builder();
+    add_chat_channel(&mut protocol_1);
+    add_input_channel(&mut protocol_1);
+    let protocol_1 = protocol_1
+        .add_message::<Chat>()
+        .add_message::<Input>()
+        .add_component::<Health>()
+        .add_component::<Name>()
+        .build();
+
+    let mut protocol_2 = Protocol::builder();
+    add_input_channel(&mut protocol_2);
+    add_chat_channel(&mut protocol_2);
+    let protocol_2 = protocol_2
+        .add_component::<Name>()
+        .add_message::<Input>()
+        .add_component::<Health>()
+        .add_message::<Chat>()
+        .build();
+
+    assert_eq!(protocol_1.fingerprint(), protocol_2.fingerprint());
+    assert_eq!(protocol_1.schema().to_json(), protocol_2.schema().to_json());
+}
+
+#[test]
+fn ids_follow_protocol_names() {
+    let protocol = Protocol::builder()
+        .add_component::<Name>()
+        .add_component::<Health>()
+        .build();
+    let schema = protocol.schema();
+
+    assert_eq!(schema.components[0].name, "Health");
+    assert_eq!(schema.components[1].name, "Name");
+}
+
+#[test]
+fn protocol_names_are_module_paths() {
+    assert_eq!(
+        ChatChannel::protocol_name(),
+        "protocol_ids::some_protocol::ChatChannel"
+    );
+    assert_eq!(Chat::protocol_name(), "protocol_ids::some_protocol::Chat");
+    assert_eq!(
+        Health::protocol_name(),
+        "protocol_ids::some_protocol::Health"
+    );
+}
+
+#[test]
+fn registering_twice_keeps_one_id() {
+    let protocol = Protocol::builder()
+        .add_component::<Health>()
+        .add_component::<Health>()
+        .build();
+
+    assert_eq!(protocol.schema().components.len(), 1);
+}
+
+#[test]
+fn duplicate_protocol_names_fail_to_build() {
+    let result = Protocol::builder()
+        .add_message::<Parcel<Reading<0>>>()
+        .add_message::<Parcel<Reading<1>>>()
+        .try_build();
+
+    assert_eq!(
+        result.err(),
+        Some(ProtocolError::DuplicateProtocolName {
+            kind_label: "Message",
+            protocol_name: "protocol_ids::some_protocol::Parcel<Reading>".to_string(),
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "share the protocol name")]
+fn duplicate_protocol_names_panic_on_build() {
+    Protocol::builder()
+        .add_message::<Parcel<Reading<0>>>()
+        .add_message::<Parcel<Reading<1>>>()
+        .build();
+}
@@ -0,0 +1,135 @@
+mod some_protocol {
+    use naia_shared::{Channel, EntityProperty, Message, Property, Replicate};
+
+    #[derive(Channel)]
+    pub struct PlayerCommandChannel;
+
+    #[derive(Message)]
+    pub struct ChatMessage {
+        #[naia(max_len = 64)]
+        pub text: String,
+        #[naia(bits = 6)]
+        pub room: i8,
+        pub target: EntityProperty,
+    }
+
+    #[derive(Message)]
+    pub struct PingMessage(pub u32, pub Vec<u8>);
+
+    #[derive(Replicate)]
+    pub struct Position {
+        pub x: Property<f32>,
+        #[naia(range = -100..=100)]
+        pub y: Property<i16>,
+        pub parent: EntityProperty,
+        pub local_only: Vec<u8>,
+    }
+}
+
+use naia_shared::{
+    schema::{FieldEncoding, FieldKind},
+    ChannelDirection, ChannelMode, Protocol, ReliableSettings,
+};
+
+use some_protocol::{ChatMessage, PingMessage, PlayerCommandChannel, Position};
+
+fn some_protocol() -> Protocol {
+    Protocol::builder()
+        .add_channel::<PlayerCommandChannel>(
+            ChannelDirection::ClientToServer,
+            ChannelMode::OrderedReliable(ReliableSettings::default()),
+        )
+        .add_message::<ChatMessage>()
+        .add_message::<PingMessage>()
+        .add_component::<Position>()
+        .build()
+}
+
+#[test]
+fn describes_channels() {
+    let schema = some_protocol().schema();
+
+    let channel = schema.channels.last().unwrap();
+    assert_eq!(channel.name, "PlayerCommandChannel");
+    assert_eq!(channel.id as usize, schema.channels.len() - 1);
+    assert!(channel.settings.direction == ChannelDirection::ClientToServer);
+}
+
+#[test]
+fn describes_messages() {
+    let schema = some_protocol().schema();
+
+    let chat = schema
+        .messages
+        .iter()
+        .find(|message| message.name == "ChatMessage")
+        .unwrap();
+    let ping = &schema.messages[chat.id as usize + 1];
+    assert_eq!(ping.name, "PingMessage");
+
+    let text = &chat.fields[0];
+    assert_eq!(text.name, "text");
+    assert_eq!(text.kind, FieldKind::Field);
+    assert_eq!(text.type_name, "String");
+    assert_eq!(text.encoding, FieldEncoding::Serde);
+    assert_eq!(text.bit_length, None);
+    assert_eq!(text.max_len, Some(64));
+
+    let room = &chat.fields[1];
+    assert_eq!(room.encoding, FieldEncoding::Bits(6));
+    assert_eq!(room.bit_length, Some(7));
+
+    let target = &chat.fields[2];
+    assert_eq!(target.kind, FieldKind::EntityProperty);
+    assert_eq!(target.encoding, FieldEncoding::Entity);
+
+    assert_eq!(ping.fields[0].name, "0");
+    assert_eq!(ping.fields[0].bit_length, Some(32));
+    assert_eq!(ping.fields[1].type_name, "Vec<u8>");
+}
+
+#[test]
+fn describes_components() {
+    let schema = some_protocol().schema();
+
+    let position = &schema.components[0];
+    assert_eq!(position.id, 0);
+    assert_eq!(position.name, "Position");
+    // the non-replicated field is left out
+    assert_eq!(position.fields.len(), 3);
+
+    let x = &position.fields[0];
+    assert_eq!(x.kind, FieldKind::Property);
+    assert_eq!(x.type_name, "f32");
+    assert_eq!(x.bit_length, Some(32));
+
+    let y = &position.fields[1];
+    assert_eq!(
+        y.encoding,
+        FieldEncoding::Range {
+            min: -100,
+            max: 100
+        }
+    );
+    assert_eq!(y.bit_length, Some(8));
+
+    assert_eq!(position.fields[2].kind, FieldKind::EntityProperty);
+}
+
+#[test]
+fn writes_json() {
+    let protocol = some_protocol();
+    let json = protocol.schema().to_json();
+
+    assert!(json.starts_with(&format!(
+        "{{\"fingerprint\":\"{:016x}\",\"channels\":[",
+        protocol.fingerprint()
+    )));
+    assert!(json.contains(
+        "\"name\":\"PlayerCommandChannel\",\"mode\":\"OrderedReliable\",\"direction\":\"ClientToServer\""
+    ));
+    assert!(json.contains(
+        "{\"name\":\"y\",\"kind\":\"Property\",\"type\":\"i16\",\"encoding\":{\"type\":\"range\",\"min\":-100,\"max\":100},\"bit_length\":8,\"max_len\":null}"
+    ));
+    assert!(json.ends_with("]}]}"));
+}
@@ -1,31 +1,36 @@
 use std::time::Duration;
 
-use naia_client::internal::{HandshakeManager as ClientHandshakeManager, HandshakeState};
+use naia_client::internal::{
+    HandshakeManager as ClientHandshakeManager, HandshakeResult as ClientHandshakeResult,
+    HandshakeState,
+};
 use naia_server::internal::{HandshakeManager as ServerHandshakeManager, HandshakeResult};
 use naia_shared::{
-    BitReader, BitWriter, FakeEntityConverter, MessageContainer, PacketType, Protocol, Serde,
-    StandardHeader,
+    BitReader, BitWriter, FakeEntityConverter, MessageContainer, PacketType, Protocol,
+    RejectReason, Serde, SerdeErrReason, StandardHeader, UnsignedInteger,
 };
 use naia_test::Auth;
 
 #[test]
 fn end_to_end_handshake_w_auth() {
-    let mut client = ClientHandshakeManager::new(Duration::new(0, 0), Duration::new(0, 0), 1);
-    let mut server = ServerHandshakeManager::new(true);
-    let mut bytes: Box<[u8]>;
-    let mut writer: BitWriter;
-    let mut reader: BitReader;
-
     // Set up Protocol
     let protocol = Protocol::builder().add_message::<Auth>().build();
+    let fingerprint = protocol.fingerprint();
     let message_kinds = protocol.message_kinds;
 
+    let mut client =
+        ClientHandshakeManager::new(Duration::new(0, 0), Duration::new(0, 0), 1, fingerprint);
+    let mut server = ServerHandshakeManager::new(true, fingerprint);
+    let mut bytes: Box<[u8]>;
+    let mut writer: BitWriter;
+    let mut reader: BitReader;
+
     // 0. set Client auth object
     let username = "charlie";
     let password = "1234567";
     client.set_auth_message(MessageContainer::from_write(
         Box::new(Auth::new(username, password)),
-        &FakeEntityConverter,
+        &mut FakeEntityConverter,
     ));
 
     // 1. Client send challenge request
@@ -50,7 +55,7 @@ fn end_to_end_handshake_w_auth() {
     {
         reader = BitReader::new(&bytes);
         StandardHeader::de(&mut reader).expect("unable to read standard header from stream");
-        client.recv_challenge_response(&mut reader);
+        client.recv_challenge_response(&mut reader).unwrap();
         assert!(client
             .connection_state
             .eq(&HandshakeState::AwaitingValidateResponse));
@@ -103,3 +108,125 @@ fn end_to_end_handshake_w_auth() {
         client.recv_validate_response();
     }
 }
+
+#[test]
+fn queued_then_rejected_when_server_full() {
+    let mut client = ClientHandshakeManager::new(Duration::new(0, 0), Duration::new(0, 0), 1, 0);
+    let server = ServerHandshakeManager::new(false, 0);
+    let mut bytes: Box<[u8]>;
+    let mut reader: BitReader;
+
+    // Client is waiting on the Server's response to its validate request
+    client.connection_state = HandshakeState::AwaitingValidateResponse;
+
+    // 1. Server places Client in the connection queue
+    {
+        bytes = server.write_queue_response(3).to_bytes();
+        reader = BitReader::new(&bytes);
+        let Some(ClientHandshakeResult::Queued(position)) = client.recv(&mut reader).unwrap()
+        else {
+            panic!("client should have been queued");
+        };
+        assert_eq!(position, 3);
+    }
+
+    // 2. Repeated responses with the same position are not reported again
+    {
+        reader = BitReader::new(&bytes);
+        assert!(client.recv(&mut reader).unwrap().is_none());
+    }
+
+    // 3. Server rejects Client because the queue is full
+    {
+        bytes = server
+            .write_reject_response(RejectReason::ServerFull)
+            .to_bytes();
+        reader = BitReader::new(&bytes);
+        let Some(ClientHandshakeResult::Rejected(reason)) = client.recv(&mut reader).unwrap()
+        else {
+            panic!("client should have been rejected");
+        };
+        assert_eq!(reason, RejectReason::ServerFull);
+    }
+}
+
+#[test]
+fn malformed_handshake_packets_are_errors() {
+    let mut client = ClientHandshakeManager::new(Duration::new(0, 0), Duration::new(0, 0), 1, 0);
+    let mut writer: BitWriter;
+    let mut bytes: Box<[u8]>;
+    let mut reader: BitReader;
+
+    // 1. Unknown packet type
+    {
+        writer = BitWriter::new();
+        false.ser(&mut writer);
+        UnsignedInteger::<4>::new(15).ser(&mut writer);
+        bytes = writer.to_bytes();
+        reader = BitReader::new(&bytes);
+        let Err(error) = client.recv(&mut reader) else {
+            panic!("unknown packet type should not be read");
+        };
+        assert_eq!(*error.reason(), SerdeErrReason::InvalidEnumVariant(15));
+    }
+
+    // 2. Reject response without a reason
+    {
+        writer = BitWriter::new();
+        PacketType::ServerRejectResponse.ser(&mut writer);
+        bytes = writer.to_bytes();
+        reader = BitReader::new(&bytes);
+        assert!(client.recv(&mut reader).is_err());
+    }
+}
+
+#[test]
+fn protocol_mismatch_is_rejected() {
+    let client_protocol = Protocol::builder().add_message::<Auth>().build();
+    let server_protocol = Protocol::builder().build();
+
+    // Same registrations produce the same fingerprint
+    assert_eq!(
+        client_protocol.fingerprint(),
+        Protocol::builder()
+            .add_message::<Auth>()
+            .build()
+            .fingerprint()
+    );
+    assert_ne!(client_protocol.fingerprint(), server_protocol.fingerprint());
+
+    let mut client = ClientHandshakeManager::new(
+        Duration::new(0, 0),
+        Duration::new(0, 0),
+        1,
+        client_protocol.fingerprint(),
+    );
+    let mut server = ServerHandshakeManager::new(false, server_protocol.fingerprint());
+    let mut bytes: Box<[u8]>;
+    let mut reader: BitReader;
+
+    // 1. Client send challenge request
+    {
+        bytes = client.write_challenge_request().to_bytes();
+    }
+
+    // 2. Server rejects the challenge request
+    {
+        reader = BitReader::new(&bytes);
+        StandardHeader::de(&mut reader).expect("unable to read standard header from stream");
+        bytes = server
+            .recv_challenge_request(&mut reader)
+            .unwrap()
+            .to_bytes();
+    }
+
+    // 3. Client receives the rejection
+    {
+        reader = BitReader::new(&bytes);
+        let Some(ClientHandshakeResult::Rejected(reason)) = client.recv(&mut reader).unwrap()
+        else {
+            panic!("client should have been rejected");
+        };
+        assert_eq!(reason, RejectReason::ProtocolMismatch);
+    }
+}